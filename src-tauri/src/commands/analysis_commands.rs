@@ -104,9 +104,10 @@ pub async fn generate_pie_chart(
 pub async fn generate_pie_chart_with_dsl(
     workspace_path: String,
     category: String,
+    depth: Option<usize>,
 ) -> Result<ChartOutput, String> {
     let workspace = load_workspace(&workspace_path)?;
-    AnalysisService::generate_pie_chart_with_dsl(&workspace, &category)
+    AnalysisService::generate_pie_chart_with_dsl(&workspace, &category, depth)
 }
 
 /// 棒グラフ用Mermaidコードを生成（旧API）
@@ -124,9 +125,10 @@ pub async fn generate_bar_chart(
 pub async fn generate_bar_chart_with_dsl(
     workspace_path: String,
     category: String,
+    depth: Option<usize>,
 ) -> Result<ChartOutput, String> {
     let workspace = load_workspace(&workspace_path)?;
-    AnalysisService::generate_bar_chart_with_dsl(&workspace, &category)
+    AnalysisService::generate_bar_chart_with_dsl(&workspace, &category, depth)
 }
 
 /// 折線グラフ用Mermaidコードを生成（旧API）
@@ -169,6 +171,7 @@ pub async fn generate_pie_chart_with_dsl_filtered(
     filter_date_field: Option<String>,
     start_date: Option<String>,
     end_date: Option<String>,
+    depth: Option<usize>,
 ) -> Result<ChartOutput, String> {
     let workspace = load_workspace(&workspace_path)?;
     AnalysisService::generate_pie_chart_with_dsl_filtered(
@@ -177,6 +180,7 @@ pub async fn generate_pie_chart_with_dsl_filtered(
         filter_date_field.as_deref(),
         start_date.as_deref(),
         end_date.as_deref(),
+        depth,
     )
 }
 
@@ -188,6 +192,7 @@ pub async fn generate_bar_chart_with_dsl_filtered(
     filter_date_field: Option<String>,
     start_date: Option<String>,
     end_date: Option<String>,
+    depth: Option<usize>,
 ) -> Result<ChartOutput, String> {
     let workspace = load_workspace(&workspace_path)?;
     AnalysisService::generate_bar_chart_with_dsl_filtered(
@@ -196,6 +201,7 @@ pub async fn generate_bar_chart_with_dsl_filtered(
         filter_date_field.as_deref(),
         start_date.as_deref(),
         end_date.as_deref(),
+        depth,
     )
 }
 
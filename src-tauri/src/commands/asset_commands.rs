@@ -0,0 +1,98 @@
+use crate::models::{AssetGcOptions, AssetGcReport, OrphanedAsset};
+use crate::repository::asset_index_repository::AssetReference;
+use crate::repository::connection_manager::{IndexConnectionManager, PooledConnection};
+use crate::repository::IndexBuilder;
+use crate::service::{AssetService, TagSchemaService};
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+/// どのタスクからも参照されていないアセットを取得
+///
+/// # Arguments
+/// * `connections` - インデックス接続の管理
+/// * `workspace_path` - ワークスペースのルートパス
+///
+/// # Returns
+/// * `Result<Vec<OrphanedAsset>, String>` - 孤立アセット一覧
+#[tauri::command]
+pub async fn list_orphaned_assets(
+    connections: State<'_, IndexConnectionManager>,
+    workspace_path: String,
+) -> Result<Vec<OrphanedAsset>, String> {
+    let root_path = PathBuf::from(&workspace_path);
+    let builder = synced_index(&connections, &root_path)?;
+
+    AssetService::find_orphaned_assets(builder.database(), &root_path)
+        .map_err(|e| format!("Failed to list orphaned assets: {}", e))
+}
+
+/// 存在しないアセットへの参照を取得
+///
+/// # Arguments
+/// * `connections` - インデックス接続の管理
+/// * `workspace_path` - ワークスペースのルートパス
+///
+/// # Returns
+/// * `Result<Vec<AssetReference>, String>` - 参照元タスクIDとアセットパスの一覧
+#[tauri::command]
+pub async fn list_missing_asset_references(
+    connections: State<'_, IndexConnectionManager>,
+    workspace_path: String,
+) -> Result<Vec<AssetReference>, String> {
+    let root_path = PathBuf::from(&workspace_path);
+    let builder = synced_index(&connections, &root_path)?;
+
+    AssetService::find_missing_references(builder.database(), &root_path)
+        .map_err(|e| format!("Failed to list missing asset references: {}", e))
+}
+
+/// 孤立アセットを削除（またはゴミ箱へ移動）
+///
+/// # Arguments
+/// * `connections` - インデックス接続の管理
+/// * `workspace_path` - ワークスペースのルートパス
+/// * `options` - ドライラン・ゴミ箱・猶予期間の設定（省略時はドライラン）
+///
+/// # Returns
+/// * `Result<AssetGcReport, String>` - 実行結果
+#[tauri::command]
+pub async fn collect_asset_garbage(
+    connections: State<'_, IndexConnectionManager>,
+    workspace_path: String,
+    options: Option<AssetGcOptions>,
+) -> Result<AssetGcReport, String> {
+    let root_path = PathBuf::from(&workspace_path);
+    let builder = synced_index(&connections, &root_path)?;
+    let options = options.unwrap_or_default();
+
+    AssetService::collect_garbage(builder.database(), &root_path, &options)
+        .map_err(|e| format!("Failed to collect asset garbage: {}", e))
+}
+
+/// 参照情報を最新化したインデックスを取得
+///
+/// 参照されているアセットを誤って孤立と判定しないよう、判定前に差分構築を行う。
+/// 構築中にエラーが出たタスクがある場合は、その参照が不明なため処理を中止する。
+fn synced_index(
+    connections: &IndexConnectionManager,
+    root_path: &Path,
+) -> Result<IndexBuilder<PooledConnection>, String> {
+    let writer = connections
+        .writer(root_path)
+        .map_err(|e| format!("Failed to open index database: {}", e))?;
+    let mut builder = IndexBuilder::with_database(writer)
+        .with_computed_fields(TagSchemaService::load_computed_fields(root_path));
+    let result = builder
+        .build_from_workspace(root_path)
+        .map_err(|e| format!("Failed to update index: {}", e))?;
+
+    if !result.errors.is_empty() {
+        return Err(format!(
+            "Failed to update index for {} task(s): {}",
+            result.errors.len(),
+            result.errors.join(", ")
+        ));
+    }
+
+    Ok(builder)
+}
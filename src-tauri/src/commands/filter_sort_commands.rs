@@ -1,7 +1,13 @@
 use crate::models;
+use crate::repository::connection_manager::IndexConnectionManager;
+use crate::repository::task_index_repository::{SqliteTaskIndexRepository, TaskQueryPage};
+use crate::service::WorkspaceService;
 use std::path::PathBuf;
 use std::fs;
 
+/// `query_tasks`のデフォルト取得件数
+const DEFAULT_QUERY_LIMIT: usize = 100;
+
 /// カスタムフィルター/ソート設定を取得
 ///
 /// # Arguments
@@ -64,4 +70,68 @@ pub async fn save_filters_and_sorts(
     Ok(())
 }
 
+/// インデックス上でフィルター・ソートを実行してタスクを取得
+///
+/// 保存済みのフィルター/ソートは名前で指定し、インライン指定がある場合はそちらを優先する。
+///
+/// # Arguments
+/// * `connections` - インデックス接続の管理
+/// * `workspace_path` - ワークスペースのルートパス
+/// * `filter_name` - 保存済みフィルター名
+/// * `sort_name` - 保存済みソート名
+/// * `filter` - インラインのフィルター式
+/// * `sort` - インラインのカスタムソート
+/// * `offset` - 取得開始位置（デフォルト0）
+/// * `limit` - 取得件数（デフォルト100）
+///
+/// # Returns
+/// * `Result<TaskQueryPage, String>` - 該当ページと総件数
+#[tauri::command]
+pub async fn query_tasks(
+    connections: tauri::State<'_, IndexConnectionManager>,
+    workspace_path: String,
+    filter_name: Option<String>,
+    sort_name: Option<String>,
+    filter: Option<models::filter_sort::FilterExpression>,
+    sort: Option<models::filter_sort::CustomSort>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<TaskQueryPage, String> {
+    let mut filter = filter;
+    let mut sort = sort;
+
+    if (filter.is_none() && filter_name.is_some()) || (sort.is_none() && sort_name.is_some()) {
+        let saved = get_filters_and_sorts(workspace_path.clone()).await?;
+
+        if filter.is_none() {
+            if let Some(name) = filter_name {
+                let found = saved.filters.into_iter().find(|f| f.name == name)
+                    .ok_or_else(|| format!("Filter not found: {}", name))?;
+                filter = Some(found.expression);
+            }
+        }
+        if sort.is_none() {
+            if let Some(name) = sort_name {
+                let found = saved.sorts.into_iter().find(|s| s.name == name)
+                    .ok_or_else(|| format!("Sort not found: {}", name))?;
+                sort = Some(found);
+            }
+        }
+    }
 
+    let separator = WorkspaceService::load_config(&workspace_path)?.tag_separator;
+    let root_path = PathBuf::from(&workspace_path);
+    let reader = connections
+        .reader(&root_path)
+        .map_err(|e| format!("Failed to open index database: {}", e))?;
+    let repo = SqliteTaskIndexRepository::new(reader);
+
+    repo.query_tasks(
+        filter.as_ref(),
+        sort.as_ref(),
+        &separator,
+        offset.unwrap_or(0),
+        limit.unwrap_or(DEFAULT_QUERY_LIMIT),
+    )
+    .map_err(|e| format!("Failed to query tasks: {}", e))
+}
@@ -0,0 +1,255 @@
+use crate::repository::connection_manager::IndexConnectionManager;
+use crate::repository::database::IntegrityReport;
+use crate::parser::ComputedFields;
+use crate::repository::IndexBuilder;
+use crate::service::{TagSchemaService, WorkspaceService};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// インデックス構築の状態管理
+pub struct IndexState {
+    /// 実行中の構築ジョブのキャンセルフラグ
+    pub cancel_flag: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+impl IndexState {
+    pub fn new() -> Self {
+        Self {
+            cancel_flag: Mutex::new(None),
+        }
+    }
+}
+
+impl Default for IndexState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// タグスキーマの変更後、インデックスに保存された計算フィールドを評価し直す
+///
+/// ファイルは変わっていないため全件構築は行わず、失敗してもログに残すだけにする
+/// （次回の構築で改めて評価される）。
+pub fn refresh_computed_tags(
+    connections: &IndexConnectionManager,
+    workspace_root: &Path,
+    computed_fields: ComputedFields,
+) {
+    let result = connections
+        .writer(workspace_root)
+        .map_err(|e| e.to_string())
+        .and_then(|writer| {
+            IndexBuilder::with_database(writer)
+                .with_computed_fields(computed_fields)
+                .refresh_computed_tags()
+                .map_err(|e| e.to_string())
+        });
+    match result {
+        Ok(updated) => log::info!("Refreshed computed tags of {} task(s)", updated),
+        Err(e) => log::error!("Failed to refresh computed tags: {}", e),
+    }
+}
+
+/// バックグラウンドでインデックス構築を開始する
+///
+/// 実行中の構築があればキャンセルしてから新しいジョブを起動する。
+/// 構築中も既存のキャッシュはコミット済みの内容のまま読み取り可能。
+/// 書き込み接続はバッチごとに借りて返すため、ファイル監視による更新も構築の完了を待たずに書き込まれる。
+///
+/// # Arguments
+/// * `app_handle` - Tauriアプリハンドル
+/// * `state` - インデックスの状態
+/// * `workspace_root` - ワークスペースのルートディレクトリ
+/// * `rebuild` - trueの場合は既存のインデックスを空にしてから全件を構築する
+///
+/// # Returns
+/// * `Result<(), String>` - 起動結果
+pub fn spawn_index_build(
+    app_handle: AppHandle,
+    state: &IndexState,
+    workspace_root: PathBuf,
+    rebuild: bool,
+) -> Result<(), String> {
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+
+    {
+        let mut guard = state
+            .cancel_flag
+            .lock()
+            .map_err(|e| format!("Failed to lock index state: {}", e))?;
+
+        // 既存のジョブがあればキャンセル
+        if let Some(previous) = guard.replace(cancel_flag.clone()) {
+            previous.store(true, Ordering::SeqCst);
+        }
+    }
+
+    std::thread::spawn(move || {
+        let manager = app_handle.state::<IndexConnectionManager>();
+        let build_result = manager
+            .pool(&workspace_root)
+            .inspect(|pool| {
+                // 破損したキャッシュを作り直した場合は通知する
+                if let Some(recovery) = pool.take_recovery() {
+                    if let Err(e) = app_handle.emit("index-recovered", &recovery) {
+                        log::error!("Failed to emit index-recovered event: {}", e);
+                    }
+                }
+            })
+            .map_err(|e| format!("Failed to open index database: {}", e))
+            .and_then(|pool| {
+                if rebuild {
+                    pool.writer()
+                        .and_then(|writer| writer.clear())
+                        .map_err(|e| format!("Failed to clear index: {}", e))?;
+                }
+
+                let mut builder = IndexBuilder::with_database(pool).with_computed_fields(
+                    TagSchemaService::load_computed_fields(&workspace_root),
+                );
+                // 検証規則の違反も解析エラーと同じく構築結果のエラーとして報告する
+                match WorkspaceService::load_config(&workspace_root.to_string_lossy()) {
+                    Ok(config) => {
+                        builder = builder
+                            .with_validation_rules(config.validation_rules, config.strict_tag_mode);
+                    }
+                    Err(e) => log::warn!("Failed to load validation rules: {}", e),
+                }
+                builder
+                    .build_from_workspace_with_progress(&workspace_root, &cancel_flag, |progress| {
+                        if let Err(e) = app_handle.emit("index-progress", progress) {
+                            log::error!("Failed to emit index-progress event: {}", e);
+                        }
+                    })
+                    .map_err(|e| format!("Failed to build index: {}", e))
+            });
+
+        match build_result {
+            Ok(result) => {
+                log::info!(
+                    "Index build finished: {} indexed, {} updated, {} skipped, {} errors{}",
+                    result.tasks_indexed,
+                    result.tasks_updated,
+                    result.tasks_skipped,
+                    result.errors.len(),
+                    if result.cancelled { " (cancelled)" } else { "" }
+                );
+                if let Err(e) = app_handle.emit("index-complete", &result) {
+                    log::error!("Failed to emit index-complete event: {}", e);
+                }
+            }
+            Err(e) => {
+                log::error!("{}", e);
+                if let Err(e) = app_handle.emit("index-error", &e) {
+                    log::error!("Failed to emit index-error event: {}", e);
+                }
+            }
+        }
+
+        // 自分のジョブが最新であれば状態をクリア
+        let state = app_handle.state::<IndexState>();
+        if let Ok(mut guard) = state.cancel_flag.lock() {
+            if guard.as_ref().is_some_and(|flag| Arc::ptr_eq(flag, &cancel_flag)) {
+                *guard = None;
+            }
+        };
+    });
+
+    Ok(())
+}
+
+/// インデックス構築を開始
+///
+/// # Arguments
+/// * `app_handle` - Tauriアプリハンドル
+/// * `state` - インデックスの状態
+/// * `workspace_path` - ワークスペースのルートパス
+///
+/// # Returns
+/// * `Result<(), String>` - 開始結果（構築の完了は`index-complete`イベントで通知）
+#[tauri::command]
+pub async fn start_index_build(
+    app_handle: AppHandle,
+    state: State<'_, IndexState>,
+    workspace_path: String,
+) -> Result<(), String> {
+    let root_path = PathBuf::from(&workspace_path);
+
+    if !root_path.is_dir() {
+        return Err(format!("Path is not a directory: {}", workspace_path));
+    }
+
+    spawn_index_build(app_handle, &state, root_path, false)
+}
+
+/// インデックスを空にしてMarkdownファイルから再構築
+///
+/// # Arguments
+/// * `app_handle` - Tauriアプリハンドル
+/// * `state` - インデックスの状態
+/// * `workspace_path` - ワークスペースのルートパス
+///
+/// # Returns
+/// * `Result<(), String>` - 開始結果（構築の完了は`index-complete`イベントで通知）
+#[tauri::command]
+pub async fn rebuild_index(
+    app_handle: AppHandle,
+    state: State<'_, IndexState>,
+    workspace_path: String,
+) -> Result<(), String> {
+    let root_path = PathBuf::from(&workspace_path);
+
+    if !root_path.is_dir() {
+        return Err(format!("Path is not a directory: {}", workspace_path));
+    }
+
+    log::info!("Index rebuild requested for: {}", workspace_path);
+    spawn_index_build(app_handle, &state, root_path, true)
+}
+
+/// インデックスの完全な整合性チェックを実行
+///
+/// # Arguments
+/// * `connections` - インデックス接続の管理
+/// * `workspace_path` - ワークスペースのルートパス
+///
+/// # Returns
+/// * `Result<IntegrityReport, String>` - チェック結果
+#[tauri::command]
+pub async fn check_index_integrity(
+    connections: State<'_, IndexConnectionManager>,
+    workspace_path: String,
+) -> Result<IntegrityReport, String> {
+    let root_path = PathBuf::from(&workspace_path);
+    let db = connections
+        .reader(&root_path)
+        .map_err(|e| format!("Failed to open index database: {}", e))?;
+
+    db.check_integrity()
+        .map_err(|e| format!("Failed to check index integrity: {}", e))
+}
+
+/// 実行中のインデックス構築をキャンセル
+///
+/// # Arguments
+/// * `state` - インデックスの状態
+///
+/// # Returns
+/// * `Result<bool, String>` - キャンセル対象のジョブがあった場合はtrue
+#[tauri::command]
+pub async fn cancel_index_build(state: State<'_, IndexState>) -> Result<bool, String> {
+    let guard = state
+        .cancel_flag
+        .lock()
+        .map_err(|e| format!("Failed to lock index state: {}", e))?;
+
+    if let Some(flag) = guard.as_ref() {
+        flag.store(true, Ordering::SeqCst);
+        log::info!("Index build cancellation requested");
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
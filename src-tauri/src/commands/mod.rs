@@ -8,6 +8,10 @@ pub mod app_settings_commands;
 pub mod tag_schema_commands;
 pub mod analysis_commands;
 pub mod gantt_commands;
+pub mod index_commands;
+pub mod asset_commands;
+pub mod validation_commands;
+pub mod tag_bundle_commands;
 
 // Re-export all commands for easy registration
 pub use workspace_commands::*;
@@ -19,3 +23,7 @@ pub use app_settings_commands::*;
 pub use tag_schema_commands::*;
 pub use analysis_commands::*;
 pub use gantt_commands::*;
+pub use index_commands::*;
+pub use asset_commands::*;
+pub use validation_commands::*;
+pub use tag_bundle_commands::*;
@@ -0,0 +1,100 @@
+use crate::commands::tag_schema_commands::apply_saved_schema;
+use crate::commands::workspace_commands::WorkspaceState;
+use crate::models::tag_bundle::{
+    BundleFormat, ImportAction, ImportPreview, ImportReport, ImportResolution, TagBundle,
+};
+use crate::repository::connection_manager::IndexConnectionManager;
+use crate::service::{TagBundleService, TagSchemaService};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+fn read_bundle(bundle_path: &str) -> Result<TagBundle, String> {
+    let content =
+        fs::read_to_string(bundle_path).map_err(|e| format!("Failed to read bundle: {}", e))?;
+    TagBundleService::parse_bundle(&content)
+}
+
+/// タグ定義とテンプレートをバンドルファイルにエクスポート
+///
+/// # Arguments
+/// * `workspace_path` - ワークスペースのルートパス
+/// * `output_path` - 出力先（拡張子が`.yaml`/`.yml`ならYAML、それ以外はJSON）
+/// * `tag_names` - エクスポートするタグ（省略時はすべて）
+/// * `template_names` - エクスポートするテンプレート（省略時はすべて）
+///
+/// # Returns
+/// * `Result<(), String>` - 書き込み結果
+#[tauri::command]
+pub async fn export_tag_bundle(
+    workspace_path: String,
+    output_path: String,
+    tag_names: Option<Vec<String>>,
+    template_names: Option<Vec<String>>,
+) -> Result<(), String> {
+    let bundle = TagBundleService::export_bundle(
+        Path::new(&workspace_path),
+        tag_names.as_deref(),
+        template_names.as_deref(),
+    )?;
+    let content =
+        TagBundleService::serialize_bundle(&bundle, BundleFormat::from_path(Path::new(&output_path)))?;
+    fs::write(&output_path, content).map_err(|e| format!("Failed to write bundle: {}", e))
+}
+
+/// バンドルファイルをインポートした場合の競合を確認
+///
+/// # Arguments
+/// * `workspace_path` - インポート先のワークスペースのルートパス
+/// * `bundle_path` - バンドルファイルのパス
+///
+/// # Returns
+/// * `Result<ImportPreview, String>` - 項目ごとの比較結果
+#[tauri::command]
+pub async fn preview_tag_bundle_import(
+    workspace_path: String,
+    bundle_path: String,
+) -> Result<ImportPreview, String> {
+    let bundle = read_bundle(&bundle_path)?;
+    TagBundleService::preview_import(Path::new(&workspace_path), &bundle)
+}
+
+/// バンドルファイルをインポート
+///
+/// インポートしたタグ定義は開いているワークスペースの検証と計算フィールドにも反映される。
+///
+/// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
+/// * `connections` - インデックスの接続管理
+/// * `workspace_path` - インポート先のワークスペースのルートパス
+/// * `bundle_path` - バンドルファイルのパス
+/// * `resolutions` - 項目ごとのインポート方法（スキップ・上書き・別名）
+/// * `default_action` - 指定のない競合項目の扱い（省略時はスキップ）
+///
+/// # Returns
+/// * `Result<ImportReport, String>` - インポート結果
+#[tauri::command]
+pub async fn import_tag_bundle(
+    workspace_state: State<'_, WorkspaceState>,
+    connections: State<'_, IndexConnectionManager>,
+    workspace_path: String,
+    bundle_path: String,
+    resolutions: Vec<ImportResolution>,
+    default_action: Option<ImportAction>,
+) -> Result<ImportReport, String> {
+    let root_path = PathBuf::from(&workspace_path);
+    let bundle = read_bundle(&bundle_path)?;
+    let report = TagBundleService::import_bundle(
+        &root_path,
+        &bundle,
+        &resolutions,
+        default_action.unwrap_or_default(),
+    )?;
+
+    if !report.tags_imported.is_empty() {
+        let schema = TagSchemaService::load_schema(&root_path)?;
+        apply_saved_schema(&workspace_state, &connections, &root_path, &schema)?;
+    }
+
+    Ok(report)
+}
@@ -1,9 +1,12 @@
-use crate::service::{WorkspaceService, TagService};
-use std::path::PathBuf;
+use crate::commands::workspace_commands::WorkspaceState;
+use crate::models::{CategoryConflictPolicy, CategoryRenameReport, NormalizationReport};
+use crate::service::TagService;
+use tauri::State;
 
 /// タグをリネーム
 ///
 /// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態（書き換えたタスクだけをその場で反映する）
 /// * `workspace_path` - ワークスペースのルートパス
 /// * `category` - タグカテゴリ名
 /// * `old_value` - 古い値
@@ -13,25 +16,74 @@ use std::path::PathBuf;
 /// * `Result<usize, String>` - 更新されたタスク数
 #[tauri::command]
 pub async fn rename_tag(
+    workspace_state: State<'_, WorkspaceState>,
     workspace_path: String,
     category: String,
     old_value: String,
     new_value: String,
 ) -> Result<usize, String> {
-    let root_path = PathBuf::from(&workspace_path);
+    workspace_state
+        .modify(&workspace_path, |workspace| {
+            TagService::rename_tag(workspace, &category, &old_value, &new_value)
+        })?
+        .map_err(|e| format!("Failed to rename tag: {}", e))
+}
 
-    let workspace_service = WorkspaceService::new();
-    let mut workspace = workspace_service
-        .load_workspace(root_path)
-        .map_err(|e| format!("Failed to load workspace: {}", e))?;
+/// 複数のタグ値を1つの値に統合
+///
+/// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態（書き換えたタスクだけをその場で反映する）
+/// * `workspace_path` - ワークスペースのルートパス
+/// * `category` - タグカテゴリ名
+/// * `old_values` - 統合元の値
+/// * `new_value` - 統合先の値
+///
+/// # Returns
+/// * `Result<usize, String>` - 更新されたタスク数
+#[tauri::command]
+pub async fn merge_tag_values(
+    workspace_state: State<'_, WorkspaceState>,
+    workspace_path: String,
+    category: String,
+    old_values: Vec<String>,
+    new_value: String,
+) -> Result<usize, String> {
+    let old_values: Vec<&str> = old_values.iter().map(String::as_str).collect();
+    workspace_state
+        .modify(&workspace_path, |workspace| {
+            TagService::merge_tag_values(workspace, &category, &old_values, &new_value)
+        })?
+        .map_err(|e| format!("Failed to merge tag values: {}", e))
+}
 
-    TagService::rename_tag(&mut workspace, &category, &old_value, &new_value)
-        .map_err(|e| format!("Failed to rename tag: {}", e))
+/// タグ値を正規の値に書き換える
+///
+/// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態（書き換えたタスクだけをその場で反映する）
+/// * `workspace_path` - ワークスペースのルートパス
+/// * `categories` - 対象のカテゴリ（省略時は正規化ルールを持つすべてのカテゴリ）
+/// * `dry_run` - trueの場合はファイルを書き換えずに差分だけを返す
+///
+/// # Returns
+/// * `Result<NormalizationReport, String>` - 書き換える値の一覧と更新されたタスク数
+#[tauri::command]
+pub async fn normalize_tags(
+    workspace_state: State<'_, WorkspaceState>,
+    workspace_path: String,
+    categories: Option<Vec<String>>,
+    dry_run: bool,
+) -> Result<NormalizationReport, String> {
+    workspace_state
+        .modify(&workspace_path, |workspace| {
+            TagService::normalize_tags(workspace, categories.as_deref(), dry_run)
+        })?
+        .map_err(|e| format!("Failed to normalize tags: {}", e))
 }
 
 /// タグを削除
 ///
 /// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態（書き換えたタスクだけをその場で反映する）
 /// * `workspace_path` - ワークスペースのルートパス
 /// * `category` - タグカテゴリ名
 /// * `value` - 削除する値（Noneの場合はカテゴリごと削除）
@@ -40,17 +92,86 @@ pub async fn rename_tag(
 /// * `Result<usize, String>` - 更新されたタスク数
 #[tauri::command]
 pub async fn delete_tag(
+    workspace_state: State<'_, WorkspaceState>,
     workspace_path: String,
     category: String,
     value: Option<String>,
 ) -> Result<usize, String> {
-    let root_path = PathBuf::from(&workspace_path);
+    workspace_state
+        .modify(&workspace_path, |workspace| {
+            TagService::delete_tag(workspace, &category, value.as_deref())
+        })?
+        .map_err(|e| format!("Failed to delete tag: {}", e))
+}
 
-    let workspace_service = WorkspaceService::new();
-    let mut workspace = workspace_service
-        .load_workspace(root_path)
-        .map_err(|e| format!("Failed to load workspace: {}", e))?;
+/// タグカテゴリ（キー）の名前を変更
+///
+/// 変更後の名前が既に使われている場合は、`conflict_policy`を指定したときだけ統合する。
+///
+/// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態（書き換えたタスクだけをその場で反映する）
+/// * `workspace_path` - ワークスペースのルートパス
+/// * `old_category` - 変更前のカテゴリ名
+/// * `new_category` - 変更後のカテゴリ名
+/// * `conflict_policy` - 両方のカテゴリを持つタスクの扱い
+///
+/// # Returns
+/// * `Result<CategoryRenameReport, String>` - 更新内容
+#[tauri::command]
+pub async fn rename_tag_category(
+    workspace_state: State<'_, WorkspaceState>,
+    workspace_path: String,
+    old_category: String,
+    new_category: String,
+    conflict_policy: Option<CategoryConflictPolicy>,
+) -> Result<CategoryRenameReport, String> {
+    let target = new_category.trim();
+    workspace_state.modify(&workspace_path, |workspace| {
+        let target_exists = workspace.tag_index.categories.contains_key(target)
+            || workspace.config.tag_configs.configs.contains_key(target);
+        let policy = match conflict_policy {
+            Some(policy) => policy,
+            None if target_exists => {
+                return Err(format!(
+                    "Category '{}' already exists; choose a conflict policy to merge",
+                    target
+                ))
+            }
+            None => CategoryConflictPolicy::default(),
+        };
 
-    TagService::delete_tag(&mut workspace, &category, value.as_deref())
-        .map_err(|e| format!("Failed to delete tag: {}", e))
+        TagService::rename_category(workspace, &old_category, target, policy)
+            .map_err(|e| format!("Failed to rename tag category: {}", e))
+    })?
+}
+
+/// 複数のタグカテゴリを1つに統合
+///
+/// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態（書き換えたタスクだけをその場で反映する）
+/// * `workspace_path` - ワークスペースのルートパス
+/// * `source_categories` - 統合元のカテゴリ名
+/// * `target_category` - 統合先のカテゴリ名
+/// * `conflict_policy` - 統合先のカテゴリを既に持つタスクの扱い
+///
+/// # Returns
+/// * `Result<CategoryRenameReport, String>` - 全カテゴリ分を合算した更新内容
+#[tauri::command]
+pub async fn merge_tag_categories(
+    workspace_state: State<'_, WorkspaceState>,
+    workspace_path: String,
+    source_categories: Vec<String>,
+    target_category: String,
+    conflict_policy: CategoryConflictPolicy,
+) -> Result<CategoryRenameReport, String> {
+    // 途中で失敗した場合も、それまでに書き換えた内容はワークスペースに残る
+    workspace_state.modify(&workspace_path, |workspace| {
+        let mut report = CategoryRenameReport::default();
+        for source in source_categories.iter().filter(|source| **source != target_category) {
+            let merged = TagService::rename_category(workspace, source, &target_category, conflict_policy)
+                .map_err(|e| format!("Failed to merge tag category '{}': {}", source, e))?;
+            report.absorb(merged);
+        }
+        Ok(report)
+    })?
 }
@@ -1,67 +1,142 @@
-use crate::service::TagSchemaService;
-use serde_json::Value;
-use std::path::PathBuf;
+use crate::commands::index_commands::refresh_computed_tags;
+use crate::commands::workspace_commands::WorkspaceState;
+use crate::models::schema_inference::SchemaInference;
+use crate::models::tag_schema::TagDefinition;
+use crate::models::{TagSchema, TagValue};
+use crate::parser::ComputedFields;
+use crate::repository::connection_manager::IndexConnectionManager;
+use crate::service::{SchemaInferenceService, TagSchemaService, WorkspaceService};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tauri::State;
 
 /// タグスキーマを読み込む
 ///
+/// 旧形式の`tag_schema.json`や`.hienmark.json`のタグ設定は、移行後の内容をメモリ上で返す
+/// （ファイルは書き換えない）。移行した内容の保存は`open_workspace`が行う。
+///
 /// # Arguments
 /// * `workspace_path` - ワークスペースのルートパス
 ///
 /// # Returns
-/// * `Result<String, String>` - スキーマのJSON文字列（存在しない場合は空のオブジェクト）
+/// * `Result<TagSchema, String>` - タグスキーマ
 #[tauri::command]
-pub async fn load_tag_schema(workspace_path: String) -> Result<String, String> {
+pub async fn load_tag_schema(workspace_path: String) -> Result<TagSchema, String> {
     let root_path = PathBuf::from(&workspace_path);
-    let schema_path = root_path.join(".hienmark").join("tag_schema.json");
-    
-    if !schema_path.exists() {
-        // ファイルが存在しない場合は空のオブジェクトを返す
-        return Ok("{}".to_string());
-    }
-    
-    std::fs::read_to_string(&schema_path)
-        .map_err(|e| format!("Failed to read tag schema: {}", e))
+    TagSchemaService::load_schema(&root_path)
 }
 
 /// タグスキーマを保存
 ///
+/// 保存後は開いているワークスペースの検証と計算フィールドにも新しいスキーマが使われる。
+///
 /// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
+/// * `connections` - インデックスの接続管理
 /// * `workspace_path` - ワークスペースのルートパス
-/// * `schema_json` - スキーマのJSON文字列
+/// * `schema` - 保存するスキーマ
 ///
 /// # Returns
-/// * `Result<(), String>` - 保存結果
+/// * `Result<(), String>` - 保存結果（スキーマが不正な場合はエラー）
 #[tauri::command]
-pub async fn save_tag_schema(workspace_path: String, schema_json: String) -> Result<(), String> {
+pub async fn save_tag_schema(
+    workspace_state: State<'_, WorkspaceState>,
+    connections: State<'_, IndexConnectionManager>,
+    workspace_path: String,
+    schema: TagSchema,
+) -> Result<(), String> {
     let root_path = PathBuf::from(&workspace_path);
-    let hienmark_dir = root_path.join(".hienmark");
-    
-    // .hienmarkディレクトリが存在しない場合は作成
-    if !hienmark_dir.exists() {
-        std::fs::create_dir_all(&hienmark_dir)
-            .map_err(|e| format!("Failed to create .hienmark directory: {}", e))?;
+    TagSchemaService::save_schema(&root_path, &schema)?;
+    apply_saved_schema(&workspace_state, &connections, &root_path, &schema)
+}
+
+/// 既存タスクのタグからタグスキーマを推定
+///
+/// タグごとに型（日付・数値・真偽値、値の種類が少なければ選択型）、選択肢の候補、
+/// タスクの割合による必須指定、外れ値を提案する。ファイルは変更しない。
+///
+/// # Arguments
+/// * `workspace_path` - ワークスペースのルートパス
+///
+/// # Returns
+/// * `Result<SchemaInference, String>` - タグごとの提案
+#[tauri::command]
+pub async fn infer_tag_schema(workspace_path: String) -> Result<SchemaInference, String> {
+    let root_path = PathBuf::from(&workspace_path);
+    let workspace = WorkspaceService::new()
+        .load_workspace(root_path.clone())
+        .map_err(|e| format!("Failed to load workspace: {}", e))?;
+    let schema = TagSchemaService::load_schema(&root_path)?;
+
+    Ok(SchemaInferenceService::infer(&workspace, &schema))
+}
+
+/// 確認した推定結果をタグスキーマに保存
+///
+/// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
+/// * `connections` - インデックスの接続管理
+/// * `workspace_path` - ワークスペースのルートパス
+/// * `definitions` - タグ名 → 保存するタグ定義
+/// * `overwrite` - 既存の定義を置き換えるか（falseの場合は未定義のタグだけ追加）
+///
+/// # Returns
+/// * `Result<TagSchema, String>` - 保存後のタグスキーマ（スキーマが不正な場合はエラー）
+#[tauri::command]
+pub async fn save_inferred_schema(
+    workspace_state: State<'_, WorkspaceState>,
+    connections: State<'_, IndexConnectionManager>,
+    workspace_path: String,
+    definitions: BTreeMap<String, TagDefinition>,
+    overwrite: bool,
+) -> Result<TagSchema, String> {
+    let root_path = PathBuf::from(&workspace_path);
+    let mut schema = TagSchemaService::load_schema(&root_path)?;
+    if SchemaInferenceService::merge_definitions(&mut schema, definitions, overwrite) > 0 {
+        TagSchemaService::save_schema(&root_path, &schema)?;
+        apply_saved_schema(&workspace_state, &connections, &root_path, &schema)?;
     }
-    
-    let schema_path = hienmark_dir.join("tag_schema.json");
-    
-    // JSONの妥当性をチェック
-    let _: Value = serde_json::from_str(&schema_json)
-        .map_err(|e| format!("Invalid JSON: {}", e))?;
-    
-    // ファイルに書き込み
-    std::fs::write(&schema_path, schema_json)
-        .map_err(|e| format!("Failed to write tag schema: {}", e))
+
+    Ok(schema)
+}
+
+/// 保存したスキーマを開いているワークスペース（タグ設定・タグインデックス）とインデックスの計算フィールドに反映
+pub(crate) fn apply_saved_schema(
+    workspace_state: &WorkspaceState,
+    connections: &IndexConnectionManager,
+    root_path: &Path,
+    schema: &TagSchema,
+) -> Result<(), String> {
+    {
+        let mut guard = workspace_state
+            .workspace
+            .lock()
+            .map_err(|e| format!("Failed to lock workspace state: {}", e))?;
+        if let Some(workspace) = guard.as_mut().filter(|w| w.root_path == root_path) {
+            workspace.apply_tag_schema(schema);
+        }
+    }
+    refresh_computed_tags(connections, root_path, ComputedFields::from_schema(schema));
+
+    Ok(())
 }
 
 /// 動的デフォルト値を計算
 ///
 /// # Arguments
-/// * `formula` - 計算式（例: `=[TODAY]+30`）
+/// * `formula` - 計算式（例: `=[TODAY]+30`、`=[start_date]+2w`）
+/// * `tags` - 計算式から参照できるタスクのタグ（省略可）
 ///
 /// # Returns
-/// * `Result<String, String>` - 計算結果のISO 8601形式の文字列
+/// * `Result<String, String>` - 計算結果のISO 8601形式の文字列（構文エラーは位置付きのメッセージ）
 #[tauri::command]
-pub fn get_dynamic_default_value(formula: String) -> Result<String, String> {
+pub fn get_dynamic_default_value(
+    formula: String,
+    tags: Option<HashMap<String, TagValue>>,
+) -> Result<String, String> {
     let service = TagSchemaService::new();
-    service.calculate_dynamic_default_value(&formula)
+    match tags {
+        Some(tags) => service.calculate_dynamic_default_value_with(&formula, &tags),
+        None => service.calculate_dynamic_default_value(&formula),
+    }
 }
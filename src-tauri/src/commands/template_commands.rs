@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::models::{TagTemplate, TagValue};
+use crate::models::{TagTemplate, TagValue, TemplateRenderContext};
 use crate::service::{TemplateService, WorkspaceService};
 
 /// テンプレート一覧を取得
@@ -46,8 +46,10 @@ pub async fn create_template(
     name: String,
     description: Option<String>,
     tags: HashMap<String, TagValue>,
+    body: Option<String>,
 ) -> Result<TagTemplate, String> {
-    let template = TagTemplate::new(name.clone(), description, tags);
+    let mut template = TagTemplate::new(name.clone(), description, tags);
+    template.body = body;
 
     // バリデーション
     let errors = TemplateService::validate_template(&template);
@@ -79,6 +81,7 @@ pub async fn update_template(
     name: String,
     description: Option<String>,
     tags: HashMap<String, TagValue>,
+    body: Option<String>,
 ) -> Result<TagTemplate, String> {
     let mut config = WorkspaceService::load_config(&workspace_path)?;
 
@@ -87,7 +90,7 @@ pub async fn update_template(
         .ok_or_else(|| format!("Template '{}' not found", name))?;
 
     // 更新
-    template.update(description, tags);
+    template.update(description, tags, body);
 
     // バリデーション
     let errors = TemplateService::validate_template(template);
@@ -155,18 +158,37 @@ pub async fn set_default_template(
 }
 
 /// テンプレートを新規タスクに適用
+///
+/// `title`と`prompts`（`{{prompt:...}}`のラベル → 入力値）はテンプレート本文のプレースホルダーに埋め込む
 #[tauri::command]
 pub async fn apply_template_to_new_task(
     workspace_path: String,
     template_name: String,
     content: String,
+    title: Option<String>,
+    prompts: Option<HashMap<String, String>>,
 ) -> Result<String, String> {
     let config = WorkspaceService::load_config(&workspace_path)?;
 
     let template = config.templates.get_template(&template_name)
         .ok_or_else(|| format!("Template '{}' not found", template_name))?;
 
-    TemplateService::apply_template_to_new_task(template, &content)
+    let context = TemplateRenderContext::new(title, prompts);
+    TemplateService::apply_template_to_new_task(template, &content, &context)
+}
+
+/// テンプレート本文で入力を求める項目（`{{prompt:...}}`のラベル）を取得
+#[tauri::command]
+pub async fn get_template_prompts(
+    workspace_path: String,
+    template_name: String,
+) -> Result<Vec<String>, String> {
+    let config = WorkspaceService::load_config(&workspace_path)?;
+
+    let template = config.templates.get_template(&template_name)
+        .ok_or_else(|| format!("Template '{}' not found", template_name))?;
+
+    Ok(TemplateService::template_prompts(template))
 }
 
 /// テンプレートを既存タスクに適用
@@ -222,16 +244,19 @@ pub async fn create_template_from_task(
     Ok(template)
 }
 
-/// テンプレートのプレビューを取得
+/// テンプレートのプレビューを取得（本文のプレースホルダーも展開する）
 #[tauri::command]
 pub async fn preview_template(
     workspace_path: String,
     template_name: String,
+    title: Option<String>,
+    prompts: Option<HashMap<String, String>>,
 ) -> Result<String, String> {
     let config = WorkspaceService::load_config(&workspace_path)?;
 
     let template = config.templates.get_template(&template_name)
         .ok_or_else(|| format!("Template '{}' not found", template_name))?;
 
-    TemplateService::preview_template(template)
+    let context = TemplateRenderContext::new(title, prompts);
+    TemplateService::preview_template(template, &context)
 }
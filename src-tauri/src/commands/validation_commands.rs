@@ -0,0 +1,24 @@
+use crate::models::ValidationReport;
+use crate::service::{SchemaValidator, WorkspaceService};
+use std::path::PathBuf;
+
+/// ワークスペース内の全タスクをタグ設定に照らして検証
+///
+/// # Arguments
+/// * `workspace_path` - ワークスペースのルートパス
+///
+/// # Returns
+/// * `Result<ValidationReport, String>` - 検証結果
+#[tauri::command]
+pub async fn validate_workspace(workspace_path: String) -> Result<ValidationReport, String> {
+    let root_path = PathBuf::from(&workspace_path);
+    if !root_path.is_dir() {
+        return Err(format!("Path is not a directory: {}", workspace_path));
+    }
+
+    let workspace = WorkspaceService::new()
+        .load_workspace(root_path)
+        .map_err(|e| format!("Failed to load workspace: {}", e))?;
+
+    Ok(SchemaValidator::validate_workspace(&workspace))
+}
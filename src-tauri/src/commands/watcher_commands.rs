@@ -1,7 +1,14 @@
-use crate::service::FileWatcherService;
-use std::path::PathBuf;
+use crate::commands::workspace_commands::WorkspaceState;
+use crate::commands::index_commands::refresh_computed_tags;
+use crate::models::Workspace;
+use crate::parser::ComputedFields;
+use crate::repository::connection_manager::IndexConnectionManager;
+use crate::service::config_reload_service::{ConfigContent, ConfigFileKind};
+use crate::service::file_watcher::{FileChangeEvent, WatchEvent, WatchOptions};
+use crate::service::{ConfigReloadService, FileWatcherService, TaskSyncService, WorkspaceService};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 /// ファイルウォッチャーの状態管理
 pub struct WatcherState {
@@ -53,9 +60,22 @@ pub async fn start_file_watcher(
         existing_watcher.stop_watching();
     }
 
+    // 設定が読めない場合も既定の設定で監視する
+    let config = WorkspaceService::load_config(&workspace_path).unwrap_or_default();
+
     // 新しいウォッチャーを作成して開始
     let mut new_watcher = FileWatcherService::new();
-    new_watcher.start_watching(app_handle, &path)?;
+    let sync_handle = app_handle.clone();
+    let workspace_root = path.clone();
+    new_watcher.start_watching(
+        app_handle,
+        &path,
+        WatchOptions::from_config(&config),
+        move |event| match event {
+            WatchEvent::Task(change) => sync_task_change(&sync_handle, &workspace_root, change),
+            WatchEvent::Config(kind) => reload_config(&sync_handle, &workspace_root, *kind),
+        },
+    )?;
 
     *watcher_guard = Some(new_watcher);
 
@@ -85,3 +105,84 @@ pub async fn stop_file_watcher(state: State<'_, WatcherState>) -> Result<(), Str
         Err("No active file watcher".to_string())
     }
 }
+
+/// 外部で変更されたタスクをメモリ上のワークスペースとインデックスに反映し、`task-updated`を通知
+///
+/// インデックスの書き込み接続は全件構築中だと実行中のバッチのコミットまで待たされるため、
+/// フロントエンドへの通知を先に行ってからインデックスを更新する。
+fn sync_task_change(app_handle: &AppHandle, workspace_root: &Path, change: &FileChangeEvent) {
+    let mut update = match TaskSyncService::load_update(change) {
+        Ok(update) => update,
+        Err(e) => {
+            // 編集途中でFront Matterが壊れている場合などは次の保存を待つ
+            log::warn!("Failed to reload changed task {}: {}", change.path, e);
+            return;
+        }
+    };
+
+    if let Ok(mut guard) = app_handle.state::<WorkspaceState>().workspace.lock() {
+        if let Some(workspace) = guard.as_mut().filter(|w| w.root_path == workspace_root) {
+            TaskSyncService::apply_to_workspace(workspace, &mut update);
+        }
+    }
+
+    if let Err(e) = app_handle.emit("task-updated", &update) {
+        log::error!("Failed to emit task-updated event: {}", e);
+    }
+
+    let result = app_handle
+        .state::<IndexConnectionManager>()
+        .writer(workspace_root)
+        .map_err(|e| e.to_string())
+        .and_then(|writer| {
+            TaskSyncService::apply_to_index(&writer, workspace_root, change).map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        log::error!("Failed to update index for {}: {}", change.path, e);
+    }
+}
+
+/// 外部で変更された設定ファイルを検証して`config-changed`を通知
+///
+/// `.hienmark.json`やタグスキーマが正しく読み込めた場合は、メモリ上のワークスペース設定も置き換える。
+fn reload_config(app_handle: &AppHandle, workspace_root: &Path, kind: ConfigFileKind) {
+    let event = ConfigReloadService::load_change(workspace_root, kind);
+
+    let update_workspace = |apply: &dyn Fn(&mut Workspace)| {
+        if let Ok(mut guard) = app_handle.state::<WorkspaceState>().workspace.lock() {
+            if let Some(workspace) = guard.as_mut().filter(|w| w.root_path == workspace_root) {
+                apply(workspace);
+            }
+        }
+    };
+
+    match (&event.content, &event.error) {
+        (Some(ConfigContent::WorkspaceConfig(config)), _) => update_workspace(&|workspace| {
+            // タグ設定・計算フィールド・検証規則は.hienmark.jsonではなくタグスキーマから読み込まれる
+            let current = &mut workspace.config;
+            let tag_configs = std::mem::take(&mut current.tag_configs);
+            let computed_fields = std::mem::take(&mut current.computed_fields);
+            let validation_rules = std::mem::take(&mut current.validation_rules);
+            *current = (**config).clone();
+            current.tag_configs = tag_configs;
+            current.computed_fields = computed_fields;
+            current.validation_rules = validation_rules;
+        }),
+        (Some(ConfigContent::TagSchema(schema)), _) => {
+            update_workspace(&|workspace| {
+                workspace.apply_tag_schema(schema);
+            });
+            refresh_computed_tags(
+                &app_handle.state::<IndexConnectionManager>(),
+                workspace_root,
+                ComputedFields::from_schema(schema),
+            );
+        }
+        (_, Some(error)) => log::warn!("Ignoring invalid config {}: {}", event.path, error),
+        _ => {}
+    }
+
+    if let Err(e) = app_handle.emit("config-changed", &event) {
+        log::error!("Failed to emit config-changed event: {}", e);
+    }
+}
@@ -1,71 +1,189 @@
-use crate::models::Workspace;
-use crate::service::WorkspaceService;
-use std::path::PathBuf;
+use crate::commands::index_commands::{spawn_index_build, IndexState};
+use crate::models::{Severity, TagViolation, Workspace};
+use crate::repository::connection_manager::IndexConnectionManager;
+use crate::service::{SchemaValidator, TagSchemaService, WorkspaceService};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use tauri::{AppHandle, State};
 
 /// ワークスペース管理のためのTauriコマンド
 
+/// 開いているワークスペースの状態管理
+///
+/// タスクを変更するコマンドとファイルウォッチャー（外部での変更）が更新し続けるため、
+/// ディスクの内容を読み直さずに最新のタスクとタグインデックスを参照できる。
+pub struct WorkspaceState {
+    pub workspace: Mutex<Option<Workspace>>,
+}
+
+impl WorkspaceState {
+    pub fn new() -> Self {
+        Self {
+            workspace: Mutex::new(None),
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Option<Workspace>>, String> {
+        self.workspace
+            .lock()
+            .map_err(|e| format!("Failed to lock workspace state: {}", e))
+    }
+
+    /// ワークスペースを参照する
+    ///
+    /// `workspace_path`のワークスペースが開かれていればメモリ上の内容を使い、
+    /// 開かれていなければディスクから読み込む。
+    ///
+    /// # Arguments
+    /// * `workspace_path` - ワークスペースのルートパス
+    /// * `read` - ワークスペースから値を取り出す関数
+    pub fn read<T>(
+        &self,
+        workspace_path: &str,
+        read: impl FnOnce(&Workspace) -> T,
+    ) -> Result<T, String> {
+        {
+            let guard = self.lock()?;
+            if let Some(workspace) = guard
+                .as_ref()
+                .filter(|w| w.root_path == Path::new(workspace_path))
+            {
+                return Ok(read(workspace));
+            }
+        }
+        load_workspace(workspace_path).map(|workspace| read(&workspace))
+    }
+
+    /// `path`を含むワークスペースが開かれていれば更新する
+    ///
+    /// # Arguments
+    /// * `path` - ワークスペースのルート、またはワークスペース内のファイルのパス
+    /// * `update` - ワークスペースを更新する関数
+    ///
+    /// # Returns
+    /// * `Result<Option<T>, String>` - 更新関数の戻り値（該当するワークスペースが開かれていなければNone）
+    pub fn update<T>(
+        &self,
+        path: &Path,
+        update: impl FnOnce(&mut Workspace) -> T,
+    ) -> Result<Option<T>, String> {
+        let mut guard = self.lock()?;
+        Ok(guard
+            .as_mut()
+            .filter(|w| path.starts_with(&w.root_path))
+            .map(update))
+    }
+
+    /// ワークスペースを書き換える
+    ///
+    /// `workspace_path`のワークスペースが開かれていればメモリ上の内容をその場で書き換え、
+    /// 開かれていなければディスクから読み込んだものに適用する。
+    ///
+    /// # Arguments
+    /// * `workspace_path` - ワークスペースのルートパス
+    /// * `modify` - ワークスペースを書き換える関数
+    pub fn modify<T>(
+        &self,
+        workspace_path: &str,
+        modify: impl FnOnce(&mut Workspace) -> T,
+    ) -> Result<T, String> {
+        {
+            let mut guard = self.lock()?;
+            if let Some(workspace) = guard
+                .as_mut()
+                .filter(|w| w.root_path == Path::new(workspace_path))
+            {
+                return Ok(modify(workspace));
+            }
+        }
+        load_workspace(workspace_path).map(|mut workspace| modify(&mut workspace))
+    }
+}
+
+impl Default for WorkspaceState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// ワークスペースディレクトリを開く
 ///
+/// 旧形式のタグスキーマ・タグ設定があれば現在の形式に移行する。
+/// インデックスの再構築はバックグラウンドで開始され、完了を待たずに返る。
+/// 構築中は既存のキャッシュ（前回までの内容）がそのまま参照される。
+///
 /// # Arguments
+/// * `app_handle` - Tauriアプリハンドル
+/// * `index_state` - インデックスの状態
+/// * `workspace_state` - 開いているワークスペースの状態
 /// * `path` - ワークスペースのルートディレクトリパス
 ///
 /// # Returns
 /// * `Result<Workspace, String>` - 読み込まれたワークスペース
 #[tauri::command]
-pub async fn open_workspace(path: String) -> Result<Workspace, String> {
-    let root_path = PathBuf::from(&path);
-
-    // パスが存在するか確認
-    if !root_path.exists() {
-        return Err(format!("Path does not exist: {}", path));
+pub async fn open_workspace(
+    app_handle: AppHandle,
+    index_state: State<'_, IndexState>,
+    workspace_state: State<'_, WorkspaceState>,
+    path: String,
+) -> Result<Workspace, String> {
+    // 旧形式のタグスキーマ・タグ設定はワークスペースを開くときに移行する
+    if let Err(e) = TagSchemaService::migrate_schema(Path::new(&path)) {
+        log::warn!("Failed to migrate tag schema: {}", e);
     }
+    let workspace = load_workspace(&path)?;
 
-    if !root_path.is_dir() {
-        return Err(format!("Path is not a directory: {}", path));
+    *workspace_state.lock()? = Some(workspace.clone());
+
+    if let Err(e) = spawn_index_build(app_handle, &index_state, workspace.root_path.clone(), false) {
+        log::warn!("Failed to start index build: {}", e);
     }
 
-    let service = WorkspaceService::new();
-    service
-        .load_workspace(root_path)
-        .map_err(|e| format!("Failed to load workspace: {}", e))
+    Ok(workspace)
 }
 
 /// ワークスペース内のタスク一覧を取得
 ///
 /// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
 /// * `workspace_path` - ワークスペースのルートパス
 ///
 /// # Returns
 /// * `Result<Vec<String>, String>` - タスクIDのリスト
 #[tauri::command]
-pub async fn list_tasks(workspace_path: String) -> Result<Vec<String>, String> {
-    let workspace = open_workspace(workspace_path).await?;
-    let task_ids: Vec<String> = workspace.tasks.keys().cloned().collect();
-    Ok(task_ids)
+pub async fn list_tasks(
+    workspace_state: State<'_, WorkspaceState>,
+    workspace_path: String,
+) -> Result<Vec<String>, String> {
+    workspace_state.read(&workspace_path, |workspace| {
+        workspace.tasks.keys().cloned().collect()
+    })
 }
 
 /// 特定のタスクを読み込む
 ///
 /// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
 /// * `workspace_path` - ワークスペースのルートパス
 /// * `task_id` - タスクID
 ///
 /// # Returns
 /// * `Result<Task, String>` - 読み込まれたタスク
 #[tauri::command]
-pub async fn get_task(workspace_path: String, task_id: String) -> Result<crate::models::Task, String> {
-    let workspace = open_workspace(workspace_path).await?;
-
-    workspace
-        .tasks
-        .get(&task_id)
-        .cloned()
+pub async fn get_task(
+    workspace_state: State<'_, WorkspaceState>,
+    workspace_path: String,
+    task_id: String,
+) -> Result<crate::models::Task, String> {
+    workspace_state
+        .read(&workspace_path, |workspace| workspace.tasks.get(&task_id).cloned())?
         .ok_or_else(|| format!("Task not found: {}", task_id))
 }
 
 /// 新しいタスクを作成
 ///
 /// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
 /// * `workspace_path` - ワークスペースのルートパス
 /// * `task_id` - タスクID（ファイル名）
 /// * `content` - タスクの初期内容
@@ -74,6 +192,7 @@ pub async fn get_task(workspace_path: String, task_id: String) -> Result<crate::
 /// * `Result<Task, String>` - 作成されたタスク
 #[tauri::command]
 pub async fn create_task(
+    workspace_state: State<'_, WorkspaceState>,
     workspace_path: String,
     task_id: String,
     content: String,
@@ -81,48 +200,98 @@ pub async fn create_task(
     let root_path = PathBuf::from(&workspace_path);
 
     let service = WorkspaceService::new();
-    service
+    let task = service
         .create_task(&root_path, &task_id, &content)
-        .map_err(|e| format!("Failed to create task: {}", e))
+        .map_err(|e| format!("Failed to create task: {}", e))?;
+
+    let created = workspace_state.update(&root_path, |workspace| {
+        workspace.upsert_task(task.clone()).clone()
+    })?;
+    Ok(created.unwrap_or(task))
 }
 
 /// タスクを保存
 ///
+/// ワークスペースのタグスキーマ（ディスク上の設定）で検証し、Errorの違反がある場合は保存しない
+/// （タグ固定モードの違反と、重大度に`error`を指定した検証規則の違反）。
+/// ワークスペースを開いているかどうかに関係なく検証される。
+///
 /// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
+/// * `workspace_path` - タスクが属するワークスペースのルートパス
 /// * `task` - 保存するタスク
 ///
 /// # Returns
-/// * `Result<(), String>` - 保存結果
+/// * `Result<Vec<TagViolation>, String>` - 保存結果（Warningの違反は警告として返す）
 #[tauri::command]
-pub async fn save_task(task: crate::models::Task) -> Result<(), String> {
+pub async fn save_task(
+    workspace_state: State<'_, WorkspaceState>,
+    workspace_path: String,
+    task: crate::models::Task,
+) -> Result<Vec<TagViolation>, String> {
+    let root_path = PathBuf::from(&workspace_path);
+    if !task.file_path.starts_with(&root_path) {
+        return Err(format!(
+            "Task file is outside the workspace: {}",
+            task.file_path.display()
+        ));
+    }
+
+    let config = WorkspaceService::load_config(&workspace_path)?;
+    let violations = SchemaValidator::new(&config)
+        .with_workspace_root(&root_path)
+        .validate_task(&task.id, &task.front_matter);
+
+    if violations.iter().any(|v| v.severity == Severity::Error) {
+        let messages: Vec<_> = violations.iter().map(|v| v.message.as_str()).collect();
+        return Err(format!("Tag validation failed: {}", messages.join("; ")));
+    }
+
     let service = WorkspaceService::new();
     service
         .save_task(&task)
-        .map_err(|e| format!("Failed to save task: {}", e))
+        .map_err(|e| format!("Failed to save task: {}", e))?;
+
+    workspace_state.update(&root_path, |workspace| {
+        workspace.upsert_task(task);
+    })?;
+
+    Ok(violations)
 }
 
 /// タスクを削除
 ///
 /// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
 /// * `workspace_path` - ワークスペースのルートパス
 /// * `task_id` - 削除するタスクID
 ///
 /// # Returns
 /// * `Result<(), String>` - 削除結果
 #[tauri::command]
-pub async fn delete_task(workspace_path: String, task_id: String) -> Result<(), String> {
+pub async fn delete_task(
+    workspace_state: State<'_, WorkspaceState>,
+    workspace_path: String,
+    task_id: String,
+) -> Result<(), String> {
     let root_path = PathBuf::from(&workspace_path);
     let file_path = root_path.join(format!("{}.md", task_id));
 
     let service = WorkspaceService::new();
     service
         .delete_task(&file_path)
-        .map_err(|e| format!("Failed to delete task: {}", e))
+        .map_err(|e| format!("Failed to delete task: {}", e))?;
+
+    workspace_state.update(&root_path, |workspace| {
+        workspace.remove_task(&task_id);
+    })?;
+    Ok(())
 }
 
 /// タスクをリネーム
 ///
 /// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
 /// * `workspace_path` - ワークスペースのルートパス
 /// * `old_task_id` - 古いタスクID
 /// * `new_task_id` - 新しいタスクID
@@ -131,6 +300,7 @@ pub async fn delete_task(workspace_path: String, task_id: String) -> Result<(),
 /// * `Result<(), String>` - リネーム結果
 #[tauri::command]
 pub async fn rename_task(
+    workspace_state: State<'_, WorkspaceState>,
     workspace_path: String,
     old_task_id: String,
     new_task_id: String,
@@ -138,22 +308,52 @@ pub async fn rename_task(
     let root_path = PathBuf::from(&workspace_path);
 
     let service = WorkspaceService::new();
-    service
+    let rewritten = service
         .rename_task(&root_path, &old_task_id, &new_task_id)
-        .map_err(|e| format!("Failed to rename task: {}", e))
+        .map_err(|e| format!("Failed to rename task: {}", e))?;
+
+    // リネームしたタスクと、depends_on参照を書き換えたタスクを読み込み直す
+    let mut reload = vec![root_path.join(format!("{}.md", new_task_id))];
+    reload.extend(rewritten);
+    reload_tasks(&workspace_state, &root_path, Some(&old_task_id), &reload)
 }
 
 /// タグインデックスを取得
 ///
 /// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
 /// * `workspace_path` - ワークスペースのルートパス
 ///
 /// # Returns
 /// * `Result<TagIndex, String>` - タグインデックス
 #[tauri::command]
-pub async fn get_tag_index(workspace_path: String) -> Result<crate::models::TagIndex, String> {
-    let workspace = open_workspace(workspace_path).await?;
-    Ok(workspace.tag_index)
+pub async fn get_tag_index(
+    workspace_state: State<'_, WorkspaceState>,
+    workspace_path: String,
+) -> Result<crate::models::TagIndex, String> {
+    workspace_state.read(&workspace_path, |workspace| workspace.tag_index.clone())
+}
+
+/// 階層タグのツリーを取得（各ノードは配下を含めた件数を持つ）
+///
+/// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
+/// * `workspace_path` - ワークスペースのルートパス
+/// * `category` - タグカテゴリ名
+///
+/// # Returns
+/// * `Result<Vec<TagNode>, String>` - 最上位のノード（区切り文字はワークスペース設定に従う）
+#[tauri::command]
+pub async fn get_tag_hierarchy(
+    workspace_state: State<'_, WorkspaceState>,
+    workspace_path: String,
+    category: String,
+) -> Result<Vec<crate::models::TagNode>, String> {
+    workspace_state.read(&workspace_path, |workspace| {
+        workspace
+            .tag_index
+            .hierarchy(&category, &workspace.config.tag_separator)
+    })
 }
 
 /// ワークスペース設定を更新
@@ -189,6 +389,7 @@ pub async fn get_workspace_config(
 /// アセットファイルをワークスペースにコピー
 ///
 /// # Arguments
+/// * `connections` - インデックス接続の管理
 /// * `workspace_path` - ワークスペースのルートパス
 /// * `source_path` - コピー元ファイルのパス
 /// * `task_id` - アセットを関連付けるタスクID
@@ -197,16 +398,23 @@ pub async fn get_workspace_config(
 /// * `Result<String, String>` - 相対パス（Markdown記法用）
 #[tauri::command]
 pub async fn copy_asset_to_workspace(
+    connections: State<'_, IndexConnectionManager>,
     workspace_path: String,
     source_path: String,
     task_id: String,
 ) -> Result<String, String> {
     let workspace_root = PathBuf::from(&workspace_path);
     let source = PathBuf::from(&source_path);
-    
+
+    // インデックスが開けなくてもコピー自体は行う
+    let writer = connections
+        .writer(&workspace_root)
+        .map_err(|e| log::warn!("Failed to open index database: {}", e))
+        .ok();
+
     let service = WorkspaceService::new();
     service
-        .copy_asset_to_workspace(&workspace_root, &source, &task_id)
+        .copy_asset_to_workspace(writer.as_deref(), &workspace_root, &source, &task_id)
         .map_err(|e| format!("Failed to copy asset: {}", e))
 }
 
@@ -234,6 +442,7 @@ pub async fn create_folder(
 /// タスクをフォルダ間で移動
 ///
 /// # Arguments
+/// * `workspace_state` - 開いているワークスペースの状態
 /// * `workspace_path` - ワークスペースのルートパス
 /// * `task_id` - タスクID（ファイル名）
 /// * `source_path` - 移動元の相対パス
@@ -243,6 +452,7 @@ pub async fn create_folder(
 /// * `Result<(), String>` - 移動結果
 #[tauri::command]
 pub async fn move_task(
+    workspace_state: State<'_, WorkspaceState>,
     workspace_path: String,
     task_id: String,
     source_path: String,
@@ -253,5 +463,53 @@ pub async fn move_task(
     let service = WorkspaceService::new();
     service
         .move_task(&root_path, &task_id, &source_path, &dest_path)
-        .map_err(|e| format!("Failed to move task: {}", e))
+        .map_err(|e| format!("Failed to move task: {}", e))?;
+
+    let new_file_path = root_path.join(&dest_path).join(format!("{}.md", task_id));
+    reload_tasks(&workspace_state, &root_path, None, &[new_file_path])
+}
+
+/// ヘルパー: 変更したタスクファイルを読み込み直して開いているワークスペースに反映
+///
+/// `removed_task_id`が指定された場合は、そのタスクをワークスペースから取り除く。
+fn reload_tasks(
+    workspace_state: &WorkspaceState,
+    root_path: &Path,
+    removed_task_id: Option<&str>,
+    file_paths: &[PathBuf],
+) -> Result<(), String> {
+    let service = WorkspaceService::new();
+    workspace_state.update(root_path, |workspace| {
+        if let Some(task_id) = removed_task_id {
+            workspace.remove_task(task_id);
+        }
+        for file_path in file_paths {
+            match service.load_task(file_path) {
+                Ok(task) => {
+                    workspace.upsert_task(task);
+                }
+                Err(e) => log::warn!("Failed to reload task {}: {}", file_path.display(), e),
+            }
+        }
+    })?;
+    Ok(())
+}
+
+/// ヘルパー: ワークスペースを読み込む
+fn load_workspace(path: &str) -> Result<Workspace, String> {
+    let root_path = PathBuf::from(path);
+
+    // パスが存在するか確認
+    if !root_path.exists() {
+        return Err(format!("Path does not exist: {}", path));
+    }
+
+    if !root_path.is_dir() {
+        return Err(format!("Path is not a directory: {}", path));
+    }
+
+    let service = WorkspaceService::new();
+    service
+        .load_workspace(root_path)
+        .map_err(|e| format!("Failed to load workspace: {}", e))
 }
@@ -24,6 +24,9 @@ pub fn run() {
     .plugin(tauri_plugin_fs::init())
     .plugin(tauri_plugin_store::Builder::new().build())
     .manage(commands::watcher_commands::WatcherState::new())
+    .manage(commands::workspace_commands::WorkspaceState::new())
+    .manage(commands::index_commands::IndexState::new())
+    .manage(repository::connection_manager::IndexConnectionManager::new())
     .invoke_handler(tauri::generate_handler![
       commands::open_workspace,
       commands::list_tasks,
@@ -35,9 +38,14 @@ pub fn run() {
       commands::create_folder,
       commands::move_task,
       commands::get_tag_index,
+      commands::get_tag_hierarchy,
       commands::rename_tag,
+      commands::merge_tag_values,
+      commands::normalize_tags,
       commands::copy_asset_to_workspace,
       commands::delete_tag,
+      commands::rename_tag_category,
+      commands::merge_tag_categories,
       commands::get_workspace_config,
       commands::update_workspace_config,
       commands::start_file_watcher,
@@ -51,16 +59,23 @@ pub fn run() {
       commands::rename_template,
       commands::set_default_template,
       commands::apply_template_to_new_task,
+      commands::get_template_prompts,
       commands::apply_template_to_existing_task,
       commands::create_template_from_task,
       commands::preview_template,
       commands::get_filters_and_sorts,
       commands::save_filters_and_sorts,
+      commands::query_tasks,
       commands::get_app_settings,
       commands::save_app_settings,
       commands::load_tag_schema,
       commands::save_tag_schema,
       commands::get_dynamic_default_value,
+      commands::infer_tag_schema,
+      commands::save_inferred_schema,
+      commands::export_tag_bundle,
+      commands::preview_tag_bundle_import,
+      commands::import_tag_bundle,
       commands::generate_gantt_chart,
       commands::generate_pie_chart,
       commands::generate_bar_chart,
@@ -85,6 +100,17 @@ pub fn run() {
       // Gantt persistence
       commands::load_project,
       commands::save_project,
+      // Index
+      commands::start_index_build,
+      commands::cancel_index_build,
+      commands::rebuild_index,
+      commands::check_index_integrity,
+      // Assets
+      commands::list_orphaned_assets,
+      commands::list_missing_asset_references,
+      commands::collect_asset_garbage,
+      // Validation
+      commands::validate_workspace,
     ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+/// どのタスクからも参照されていないアセット
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedAsset {
+    /// ワークスペースルートからの相対パス
+    pub path: String,
+
+    /// ファイルサイズ（バイト）
+    #[serde(rename = "fileSize")]
+    pub file_size: u64,
+
+    /// 最終更新日時（Unix timestamp）
+    #[serde(rename = "modifiedAt")]
+    pub modified_at: i64,
+}
+
+/// アセットのガベージコレクション設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetGcOptions {
+    /// trueの場合は削除対象の一覧のみを返し、ファイルには触れない
+    #[serde(rename = "dryRun", default = "default_true")]
+    pub dry_run: bool,
+
+    /// trueの場合は削除せず`.hienmark/trash`に移動する
+    #[serde(rename = "useTrash", default = "default_true")]
+    pub use_trash: bool,
+
+    /// 更新からこの秒数が経過していないアセットは対象外（貼り付け直後で未保存のタスク対策）
+    #[serde(rename = "minAgeSecs", default = "default_min_age_secs")]
+    pub min_age_secs: u64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_min_age_secs() -> u64 {
+    24 * 60 * 60
+}
+
+impl Default for AssetGcOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: true,
+            use_trash: true,
+            min_age_secs: default_min_age_secs(),
+        }
+    }
+}
+
+/// アセットのガベージコレクション結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetGcReport {
+    /// ドライランかどうか
+    #[serde(rename = "dryRun")]
+    pub dry_run: bool,
+
+    /// 削除（またはゴミ箱へ移動）されたアセット。ドライランの場合は対象となるアセット
+    pub removed: Vec<OrphanedAsset>,
+
+    /// 猶予期間内のため対象外となった孤立アセット
+    #[serde(rename = "skippedRecent")]
+    pub skipped_recent: Vec<OrphanedAsset>,
+
+    /// 解放された（ドライランの場合は解放される）バイト数
+    #[serde(rename = "bytesFreed")]
+    pub bytes_freed: u64,
+
+    /// ゴミ箱の移動先ディレクトリ（ワークスペースルートからの相対パス）
+    #[serde(rename = "trashDir")]
+    pub trash_dir: Option<String>,
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// チャートDSL - 分析結果を構造化データとして表現
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +48,9 @@ pub struct LineChart {
     pub title: String,
     pub y_axis_label: String,
     pub series: Vec<TimeSeriesPoint>,
+    /// 通貨タグのメトリックの通貨コード別の系列（`series`と同じ日付、表示はこちらを優先）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub currency_series: BTreeMap<String, Vec<TimeSeriesPoint>>,
 }
 
 /// 棒グラフ用データ構造
@@ -23,6 +23,9 @@ pub enum ComparisonOperator {
     EndsWith,
     #[serde(rename = "regex")]
     Regex,
+    /// 階層タグの前方一致（`area/backend`は`area/backend`と`area/backend/api`に一致する）
+    #[serde(rename = "under")]
+    Under,
 }
 
 /// 論理演算子
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use crate::models::{Task, TagValue};
+use crate::models::tag::is_under_tag_path;
+use crate::models::typed_value::CurrencyAmount;
 use crate::models::filter_sort::{FilterExpression, FilterCondition, ComparisonOperator, LogicalOperator};
 
 /// メトリック計算タイプ
@@ -28,16 +31,10 @@ pub struct Metric {
 
 impl Metric {
     /// タスクのリストに対してメトリックを評価し、値を計算する
-    pub fn evaluate(&self, tasks: &[&Task]) -> f64 {
-        // フィルターを適用
-        let filtered_tasks: Vec<&Task> = if let Some(ref filter_expr) = self.filter_expression {
-            tasks.iter()
-                .filter(|task| Self::evaluate_filter_expression(task, filter_expr))
-                .copied()
-                .collect()
-        } else {
-            tasks.iter().copied().collect()
-        };
+    ///
+    /// `separator`は階層タグの区切り文字（`under`演算子で使用）
+    pub fn evaluate(&self, tasks: &[&Task], separator: &str) -> f64 {
+        let filtered_tasks = self.filter_tasks(tasks, separator);
 
         match self.calculation_type {
             MetricCalculationType::Count => filtered_tasks.len() as f64,
@@ -69,18 +66,76 @@ impl Metric {
         }
     }
 
+    /// 通貨タグのメトリックを通貨コードごとに評価する
+    ///
+    /// 異なる通貨の金額は合算せず、通貨コード別に合計・平均を計算する。
+    /// 通貨コードのない数値は`default_currency`の金額として扱い、指定がなければ集計しない。
+    /// 件数のメトリックは通貨に関係しないため空の結果を返す。
+    ///
+    /// # Arguments
+    /// * `tasks` - 対象のタスク
+    /// * `separator` - 階層タグの区切り文字
+    /// * `default_currency` - 通貨コードのない数値に適用する通貨
+    ///
+    /// # Returns
+    /// * `BTreeMap<String, f64>` - 通貨コード → 値
+    pub fn evaluate_by_currency(
+        &self,
+        tasks: &[&Task],
+        separator: &str,
+        default_currency: Option<&str>,
+    ) -> BTreeMap<String, f64> {
+        let Some(ref source_tag) = self.source_tag else {
+            return BTreeMap::new();
+        };
+
+        let mut totals: BTreeMap<String, (f64, usize)> = BTreeMap::new();
+        for task in self.filter_tasks(tasks, separator) {
+            let Some(amount) = task
+                .tag(source_tag)
+                .and_then(|value| CurrencyAmount::from_tag_value(value, default_currency))
+            else {
+                continue;
+            };
+            let total = totals.entry(amount.currency).or_insert((0.0, 0));
+            total.0 += amount.amount;
+            total.1 += 1;
+        }
+
+        totals
+            .into_iter()
+            .filter_map(|(currency, (sum, count))| match self.calculation_type {
+                MetricCalculationType::Count => None,
+                MetricCalculationType::Sum => Some((currency, sum)),
+                MetricCalculationType::Average => Some((currency, sum / count as f64)),
+            })
+            .collect()
+    }
+
+    /// フィルターに一致するタスクを抽出
+    fn filter_tasks<'a>(&self, tasks: &[&'a Task], separator: &str) -> Vec<&'a Task> {
+        if let Some(ref filter_expr) = self.filter_expression {
+            tasks.iter()
+                .filter(|task| Self::evaluate_filter_expression(task, filter_expr, separator))
+                .copied()
+                .collect()
+        } else {
+            tasks.to_vec()
+        }
+    }
+
     /// フィルター式を評価
-    fn evaluate_filter_expression(task: &Task, expression: &FilterExpression) -> bool {
+    fn evaluate_filter_expression(task: &Task, expression: &FilterExpression, separator: &str) -> bool {
         // 単一条件
         if let Some(ref condition) = expression.condition {
-            return Self::evaluate_condition(task, condition);
+            return Self::evaluate_condition(task, condition, separator);
         }
 
         // 複合式
         if let Some(ref expressions) = expression.expressions {
             let results: Vec<bool> = expressions
                 .iter()
-                .map(|expr| Self::evaluate_filter_expression(task, expr))
+                .map(|expr| Self::evaluate_filter_expression(task, expr, separator))
                 .collect();
 
             if let Some(ref op) = expression.logical_operator {
@@ -99,8 +154,8 @@ impl Metric {
     }
 
     /// 条件を評価
-    fn evaluate_condition(task: &Task, condition: &FilterCondition) -> bool {
-        let task_value = task.front_matter.tags.get(&condition.tag_key);
+    fn evaluate_condition(task: &Task, condition: &FilterCondition, separator: &str) -> bool {
+        let task_value = task.tag(&condition.tag_key);
 
         match condition.operator {
             ComparisonOperator::Equal => {
@@ -134,6 +189,19 @@ impl Metric {
                 // 簡易実装: containsと同じ扱い
                 Self::compare_string(task_value, &condition.value, |a, b| a.contains(b))
             }
+            ComparisonOperator::Under => {
+                let Some(prefix) = condition.value.as_str() else {
+                    return false;
+                };
+                // 配列タグはいずれかの要素が配下にあれば一致
+                match task_value {
+                    Some(TagValue::String(s)) => is_under_tag_path(s, prefix, separator),
+                    Some(TagValue::Array(items)) => {
+                        items.iter().any(|item| is_under_tag_path(item, prefix, separator))
+                    }
+                    _ => false,
+                }
+            }
         }
     }
 
@@ -187,7 +255,7 @@ impl Metric {
 
     /// タスクから数値を抽出
     fn extract_numeric_value(task: &Task, tag_key: &str) -> Option<f64> {
-        task.front_matter.tags.get(tag_key).and_then(|v| match v {
+        task.tag(tag_key).and_then(|v| match v {
             TagValue::Number(n) => Some(*n as f64),
             TagValue::Float(f) => Some(*f),
             _ => None,
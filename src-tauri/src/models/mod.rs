@@ -6,11 +6,20 @@ pub mod tag_config;
 pub mod filter_sort;
 pub mod chart;
 pub mod metric;
+pub mod asset;
+pub mod validation;
+pub mod tag_schema;
+pub mod tag_bundle;
+pub mod typed_value;
+pub mod schema_inference;
 
 pub use task::{Task, FrontMatter, TagValue};
-pub use tag::TagIndex;
-pub use workspace::{Workspace, WorkspaceConfig};
-pub use template::{TagTemplate, TemplateCollection};
+pub use tag::{
+    CategoryConflictPolicy, CategoryRenameReport, NormalizationReport, TagIndex, TagNode,
+    TagValueChange,
+};
+pub use workspace::{WatchBackend, Workspace, WorkspaceConfig};
+pub use template::{TagTemplate, TemplateCollection, TemplateRenderContext};
 pub use tag_config::TagConfigCollection;
 pub use filter_sort::{
     CustomFiltersAndSorts, 
@@ -19,3 +28,6 @@ pub use chart::{
     ChartOutput,
 };
 pub use metric::Metric;
+pub use asset::{AssetGcOptions, AssetGcReport, OrphanedAsset};
+pub use validation::{Severity, TagViolation, ValidationReport};
+pub use tag_schema::{TagSchema, TAG_SCHEMA_VERSION};
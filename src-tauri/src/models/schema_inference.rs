@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use super::tag_schema::TagDefinition;
+use super::TagValue;
+
+/// 既存タスクから推定したタグスキーマの提案
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaInference {
+    /// 走査したタスク数
+    #[serde(rename = "tasksScanned")]
+    pub tasks_scanned: usize,
+
+    /// タグごとの提案（タグ名順）
+    pub tags: Vec<InferredTag>,
+}
+
+/// 1つのタグについての提案
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InferredTag {
+    /// タグ名
+    pub name: String,
+
+    /// 提案するタグ定義（確認・編集してから`save_inferred_schema`で保存する）
+    pub definition: TagDefinition,
+
+    /// タグを持つタスクの割合（0.0〜1.0）
+    pub coverage: f64,
+
+    /// タグを持つタスク数
+    #[serde(rename = "taskCount")]
+    pub task_count: usize,
+
+    /// 出現した値と出現回数（多い順）
+    pub values: Vec<ValueCount>,
+
+    /// 提案した定義に合わない値、または欠落
+    pub outliers: Vec<TagOutlier>,
+
+    /// タグスキーマに既に定義があるか
+    pub existing: bool,
+}
+
+/// 値と出現回数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// 外れ値の種類
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OutlierKind {
+    /// 推定した型に合わない値
+    TypeMismatch,
+    /// 大文字・小文字だけが異なる表記ゆれ
+    CaseVariant,
+    /// 選択肢に含めなかった、1度しか使われていない値
+    RareValue,
+    /// 他の値から大きく外れた数値
+    OutOfRange,
+    /// 必須と推定したタグがない
+    Missing,
+}
+
+/// 推定した定義から外れた値
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagOutlier {
+    /// 対象タスクのID
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+
+    /// 外れ値の種類
+    pub kind: OutlierKind,
+
+    /// 対象の値（欠落の場合はNone）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<TagValue>,
+
+    /// 表示用のメッセージ
+    pub message: String,
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
+use super::tag_config::ValueNormalization;
 use super::{TagValue};
 
 /// 全タスクから集約されたタグ情報
@@ -12,6 +13,10 @@ pub struct TagIndex {
     /// 最終更新日時
     #[serde(rename = "updatedAt")]
     pub updated_at: chrono::DateTime<chrono::Utc>,
+
+    /// カテゴリ名 → 値の正規化ルール（タグ設定から設定される）
+    #[serde(skip)]
+    normalization: HashMap<String, ValueNormalization>,
 }
 
 impl TagIndex {
@@ -19,11 +24,32 @@ impl TagIndex {
         Self {
             categories: HashMap::new(),
             updated_at: chrono::Utc::now(),
+            normalization: HashMap::new(),
         }
     }
 
+    /// 値の正規化ルールを設定し、タスクのタグからインデックスを作り直す
+    ///
+    /// # Arguments
+    /// * `normalization` - カテゴリ名 → 正規化ルール
+    /// * `tasks` - タスクID → タグマップ
+    pub fn rebuild<'a>(
+        &mut self,
+        normalization: HashMap<String, ValueNormalization>,
+        tasks: impl IntoIterator<Item = (&'a String, &'a HashMap<String, TagValue>)>,
+    ) {
+        self.categories.clear();
+        self.normalization = normalization;
+        for (task_id, tags) in tasks {
+            self.index_task(task_id, tags);
+        }
+        self.updated_at = chrono::Utc::now();
+    }
+
     /// タスクのタグをインデックスに追加
     ///
+    /// 配列の値は要素ごとにカウントする。正規化ルールのあるカテゴリは正規の値でカウントする。
+    ///
     /// # Arguments
     /// * `task_id` - タスクID
     /// * `tags` - タスクのタグマップ
@@ -34,18 +60,188 @@ impl TagIndex {
                 .entry(category_name.clone())
                 .or_insert_with(|| TagCategory::new(category_name.clone()));
 
-            // タスクIDを追加（重複チェック）
-            if !category.task_ids.contains(&task_id.to_string()) {
-                category.task_ids.push(task_id.to_string());
-            }
+            category.task_ids.insert(task_id.to_string());
 
             // 値の出現回数をカウント
-            let value_str = tag_value.to_string_value();
-            *category.values.entry(value_str).or_insert(0) += 1;
+            for value in canonical_elements(tag_value, self.normalization.get(category_name)) {
+                *category.values.entry(value).or_insert(0) += 1;
+            }
         }
 
         self.updated_at = chrono::Utc::now();
     }
+
+    /// タスクのタグをインデックスから取り除く
+    ///
+    /// 出現回数が0になった値と、タスクがなくなったカテゴリは削除する。
+    ///
+    /// # Arguments
+    /// * `task_id` - タスクID
+    /// * `tags` - インデックス登録時のタグマップ
+    pub fn remove_task(&mut self, task_id: &str, tags: &HashMap<String, TagValue>) {
+        for (category_name, tag_value) in tags {
+            let Some(category) = self.categories.get_mut(category_name) else {
+                continue;
+            };
+
+            category.task_ids.remove(task_id);
+            for value in canonical_elements(tag_value, self.normalization.get(category_name)) {
+                if let Some(count) = category.values.get_mut(&value) {
+                    *count = count.saturating_sub(1);
+                    if *count == 0 {
+                        category.values.remove(&value);
+                    }
+                }
+            }
+
+            if category.task_ids.is_empty() {
+                self.categories.remove(category_name);
+            }
+        }
+
+        self.updated_at = chrono::Utc::now();
+    }
+
+    /// タスクのタグの変更をインデックスに反映
+    ///
+    /// # Arguments
+    /// * `task_id` - タスクID
+    /// * `old_tags` - 変更前のタグマップ
+    /// * `new_tags` - 変更後のタグマップ
+    pub fn update_task(
+        &mut self,
+        task_id: &str,
+        old_tags: &HashMap<String, TagValue>,
+        new_tags: &HashMap<String, TagValue>,
+    ) {
+        self.remove_task(task_id, old_tags);
+        self.index_task(task_id, new_tags);
+    }
+
+    /// カテゴリの値を区切り文字で階層化したツリーを取得
+    ///
+    /// # Arguments
+    /// * `category` - タグカテゴリ名
+    /// * `separator` - 階層の区切り文字（空の場合は階層化しない）
+    ///
+    /// # Returns
+    /// * `Vec<TagNode>` - 最上位のノード（名前順）。カテゴリがなければ空
+    pub fn hierarchy(&self, category: &str, separator: &str) -> Vec<TagNode> {
+        let Some(category) = self.categories.get(category) else {
+            return Vec::new();
+        };
+
+        let mut root = NodeBuilder::default();
+        for (value, count) in &category.values {
+            let mut node = &mut root;
+            for segment in split_tag_path(value, separator) {
+                node = node.children.entry(segment.to_string()).or_default();
+                node.total += count;
+            }
+            node.count += count;
+        }
+        root.into_nodes("", separator)
+    }
+}
+
+/// 階層タグの1ノード（例: `area/backend/api`の`backend`）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagNode {
+    /// このノードの名前（区切り文字で分けた1階層分）
+    pub name: String,
+
+    /// 最上位からのパス（例: `area/backend`）
+    pub path: String,
+
+    /// このパスちょうどの値の出現回数
+    pub count: usize,
+
+    /// 配下のノードを含めた出現回数
+    pub total: usize,
+
+    /// 子ノード（名前順）
+    pub children: Vec<TagNode>,
+}
+
+#[derive(Default)]
+struct NodeBuilder {
+    count: usize,
+    total: usize,
+    children: BTreeMap<String, NodeBuilder>,
+}
+
+impl NodeBuilder {
+    fn into_nodes(self, parent_path: &str, separator: &str) -> Vec<TagNode> {
+        self.children
+            .into_iter()
+            .map(|(name, builder)| {
+                let path = if parent_path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}{}{}", parent_path, separator, name)
+                };
+                TagNode {
+                    count: builder.count,
+                    total: builder.total,
+                    children: builder.into_nodes(&path, separator),
+                    name,
+                    path,
+                }
+            })
+            .collect()
+    }
+}
+
+/// インデックスでカウントする値（配列は要素ごと）
+///
+/// 正規化ルールがあれば正規の値に変換し、同じタスク内で重複した要素は1つにまとめる。
+pub fn canonical_elements(value: &TagValue, normalization: Option<&ValueNormalization>) -> Vec<String> {
+    let elements = match value {
+        TagValue::Array(items) => items.clone(),
+        other => vec![other.to_string_value()],
+    };
+    let Some(normalization) = normalization else {
+        return elements;
+    };
+    let mut canonical: Vec<String> = Vec::new();
+    for element in elements {
+        let element = normalization.canonical(&element);
+        if !canonical.contains(&element) {
+            canonical.push(element);
+        }
+    }
+    canonical
+}
+
+fn split_tag_path<'a>(value: &'a str, separator: &'a str) -> Vec<&'a str> {
+    if separator.is_empty() {
+        vec![value]
+    } else {
+        value.split(separator).collect()
+    }
+}
+
+/// 値が階層タグ`prefix`自身、またはその配下にあるか（`area/back`は`area/backend`を含まない）
+pub fn is_under_tag_path(value: &str, prefix: &str, separator: &str) -> bool {
+    if value == prefix {
+        return true;
+    }
+    !separator.is_empty()
+        && value
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(separator))
+}
+
+/// 階層タグを先頭から`depth`階層までに切り詰める（`depth`が0なら切り詰めない）
+pub fn truncate_tag_path(value: &str, separator: &str, depth: usize) -> String {
+    if depth == 0 || separator.is_empty() {
+        return value.to_string();
+    }
+    split_tag_path(value, separator)
+        .into_iter()
+        .take(depth)
+        .collect::<Vec<_>>()
+        .join(separator)
 }
 
 impl Default for TagIndex {
@@ -62,9 +258,9 @@ pub struct TagCategory {
     /// 出現した値とその使用回数
     pub values: HashMap<String, usize>,
 
-    /// このカテゴリを持つタスクID一覧
+    /// このカテゴリを持つタスクID一覧（ID順）
     #[serde(rename = "taskIds")]
-    pub task_ids: Vec<String>,
+    pub task_ids: BTreeSet<String>,
 }
 
 impl TagCategory {
@@ -72,7 +268,171 @@ impl TagCategory {
         Self {
             name,
             values: HashMap::new(),
-            task_ids: Vec::new(),
+            task_ids: BTreeSet::new(),
         }
     }
 }
+
+/// カテゴリ名の変更・統合で、移動先のカテゴリを既に持つタスクの扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CategoryConflictPolicy {
+    /// 移動先の値を残し、移動元の値は捨てる
+    #[default]
+    KeepTarget,
+    /// 移動元の値で上書きする
+    Overwrite,
+    /// 両方の値を重複なしの配列にまとめる
+    Combine,
+    /// タスクを変更せず、競合として報告する
+    Skip,
+}
+
+/// カテゴリ名の変更・統合の結果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CategoryRenameReport {
+    /// 更新されたタスク数
+    #[serde(rename = "tasksUpdated")]
+    pub tasks_updated: usize,
+
+    /// 両方のカテゴリを持っていたタスクID
+    pub conflicts: Vec<String>,
+
+    /// 競合のため変更しなかったタスクID（`Skip`の場合）
+    #[serde(rename = "skippedTasks")]
+    pub skipped_tasks: Vec<String>,
+
+    /// 更新されたテンプレート数
+    #[serde(rename = "templatesUpdated")]
+    pub templates_updated: usize,
+
+    /// タグスキーマを更新したか
+    #[serde(rename = "schemaUpdated")]
+    pub schema_updated: bool,
+
+    /// 更新された保存済みフィルタ・ソートの数
+    #[serde(rename = "filtersUpdated")]
+    pub filters_updated: usize,
+
+    /// 分析設定を更新したか
+    #[serde(rename = "analysisSettingsUpdated")]
+    pub analysis_settings_updated: bool,
+}
+
+impl CategoryRenameReport {
+    /// 別の結果を合算する（複数カテゴリの統合用）
+    pub fn absorb(&mut self, other: CategoryRenameReport) {
+        self.tasks_updated += other.tasks_updated;
+        self.conflicts.extend(other.conflicts);
+        self.skipped_tasks.extend(other.skipped_tasks);
+        self.templates_updated += other.templates_updated;
+        self.schema_updated |= other.schema_updated;
+        self.filters_updated += other.filters_updated;
+        self.analysis_settings_updated |= other.analysis_settings_updated;
+    }
+}
+
+/// タグ値の正規化で書き換わる1つの値
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagValueChange {
+    /// タスクID
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+
+    /// タグカテゴリ名
+    pub category: String,
+
+    /// 変更前の値
+    pub before: TagValue,
+
+    /// 変更後の値
+    pub after: TagValue,
+}
+
+/// タグ値の正規化の結果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NormalizationReport {
+    /// 書き換える（ドライランでは書き換えられる）値（タスクID・カテゴリ名順）
+    pub changes: Vec<TagValueChange>,
+
+    /// 更新されたタスク数（ドライランでは0）
+    #[serde(rename = "tasksUpdated")]
+    pub tasks_updated: usize,
+
+    /// ファイルを書き換えずに差分だけを返したか
+    #[serde(rename = "dryRun")]
+    pub dry_run: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hierarchy_rolls_up_counts() {
+        let mut index = TagIndex::new();
+        for (task_id, value) in [
+            ("a", "area/backend/api"),
+            ("b", "area/backend/api"),
+            ("c", "area/backend"),
+            ("d", "area/frontend"),
+            ("e", "client"),
+        ] {
+            let mut tags = HashMap::new();
+            tags.insert("area".to_string(), TagValue::String(value.to_string()));
+            index.index_task(task_id, &tags);
+        }
+
+        let tree = index.hierarchy("area", "/");
+        assert_eq!(tree.iter().map(|n| n.path.as_str()).collect::<Vec<_>>(), vec!["area", "client"]);
+        let area = &tree[0];
+        assert_eq!((area.count, area.total), (0, 4));
+        let backend = &area.children[0];
+        assert_eq!((backend.path.as_str(), backend.count, backend.total), ("area/backend", 1, 3));
+        assert_eq!(backend.children[0].path, "area/backend/api");
+        assert_eq!(backend.children[0].total, 2);
+
+        assert!(index.hierarchy("missing", "/").is_empty());
+        assert_eq!(index.hierarchy("area", "").len(), 4);
+    }
+
+    #[test]
+    fn test_index_counts_array_elements_and_updates_incrementally() {
+        let labels = |items: &[&str]| {
+            HashMap::from([(
+                "tags".to_string(),
+                TagValue::Array(items.iter().map(|s| s.to_string()).collect()),
+            )])
+        };
+        let mut index = TagIndex::new();
+        index.index_task("a", &labels(&["モバイル", "iOS"]));
+        index.index_task("b", &labels(&["iOS"]));
+
+        let category = &index.categories["tags"];
+        assert_eq!(category.values["iOS"], 2);
+        assert_eq!(category.values["モバイル"], 1);
+        assert_eq!(category.task_ids.iter().collect::<Vec<_>>(), vec!["a", "b"]);
+
+        index.update_task("a", &labels(&["モバイル", "iOS"]), &labels(&["Android"]));
+        let category = &index.categories["tags"];
+        assert_eq!(category.values["iOS"], 1);
+        assert_eq!(category.values["Android"], 1);
+        assert!(!category.values.contains_key("モバイル"));
+
+        index.remove_task("a", &labels(&["Android"]));
+        index.remove_task("b", &labels(&["iOS"]));
+        assert!(index.categories.is_empty());
+    }
+
+    #[test]
+    fn test_tag_path_helpers() {
+        assert!(is_under_tag_path("area/backend/api", "area/backend", "/"));
+        assert!(is_under_tag_path("area/backend", "area/backend", "/"));
+        assert!(!is_under_tag_path("area/backend-old", "area/backend", "/"));
+        assert!(!is_under_tag_path("area/backend/api", "area/backend", ""));
+
+        assert_eq!(truncate_tag_path("area/backend/api", "/", 2), "area/backend");
+        assert_eq!(truncate_tag_path("area", "/", 2), "area");
+        assert_eq!(truncate_tag_path("area/backend", "/", 0), "area/backend");
+    }
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::tag_schema::TagDefinition;
+use super::TagTemplate;
+
+/// タグ定義バンドルの現在のフォーマットバージョン
+pub const TAG_BUNDLE_VERSION: u32 = 1;
+
+/// ワークスペース間で共有するタグ定義とテンプレートのバンドル
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagBundle {
+    /// フォーマットバージョン
+    pub version: u32,
+
+    /// エクスポート日時
+    #[serde(rename = "exportedAt")]
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+
+    /// タグ名 → タグ定義
+    #[serde(default)]
+    pub tags: BTreeMap<String, TagDefinition>,
+
+    /// テンプレート名 → テンプレート
+    #[serde(default)]
+    pub templates: BTreeMap<String, TagTemplate>,
+}
+
+/// バンドルのファイル形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleFormat {
+    Json,
+    Yaml,
+}
+
+impl BundleFormat {
+    /// 拡張子から形式を判定（`.yaml`/`.yml`以外はJSON）
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                BundleFormat::Yaml
+            }
+            _ => BundleFormat::Json,
+        }
+    }
+}
+
+/// バンドル内の項目の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleItemKind {
+    Tag,
+    Template,
+}
+
+/// インポート先との比較結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BundleItemStatus {
+    /// インポート先に同名の項目がない
+    New,
+    /// 同名の項目があり、内容も同じ
+    Identical,
+    /// 同名の項目があり、内容が異なる
+    Conflict,
+}
+
+/// 同名の項目がある場合の扱い
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportAction {
+    /// インポートしない
+    #[default]
+    Skip,
+    /// バンドルの内容で上書きする
+    Overwrite,
+    /// 別名でインポートする
+    Rename,
+}
+
+/// 項目ごとのインポート方法の指定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResolution {
+    pub kind: BundleItemKind,
+    pub name: String,
+    pub action: ImportAction,
+    /// `Rename`の場合のインポート後の名前
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_name: Option<String>,
+}
+
+/// プレビューの1項目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleItemPreview {
+    pub kind: BundleItemKind,
+    pub name: String,
+    pub status: BundleItemStatus,
+}
+
+/// インポートのプレビュー（ファイルは変更しない）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPreview {
+    pub version: u32,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    pub items: Vec<BundleItemPreview>,
+}
+
+/// インポート結果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    /// 追加・上書きしたタグ（別名でインポートしたものは新しい名前）
+    pub tags_imported: Vec<String>,
+    /// 追加・上書きしたテンプレート（別名でインポートしたものは新しい名前）
+    pub templates_imported: Vec<String>,
+    /// スキップした項目（内容が同じだったものを含む）
+    pub skipped: Vec<BundleItemPreview>,
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use super::TagValue;
 
@@ -41,6 +41,99 @@ pub struct TagConfig {
     /// 説明文
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// 通貨型で通貨コードのない数値に適用する通貨（ISO 4217）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+
+    /// 値の正規化ルール（同義語・大文字小文字の扱い）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalization: Option<ValueNormalization>,
+}
+
+/// タグ値の正規化ルール
+///
+/// `done`・`Done`・`closed`のような表記ゆれを正規の値にまとめる。
+/// インデックスとチャートは正規の値で集計し、`normalize_tags`でファイルを書き換えられる。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValueNormalization {
+    /// 正規の値 → 同義語のリスト
+    #[serde(default)]
+    pub synonyms: BTreeMap<String, Vec<String>>,
+
+    /// 大文字・小文字の扱い
+    #[serde(rename = "caseFolding", default)]
+    pub case_folding: CaseFolding,
+}
+
+/// 正規化での大文字・小文字の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseFolding {
+    /// 区別する（正規の値・同義語と完全に一致した値だけをまとめる）
+    #[default]
+    Sensitive,
+    /// 区別せずに正規の値・同義語と照合する
+    Insensitive,
+    /// 区別せずに照合し、どれにも一致しない値は小文字にそろえる
+    Lower,
+}
+
+impl ValueNormalization {
+    /// 値を正規の値に変換（一致するルールがなければそのまま）
+    ///
+    /// # Arguments
+    /// * `value` - タグ値（配列の場合は1要素）
+    ///
+    /// # Returns
+    /// * `String` - 正規の値
+    pub fn canonical(&self, value: &str) -> String {
+        let key = self.fold(value);
+        // 正規の値そのものとの一致を同義語より優先する
+        if let Some(canonical) = self.synonyms.keys().find(|c| self.fold(c) == key) {
+            return canonical.clone();
+        }
+        for (canonical, synonyms) in &self.synonyms {
+            if synonyms.iter().any(|s| self.fold(s) == key) {
+                return canonical.clone();
+            }
+        }
+        match self.case_folding {
+            CaseFolding::Lower => key,
+            _ => value.to_string(),
+        }
+    }
+
+    /// 同じ値が複数の正規の値に割り当てられていないかを確認
+    ///
+    /// # Returns
+    /// * `Vec<String>` - 重複している値の説明（問題がなければ空）
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut owners: BTreeMap<String, &str> = BTreeMap::new();
+        let mut conflicts = Vec::new();
+        for (canonical, synonyms) in &self.synonyms {
+            for value in std::iter::once(canonical).chain(synonyms) {
+                match owners.get(&self.fold(value)) {
+                    Some(owner) if *owner != canonical.as_str() => conflicts.push(format!(
+                        "'{}' is mapped to both '{}' and '{}'",
+                        value, owner, canonical
+                    )),
+                    _ => {
+                        owners.insert(self.fold(value), canonical);
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// 照合用のキー（大文字・小文字を区別しない場合は小文字）
+    fn fold(&self, value: &str) -> String {
+        match self.case_folding {
+            CaseFolding::Sensitive => value.to_string(),
+            CaseFolding::Insensitive | CaseFolding::Lower => value.to_lowercase(),
+        }
+    }
 }
 
 /// タグの型定義
@@ -60,6 +153,14 @@ pub enum TagType {
     Date,
     /// 配列型（文字列の配列）
     Array,
+    /// 通貨型（`1200.50 USD`、または既定の通貨の金額）
+    Currency,
+    /// ハイパーリンク型（URL、または`[テキスト](URL)`）
+    Hyperlink,
+    /// 画像型（アセットディレクトリ内のファイルへのパス）
+    Image,
+    /// 日時型（時刻付きのISO 8601）
+    Datetime,
 }
 
 impl Default for TagConfig {
@@ -71,6 +172,8 @@ impl Default for TagConfig {
             default_value: None,
             required: false,
             description: None,
+            currency: None,
+            normalization: None,
         }
     }
 }
@@ -128,6 +231,19 @@ impl TagConfigCollection {
     pub fn set_config(&mut self, tag_name: String, config: TagConfig) {
         self.configs.insert(tag_name, config);
     }
+
+    /// 正規化ルールを持つタグのルール一覧
+    pub fn normalization_rules(&self) -> HashMap<String, ValueNormalization> {
+        self.configs
+            .iter()
+            .filter_map(|(name, config)| {
+                config
+                    .normalization
+                    .clone()
+                    .map(|rules| (name.clone(), rules))
+            })
+            .collect()
+    }
     
     /// デフォルトのタグ設定を作成
     pub fn create_default_configs() -> Self {
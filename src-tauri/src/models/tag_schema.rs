@@ -0,0 +1,923 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use super::tag_config::{
+    AllowedValueType, TagConfig, TagConfigCollection, TagType, ValueNormalization,
+};
+use super::typed_value::is_currency_code;
+use super::validation::Severity;
+use super::TagValue;
+use crate::parser::expression::ExpressionParser;
+
+/// `tag_schema.json`の現在のフォーマットバージョン
+pub const TAG_SCHEMA_VERSION: u32 = 1;
+
+/// タグスキーマ（`.hienmark/tag_schema.json`）
+///
+/// タグの属性（入力UIの種類と設定）と、旧`TagConfigCollection`が持っていた
+/// エイリアス・説明・必須指定を1つのファイルにまとめたもの。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagSchema {
+    /// フォーマットバージョン
+    pub version: u32,
+
+    /// タグ名 → タグ定義（ファイルの差分が安定するよう名前順で保存する）
+    #[serde(default)]
+    pub tags: BTreeMap<String, TagDefinition>,
+
+    /// 複数のタグにまたがる検証規則（定義順に評価する）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<ValidationRule>,
+}
+
+/// タスクごとに評価する、複数のタグにまたがる検証規則
+///
+/// ```json
+/// {"id": "close-needs-date", "when": "status == close", "require": ["completed_at"]}
+/// {"id": "end-after-start", "assert": "end_date >= start_date", "severity": "error"}
+/// ```
+///
+/// 式は計算フィールドと同じ構文で、計算フィールドの値も参照できる。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationRule {
+    /// 規則の識別子（違反の報告に使う）
+    pub id: String,
+
+    /// 規則を適用する条件の式（省略時は全タスク）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+
+    /// 値が必要なタグ
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub require: Vec<String>,
+
+    /// 満たすべき条件の式（参照するタグの値がなく評価できない場合は違反としない）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assert: Option<String>,
+
+    /// 違反時のメッセージ（省略時は規則の内容から作る）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// 重大度（指定した場合はタグ固定モードでなくても優先する。
+    /// 省略時はタグ固定モードならError、それ以外はWarning）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+}
+
+impl ValidationRule {
+    /// 違反時の重大度
+    ///
+    /// `error`を指定した規則はタグ固定モードでなくてもErrorになり、タスクの保存を止める。
+    pub fn severity_for(&self, strict_mode: bool) -> Severity {
+        self.severity.unwrap_or(if strict_mode {
+            Severity::Error
+        } else {
+            Severity::Warning
+        })
+    }
+}
+
+/// 1つのタグの定義
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagDefinition {
+    /// 属性の種類と設定（`{"type": ..., "options": {...}}`）
+    #[serde(flatten)]
+    pub attribute: TagAttribute,
+
+    /// タグのエイリアス（表示名）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+
+    /// 説明文
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// 必須かどうか
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub required: bool,
+
+    /// 値の正規化ルール（同義語・大文字小文字の扱い）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalization: Option<ValueNormalization>,
+}
+
+/// タグの属性
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "options")]
+pub enum TagAttribute {
+    String(StringAttributeOptions),
+    Number(NumberAttributeOptions),
+    Boolean(BooleanAttributeOptions),
+    Datetime(DatetimeAttributeOptions),
+    Select(SelectAttributeOptions),
+    MultiSelect(MultiSelectAttributeOptions),
+    Currency(CurrencyAttributeOptions),
+    Image(EmptyAttributeOptions),
+    Hyperlink(EmptyAttributeOptions),
+    /// ファイルには保存せず、他のタグから計算する読み取り専用のフィールド
+    Computed(ComputedAttributeOptions),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StringAttributeOptions {
+    #[serde(rename = "maxLength", default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+
+    /// 値全体が一致すべき正規表現
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
+    #[serde(
+        rename = "defaultValue",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_value: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NumberAttributeOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+
+    #[serde(
+        rename = "decimalPlaces",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub decimal_places: Option<u32>,
+
+    #[serde(
+        rename = "defaultValue",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_value: Option<f64>,
+
+    #[serde(
+        rename = "formatAsPercentage",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub format_as_percentage: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BooleanAttributeOptions {
+    #[serde(
+        rename = "defaultValue",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_value: Option<bool>,
+}
+
+/// 日時の表示形式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DatetimeFormat {
+    DateOnly,
+    DateTime,
+}
+
+/// 日時のデフォルト値
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DatetimeDefault {
+    /// 文字列をそのまま使う（古いスキーマの形式）
+    Literal(String),
+    Rule(DatetimeDefaultRule),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DatetimeDefaultRule {
+    /// 固定の日時
+    Static { value: String },
+    /// 計算式（例: `=[TODAY]+7`）
+    Dynamic { formula: String },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DatetimeAttributeOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<DatetimeFormat>,
+
+    #[serde(
+        rename = "defaultValue",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_value: Option<DatetimeDefault>,
+}
+
+/// 選択肢の表示形式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SelectDisplayFormat {
+    Dropdown,
+    Radio,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SelectAttributeOptions {
+    #[serde(rename = "optionsList", default)]
+    pub options_list: Vec<String>,
+
+    #[serde(
+        rename = "allowManualEntry",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub allow_manual_entry: Option<bool>,
+
+    #[serde(
+        rename = "defaultValue",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_value: Option<String>,
+
+    #[serde(
+        rename = "displayFormat",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub display_format: Option<SelectDisplayFormat>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MultiSelectAttributeOptions {
+    #[serde(rename = "optionsList", default)]
+    pub options_list: Vec<String>,
+
+    #[serde(
+        rename = "allowManualEntry",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub allow_manual_entry: Option<bool>,
+
+    #[serde(
+        rename = "defaultValue",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_value: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CurrencyAttributeOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+
+    #[serde(
+        rename = "decimalPlaces",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub decimal_places: Option<u32>,
+
+    #[serde(
+        rename = "defaultValue",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_value: Option<f64>,
+
+    /// ISO 4217の通貨コード（例: `JPY`, `USD`）
+    #[serde(
+        rename = "currencyFormat",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub currency_format: Option<String>,
+}
+
+/// 計算フィールドの設定
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComputedAttributeOptions {
+    /// 計算式（例: `end_date - start_date`）
+    pub expression: String,
+}
+
+/// 設定項目のない属性（画像・ハイパーリンク）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EmptyAttributeOptions {}
+
+impl Default for TagSchema {
+    fn default() -> Self {
+        Self {
+            version: TAG_SCHEMA_VERSION,
+            tags: BTreeMap::new(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl TagSchema {
+    /// スキーマの内容を検証
+    ///
+    /// JSONの構造はデシリアライズ時に検証されるため、ここでは値同士の整合性を確認する。
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - 問題がある場合は全ての問題をまとめたメッセージ
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+
+        if self.version != TAG_SCHEMA_VERSION {
+            errors.push(format!(
+                "Unsupported tag schema version: {} (expected {})",
+                self.version, TAG_SCHEMA_VERSION
+            ));
+        }
+
+        for (name, definition) in &self.tags {
+            if name.trim().is_empty() {
+                errors.push("Tag name must not be empty".to_string());
+            }
+            definition.attribute.check(name, &mut errors);
+            if let Some(normalization) = &definition.normalization {
+                for conflict in normalization.conflicts() {
+                    errors.push(format!("{}: {}", name, conflict));
+                }
+            }
+        }
+        self.check_computed_cycles(&mut errors);
+        self.check_rules(&mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// 検証用のタグ設定に変換（計算フィールドはファイルに書かれないため含めない）
+    pub fn to_tag_configs(&self) -> TagConfigCollection {
+        let mut collection = TagConfigCollection::new();
+        for (name, definition) in &self.tags {
+            if matches!(definition.attribute, TagAttribute::Computed(_)) {
+                continue;
+            }
+            collection.set_config(name.clone(), definition.to_tag_config());
+        }
+        collection
+    }
+
+    /// 検証規則の識別子の重複と式の構文を確認
+    fn check_rules(&self, errors: &mut Vec<String>) {
+        let mut ids = HashSet::new();
+        for rule in &self.rules {
+            if rule.id.trim().is_empty() {
+                errors.push("Rule id must not be empty".to_string());
+            } else if !ids.insert(rule.id.as_str()) {
+                errors.push(format!("Duplicate rule id: {}", rule.id));
+            }
+            if rule.require.is_empty() && rule.assert.is_none() {
+                errors.push(format!("rule {}: either require or assert is needed", rule.id));
+            }
+            for (label, source) in [("when", &rule.when), ("assert", &rule.assert)] {
+                if let Some(Err(e)) = source.as_deref().map(ExpressionParser::parse) {
+                    errors.push(format!("rule {}: invalid {} expression: {}", rule.id, label, e));
+                }
+            }
+        }
+    }
+
+    /// 計算フィールド同士の循環参照を検出
+    fn check_computed_cycles(&self, errors: &mut Vec<String>) {
+        let dependencies: BTreeMap<&str, Vec<&str>> = self
+            .tags
+            .iter()
+            .filter_map(|(name, definition)| match &definition.attribute {
+                TagAttribute::Computed(options) => Some((name.as_str(), options)),
+                _ => None,
+            })
+            .filter_map(|(name, options)| {
+                ExpressionParser::parse(&options.expression)
+                    .ok()
+                    .map(|expression| (name, expression))
+            })
+            .map(|(name, expression)| {
+                let references = expression
+                    .names()
+                    .into_iter()
+                    .filter_map(|n| self.tags.get_key_value(n))
+                    .map(|(k, _)| k.as_str())
+                    .collect();
+                (name, references)
+            })
+            .collect();
+
+        let mut reported = HashSet::new();
+        for start in dependencies.keys() {
+            let mut path = vec![*start];
+            if let Some(cycle) = find_cycle(&dependencies, &mut path) {
+                if reported.insert(cycle.iter().min().copied()) {
+                    errors.push(format!("Circular reference: {}", cycle.join(" -> ")));
+                }
+            }
+        }
+    }
+}
+
+impl From<&TagConfigCollection> for TagSchema {
+    fn from(collection: &TagConfigCollection) -> Self {
+        Self {
+            version: TAG_SCHEMA_VERSION,
+            tags: collection
+                .configs
+                .iter()
+                .map(|(name, config)| (name.clone(), TagDefinition::from(config)))
+                .collect(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl TagDefinition {
+    /// 検証用のタグ設定に変換
+    ///
+    /// 計算フィールドは文字列として扱う（値を書き込むこと自体が読み取り専用違反になる）。
+    pub fn to_tag_config(&self) -> TagConfig {
+        let (tag_type, allowed_value_type, default_value) = match &self.attribute {
+            TagAttribute::String(options) => (
+                TagType::String,
+                options.pattern.clone().map(AllowedValueType::Pattern),
+                options.default_value.clone().map(TagValue::String),
+            ),
+            TagAttribute::Number(NumberAttributeOptions {
+                min,
+                max,
+                default_value,
+                ..
+            }) => (
+                TagType::Number,
+                range(*min, *max),
+                default_value.map(number_value),
+            ),
+            TagAttribute::Currency(CurrencyAttributeOptions {
+                min,
+                max,
+                default_value,
+                ..
+            }) => (
+                TagType::Currency,
+                range(*min, *max),
+                default_value.map(number_value),
+            ),
+            TagAttribute::Boolean(options) => (
+                TagType::Boolean,
+                None,
+                options.default_value.map(TagValue::Bool),
+            ),
+            TagAttribute::Datetime(options) => (
+                match options.format {
+                    Some(DatetimeFormat::DateTime) => TagType::Datetime,
+                    _ => TagType::Date,
+                },
+                None,
+                match &options.default_value {
+                    Some(DatetimeDefault::Literal(value))
+                    | Some(DatetimeDefault::Rule(DatetimeDefaultRule::Static { value })) => {
+                        Some(TagValue::String(value.clone()))
+                    }
+                    // 計算式は値の作成時に評価する
+                    _ => None,
+                },
+            ),
+            TagAttribute::Select(options) => (
+                TagType::Select,
+                options_list(&options.options_list, options.allow_manual_entry),
+                options.default_value.clone().map(TagValue::String),
+            ),
+            TagAttribute::MultiSelect(options) => (
+                TagType::MultiSelect,
+                options_list(&options.options_list, options.allow_manual_entry),
+                options.default_value.clone().map(TagValue::Array),
+            ),
+            TagAttribute::Image(_) => (TagType::Image, None, None),
+            TagAttribute::Hyperlink(_) => (TagType::Hyperlink, None, None),
+            TagAttribute::Computed(_) => (TagType::String, None, None),
+        };
+
+        TagConfig {
+            alias: self.alias.clone(),
+            tag_type,
+            allowed_value_type,
+            default_value,
+            required: self.required,
+            description: self.description.clone(),
+            currency: match &self.attribute {
+                TagAttribute::Currency(options) => options.currency_format.clone(),
+                _ => None,
+            },
+            normalization: self.normalization.clone(),
+        }
+    }
+}
+
+impl From<&TagConfig> for TagDefinition {
+    /// 旧形式のタグ設定から変換（`.hienmark.json`からの移行用）
+    fn from(config: &TagConfig) -> Self {
+        let list = match &config.allowed_value_type {
+            Some(AllowedValueType::List(values)) => Some(values.clone()),
+            _ => None,
+        };
+        let (min, max) = match config.allowed_value_type {
+            // 片側だけの範囲は無限大で表されている
+            Some(AllowedValueType::Range { min, max }) => {
+                (Some(min).filter(|v| v.is_finite()), Some(max).filter(|v| v.is_finite()))
+            }
+            _ => (None, None),
+        };
+        let default_text = config.default_value.as_ref().map(TagValue::to_string_value);
+
+        let attribute = match config.tag_type {
+            TagType::String => TagAttribute::String(StringAttributeOptions {
+                max_length: None,
+                pattern: match &config.allowed_value_type {
+                    Some(AllowedValueType::Pattern(pattern)) => Some(pattern.clone()),
+                    _ => None,
+                },
+                default_value: default_text,
+            }),
+            TagType::Select => TagAttribute::Select(SelectAttributeOptions {
+                allow_manual_entry: Some(list.is_none()),
+                options_list: list.unwrap_or_default(),
+                default_value: default_text,
+                display_format: None,
+            }),
+            TagType::MultiSelect | TagType::Array => {
+                TagAttribute::MultiSelect(MultiSelectAttributeOptions {
+                    allow_manual_entry: Some(list.is_none()),
+                    options_list: list.unwrap_or_default(),
+                    default_value: match &config.default_value {
+                        Some(TagValue::Array(values)) => Some(values.clone()),
+                        Some(other) => Some(vec![other.to_string_value()]),
+                        None => None,
+                    },
+                })
+            }
+            TagType::Number => TagAttribute::Number(NumberAttributeOptions {
+                min,
+                max,
+                decimal_places: None,
+                default_value: match config.default_value {
+                    Some(TagValue::Number(n)) => Some(n as f64),
+                    Some(TagValue::Float(f)) => Some(f),
+                    _ => None,
+                },
+                format_as_percentage: None,
+            }),
+            TagType::Boolean => TagAttribute::Boolean(BooleanAttributeOptions {
+                default_value: match config.default_value {
+                    Some(TagValue::Bool(b)) => Some(b),
+                    _ => None,
+                },
+            }),
+            TagType::Date | TagType::Datetime => TagAttribute::Datetime(DatetimeAttributeOptions {
+                format: Some(match config.tag_type {
+                    TagType::Datetime => DatetimeFormat::DateTime,
+                    _ => DatetimeFormat::DateOnly,
+                }),
+                default_value: default_text.map(DatetimeDefault::Literal),
+            }),
+            TagType::Currency => TagAttribute::Currency(CurrencyAttributeOptions {
+                min,
+                max,
+                decimal_places: None,
+                default_value: match config.default_value {
+                    Some(TagValue::Number(n)) => Some(n as f64),
+                    Some(TagValue::Float(f)) => Some(f),
+                    _ => None,
+                },
+                currency_format: config.currency.clone(),
+            }),
+            TagType::Hyperlink => TagAttribute::Hyperlink(EmptyAttributeOptions {}),
+            TagType::Image => TagAttribute::Image(EmptyAttributeOptions {}),
+        };
+
+        Self {
+            attribute,
+            alias: config.alias.clone(),
+            description: config.description.clone(),
+            required: config.required,
+            normalization: config.normalization.clone(),
+        }
+    }
+}
+
+impl TagAttribute {
+    /// 属性の設定値同士の整合性を確認
+    fn check(&self, name: &str, errors: &mut Vec<String>) {
+        match self {
+            TagAttribute::String(options) => {
+                if let (Some(max_length), Some(default)) =
+                    (options.max_length, &options.default_value)
+                {
+                    if default.chars().count() > max_length {
+                        errors.push(format!(
+                            "{}: default value is longer than maxLength ({})",
+                            name, max_length
+                        ));
+                    }
+                }
+            }
+            TagAttribute::Number(NumberAttributeOptions {
+                min,
+                max,
+                default_value,
+                ..
+            }) => check_range(name, *min, *max, *default_value, errors),
+            TagAttribute::Currency(options) => {
+                check_range(
+                    name,
+                    options.min,
+                    options.max,
+                    options.default_value,
+                    errors,
+                );
+                if let Some(code) = &options.currency_format {
+                    if !is_currency_code(code) {
+                        errors.push(format!(
+                            "{}: currencyFormat must be a 3-letter currency code: {}",
+                            name, code
+                        ));
+                    }
+                }
+            }
+            TagAttribute::Select(options) => {
+                check_options(name, &options.options_list, errors);
+                let defaults: Vec<_> = options.default_value.iter().collect();
+                check_defaults(name, options, &defaults, errors);
+            }
+            TagAttribute::MultiSelect(options) => {
+                check_options(name, &options.options_list, errors);
+                let defaults: Vec<_> = options.default_value.iter().flatten().collect();
+                check_defaults(name, options, &defaults, errors);
+            }
+            TagAttribute::Boolean(_)
+            | TagAttribute::Datetime(_)
+            | TagAttribute::Image(_)
+            | TagAttribute::Hyperlink(_) => {}
+            TagAttribute::Computed(options) => {
+                if let Err(e) = ExpressionParser::parse(&options.expression) {
+                    errors.push(format!("{}: invalid expression: {}", name, e));
+                }
+            }
+        }
+    }
+}
+
+/// 依存関係を深さ優先でたどり、最初に見つかった循環を返す
+fn find_cycle<'a>(
+    dependencies: &BTreeMap<&'a str, Vec<&'a str>>,
+    path: &mut Vec<&'a str>,
+) -> Option<Vec<&'a str>> {
+    let current = *path.last()?;
+    for next in dependencies.get(current).into_iter().flatten() {
+        if let Some(position) = path.iter().position(|n| n == next) {
+            let mut cycle = path[position..].to_vec();
+            cycle.push(next);
+            return Some(cycle);
+        }
+        if dependencies.contains_key(next) {
+            path.push(next);
+            if let Some(cycle) = find_cycle(dependencies, path) {
+                return Some(cycle);
+            }
+            path.pop();
+        }
+    }
+    None
+}
+
+/// 選択肢を持つ属性の共通部分
+trait ListOptions {
+    fn options_list(&self) -> &[String];
+    fn allows_manual_entry(&self) -> bool;
+}
+
+impl ListOptions for SelectAttributeOptions {
+    fn options_list(&self) -> &[String] {
+        &self.options_list
+    }
+
+    fn allows_manual_entry(&self) -> bool {
+        self.allow_manual_entry.unwrap_or(false)
+    }
+}
+
+impl ListOptions for MultiSelectAttributeOptions {
+    fn options_list(&self) -> &[String] {
+        &self.options_list
+    }
+
+    fn allows_manual_entry(&self) -> bool {
+        self.allow_manual_entry.unwrap_or(false)
+    }
+}
+
+fn check_range(
+    name: &str,
+    min: Option<f64>,
+    max: Option<f64>,
+    default_value: Option<f64>,
+    errors: &mut Vec<String>,
+) {
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            errors.push(format!(
+                "{}: min ({}) is greater than max ({})",
+                name, min, max
+            ));
+        }
+    }
+    if let Some(default) = default_value {
+        if min.is_some_and(|min| default < min) || max.is_some_and(|max| default > max) {
+            errors.push(format!(
+                "{}: default value {} is out of range",
+                name, default
+            ));
+        }
+    }
+}
+
+fn check_options(name: &str, options_list: &[String], errors: &mut Vec<String>) {
+    let mut seen = HashSet::new();
+    for option in options_list {
+        if !seen.insert(option) {
+            errors.push(format!("{}: duplicate option: {}", name, option));
+        }
+    }
+}
+
+fn check_defaults(
+    name: &str,
+    options: &impl ListOptions,
+    defaults: &[&String],
+    errors: &mut Vec<String>,
+) {
+    if options.allows_manual_entry() {
+        return;
+    }
+    for default in defaults {
+        if !options.options_list().contains(default) {
+            errors.push(format!(
+                "{}: default value is not in optionsList: {}",
+                name, default
+            ));
+        }
+    }
+}
+
+fn range(min: Option<f64>, max: Option<f64>) -> Option<AllowedValueType> {
+    if min.is_none() && max.is_none() {
+        return None;
+    }
+    Some(AllowedValueType::Range {
+        min: min.unwrap_or(f64::NEG_INFINITY),
+        max: max.unwrap_or(f64::INFINITY),
+    })
+}
+
+fn options_list(options: &[String], allow_manual_entry: Option<bool>) -> Option<AllowedValueType> {
+    if allow_manual_entry.unwrap_or(false) {
+        Some(AllowedValueType::DirectInput)
+    } else {
+        Some(AllowedValueType::List(options.to_vec()))
+    }
+}
+
+fn number_value(value: f64) -> TagValue {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        TagValue::Number(value as i64)
+    } else {
+        TagValue::Float(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_legacy_conversion() {
+        let json = r#"{
+            "version": 1,
+            "tags": {
+                "status": {
+                    "type": "Select",
+                    "options": {"optionsList": ["open", "close"], "defaultValue": "open", "displayFormat": "radio"},
+                    "alias": "ステータス",
+                    "required": true
+                },
+                "due": {"type": "Datetime", "options": {"defaultValue": {"type": "dynamic", "formula": "=[TODAY]+7"}}},
+                "cover": {"type": "Image", "options": {}},
+                "link": {"type": "Hyperlink", "options": {}},
+                "price": {"type": "Currency", "options": {"min": 0.0, "currencyFormat": "USD"}},
+                "started": {"type": "Datetime", "options": {"format": "dateTime"}}
+            }
+        }"#;
+        let schema: TagSchema = serde_json::from_str(json).unwrap();
+        assert!(schema.validate().is_ok());
+
+        let serialized = serde_json::to_string(&schema).unwrap();
+        assert_eq!(
+            serde_json::from_str::<TagSchema>(&serialized).unwrap(),
+            schema
+        );
+
+        let configs = schema.to_tag_configs();
+        let status = &configs.configs["status"];
+        assert!(status.required);
+        assert!(matches!(
+            status.allowed_value_type,
+            Some(AllowedValueType::List(ref values)) if values.len() == 2
+        ));
+        assert!(configs.configs["due"].default_value.is_none());
+        assert!(matches!(configs.configs["due"].tag_type, TagType::Date));
+        assert!(matches!(configs.configs["started"].tag_type, TagType::Datetime));
+        assert!(matches!(configs.configs["cover"].tag_type, TagType::Image));
+        assert!(matches!(configs.configs["link"].tag_type, TagType::Hyperlink));
+        assert!(matches!(configs.configs["price"].tag_type, TagType::Currency));
+        assert_eq!(configs.configs["price"].currency.as_deref(), Some("USD"));
+
+        // 通貨・画像・ハイパーリンク・日時は旧形式のタグ設定を経由しても属性が変わらない
+        for name in ["cover", "link", "price", "started"] {
+            assert_eq!(
+                TagDefinition::from(&configs.configs[name]).attribute,
+                schema.tags[name].attribute,
+                "{}",
+                name
+            );
+        }
+
+        // 旧形式のタグ設定はリスト・パターン・範囲の制約を保ったまま変換される
+        let legacy = TagSchema::from(&TagConfigCollection::create_default_configs());
+        let back = legacy.to_tag_configs();
+        assert!(matches!(
+            back.configs["priority"].allowed_value_type,
+            Some(AllowedValueType::List(ref values)) if values.contains(&"urgent".to_string())
+        ));
+        assert!(matches!(back.configs["due_date"].tag_type, TagType::Date));
+        assert!(legacy.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_reports_inconsistent_options() {
+        let json = r#"{
+            "version": 2,
+            "tags": {
+                "estimate": {"type": "Number", "options": {"min": 10, "max": 1}},
+                "price": {"type": "Currency", "options": {"currencyFormat": "yen"}},
+                "kind": {"type": "Select", "options": {"optionsList": ["a", "a"], "defaultValue": "b"}},
+                "start": {"type": "Computed", "options": {"expression": "end - 1"}},
+                "end": {"type": "Computed", "options": {"expression": "start + 1"}},
+                "score": {"type": "Computed", "options": {"expression": "(effort"}},
+                "status": {
+                    "type": "Select",
+                    "options": {"optionsList": ["done", "open"]},
+                    "normalization": {
+                        "synonyms": {"done": ["closed"], "open": ["Closed"]},
+                        "caseFolding": "insensitive"
+                    }
+                }
+            },
+            "rules": [
+                {"id": "due", "assert": "due >= start"},
+                {"id": "due", "when": "status == (", "require": ["done_at"]},
+                {"id": "empty"}
+            ]
+        }"#;
+        let schema: TagSchema = serde_json::from_str(json).unwrap();
+        let errors = schema.validate().unwrap_err();
+        assert!(errors.contains("Unsupported tag schema version: 2"));
+        assert!(errors.contains("estimate: min (10) is greater than max (1)"));
+        assert!(errors.contains("price: currencyFormat"));
+        assert!(errors.contains("kind: duplicate option: a"));
+        assert!(errors.contains("kind: default value is not in optionsList: b"));
+        assert!(errors.contains("Circular reference: end -> start -> end"));
+        assert!(errors.contains("score: invalid expression: Expected ')' (column 8)"));
+        assert!(errors.contains("status: 'Closed' is mapped to both 'done' and 'open'"));
+        assert!(errors.contains("Duplicate rule id: due"));
+        assert!(errors.contains("rule due: invalid when expression"));
+        assert!(errors.contains("rule empty: either require or assert is needed"));
+        assert!(!schema.to_tag_configs().configs.contains_key("score"));
+        assert!(schema.to_tag_configs().configs["status"].normalization.is_some());
+
+        // 型の誤りはデシリアライズの時点でエラーになる
+        let invalid =
+            r#"{"version": 1, "tags": {"x": {"type": "Number", "options": {"min": "low"}}}}"#;
+        assert!(serde_json::from_str::<TagSchema>(invalid).is_err());
+    }
+}
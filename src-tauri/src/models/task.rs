@@ -26,10 +26,27 @@ pub struct Task {
     /// Front Matterのタグ順序
     #[serde(rename = "tagOrder", skip_serializing_if = "Option::is_none")]
     pub tag_order: Option<Vec<String>>,
+
+    /// 計算フィールドの値（読み取り専用、ファイルには書き込まない）
+    #[serde(
+        rename = "computedTags",
+        default,
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub computed_tags: HashMap<String, TagValue>,
+}
+
+impl Task {
+    /// タグの値を取得（計算フィールドを優先し、なければFront Matterを参照）
+    pub fn tag(&self, key: &str) -> Option<&TagValue> {
+        self.computed_tags
+            .get(key)
+            .or_else(|| self.front_matter.tags.get(key))
+    }
 }
 
 /// Front Matterデータ構造
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct FrontMatter {
     /// 任意のキー・バリューペア
     #[serde(flatten)]
@@ -37,7 +54,7 @@ pub struct FrontMatter {
 }
 
 /// タグ値 (文字列、数値、配列、日付など)
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(untagged)]
 pub enum TagValue {
     String(String),
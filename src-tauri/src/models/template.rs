@@ -16,6 +16,10 @@ pub struct TagTemplate {
     /// デフォルトタグ値
     pub tags: HashMap<String, TagValue>,
 
+    /// タスク本文のひな形（Markdown、`{{title}}`などのプレースホルダーを含められる）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+
     /// 作成日時
     #[serde(rename = "createdAt")]
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -32,19 +36,53 @@ impl TagTemplate {
             name,
             description,
             tags,
+            body: None,
             created_at: now,
             updated_at: now,
         }
     }
 
     /// テンプレートを更新
-    pub fn update(&mut self, description: Option<String>, tags: HashMap<String, TagValue>) {
+    pub fn update(
+        &mut self,
+        description: Option<String>,
+        tags: HashMap<String, TagValue>,
+        body: Option<String>,
+    ) {
         self.description = description;
         self.tags = tags;
+        self.body = body;
         self.updated_at = chrono::Utc::now();
     }
 }
 
+/// テンプレート本文のプレースホルダーに埋め込む値
+#[derive(Debug, Clone, Default)]
+pub struct TemplateRenderContext {
+    /// `{{title}}`に埋め込むタスクのタイトル
+    pub title: String,
+
+    /// `{{user}}`に埋め込むユーザー名
+    pub user: String,
+
+    /// `{{prompt:...}}`のラベル → 入力された値
+    pub prompts: HashMap<String, String>,
+}
+
+impl TemplateRenderContext {
+    /// ユーザー名は環境変数（`USER`または`USERNAME`）から取得する
+    pub fn new(title: Option<String>, prompts: Option<HashMap<String, String>>) -> Self {
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_default();
+        Self {
+            title: title.unwrap_or_default(),
+            user,
+            prompts: prompts.unwrap_or_default(),
+        }
+    }
+}
+
 /// テンプレートコレクション - ワークスペース設定に保存
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TemplateCollection {
@@ -0,0 +1,154 @@
+use regex::Regex;
+use std::fmt;
+use std::sync::OnceLock;
+
+use super::TagValue;
+
+/// 通貨タグの値（金額と ISO 4217 の通貨コード）
+///
+/// Front Matterでは`1200.50 USD`の形式で保存する。通貨コードのない数値は
+/// スキーマの`currencyFormat`で指定した通貨の金額として扱う。
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrencyAmount {
+    pub amount: f64,
+    pub currency: String,
+}
+
+impl CurrencyAmount {
+    /// `1200.50 USD`または`USD 1200.50`の形式を解析
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.split_whitespace();
+        let (first, second) = (parts.next()?, parts.next()?);
+        if parts.next().is_some() {
+            return None;
+        }
+        let (amount, currency) = if is_currency_code(second) {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        if !is_currency_code(currency) || !is_decimal(amount) {
+            return None;
+        }
+        Some(Self {
+            amount: amount.parse().ok()?,
+            currency: currency.to_string(),
+        })
+    }
+
+    /// タグ値から金額を取り出す（数値は`default_currency`の金額として扱う）
+    pub fn from_tag_value(value: &TagValue, default_currency: Option<&str>) -> Option<Self> {
+        let amount = match value {
+            TagValue::String(s) => return Self::parse(s),
+            TagValue::Number(n) => *n as f64,
+            TagValue::Float(f) => *f,
+            _ => return None,
+        };
+        Some(Self {
+            amount,
+            currency: default_currency?.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for CurrencyAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+/// ハイパーリンクタグの値
+///
+/// Front Matterでは`[表示テキスト](URL)`、またはテキストがなければURLのみで保存する。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hyperlink {
+    pub url: String,
+    pub text: String,
+}
+
+impl Hyperlink {
+    /// `[text](url)`またはURLのみの文字列を解析（URLの形式は検証しない）
+    pub fn parse(value: &str) -> Self {
+        static LINK: OnceLock<Regex> = OnceLock::new();
+        let link = LINK.get_or_init(|| Regex::new(r"^\[(.*)\]\((\S*)\)$").unwrap());
+        let value = value.trim();
+        match link.captures(value) {
+            Some(caps) => Self {
+                text: caps[1].to_string(),
+                url: caps[2].to_string(),
+            },
+            None => Self {
+                url: value.to_string(),
+                text: String::new(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Hyperlink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.text.is_empty() {
+            write!(f, "{}", self.url)
+        } else {
+            write!(f, "[{}]({})", self.text, self.url)
+        }
+    }
+}
+
+/// 英大文字3文字の通貨コードかどうか
+pub fn is_currency_code(code: &str) -> bool {
+    code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase())
+}
+
+/// 符号付きの10進数（指数表記なし）かどうか
+fn is_decimal(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, "0"));
+    !integer.is_empty()
+        && !fraction.is_empty()
+        && integer.chars().all(|c| c.is_ascii_digit())
+        && fraction.chars().all(|c| c.is_ascii_digit())
+}
+
+/// スキームとホストを持つURL、または`mailto:`のアドレスかどうか
+pub fn is_url(value: &str) -> bool {
+    static URL: OnceLock<Regex> = OnceLock::new();
+    URL.get_or_init(|| {
+        Regex::new(r"^(?:[A-Za-z][A-Za-z0-9+.-]*://[^\s/?#@]+(?:[/?#]\S*)?|mailto:[^\s@]+@[^\s@]+)$")
+            .unwrap()
+    })
+    .is_match(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_currency_and_hyperlink_round_trip() {
+        let amount = CurrencyAmount::parse("1200.50 USD").unwrap();
+        assert_eq!(amount.amount, 1200.5);
+        assert_eq!(amount.currency, "USD");
+        assert_eq!(CurrencyAmount::parse(&amount.to_string()), Some(amount));
+        assert_eq!(CurrencyAmount::parse("JPY -300").unwrap().amount, -300.0);
+        for invalid in ["1200", "1200 usd", "1,200 USD", "1e3 USD", "12. EUR", "1 USD EUR"] {
+            assert_eq!(CurrencyAmount::parse(invalid), None, "{}", invalid);
+        }
+        assert_eq!(
+            CurrencyAmount::from_tag_value(&TagValue::Number(500), Some("JPY")).unwrap().currency,
+            "JPY"
+        );
+        assert_eq!(CurrencyAmount::from_tag_value(&TagValue::Number(500), None), None);
+
+        for value in ["[Docs](https://example.com/a?b=c)", "https://example.com"] {
+            assert_eq!(Hyperlink::parse(value).to_string(), value);
+        }
+        assert_eq!(Hyperlink::parse("[Docs](https://example.com)").text, "Docs");
+
+        assert!(is_url("https://example.com/path?q=1#top"));
+        assert!(is_url("mailto:dev@example.com"));
+        assert!(!is_url("example.com"));
+        assert!(!is_url("https://"));
+        assert!(!is_url("https://exa mple.com"));
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use super::TagValue;
+
+/// 違反の重大度
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// 保存を拒否する（タグ固定モード）
+    Error,
+    /// 保存は行うが利用者に知らせる
+    Warning,
+}
+
+/// 違反の種類
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ViolationKind {
+    /// 必須のタグがない、または空
+    MissingRequired,
+    /// タグの型と値が一致しない
+    TypeMismatch,
+    /// リストで定義された選択肢にない値
+    NotInList,
+    /// パターンに一致しない値
+    PatternMismatch,
+    /// 範囲外の数値
+    OutOfRange,
+    /// タグ固定モードで許可されていないカテゴリ
+    UnknownCategory,
+    /// 計算フィールドと同名のタグ（計算フィールドは読み取り専用）
+    ReadOnly,
+    /// 画像タグの参照先がアセットディレクトリ内にない、または存在しない
+    MissingAsset,
+    /// タグスキーマの検証規則（複数のタグにまたがる条件）を満たさない
+    RuleViolation,
+}
+
+/// タグ設定に対する違反
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagViolation {
+    /// 対象タスクのID
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+
+    /// 違反したタグのカテゴリ名
+    pub category: String,
+
+    /// 違反の種類
+    pub kind: ViolationKind,
+
+    /// 重大度
+    pub severity: Severity,
+
+    /// 違反した値（必須タグの欠落の場合はNone）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<TagValue>,
+
+    /// 表示用のメッセージ
+    pub message: String,
+
+    /// 違反した検証規則の識別子（規則による違反の場合）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+}
+
+/// ワークスペース全体の検証結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// 検証したタスク数
+    #[serde(rename = "tasksChecked")]
+    pub tasks_checked: usize,
+
+    /// タグ固定モードで検証したかどうか（trueの場合、違反はすべてError）
+    ///
+    /// 重大度を指定した検証規則の違反は、モードによらずその重大度になる。
+    #[serde(rename = "strictMode")]
+    pub strict_mode: bool,
+
+    /// 検出された違反（タスクID順）
+    pub violations: Vec<TagViolation>,
+
+    /// タグ設定自体の問題（不正な正規表現など）
+    #[serde(rename = "schemaErrors")]
+    pub schema_errors: Vec<String>,
+}
@@ -1,8 +1,10 @@
+use chrono::Local;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use super::{Task, TagIndex, TemplateCollection, TagConfigCollection};
+use super::{Task, TagIndex, TemplateCollection, TagConfigCollection, TagSchema};
+use crate::parser::{ComputedFields, ValidationRules};
 
 /// アプリケーションのワークスペース状態
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +33,65 @@ impl Workspace {
             config: WorkspaceConfig::default(),
         }
     }
+
+    /// タグスキーマを反映し、正規化ルールに合わせてタグインデックスと計算フィールドを作り直す
+    pub fn apply_tag_schema(&mut self, schema: &TagSchema) {
+        self.config.apply_tag_schema(schema);
+        self.reindex_tags();
+        self.refresh_computed_tags();
+    }
+
+    /// 現在のタグ設定の正規化ルールでタグインデックスを作り直す
+    pub fn reindex_tags(&mut self) {
+        let normalization = self.config.tag_configs.normalization_rules();
+        self.tag_index.rebuild(
+            normalization,
+            self.tasks
+                .iter()
+                .map(|(id, task)| (id, &task.front_matter.tags)),
+        );
+    }
+
+    /// タスクを追加（同じIDのタスクは置き換え）し、タグインデックスと計算フィールドを更新
+    ///
+    /// # Returns
+    /// * `&Task` - 計算フィールドを評価した登録済みのタスク
+    pub fn upsert_task(&mut self, mut task: Task) -> &Task {
+        task.computed_tags = self
+            .config
+            .computed_fields
+            .evaluate(&task.front_matter.tags, Local::now().date_naive());
+        match self.tasks.get(&task.id) {
+            Some(previous) => self.tag_index.update_task(
+                &task.id,
+                &previous.front_matter.tags,
+                &task.front_matter.tags,
+            ),
+            None => self.tag_index.index_task(&task.id, &task.front_matter.tags),
+        }
+
+        let task_id = task.id.clone();
+        self.tasks.insert(task_id.clone(), task);
+        &self.tasks[&task_id]
+    }
+
+    /// タスクを取り除き、タグインデックスからも削除
+    pub fn remove_task(&mut self, task_id: &str) -> Option<Task> {
+        let task = self.tasks.remove(task_id)?;
+        self.tag_index.remove_task(task_id, &task.front_matter.tags);
+        Some(task)
+    }
+
+    /// 全タスクの計算フィールドを評価し直す
+    pub fn refresh_computed_tags(&mut self) {
+        let today = Local::now().date_naive();
+        for task in self.tasks.values_mut() {
+            task.computed_tags = self
+                .config
+                .computed_fields
+                .evaluate(&task.front_matter.tags, today);
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,14 +108,41 @@ pub struct WorkspaceConfig {
     #[serde(rename = "watchEnabled")]
     pub watch_enabled: bool,
 
+    /// ファイル変更イベントをまとめる待機時間（ミリ秒）
+    #[serde(rename = "watchDebounceMs", default = "default_watch_debounce_ms")]
+    pub watch_debounce_ms: u64,
+
+    /// ファイル監視の方式
+    #[serde(rename = "watchBackend", default)]
+    pub watch_backend: WatchBackend,
+
+    /// ポーリング監視の間隔（ミリ秒）
+    #[serde(rename = "watchPollIntervalMs", default = "default_watch_poll_interval_ms")]
+    pub watch_poll_interval_ms: u64,
+
     /// タグテンプレートコレクション
     #[serde(default)]
     pub templates: TemplateCollection,
 
     /// タグ設定コレクション
-    #[serde(default)]
+    ///
+    /// 保存先は`.hienmark/tag_schema.json`に統合されたため、このファイルには書き込まない。
+    /// 読み込み時にタグスキーマから再構成され、古いファイルに残っている値は移行される。
+    #[serde(default, skip_serializing)]
     pub tag_configs: TagConfigCollection,
 
+    /// タグスキーマで定義された計算フィールド（タグスキーマから再構成される）
+    #[serde(skip)]
+    pub computed_fields: ComputedFields,
+
+    /// タグスキーマで定義された検証規則（タグスキーマから再構成される）
+    #[serde(skip)]
+    pub validation_rules: ValidationRules,
+
+    /// 階層タグの区切り文字（例: `area/backend/api`の`/`）。空の場合は階層化しない
+    #[serde(rename = "tagSeparator", default = "default_tag_separator")]
+    pub tag_separator: String,
+
     /// 自動保存の有効化
     #[serde(rename = "autoSaveEnabled", default)]
     pub auto_save_enabled: bool,
@@ -96,6 +184,44 @@ pub struct WorkspaceConfig {
     pub git_integration: bool,
 }
 
+impl WorkspaceConfig {
+    /// タグスキーマから導出される設定（タグ設定・計算フィールド・検証規則）を反映
+    pub fn apply_tag_schema(&mut self, schema: &TagSchema) {
+        self.tag_configs = schema.to_tag_configs();
+        self.computed_fields = ComputedFields::from_schema(schema);
+        self.validation_rules = ValidationRules::from_schema(schema);
+    }
+}
+
+fn default_watch_debounce_ms() -> u64 {
+    300
+}
+
+fn default_watch_poll_interval_ms() -> u64 {
+    2000
+}
+
+fn default_tag_separator() -> String {
+    "/".to_string()
+}
+
+/// ファイル監視の方式
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchBackend {
+    /// OSのファイル通知を使い、利用できない場合はポーリングに切り替える
+    ///
+    /// SMB・NFSなどのネットワークドライブでは、他のマシンからの変更がOSのファイル通知に届かない。
+    /// Linuxではマウント情報からネットワークファイルシステムを検出してポーリングを使うが、
+    /// 他のOSではこうしたフォルダ（同期の遅れるクラウド同期フォルダを含む）に`Polling`を指定する必要がある。
+    #[default]
+    Auto,
+    /// OSのファイル通知のみ
+    Native,
+    /// 更新日時とサイズの定期的な比較（ネットワークドライブ・同期フォルダ向け）
+    Polling,
+}
+
 impl Default for WorkspaceConfig {
     fn default() -> Self {
         Self {
@@ -106,8 +232,14 @@ impl Default for WorkspaceConfig {
                 "tags".to_string(),
             ],
             watch_enabled: true,
+            watch_debounce_ms: default_watch_debounce_ms(),
+            watch_backend: WatchBackend::default(),
+            watch_poll_interval_ms: default_watch_poll_interval_ms(),
             templates: TemplateCollection::new(),
             tag_configs: TagConfigCollection::create_default_configs(),
+            computed_fields: ComputedFields::default(),
+            validation_rules: ValidationRules::default(),
+            tag_separator: default_tag_separator(),
             auto_save_enabled: true,
             auto_save_interval: 3000,
             theme: None,
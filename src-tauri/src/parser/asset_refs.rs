@@ -0,0 +1,209 @@
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::OnceLock;
+
+use crate::models::typed_value::Hyperlink;
+use crate::models::TagValue;
+
+/// アセット格納ディレクトリ（ワークスペースルートからの相対パス）
+pub const ASSETS_DIR: &str = ".hienmark/assets";
+
+/// タスク本文からアセット参照を抽出するパーサー
+pub struct AssetReferenceParser;
+
+impl AssetReferenceParser {
+    /// 本文中のアセット参照を抽出
+    ///
+    /// Markdownのリンク・画像（`[..](path)`、`![..](path)`）、参照リンク定義（`[id]: path`）、
+    /// HTMLの`src`/`href`属性を対象とする。参照先はタスクファイルの位置を基準に解決し、
+    /// `.hienmark/assets`配下を指すものだけをワークスペースルートからの相対パスで返す。
+    ///
+    /// # Arguments
+    /// * `body` - タスク本文
+    /// * `task_relative_path` - タスクファイルのワークスペースルートからの相対パス
+    ///
+    /// # Returns
+    /// * `Vec<String>` - 重複を除いたアセットの相対パス（昇順）
+    pub fn extract(body: &str, task_relative_path: &str) -> Vec<String> {
+        static INLINE: OnceLock<Regex> = OnceLock::new();
+        static DEFINITION: OnceLock<Regex> = OnceLock::new();
+        static HTML: OnceLock<Regex> = OnceLock::new();
+        let inline = INLINE.get_or_init(|| {
+            Regex::new(r#"\]\(\s*(<[^>]*>|[^)\s]+)(?:\s+["'(][^)]*)?\)"#).unwrap()
+        });
+        let definition = DEFINITION
+            .get_or_init(|| Regex::new(r#"(?m)^\s{0,3}\[[^\]]+\]:\s*(<[^>]*>|\S+)"#).unwrap());
+        let html = HTML
+            .get_or_init(|| Regex::new(r#"(?i)\b(?:src|href)\s*=\s*["']([^"']+)["']"#).unwrap());
+
+        let targets = inline
+            .captures_iter(body)
+            .chain(definition.captures_iter(body))
+            .chain(html.captures_iter(body))
+            .map(|caps| caps[1].trim_start_matches('<').trim_end_matches('>').to_string());
+
+        let mut references = BTreeSet::new();
+        for target in targets {
+            if let Some(path) = Self::resolve(&target, task_relative_path) {
+                references.insert(path);
+            }
+        }
+
+        references.into_iter().collect()
+    }
+
+    /// Front Matterのタグの値（画像・ハイパーリンク）からアセット参照を抽出
+    ///
+    /// 画像のパスと`[text](path)`形式のリンク先は、スキーマ検証と同じくワークスペースルートを基準に解決する。
+    /// タグの型によらず、アセットディレクトリを指す値をすべて対象とする。
+    ///
+    /// # Arguments
+    /// * `tags` - タスクのタグ
+    ///
+    /// # Returns
+    /// * `Vec<String>` - 重複を除いたアセットの相対パス（昇順）
+    pub fn extract_from_tags(tags: &HashMap<String, TagValue>) -> Vec<String> {
+        let mut references = BTreeSet::new();
+        for value in tags.values() {
+            let values = match value {
+                TagValue::String(s) => std::slice::from_ref(s),
+                TagValue::Array(items) => items.as_slice(),
+                _ => continue,
+            };
+            for value in values {
+                if let Some(path) = Self::resolve(Hyperlink::parse(value).url.trim(), "") {
+                    references.insert(path);
+                }
+            }
+        }
+        references.into_iter().collect()
+    }
+
+    /// 参照先をワークスペースルートからの相対パスに解決
+    ///
+    /// 外部URL・アンカーや、アセットディレクトリ外を指す参照の場合はNoneを返す。
+    pub fn resolve(target: &str, task_relative_path: &str) -> Option<String> {
+        // スキーム付きの参照（http:, data:, mailto:など）は対象外
+        if target.is_empty() || target.starts_with('#') || target.contains(':') {
+            return None;
+        }
+
+        // クエリ・フラグメントを除去してパーセントエンコーディングを復元
+        let path = target.split(['?', '#']).next().unwrap_or_default();
+        let path = Self::percent_decode(path).replace('\\', "/");
+
+        // 先頭が`/`またはアセットディレクトリで始まる場合はルート基準、それ以外はタスクの位置基準
+        let mut segments: Vec<String> = Vec::new();
+        if !(path.starts_with('/') || path.starts_with(ASSETS_DIR)) {
+            let task_dir = task_relative_path.replace('\\', "/");
+            if let Some((dir, _)) = task_dir.rsplit_once('/') {
+                segments.extend(dir.split('/').filter(|s| !s.is_empty()).map(str::to_string));
+            }
+        }
+
+        for segment in path.split('/') {
+            match segment {
+                "" | "." => {}
+                ".." => {
+                    // ルートより上を指す参照は無効
+                    segments.pop()?;
+                }
+                other => segments.push(other.to_string()),
+            }
+        }
+
+        let resolved = segments.join("/");
+        let prefix = format!("{}/", ASSETS_DIR);
+        if resolved.starts_with(&prefix) && resolved.len() > prefix.len() {
+            Some(resolved)
+        } else {
+            None
+        }
+    }
+
+    /// `%XX`形式のパーセントエンコーディングを復元（不正な並びはそのまま残す）
+    fn percent_decode(input: &str) -> String {
+        let bytes = input.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%' && i + 2 < bytes.len() {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                    decoded.push(byte);
+                    i += 3;
+                    continue;
+                }
+            }
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+        String::from_utf8_lossy(&decoded).into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_markdown_and_html_references() {
+        let body = r#"
+![screenshot](.hienmark/assets/shot_1.png)
+[spec](<.hienmark/assets/my%20spec.pdf> "Spec")
+<img src=".hienmark/assets/diagram.svg" width="200">
+[logo]: .hienmark/assets/logo.png
+[external](https://example.com/a.png) [anchor](#top) [other](notes/readme.md)
+![again](.hienmark/assets/shot_1.png)
+"#;
+        let refs = AssetReferenceParser::extract(body, "task.md");
+        assert_eq!(
+            refs,
+            vec![
+                ".hienmark/assets/diagram.svg",
+                ".hienmark/assets/logo.png",
+                ".hienmark/assets/my spec.pdf",
+                ".hienmark/assets/shot_1.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_from_tags() {
+        let mut tags = HashMap::new();
+        tags.insert("cover".to_string(), TagValue::String(".hienmark/assets/cover.png".to_string()));
+        tags.insert(
+            "spec".to_string(),
+            TagValue::String("[Spec](.hienmark/assets/spec.pdf)".to_string()),
+        );
+        tags.insert("site".to_string(), TagValue::String("[Site](https://example.com)".to_string()));
+        tags.insert(
+            "shots".to_string(),
+            TagValue::Array(vec!["/.hienmark/assets/a.png".to_string(), "todo".to_string()]),
+        );
+        tags.insert("count".to_string(), TagValue::Number(3));
+
+        assert_eq!(
+            AssetReferenceParser::extract_from_tags(&tags),
+            vec![
+                ".hienmark/assets/a.png",
+                ".hienmark/assets/cover.png",
+                ".hienmark/assets/spec.pdf",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_relative_to_task_folder() {
+        assert_eq!(
+            AssetReferenceParser::resolve("../.hienmark/assets/a.png", "project/task.md"),
+            Some(".hienmark/assets/a.png".to_string())
+        );
+        assert_eq!(
+            AssetReferenceParser::resolve("/.hienmark/assets/a.png", "project/task.md"),
+            Some(".hienmark/assets/a.png".to_string())
+        );
+        assert_eq!(AssetReferenceParser::resolve("../../a.png", "project/task.md"), None);
+        assert_eq!(AssetReferenceParser::resolve("images/a.png", "project/task.md"), None);
+    }
+}
@@ -0,0 +1,796 @@
+use crate::models::tag_schema::TagAttribute;
+use crate::models::{TagSchema, TagValue};
+use crate::parser::formula::{FormulaError, FormulaValue};
+use chrono::{Duration, NaiveDate};
+use std::collections::{HashMap, HashSet};
+
+/// 計算フィールドの式
+///
+/// ```text
+/// expr       := or
+/// or         := and ("||" and)*
+/// and        := not ("&&" not)*
+/// not        := "!" not | comparison
+/// comparison := sum (("==" | "!=" | "<" | "<=" | ">" | ">=") sum)?
+/// sum        := product (("+" | "-") product)*
+/// product    := unary (("*" | "/") unary)*
+/// unary      := "-" unary | primary
+/// primary    := number | string | TODAY | true | false | null
+///             | name | "[" name "]" | "(" expr ")"
+/// ```
+///
+/// 名前はタグ（または他の計算フィールド）を参照する。スキーマにもタスクにも存在しない
+/// 名前は文字列として扱うため、`status != close`のように値を引用符なしで書ける。
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Literal(ExpressionValue),
+    Today,
+    Name(String),
+    Unary {
+        op: UnaryOp,
+        operand: Box<Expression>,
+    },
+    Binary {
+        op: BinaryOp,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+/// 式の値
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    Date(NaiveDate),
+}
+
+impl ExpressionValue {
+    /// タグの値を式の値に変換（日付として読める文字列は日付になる）
+    pub fn from_tag(value: &TagValue) -> Self {
+        match value {
+            TagValue::Number(n) => ExpressionValue::Number(*n as f64),
+            TagValue::Float(f) => ExpressionValue::Number(*f),
+            TagValue::Bool(b) => ExpressionValue::Bool(*b),
+            TagValue::String(s) => match FormulaValue::parse(s) {
+                Some(date) => ExpressionValue::Date(date.date()),
+                None => ExpressionValue::Text(s.clone()),
+            },
+            TagValue::Array(items) => ExpressionValue::Text(items.join(", ")),
+        }
+    }
+
+    /// タグの値に変換（nullは値なし）
+    pub fn into_tag(self) -> Option<TagValue> {
+        match self {
+            ExpressionValue::Null => None,
+            ExpressionValue::Bool(b) => Some(TagValue::Bool(b)),
+            ExpressionValue::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+                Some(TagValue::Number(n as i64))
+            }
+            ExpressionValue::Number(n) if n.is_finite() => Some(TagValue::Float(n)),
+            ExpressionValue::Number(_) => None,
+            ExpressionValue::Text(s) => Some(TagValue::String(s)),
+            ExpressionValue::Date(date) => {
+                Some(TagValue::String(date.format("%Y-%m-%d").to_string()))
+            }
+        }
+    }
+
+    pub(crate) fn is_truthy(&self) -> bool {
+        match self {
+            ExpressionValue::Null => false,
+            ExpressionValue::Bool(b) => *b,
+            ExpressionValue::Number(n) => *n != 0.0,
+            ExpressionValue::Text(s) => !s.is_empty(),
+            ExpressionValue::Date(_) => true,
+        }
+    }
+}
+
+/// 名前の参照結果
+pub enum NameLookup {
+    /// 値がある
+    Value(ExpressionValue),
+    /// スキーマで定義されているがタスクに値がない
+    Missing,
+    /// どこにも定義されていない（文字列として扱う）
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Number(f64),
+    Text(String),
+    Name(String),
+    Symbol(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    column: usize,
+    /// トークン末尾の次の文字位置（0始まり）
+    end: usize,
+}
+
+const SYMBOLS: [&str; 16] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "+", "-", "*", "/", "(", ")", "]",
+];
+
+/// 計算フィールドの式のパーサー
+pub struct ExpressionParser {
+    tokens: Vec<Token>,
+    position: usize,
+    end: usize,
+}
+
+impl ExpressionParser {
+    /// 式を解析
+    ///
+    /// # Arguments
+    /// * `source` - 式（例: `end_date - start_date`）
+    ///
+    /// # Returns
+    /// * `Result<Expression, FormulaError>` - 解析結果（エラーは位置付き）
+    pub fn parse(source: &str) -> Result<Expression, FormulaError> {
+        let mut parser = Self {
+            tokens: Self::tokenize(source)?,
+            position: 0,
+            end: source.chars().count() + 1,
+        };
+        if parser.tokens.is_empty() {
+            return Err(error(parser.end, "Expression is empty"));
+        }
+
+        let expression = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.position) {
+            return Err(error(
+                token.column,
+                &format!("Unexpected {}", describe(&token.kind)),
+            ));
+        }
+        Ok(expression)
+    }
+
+    /// 式中のタグ名の参照を書き換える
+    ///
+    /// 書式（空白や文字列リテラル）はそのまま残し、参照部分だけを置き換える。
+    ///
+    /// # Arguments
+    /// * `source` - 式
+    /// * `from` - 変更前のタグ名
+    /// * `to` - 変更後のタグ名
+    ///
+    /// # Returns
+    /// * `Result<Option<String>, FormulaError>` - 書き換えた式（参照がなければNone）
+    pub fn rename_reference(
+        source: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<String>, FormulaError> {
+        let chars: Vec<char> = source.chars().collect();
+        let replacement = if is_bare_name(to) {
+            to.to_string()
+        } else {
+            format!("[{}]", to)
+        };
+
+        let mut renamed = String::new();
+        let mut copied = 0;
+        for token in Self::tokenize(source)? {
+            if token.kind == TokenKind::Name(from.to_string()) && !is_keyword(from) {
+                renamed.extend(&chars[copied..token.column - 1]);
+                renamed.push_str(&replacement);
+                copied = token.end;
+            }
+        }
+        if copied == 0 {
+            return Ok(None);
+        }
+        renamed.extend(&chars[copied..]);
+        Ok(Some(renamed))
+    }
+
+    fn tokenize(source: &str) -> Result<Vec<Token>, FormulaError> {
+        let chars: Vec<char> = source.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let column = i + 1;
+
+            if c.is_whitespace() {
+                i += 1;
+            } else if c.is_ascii_digit() {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = text
+                    .parse::<f64>()
+                    .map_err(|_| error(column, &format!("Invalid number '{}'", text)))?;
+                tokens.push(Token {
+                    kind: TokenKind::Number(number),
+                    column,
+                    end: i,
+                });
+            } else if c == '"' || c == '\'' {
+                let close = chars[i + 1..]
+                    .iter()
+                    .position(|&d| d == c)
+                    .ok_or_else(|| error(column, "Unterminated string"))?;
+                tokens.push(Token {
+                    kind: TokenKind::Text(chars[i + 1..i + 1 + close].iter().collect()),
+                    column,
+                    end: i + close + 2,
+                });
+                i += close + 2;
+            } else if c.is_alphanumeric() || c == '_' {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Name(chars[start..i].iter().collect()),
+                    column,
+                    end: i,
+                });
+            } else if c == '[' {
+                // 記号を含むタグ名は角括弧で囲む
+                let close = chars[i + 1..]
+                    .iter()
+                    .position(|&d| d == ']')
+                    .ok_or_else(|| error(column, "Missing ']'"))?;
+                let name: String = chars[i + 1..i + 1 + close].iter().collect();
+                if name.trim().is_empty() {
+                    return Err(error(column, "Empty reference '[]'"));
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Name(name.trim().to_string()),
+                    column,
+                    end: i + close + 2,
+                });
+                i += close + 2;
+            } else {
+                let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+                let symbol = SYMBOLS
+                    .iter()
+                    .find(|symbol| rest.starts_with(*symbol))
+                    .ok_or_else(|| error(column, &format!("Unexpected character '{}'", c)))?;
+                tokens.push(Token {
+                    kind: TokenKind::Symbol(symbol),
+                    column,
+                    end: i + symbol.len(),
+                });
+                i += symbol.len();
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    fn peek_symbol(&self) -> Option<&'static str> {
+        match self.tokens.get(self.position) {
+            Some(Token {
+                kind: TokenKind::Symbol(symbol),
+                ..
+            }) => Some(symbol),
+            _ => None,
+        }
+    }
+
+    fn binary(
+        &mut self,
+        operators: &[(&str, BinaryOp)],
+        next: fn(&mut Self) -> Result<Expression, FormulaError>,
+        repeat: bool,
+    ) -> Result<Expression, FormulaError> {
+        let mut left = next(self)?;
+        while let Some(op) = self
+            .peek_symbol()
+            .and_then(|symbol| operators.iter().find(|(s, _)| *s == symbol))
+            .map(|(_, op)| *op)
+        {
+            self.position += 1;
+            let right = next(self)?;
+            left = Expression::Binary {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+            if !repeat {
+                break;
+            }
+        }
+        Ok(left)
+    }
+
+    fn parse_or(&mut self) -> Result<Expression, FormulaError> {
+        self.binary(&[("||", BinaryOp::Or)], Self::parse_and, true)
+    }
+
+    fn parse_and(&mut self) -> Result<Expression, FormulaError> {
+        self.binary(&[("&&", BinaryOp::And)], Self::parse_not, true)
+    }
+
+    fn parse_not(&mut self) -> Result<Expression, FormulaError> {
+        if self.peek_symbol() == Some("!") {
+            self.position += 1;
+            return Ok(Expression::Unary {
+                op: UnaryOp::Not,
+                operand: Box::new(self.parse_not()?),
+            });
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expression, FormulaError> {
+        self.binary(
+            &[
+                ("==", BinaryOp::Eq),
+                ("!=", BinaryOp::Ne),
+                ("<=", BinaryOp::Le),
+                (">=", BinaryOp::Ge),
+                ("<", BinaryOp::Lt),
+                (">", BinaryOp::Gt),
+            ],
+            Self::parse_sum,
+            false,
+        )
+    }
+
+    fn parse_sum(&mut self) -> Result<Expression, FormulaError> {
+        self.binary(
+            &[("+", BinaryOp::Add), ("-", BinaryOp::Sub)],
+            Self::parse_product,
+            true,
+        )
+    }
+
+    fn parse_product(&mut self) -> Result<Expression, FormulaError> {
+        self.binary(
+            &[("*", BinaryOp::Mul), ("/", BinaryOp::Div)],
+            Self::parse_unary,
+            true,
+        )
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, FormulaError> {
+        if self.peek_symbol() == Some("-") {
+            self.position += 1;
+            return Ok(Expression::Unary {
+                op: UnaryOp::Neg,
+                operand: Box::new(self.parse_unary()?),
+            });
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, FormulaError> {
+        let Some(token) = self.tokens.get(self.position).cloned() else {
+            return Err(error(self.end, "Unexpected end of expression"));
+        };
+        self.position += 1;
+
+        match token.kind {
+            TokenKind::Number(n) => Ok(Expression::Literal(ExpressionValue::Number(n))),
+            TokenKind::Text(s) => Ok(Expression::Literal(ExpressionValue::Text(s))),
+            TokenKind::Name(name) => Ok(match name.as_str() {
+                "TODAY" => Expression::Today,
+                "true" => Expression::Literal(ExpressionValue::Bool(true)),
+                "false" => Expression::Literal(ExpressionValue::Bool(false)),
+                "null" => Expression::Literal(ExpressionValue::Null),
+                _ => Expression::Name(name),
+            }),
+            TokenKind::Symbol("(") => {
+                let inner = self.parse_or()?;
+                match self.peek_symbol() {
+                    Some(")") => {
+                        self.position += 1;
+                        Ok(inner)
+                    }
+                    _ => Err(error(
+                        self.tokens
+                            .get(self.position)
+                            .map_or(self.end, |t| t.column),
+                        "Expected ')'",
+                    )),
+                }
+            }
+            other => Err(error(
+                token.column,
+                &format!("Unexpected {}", describe(&other)),
+            )),
+        }
+    }
+}
+
+impl Expression {
+    /// 式が参照している名前
+    pub fn names(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.collect_names(&mut names);
+        names
+    }
+
+    fn collect_names<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Expression::Name(name) => names.push(name),
+            Expression::Unary { operand, .. } => operand.collect_names(names),
+            Expression::Binary { left, right, .. } => {
+                left.collect_names(names);
+                right.collect_names(names);
+            }
+            Expression::Literal(_) | Expression::Today => {}
+        }
+    }
+
+    /// 式を評価
+    ///
+    /// 型が合わない演算（文字列の掛け算など）やゼロ除算はnullになる。
+    ///
+    /// # Arguments
+    /// * `today` - `TODAY`の値
+    /// * `lookup` - 名前の参照先を返す関数
+    ///
+    /// # Returns
+    /// * `ExpressionValue` - 評価結果
+    pub fn evaluate(
+        &self,
+        today: NaiveDate,
+        lookup: &mut dyn FnMut(&str) -> NameLookup,
+    ) -> ExpressionValue {
+        use ExpressionValue::*;
+
+        match self {
+            Expression::Literal(value) => value.clone(),
+            Expression::Today => Date(today),
+            Expression::Name(name) => match lookup(name) {
+                NameLookup::Value(value) => value,
+                NameLookup::Missing => Null,
+                NameLookup::Unknown => Text(name.clone()),
+            },
+            Expression::Unary { op, operand } => {
+                let value = operand.evaluate(today, lookup);
+                match (op, value) {
+                    (UnaryOp::Neg, Number(n)) => Number(-n),
+                    (UnaryOp::Neg, _) => Null,
+                    (UnaryOp::Not, value) => Bool(!value.is_truthy()),
+                }
+            }
+            Expression::Binary { op, left, right } => {
+                let left = left.evaluate(today, lookup);
+                match op {
+                    BinaryOp::And if !left.is_truthy() => return Bool(false),
+                    BinaryOp::Or if left.is_truthy() => return Bool(true),
+                    BinaryOp::And | BinaryOp::Or => {
+                        return Bool(right.evaluate(today, lookup).is_truthy())
+                    }
+                    _ => {}
+                }
+                let right = right.evaluate(today, lookup);
+                apply(*op, left, right)
+            }
+        }
+    }
+}
+
+fn apply(op: BinaryOp, left: ExpressionValue, right: ExpressionValue) -> ExpressionValue {
+    use ExpressionValue::*;
+
+    let days = |n: f64| Duration::try_days(n.trunc() as i64);
+    match (op, left, right) {
+        (BinaryOp::Add, Number(a), Number(b)) => Number(a + b),
+        (BinaryOp::Add, Date(d), Number(n)) | (BinaryOp::Add, Number(n), Date(d)) => days(n)
+            .and_then(|duration| d.checked_add_signed(duration))
+            .map_or(Null, Date),
+        (BinaryOp::Add, Text(a), Text(b)) => Text(a + &b),
+        (BinaryOp::Sub, Number(a), Number(b)) => Number(a - b),
+        (BinaryOp::Sub, Date(d), Number(n)) => days(n)
+            .and_then(|duration| d.checked_sub_signed(duration))
+            .map_or(Null, Date),
+        (BinaryOp::Sub, Date(a), Date(b)) => Number((a - b).num_days() as f64),
+        (BinaryOp::Mul, Number(a), Number(b)) => Number(a * b),
+        (BinaryOp::Div, Number(a), Number(b)) if b != 0.0 => Number(a / b),
+        (BinaryOp::Eq, a, b) => Bool(equals(&a, &b)),
+        (BinaryOp::Ne, a, b) => Bool(!equals(&a, &b)),
+        (BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge, a, b) => {
+            match compare(&a, &b) {
+                Some(ordering) => Bool(match op {
+                    BinaryOp::Lt => ordering.is_lt(),
+                    BinaryOp::Le => ordering.is_le(),
+                    BinaryOp::Gt => ordering.is_gt(),
+                    _ => ordering.is_ge(),
+                }),
+                None => Null,
+            }
+        }
+        _ => Null,
+    }
+}
+
+/// 文字列を相手の型に合わせてから比較する
+fn coerce(a: &ExpressionValue, b: &ExpressionValue) -> (ExpressionValue, ExpressionValue) {
+    use ExpressionValue::*;
+
+    let convert = |text: &str, like: &ExpressionValue| match like {
+        Number(_) => text.trim().parse().map(Number).ok(),
+        Bool(_) => text.trim().parse().map(Bool).ok(),
+        Date(_) => FormulaValue::parse(text).map(|value| Date(value.date())),
+        _ => None,
+    };
+    match (a, b) {
+        (Text(text), other) => (convert(text, other).unwrap_or_else(|| a.clone()), b.clone()),
+        (other, Text(text)) => (a.clone(), convert(text, other).unwrap_or_else(|| b.clone())),
+        _ => (a.clone(), b.clone()),
+    }
+}
+
+fn equals(a: &ExpressionValue, b: &ExpressionValue) -> bool {
+    match coerce(a, b) {
+        (ExpressionValue::Number(x), ExpressionValue::Number(y)) => x == y,
+        (x, y) => x == y,
+    }
+}
+
+fn compare(a: &ExpressionValue, b: &ExpressionValue) -> Option<std::cmp::Ordering> {
+    use ExpressionValue::*;
+
+    match coerce(a, b) {
+        (Number(x), Number(y)) => x.partial_cmp(&y),
+        (Date(x), Date(y)) => Some(x.cmp(&y)),
+        (Text(x), Text(y)) => Some(x.cmp(&y)),
+        _ => None,
+    }
+}
+
+fn describe(kind: &TokenKind) -> String {
+    match kind {
+        TokenKind::Number(n) => format!("number {}", n),
+        TokenKind::Text(s) => format!("string \"{}\"", s),
+        TokenKind::Name(name) => format!("'{}'", name),
+        TokenKind::Symbol(symbol) => format!("'{}'", symbol),
+    }
+}
+
+fn is_keyword(name: &str) -> bool {
+    matches!(name, "TODAY" | "true" | "false" | "null")
+}
+
+/// 角括弧なしで書けるタグ名か
+fn is_bare_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| !c.is_ascii_digit())
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && !is_keyword(name)
+}
+
+fn error(column: usize, message: &str) -> FormulaError {
+    FormulaError {
+        column,
+        message: message.to_string(),
+    }
+}
+
+/// タグスキーマで定義された計算フィールドの集合
+///
+/// 計算結果はファイルには保存せず、ワークスペースの読み込み時とインデックス構築時に
+/// タスクごとに評価する。解析できない式はスキーマの検証で報告されるため、ここでは無視する。
+#[derive(Debug, Clone, Default)]
+pub struct ComputedFields {
+    fields: Vec<(String, Expression)>,
+    /// スキーマで定義されたタグ名（値がなければnull、それ以外の名前は文字列になる）
+    known_names: HashSet<String>,
+}
+
+impl ComputedFields {
+    /// タグスキーマから計算フィールドを取り出す
+    pub fn from_schema(schema: &TagSchema) -> Self {
+        let fields = schema
+            .tags
+            .iter()
+            .filter_map(|(name, definition)| match &definition.attribute {
+                TagAttribute::Computed(options) => ExpressionParser::parse(&options.expression)
+                    .ok()
+                    .map(|expression| (name.clone(), expression)),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            fields,
+            known_names: schema.tags.keys().cloned().collect(),
+        }
+    }
+
+    /// 計算フィールドの名前
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// 1タスク分の計算フィールドを評価
+    ///
+    /// # Arguments
+    /// * `tags` - タスクのFront Matter
+    /// * `today` - `TODAY`の値
+    ///
+    /// # Returns
+    /// * `HashMap<String, TagValue>` - 計算結果（nullになったフィールドは含まない）
+    pub fn evaluate(
+        &self,
+        tags: &HashMap<String, TagValue>,
+        today: NaiveDate,
+    ) -> HashMap<String, TagValue> {
+        let mut resolved: HashMap<&str, ExpressionValue> = HashMap::new();
+        for (name, _) in &self.fields {
+            self.resolve(name, tags, today, &mut resolved, &mut Vec::new());
+        }
+
+        resolved
+            .into_iter()
+            .filter_map(|(name, value)| value.into_tag().map(|tag| (name.to_string(), tag)))
+            .collect()
+    }
+
+    fn resolve<'a>(
+        &'a self,
+        name: &str,
+        tags: &HashMap<String, TagValue>,
+        today: NaiveDate,
+        resolved: &mut HashMap<&'a str, ExpressionValue>,
+        evaluating: &mut Vec<&'a str>,
+    ) -> Option<ExpressionValue> {
+        let (field_name, expression) = self.fields.iter().find(|(n, _)| n == name)?;
+        if let Some(value) = resolved.get(field_name.as_str()) {
+            return Some(value.clone());
+        }
+        // 循環参照はスキーマの検証で報告されるため、ここではnullとして扱う
+        if evaluating.contains(&field_name.as_str()) {
+            return Some(ExpressionValue::Null);
+        }
+
+        evaluating.push(field_name);
+        let value = expression.evaluate(today, &mut |reference| {
+            if let Some(value) = self.resolve(reference, tags, today, resolved, evaluating) {
+                return NameLookup::Value(value);
+            }
+            match tags.get(reference) {
+                Some(value) => NameLookup::Value(ExpressionValue::from_tag(value)),
+                None if self.known_names.contains(reference) => NameLookup::Missing,
+                None => NameLookup::Unknown,
+            }
+        });
+        evaluating.pop();
+
+        resolved.insert(field_name, value.clone());
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(fields: &[(&str, &str)]) -> TagSchema {
+        let json = serde_json::json!({
+            "version": 1,
+            "tags": fields.iter().map(|(name, expression)| {
+                (name.to_string(), serde_json::json!({"type": "Computed", "options": {"expression": expression}}))
+            }).collect::<serde_json::Map<_, _>>(),
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn tags(entries: &[(&str, TagValue)]) -> HashMap<String, TagValue> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_evaluate_computed_fields() {
+        let fields = ComputedFields::from_schema(&schema(&[
+            ("duration", "end_date - start_date"),
+            ("overdue", "due_date < TODAY && status != close"),
+            ("score", "priority_weight * effort"),
+            ("late_score", "overdue && score >= 10"),
+            ("half", "effort / 0"),
+        ]));
+        let today = NaiveDate::from_ymd_opt(2025, 2, 1).unwrap();
+
+        let result = fields.evaluate(
+            &tags(&[
+                ("start_date", TagValue::String("2025-01-10".into())),
+                ("end_date", TagValue::String("2025-01-24".into())),
+                ("due_date", TagValue::String("2025-01-31".into())),
+                ("status", TagValue::String("open".into())),
+                ("priority_weight", TagValue::Number(3)),
+                ("effort", TagValue::Float(4.0)),
+            ]),
+            today,
+        );
+        assert_eq!(result["duration"], TagValue::Number(14));
+        assert_eq!(result["overdue"], TagValue::Bool(true));
+        assert_eq!(result["score"], TagValue::Number(12));
+        assert_eq!(result["late_score"], TagValue::Bool(true));
+        assert!(!result.contains_key("half"));
+
+        let closed = fields.evaluate(
+            &tags(&[
+                ("due_date", TagValue::String("2025-01-31".into())),
+                ("status", TagValue::String("close".into())),
+            ]),
+            today,
+        );
+        assert_eq!(closed["overdue"], TagValue::Bool(false));
+        assert!(!closed.contains_key("duration"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("", "Expression is empty (column 1)"),
+            ("a +", "Unexpected end of expression (column 4)"),
+            ("(a + b", "Expected ')' (column 7)"),
+            ("a b", "Unexpected 'b' (column 3)"),
+            ("a # b", "Unexpected character '#' (column 3)"),
+            ("\"open", "Unterminated string (column 1)"),
+        ];
+        for (source, message) in cases {
+            assert_eq!(
+                ExpressionParser::parse(source).unwrap_err().to_string(),
+                message,
+                "{}",
+                source
+            );
+        }
+
+        let expression = ExpressionParser::parse("-[start-date] + 1 * 2 == 3 || !done").unwrap();
+        assert_eq!(expression.names(), vec!["start-date", "done"]);
+    }
+
+    #[test]
+    fn test_rename_reference() {
+        let renamed = |source, from, to| ExpressionParser::rename_reference(source, from, to).unwrap();
+
+        assert_eq!(
+            renamed("due  - [start] + \"due\"", "due", "due_date"),
+            Some("due_date  - [start] + \"due\"".to_string())
+        );
+        assert_eq!(
+            renamed("[start] - TODAY", "start", "start-date"),
+            Some("[start-date] - TODAY".to_string())
+        );
+        assert_eq!(renamed("points * 10", "due", "due_date"), None);
+        assert!(ExpressionParser::rename_reference("due +", "due", "x").is_ok());
+    }
+}
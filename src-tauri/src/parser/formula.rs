@@ -0,0 +1,558 @@
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, TimeZone, Weekday};
+use std::fmt;
+
+/// 動的デフォルト値の計算式
+///
+/// ```text
+/// formula   := "="? base? operation*
+/// base      := "[TODAY]" | "[NOW]" | "[" field "]"
+/// operation := ("+" | "-") number unit?
+///            | "end of month"
+///            | "next" weekday
+/// unit      := d | w | m | y | bd | "business days"（省略時は日）
+/// ```
+///
+/// 基準を省略した場合は`[TODAY]`になる（例: `=next monday`）。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Formula {
+    pub base: FormulaBase,
+    pub operations: Vec<FormulaOperation>,
+}
+
+/// 計算の基準となる日時
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaBase {
+    /// 今日の日付
+    Today,
+    /// 現在の日時
+    Now,
+    /// 他のタグの値（例: `[start_date]`）
+    Field(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormulaOperation {
+    /// 加算・減算（負の値は減算）
+    Offset { amount: i64, unit: FormulaUnit },
+    /// 月末に移動
+    EndOfMonth,
+    /// 次の指定曜日に移動（同じ曜日の場合は1週間後）
+    NextWeekday(Weekday),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormulaUnit {
+    Days,
+    Weeks,
+    Months,
+    Years,
+    /// 土日を除いた日数
+    BusinessDays,
+}
+
+/// 計算式の構文エラー
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormulaError {
+    /// エラー位置（計算式の先頭からの文字数、1始まり）
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FormulaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (column {})", self.message, self.column)
+    }
+}
+
+/// 計算結果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormulaValue {
+    Date(NaiveDate),
+    DateTime(DateTime<Local>),
+}
+
+impl FormulaValue {
+    /// タグの値（`YYYY-MM-DD`またはRFC 3339）を解釈
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            return Some(FormulaValue::Date(date));
+        }
+        DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|datetime| FormulaValue::DateTime(datetime.with_timezone(&Local)))
+    }
+
+    pub fn date(self) -> NaiveDate {
+        match self {
+            FormulaValue::Date(date) => date,
+            FormulaValue::DateTime(datetime) => datetime.date_naive(),
+        }
+    }
+
+    /// 時刻を保ったまま日付を置き換える
+    fn with_date(self, date: NaiveDate) -> Result<Self, String> {
+        match self {
+            FormulaValue::Date(_) => Ok(FormulaValue::Date(date)),
+            FormulaValue::DateTime(datetime) => Local
+                .from_local_datetime(&date.and_time(datetime.time()))
+                .earliest()
+                .map(FormulaValue::DateTime)
+                .ok_or_else(|| format!("{} does not exist in the local time zone", date)),
+        }
+    }
+
+    /// タグに書き込む形式（日付は`YYYY-MM-DD`、日時はRFC 3339）
+    pub fn to_tag_string(self) -> String {
+        match self {
+            FormulaValue::Date(date) => date.format("%Y-%m-%d").to_string(),
+            FormulaValue::DateTime(datetime) => datetime.to_rfc3339(),
+        }
+    }
+
+    /// RFC 3339形式（日付はその日の0時）
+    pub fn to_rfc3339(self) -> String {
+        match self {
+            FormulaValue::Date(date) => Local
+                .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default())
+                .earliest()
+                .map(|datetime| datetime.to_rfc3339())
+                .unwrap_or_else(|| date.format("%Y-%m-%d").to_string()),
+            FormulaValue::DateTime(datetime) => datetime.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Reference(String),
+    Plus,
+    Minus,
+    Number(i64),
+    Word(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    column: usize,
+}
+
+/// 動的デフォルト値の計算式のパーサー
+pub struct FormulaParser;
+
+impl FormulaParser {
+    /// 計算式かどうか（`=`で始まる文字列）
+    pub fn is_formula(value: &str) -> bool {
+        value.trim_start().starts_with('=')
+    }
+
+    /// 計算式を解析
+    ///
+    /// # Arguments
+    /// * `formula` - 計算式（例: `=[start_date]+2w`、先頭の`=`は省略可）
+    ///
+    /// # Returns
+    /// * `Result<Formula, FormulaError>` - 解析結果（エラーは位置付き）
+    pub fn parse(formula: &str) -> Result<Formula, FormulaError> {
+        let tokens = Self::tokenize(formula)?;
+        let end = formula.chars().count() + 1;
+        let mut tokens = tokens.into_iter().peekable();
+
+        if tokens.peek().is_none() {
+            return Err(error(end, "Formula is empty"));
+        }
+
+        let base = match tokens.peek() {
+            Some(Token {
+                kind: TokenKind::Reference(name),
+                ..
+            }) => {
+                let base = match name.to_ascii_uppercase().as_str() {
+                    "TODAY" => FormulaBase::Today,
+                    "NOW" => FormulaBase::Now,
+                    _ => FormulaBase::Field(name.clone()),
+                };
+                tokens.next();
+                base
+            }
+            _ => FormulaBase::Today,
+        };
+
+        let mut operations = Vec::new();
+        while let Some(token) = tokens.next() {
+            match token.kind {
+                TokenKind::Plus | TokenKind::Minus => {
+                    let sign = if token.kind == TokenKind::Plus { 1 } else { -1 };
+                    let symbol = if sign == 1 { '+' } else { '-' };
+                    let amount = match tokens.next() {
+                        Some(Token {
+                            kind: TokenKind::Number(n),
+                            ..
+                        }) => n * sign,
+                        Some(other) => {
+                            return Err(error(
+                                other.column,
+                                &format!("Expected a number after '{}'", symbol),
+                            ))
+                        }
+                        None => {
+                            return Err(error(
+                                end,
+                                &format!("Expected a number after '{}'", symbol),
+                            ))
+                        }
+                    };
+
+                    // 単位の後に続く操作（end of month / next ...）は単位として扱わない
+                    let unit = match tokens.peek() {
+                        Some(Token {
+                            kind: TokenKind::Word(word),
+                            column,
+                        }) if word != "end" && word != "next" => {
+                            let column = *column;
+                            let word = word.clone();
+                            tokens.next();
+                            match word.as_str() {
+                                "d" | "day" | "days" => FormulaUnit::Days,
+                                "w" | "week" | "weeks" => FormulaUnit::Weeks,
+                                "m" | "month" | "months" => FormulaUnit::Months,
+                                "y" | "year" | "years" => FormulaUnit::Years,
+                                "bd" => FormulaUnit::BusinessDays,
+                                "business" => match tokens.next() {
+                                    Some(Token {
+                                        kind: TokenKind::Word(next),
+                                        ..
+                                    }) if next == "day" || next == "days" => {
+                                        FormulaUnit::BusinessDays
+                                    }
+                                    _ => return Err(error(column, "Expected 'business days'")),
+                                },
+                                other => return Err(error(
+                                    column,
+                                    &format!(
+                                        "Unknown unit '{}' (expected d, w, m, y or business days)",
+                                        other
+                                    ),
+                                )),
+                            }
+                        }
+                        _ => FormulaUnit::Days,
+                    };
+
+                    operations.push(FormulaOperation::Offset { amount, unit });
+                }
+                TokenKind::Word(word) if word == "end" => {
+                    let of = tokens.next();
+                    let month = tokens.next();
+                    match (of.map(|t| t.kind), month.map(|t| t.kind)) {
+                        (Some(TokenKind::Word(of)), Some(TokenKind::Word(month)))
+                            if of == "of" && month == "month" =>
+                        {
+                            operations.push(FormulaOperation::EndOfMonth)
+                        }
+                        _ => return Err(error(token.column, "Expected 'end of month'")),
+                    }
+                }
+                TokenKind::Word(word) if word == "next" => match tokens.next() {
+                    Some(Token {
+                        kind: TokenKind::Word(day),
+                        column,
+                    }) => {
+                        let weekday = day
+                            .parse::<Weekday>()
+                            .map_err(|_| error(column, &format!("Unknown weekday '{}'", day)))?;
+                        operations.push(FormulaOperation::NextWeekday(weekday));
+                    }
+                    Some(other) => {
+                        return Err(error(other.column, "Expected a weekday after 'next'"))
+                    }
+                    None => return Err(error(end, "Expected a weekday after 'next'")),
+                },
+                TokenKind::Word(word) => {
+                    return Err(error(token.column, &format!("Unexpected '{}'", word)))
+                }
+                TokenKind::Number(n) => {
+                    return Err(error(
+                        token.column,
+                        &format!("Expected '+' or '-' before {}", n),
+                    ))
+                }
+                TokenKind::Reference(name) => {
+                    return Err(error(
+                        token.column,
+                        &format!(
+                            "Unexpected [{}]: only the first reference is used as the base date",
+                            name
+                        ),
+                    ))
+                }
+            }
+        }
+
+        Ok(Formula { base, operations })
+    }
+
+    fn tokenize(formula: &str) -> Result<Vec<Token>, FormulaError> {
+        let chars: Vec<char> = formula.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+
+        // 先頭の`=`は省略可
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+        if i < chars.len() && chars[i] == '=' {
+            i += 1;
+        }
+
+        while i < chars.len() {
+            let c = chars[i];
+            let column = i + 1;
+            match c {
+                c if c.is_whitespace() => i += 1,
+                '+' => {
+                    tokens.push(Token {
+                        kind: TokenKind::Plus,
+                        column,
+                    });
+                    i += 1;
+                }
+                '-' => {
+                    tokens.push(Token {
+                        kind: TokenKind::Minus,
+                        column,
+                    });
+                    i += 1;
+                }
+                '[' => {
+                    let close = chars[i + 1..]
+                        .iter()
+                        .position(|&c| c == ']')
+                        .ok_or_else(|| error(column, "Missing ']'"))?;
+                    let name: String = chars[i + 1..i + 1 + close].iter().collect();
+                    let name = name.trim().to_string();
+                    if name.is_empty() {
+                        return Err(error(column, "Empty reference '[]'"));
+                    }
+                    tokens.push(Token {
+                        kind: TokenKind::Reference(name),
+                        column,
+                    });
+                    i += close + 2;
+                }
+                c if c.is_ascii_digit() => {
+                    let start = i;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                    let digits: String = chars[start..i].iter().collect();
+                    let number = digits
+                        .parse::<i64>()
+                        .map_err(|_| error(column, &format!("Number is too large: {}", digits)))?;
+                    tokens.push(Token {
+                        kind: TokenKind::Number(number),
+                        column,
+                    });
+                }
+                c if c.is_alphabetic() => {
+                    let start = i;
+                    while i < chars.len() && chars[i].is_alphabetic() {
+                        i += 1;
+                    }
+                    let word: String = chars[start..i].iter().collect();
+                    tokens.push(Token {
+                        kind: TokenKind::Word(word.to_lowercase()),
+                        column,
+                    });
+                }
+                other => return Err(error(column, &format!("Unexpected character '{}'", other))),
+            }
+        }
+
+        Ok(tokens)
+    }
+}
+
+impl Formula {
+    /// 計算式を評価
+    ///
+    /// # Arguments
+    /// * `now` - 現在の日時
+    /// * `field` - 参照されたタグの値を返す関数
+    ///
+    /// # Returns
+    /// * `Result<FormulaValue, String>` - 計算結果（`[NOW]`または日時のタグが基準なら日時、それ以外は日付）
+    pub fn evaluate(
+        &self,
+        now: DateTime<Local>,
+        mut field: impl FnMut(&str) -> Result<FormulaValue, String>,
+    ) -> Result<FormulaValue, String> {
+        let mut value = match &self.base {
+            FormulaBase::Today => FormulaValue::Date(now.date_naive()),
+            FormulaBase::Now => FormulaValue::DateTime(now),
+            FormulaBase::Field(name) => field(name)?,
+        };
+
+        for operation in &self.operations {
+            let date = value.date();
+            let moved = match *operation {
+                FormulaOperation::Offset { amount, unit } => offset(date, amount, unit),
+                FormulaOperation::EndOfMonth => end_of_month(date),
+                FormulaOperation::NextWeekday(weekday) => {
+                    let days = (7 + weekday.num_days_from_monday() as i64
+                        - date.weekday().num_days_from_monday() as i64)
+                        % 7;
+                    date.checked_add_signed(Duration::days(if days == 0 { 7 } else { days }))
+                }
+            }
+            .ok_or_else(|| "Date is out of range".to_string())?;
+            value = value.with_date(moved)?;
+        }
+
+        Ok(value)
+    }
+}
+
+/// 営業日の加算で扱う最大日数（約400年）
+const MAX_BUSINESS_DAYS: i64 = 100_000;
+
+fn error(column: usize, message: &str) -> FormulaError {
+    FormulaError {
+        column,
+        message: message.to_string(),
+    }
+}
+
+fn offset(date: NaiveDate, amount: i64, unit: FormulaUnit) -> Option<NaiveDate> {
+    match unit {
+        FormulaUnit::Days => date.checked_add_signed(Duration::try_days(amount)?),
+        FormulaUnit::Weeks => date.checked_add_signed(Duration::try_weeks(amount)?),
+        FormulaUnit::Months => add_months(date, amount),
+        FormulaUnit::Years => add_months(date, amount.checked_mul(12)?),
+        FormulaUnit::BusinessDays => {
+            // 1日ずつ進めるため、日付の範囲を大きく超える指定は先に弾く
+            if amount.abs() > MAX_BUSINESS_DAYS {
+                return None;
+            }
+            let step = Duration::days(amount.signum());
+            let mut current = date;
+            let mut remaining = amount.abs();
+            while remaining > 0 {
+                current = current.checked_add_signed(step)?;
+                if !matches!(current.weekday(), Weekday::Sat | Weekday::Sun) {
+                    remaining -= 1;
+                }
+            }
+            Some(current)
+        }
+    }
+}
+
+/// 月の加減算（移動先の月に同じ日がない場合は月末になる）
+fn add_months(date: NaiveDate, months: i64) -> Option<NaiveDate> {
+    let count = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    if months < 0 {
+        date.checked_sub_months(count)
+    } else {
+        date.checked_add_months(count)
+    }
+}
+
+fn end_of_month(date: NaiveDate) -> Option<NaiveDate> {
+    let first = date.with_day(1)?;
+    first.checked_add_months(Months::new(1))?.pred_opt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// 2025-01-31（金）を今日として評価
+    fn evaluate(formula: &str) -> Result<String, String> {
+        let now = Local
+            .from_local_datetime(&date("2025-01-31").and_hms_opt(9, 30, 0).unwrap())
+            .unwrap();
+        FormulaParser::parse(formula)
+            .map_err(|e| e.to_string())?
+            .evaluate(now, |name| match name {
+                "start_date" => Ok(FormulaValue::Date(date("2025-02-10"))),
+                other => Err(format!("{} is not set", other)),
+            })
+            .map(|value| value.to_tag_string())
+    }
+
+    #[test]
+    fn test_parse_operations() {
+        let formula =
+            FormulaParser::parse("=[start_date] + 2w - 5 business days end of month next mon")
+                .unwrap();
+        assert_eq!(formula.base, FormulaBase::Field("start_date".to_string()));
+        assert_eq!(
+            formula.operations,
+            vec![
+                FormulaOperation::Offset {
+                    amount: 2,
+                    unit: FormulaUnit::Weeks
+                },
+                FormulaOperation::Offset {
+                    amount: -5,
+                    unit: FormulaUnit::BusinessDays
+                },
+                FormulaOperation::EndOfMonth,
+                FormulaOperation::NextWeekday(Weekday::Mon),
+            ]
+        );
+        assert_eq!(
+            FormulaParser::parse("next friday").unwrap().base,
+            FormulaBase::Today
+        );
+    }
+
+    #[test]
+    fn test_evaluate_units_and_calendar_operations() {
+        assert_eq!(evaluate("=[TODAY]+30").unwrap(), "2025-03-02");
+        assert_eq!(evaluate("=[TODAY]+1m").unwrap(), "2025-02-28");
+        assert_eq!(evaluate("=[TODAY]-1y").unwrap(), "2024-01-31");
+        assert_eq!(evaluate("=[TODAY]+2w").unwrap(), "2025-02-14");
+        assert_eq!(evaluate("=[TODAY]+5bd").unwrap(), "2025-02-07");
+        assert_eq!(evaluate("=[TODAY]-1 business day").unwrap(), "2025-01-30");
+        assert_eq!(evaluate("=end of month").unwrap(), "2025-01-31");
+        assert_eq!(evaluate("=[TODAY]+1m end of month").unwrap(), "2025-02-28");
+        assert_eq!(evaluate("=next friday").unwrap(), "2025-02-07");
+        assert_eq!(evaluate("=[start_date]+14").unwrap(), "2025-02-24");
+        assert!(evaluate("=[NOW]+1d")
+            .unwrap()
+            .starts_with("2025-02-01T09:30:00"));
+        assert_eq!(evaluate("=[due]+1").unwrap_err(), "due is not set");
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_problem() {
+        let cases = [
+            ("=", "Formula is empty (column 2)"),
+            (
+                "=[TODAY]+2x",
+                "Unknown unit 'x' (expected d, w, m, y or business days) (column 11)",
+            ),
+            ("=[TODAY]+", "Expected a number after '+' (column 10)"),
+            ("=[TODAY] 3", "Expected '+' or '-' before 3 (column 10)"),
+            ("=[TODAY", "Missing ']' (column 2)"),
+            ("=next someday", "Unknown weekday 'someday' (column 7)"),
+            ("=end of year", "Expected 'end of month' (column 2)"),
+            ("=[TODAY]*2", "Unexpected character '*' (column 9)"),
+        ];
+        for (formula, message) in cases {
+            assert_eq!(
+                FormulaParser::parse(formula).unwrap_err().to_string(),
+                message,
+                "{}",
+                formula
+            );
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::models::typed_value::Hyperlink;
 use crate::models::{FrontMatter, TagValue};
 use std::collections::HashMap;
 use regex::Regex;
@@ -118,6 +119,11 @@ impl FrontMatterParser {
                     .collect();
                 TagValue::Array(strings)
             }
+            // `{url, text}`形式のハイパーリンクは`[text](url)`の文字列として扱う
+            serde_yaml::Value::Mapping(map) if map.contains_key("url") => {
+                let field = |key: &str| map.get(key).and_then(|v| v.as_str()).unwrap_or("").to_string();
+                TagValue::String(Hyperlink { url: field("url"), text: field("text") }.to_string())
+            }
             _ => TagValue::String(value.as_str().unwrap_or("").to_string()),
         }
     }
@@ -154,7 +160,7 @@ impl FrontMatterParser {
                         if s.is_empty() {
                             yaml_lines.push(format!("{}: ", key));
                         } else {
-                            yaml_lines.push(format!("{}: {}", key, s));
+                            yaml_lines.push(format!("{}: {}", key, Self::yaml_scalar(s, false)));
                         }
                     }
                     TagValue::Number(n) => {
@@ -170,7 +176,8 @@ impl FrontMatterParser {
                         if arr.is_empty() {
                             yaml_lines.push(format!("{}: []", key));
                         } else {
-                            let array_str = format!("[{}]", arr.join(", "));
+                            let items: Vec<String> = arr.iter().map(|item| Self::yaml_scalar(item, true)).collect();
+                            let array_str = format!("[{}]", items.join(", "));
                             yaml_lines.push(format!("{}: {}", key, array_str));
                         }
                     }
@@ -181,6 +188,25 @@ impl FrontMatterParser {
         let yaml = yaml_lines.join("\n");
         Ok(format!("---\n{}\n---\n\n{}", yaml, content))
     }
+
+    /// 文字列をYAMLのスカラーとして書き出す
+    ///
+    /// そのままでは別の値（数値・真偽値・リストなど）として読まれる文字列や、
+    /// フロー形式の配列内で区切り文字を含む文字列は二重引用符で囲む。
+    fn yaml_scalar(value: &str, in_flow: bool) -> String {
+        let plain = !value.contains('\n')
+            && (!in_flow || !value.contains([',', '[', ']', '{', '}']))
+            && matches!(
+                serde_yaml::from_str::<serde_yaml::Value>(value),
+                Ok(serde_yaml::Value::String(ref parsed)) if parsed == value
+            );
+        if plain {
+            value.to_string()
+        } else {
+            // JSONの文字列はYAMLの二重引用符スカラーとしてそのまま読める
+            serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -274,4 +300,35 @@ Content"#;
         assert!(result.contains("- inprogress"));
         assert!(result.contains("- close"));
     }
+
+    #[test]
+    fn test_typed_values_round_trip() {
+        let values = [
+            ("price", TagValue::String("1200.50 USD".to_string())),
+            ("link", TagValue::String("[Spec: v2](https://example.com/a?b=c#d)".to_string())),
+            ("cover", TagValue::String(".hienmark/assets/cover.png".to_string())),
+            ("started", TagValue::String("2025-01-31T09:30:00+09:00".to_string())),
+            ("code", TagValue::String("0123".to_string())),
+            ("note", TagValue::String("a # b".to_string())),
+            ("labels", TagValue::Array(vec!["a, b".to_string(), "[c]".to_string(), "d".to_string()])),
+        ];
+        let front_matter = FrontMatter {
+            tags: values.iter().map(|(k, v)| (k.to_string(), v.clone())).collect(),
+        };
+
+        let serialized = FrontMatterParser::serialize(&front_matter, "Task body").unwrap();
+        let (parsed, body) = FrontMatterParser::parse(&serialized).unwrap();
+        assert_eq!(parsed.tags, front_matter.tags);
+        assert_eq!(body, "Task body");
+        assert!(serialized.contains("price: 1200.50 USD"));
+
+        // 以前の形式の`{url, text}`はMarkdownリンクとして読み込む
+        let legacy = "---\nlink:\n  url: https://example.com\n  text: Example\n---\n\nbody";
+        let (parsed, _) = FrontMatterParser::parse(legacy).unwrap();
+        assert_eq!(
+            parsed.tags["link"],
+            TagValue::String("[Example](https://example.com)".to_string())
+        );
+    }
+
 }
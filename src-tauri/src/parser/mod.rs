@@ -1,3 +1,13 @@
 pub mod frontmatter;
+pub mod asset_refs;
+pub mod formula;
+pub mod expression;
+pub mod validation_rules;
+pub mod placeholder;
 
 pub use frontmatter::FrontMatterParser;
+pub use asset_refs::AssetReferenceParser;
+pub use formula::FormulaParser;
+pub use expression::ComputedFields;
+pub use validation_rules::ValidationRules;
+pub use placeholder::{Placeholder, PlaceholderParser};
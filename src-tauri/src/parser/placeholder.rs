@@ -0,0 +1,153 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+use super::FormulaParser;
+
+/// テンプレート本文のプレースホルダー
+#[derive(Debug, Clone, PartialEq)]
+pub enum Placeholder {
+    /// `{{title}}` - タスクのタイトル
+    Title,
+    /// `{{date}}` - 今日の日付（`YYYY-MM-DD`）
+    Date,
+    /// `{{user}}` - 作成者のユーザー名
+    User,
+    /// `{{prompt:Component}}` - 適用時にユーザーへ入力を求める値
+    Prompt(String),
+    /// `{{=[TODAY]+7}}` - 動的デフォルト値の計算式
+    Formula(String),
+}
+
+/// テンプレート本文のプレースホルダー（`{{...}}`）を解析・置換するパーサー
+pub struct PlaceholderParser;
+
+impl PlaceholderParser {
+    fn pattern() -> &'static Regex {
+        static PATTERN: OnceLock<Regex> = OnceLock::new();
+        PATTERN.get_or_init(|| Regex::new(r"\{\{\s*(.*?)\s*\}\}").unwrap())
+    }
+
+    /// `{{`と`}}`の内側を解釈
+    ///
+    /// # Arguments
+    /// * `inner` - 括弧の内側（前後の空白は除去済み）
+    ///
+    /// # Returns
+    /// * `Result<Placeholder, String>` - 解釈結果（未知の名前や不正な計算式はエラー）
+    pub fn parse(inner: &str) -> Result<Placeholder, String> {
+        if FormulaParser::is_formula(inner) {
+            FormulaParser::parse(inner).map_err(|e| format!("Invalid formula '{}': {}", inner, e))?;
+            return Ok(Placeholder::Formula(inner.to_string()));
+        }
+        if let Some(label) = inner.strip_prefix("prompt:") {
+            let label = label.trim();
+            if label.is_empty() {
+                return Err("Prompt placeholder needs a label: {{prompt:Label}}".to_string());
+            }
+            return Ok(Placeholder::Prompt(label.to_string()));
+        }
+        match inner {
+            "title" => Ok(Placeholder::Title),
+            "date" => Ok(Placeholder::Date),
+            "user" => Ok(Placeholder::User),
+            _ => Err(format!("Unknown placeholder '{{{{{}}}}}'", inner)),
+        }
+    }
+
+    /// 本文中のプレースホルダーを置換
+    ///
+    /// # Arguments
+    /// * `body` - テンプレート本文
+    /// * `resolve` - プレースホルダーの値を返す関数（`None`の場合は元の記述をそのまま残す）
+    ///
+    /// # Returns
+    /// * `Result<String, String>` - 置換後の本文
+    pub fn render<F>(body: &str, mut resolve: F) -> Result<String, String>
+    where
+        F: FnMut(&Placeholder) -> Result<Option<String>, String>,
+    {
+        let mut rendered = String::with_capacity(body.len());
+        let mut last = 0;
+        for caps in Self::pattern().captures_iter(body) {
+            let whole = caps.get(0).unwrap();
+            rendered.push_str(&body[last..whole.start()]);
+            match resolve(&Self::parse(&caps[1])?)? {
+                Some(value) => rendered.push_str(&value),
+                None => rendered.push_str(whole.as_str()),
+            }
+            last = whole.end();
+        }
+        rendered.push_str(&body[last..]);
+        Ok(rendered)
+    }
+
+    /// 本文中の`{{prompt:...}}`のラベルを出現順に返す（重複は除く）
+    pub fn prompts(body: &str) -> Vec<String> {
+        let mut labels: Vec<String> = Vec::new();
+        for caps in Self::pattern().captures_iter(body) {
+            if let Ok(Placeholder::Prompt(label)) = Self::parse(&caps[1]) {
+                if !labels.contains(&label) {
+                    labels.push(label);
+                }
+            }
+        }
+        labels
+    }
+
+    /// 本文中のプレースホルダーを検証
+    ///
+    /// # Returns
+    /// * `Vec<String>` - エラーメッセージのリスト
+    pub fn validate(body: &str) -> Vec<String> {
+        Self::pattern()
+            .captures_iter(body)
+            .filter_map(|caps| Self::parse(&caps[1]).err())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_placeholders() {
+        assert_eq!(PlaceholderParser::parse("title"), Ok(Placeholder::Title));
+        assert_eq!(
+            PlaceholderParser::parse("prompt: Component"),
+            Ok(Placeholder::Prompt("Component".to_string()))
+        );
+        assert_eq!(
+            PlaceholderParser::parse("=[TODAY]+7"),
+            Ok(Placeholder::Formula("=[TODAY]+7".to_string()))
+        );
+        assert_eq!(
+            PlaceholderParser::parse("owner"),
+            Err("Unknown placeholder '{{owner}}'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_render_and_prompts() {
+        let body = "# {{ title }}\n\n- Component: {{prompt:Component}}\n- Again: {{prompt:Component}}\n- Env: {{prompt:Env}}";
+        assert_eq!(PlaceholderParser::prompts(body), vec!["Component", "Env"]);
+
+        let rendered = PlaceholderParser::render(body, |placeholder| {
+            Ok(match placeholder {
+                Placeholder::Title => Some("Crash on save".to_string()),
+                Placeholder::Prompt(label) if label == "Component" => Some("editor".to_string()),
+                _ => None,
+            })
+        })
+        .unwrap();
+        assert_eq!(
+            rendered,
+            "# Crash on save\n\n- Component: editor\n- Again: editor\n- Env: {{prompt:Env}}"
+        );
+
+        assert_eq!(
+            PlaceholderParser::validate("{{date}} {{nope}} {{=[TODAY]+}}").len(),
+            2
+        );
+    }
+}
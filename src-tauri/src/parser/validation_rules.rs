@@ -0,0 +1,150 @@
+use crate::models::tag_schema::ValidationRule;
+use crate::models::{TagSchema, TagValue};
+use crate::parser::expression::{Expression, ExpressionParser, ExpressionValue, NameLookup};
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
+
+/// タグスキーマで定義された検証規則の集合
+///
+/// 式は作成時に一度だけ解析する。解析できない式を持つ規則は評価せず、
+/// `errors`として検証結果のスキーマエラーに含める。
+#[derive(Debug, Clone, Default)]
+pub struct ValidationRules {
+    rules: Vec<CompiledRule>,
+    /// スキーマで定義されたタグ名（値がなければnull、それ以外の名前は文字列になる）
+    known_names: HashSet<String>,
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    rule: ValidationRule,
+    when: Option<Expression>,
+    assert: Option<Expression>,
+}
+
+/// 規則に違反した内容
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleFailure<'a> {
+    /// 違反した規則
+    pub rule: &'a ValidationRule,
+    /// 違反を報告するタグ（`require`の場合は欠けているタグ、`assert`の場合は最初に参照するタグ）
+    pub category: String,
+    /// 対象のタグの値
+    pub value: Option<TagValue>,
+    /// 表示用のメッセージ
+    pub message: String,
+}
+
+impl ValidationRules {
+    /// タグスキーマから検証規則を取り出す
+    pub fn from_schema(schema: &TagSchema) -> Self {
+        let mut rules = Vec::new();
+        let mut errors = Vec::new();
+        for rule in &schema.rules {
+            let parse = |label: &str, source: &Option<String>| match source.as_deref() {
+                Some(source) => ExpressionParser::parse(source).map(Some).map_err(|e| {
+                    format!("Invalid {} expression in rule {}: {}", label, rule.id, e)
+                }),
+                None => Ok(None),
+            };
+            match (parse("when", &rule.when), parse("assert", &rule.assert)) {
+                (Ok(when), Ok(assert)) => rules.push(CompiledRule {
+                    rule: rule.clone(),
+                    when,
+                    assert,
+                }),
+                (when, assert) => errors.extend(when.err().into_iter().chain(assert.err())),
+            }
+        }
+
+        Self {
+            rules,
+            known_names: schema.tags.keys().cloned().collect(),
+            errors,
+        }
+    }
+
+    /// 解析できなかった規則の式
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// 1タスク分のタグに規則を適用
+    ///
+    /// # Arguments
+    /// * `tags` - タスクのタグ（計算フィールドの値を含む）
+    /// * `today` - `TODAY`の値
+    ///
+    /// # Returns
+    /// * `Vec<RuleFailure>` - 違反（規則の定義順）
+    pub fn evaluate(&self, tags: &HashMap<String, TagValue>, today: NaiveDate) -> Vec<RuleFailure<'_>> {
+        let mut failures = Vec::new();
+        for compiled in &self.rules {
+            let rule = &compiled.rule;
+            let mut lookup = |name: &str| match tags.get(name) {
+                Some(value) => NameLookup::Value(ExpressionValue::from_tag(value)),
+                None if self.known_names.contains(name) || rule.require.iter().any(|r| r == name) => {
+                    NameLookup::Missing
+                }
+                None => NameLookup::Unknown,
+            };
+
+            // 条件が評価できない（参照するタグがない）場合も適用しない
+            if let Some(when) = &compiled.when {
+                if !when.evaluate(today, &mut lookup).is_truthy() {
+                    continue;
+                }
+            }
+
+            for name in &rule.require {
+                if tags.get(name).is_some_and(|value| !is_blank(value)) {
+                    continue;
+                }
+                failures.push(RuleFailure {
+                    rule,
+                    category: name.clone(),
+                    value: tags.get(name).cloned(),
+                    message: rule.message.clone().unwrap_or_else(|| match &rule.when {
+                        Some(when) => format!("{} is required when {}", name, when),
+                        None => format!("{} is required", name),
+                    }),
+                });
+            }
+
+            if let Some(assert) = &compiled.assert {
+                let holds = match assert.evaluate(today, &mut lookup) {
+                    ExpressionValue::Null => true,
+                    value => value.is_truthy(),
+                };
+                if holds {
+                    continue;
+                }
+                let category = assert
+                    .names()
+                    .into_iter()
+                    .find(|name| tags.contains_key(*name))
+                    .unwrap_or(rule.id.as_str())
+                    .to_string();
+                failures.push(RuleFailure {
+                    rule,
+                    value: tags.get(&category).cloned(),
+                    category,
+                    message: rule.message.clone().unwrap_or_else(|| {
+                        format!("{} must hold", rule.assert.as_deref().unwrap_or_default())
+                    }),
+                });
+            }
+        }
+        failures
+    }
+}
+
+/// 空文字列・空配列は未入力として扱う
+fn is_blank(value: &TagValue) -> bool {
+    match value {
+        TagValue::String(s) => s.trim().is_empty(),
+        TagValue::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
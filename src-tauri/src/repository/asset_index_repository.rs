@@ -1,8 +1,15 @@
 // Asset index repository
 
 use crate::repository::database::IndexDatabase;
+use crate::utils::media::MediaInfo;
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Result as SqliteResult};
+use rusqlite::{params, Connection, Result as SqliteResult, Row};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::borrow::Borrow;
+use std::fs;
+use std::io;
+use std::path::Path;
 
 /// アセットインデックスのリポジトリトレイト
 pub trait AssetIndexRepository {
@@ -29,24 +36,69 @@ pub struct AssetIndexEntry {
     pub file_size: Option<i64>,
     pub mime_type: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// ファイル内容のSHA-256（16進文字列）
+    pub content_hash: Option<String>,
+    /// 画像の幅（ピクセル）
+    pub width: Option<i64>,
+    /// 画像の高さ（ピクセル）
+    pub height: Option<i64>,
+}
+
+impl AssetIndexEntry {
+    /// ワークスペース内のアセットファイルを解析してエントリを作成
+    ///
+    /// # Arguments
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    /// * `asset_path` - アセットの相対パス（IDとしても使用）
+    /// * `task_id` - 所有タスクID
+    pub fn from_file(workspace_root: &Path, asset_path: &str, task_id: &str) -> Result<Self, io::Error> {
+        let bytes = fs::read(workspace_root.join(asset_path))?;
+        let media = MediaInfo::inspect(&bytes);
+
+        Ok(Self {
+            id: asset_path.to_string(),
+            task_id: task_id.to_string(),
+            file_path: asset_path.to_string(),
+            relative_path: asset_path.to_string(),
+            file_size: Some(bytes.len() as i64),
+            mime_type: media.mime_type.map(str::to_string),
+            created_at: Utc::now(),
+            content_hash: Some(format!("{:x}", Sha256::digest(&bytes))),
+            width: media.width.map(i64::from),
+            height: media.height.map(i64::from),
+        })
+    }
+}
+
+/// タスク本文からアセットへの参照
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetReference {
+    pub task_id: String,
+    /// ワークスペースルートからの相対パス
+    pub asset_path: String,
 }
 
 /// SQLite実装
-pub struct SqliteAssetIndexRepository<'a> {
-    db: &'a IndexDatabase,
+pub struct SqliteAssetIndexRepository<D: Borrow<IndexDatabase>> {
+    db: D,
 }
 
-impl<'a> SqliteAssetIndexRepository<'a> {
-    pub fn new(db: &'a IndexDatabase) -> Self {
+impl<D: Borrow<IndexDatabase>> SqliteAssetIndexRepository<D> {
+    pub fn new(db: D) -> Self {
         Self { db }
     }
 
+    fn connection(&self) -> &Connection {
+        self.db.borrow().connection()
+    }
+
     /// アセットを追加または更新
     pub fn upsert_asset(&self, entry: &AssetIndexEntry) -> SqliteResult<()> {
         let created_at = entry.created_at.timestamp();
-        self.db.connection().execute(
-            "INSERT OR REPLACE INTO assets_index (id, task_id, file_path, relative_path, file_size, mime_type, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        self.connection().execute(
+            "INSERT OR REPLACE INTO assets_index (id, task_id, file_path, relative_path, file_size, mime_type, created_at, content_hash, width, height)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             params![
                 entry.id,
                 entry.task_id,
@@ -54,7 +106,10 @@ impl<'a> SqliteAssetIndexRepository<'a> {
                 entry.relative_path,
                 entry.file_size,
                 entry.mime_type,
-                created_at
+                created_at,
+                entry.content_hash,
+                entry.width,
+                entry.height
             ],
         )?;
         Ok(())
@@ -62,7 +117,7 @@ impl<'a> SqliteAssetIndexRepository<'a> {
 
     /// アセットを削除
     pub fn delete_asset(&self, asset_id: &str) -> SqliteResult<()> {
-        self.db.connection().execute(
+        self.connection().execute(
             "DELETE FROM assets_index WHERE id = ?1",
             params![asset_id],
         )?;
@@ -71,38 +126,132 @@ impl<'a> SqliteAssetIndexRepository<'a> {
 
     /// タスクIDでアセットを削除
     pub fn delete_assets_by_task(&self, task_id: &str) -> SqliteResult<()> {
-        self.db.connection().execute(
+        self.connection().execute(
             "DELETE FROM assets_index WHERE task_id = ?1",
             params![task_id],
         )?;
         Ok(())
     }
 
+    /// アセットが未登録の場合のみ追加（既存の所有タスクは変更しない）
+    pub fn insert_asset_if_absent(&self, entry: &AssetIndexEntry) -> SqliteResult<()> {
+        self.connection().execute(
+            "INSERT OR IGNORE INTO assets_index (id, task_id, file_path, relative_path, file_size, mime_type, created_at, content_hash, width, height)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                entry.id,
+                entry.task_id,
+                entry.file_path,
+                entry.relative_path,
+                entry.file_size,
+                entry.mime_type,
+                entry.created_at.timestamp(),
+                entry.content_hash,
+                entry.width,
+                entry.height
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// ファイルパスでアセットを削除
+    pub fn delete_asset_by_path(&self, file_path: &str) -> SqliteResult<()> {
+        self.connection().execute(
+            "DELETE FROM assets_index WHERE file_path = ?1",
+            params![file_path],
+        )?;
+        Ok(())
+    }
+
+    /// タスクのアセット参照を置き換える
+    pub fn replace_task_references(&self, task_id: &str, asset_paths: &[String]) -> SqliteResult<()> {
+        let connection = self.connection();
+        connection.execute(
+            "DELETE FROM asset_references WHERE task_id = ?1",
+            params![task_id],
+        )?;
+
+        let mut stmt = connection.prepare(
+            "INSERT OR IGNORE INTO asset_references (task_id, asset_path) VALUES (?1, ?2)",
+        )?;
+        for asset_path in asset_paths {
+            stmt.execute(params![task_id, asset_path])?;
+        }
+
+        Ok(())
+    }
+
+    /// 全アセット参照の一覧を取得
+    pub fn list_references(&self) -> SqliteResult<Vec<AssetReference>> {
+        let mut stmt = self.connection().prepare(
+            "SELECT task_id, asset_path FROM asset_references ORDER BY asset_path, task_id",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(AssetReference {
+                task_id: row.get(0)?,
+                asset_path: row.get(1)?,
+            })
+        })?;
+
+        let mut references = Vec::new();
+        for row_result in rows {
+            references.push(row_result?);
+        }
+
+        Ok(references)
+    }
+
+    /// コンテンツハッシュが一致するアセットを取得
+    pub fn find_by_content_hash(&self, content_hash: &str) -> SqliteResult<Vec<AssetIndexEntry>> {
+        let mut stmt = self.connection().prepare(
+            "SELECT id, task_id, file_path, relative_path, file_size, mime_type, created_at, content_hash, width, height
+             FROM assets_index
+             WHERE content_hash = ?1
+             ORDER BY created_at",
+        )?;
+
+        let rows = stmt.query_map(params![content_hash], Self::row_to_entry)?;
+
+        let mut entries = Vec::new();
+        for row_result in rows {
+            entries.push(row_result?);
+        }
+
+        Ok(entries)
+    }
+
+    /// 行をAssetIndexEntryに変換
+    fn row_to_entry(row: &Row) -> SqliteResult<AssetIndexEntry> {
+        Ok(AssetIndexEntry {
+            id: row.get(0)?,
+            task_id: row.get(1)?,
+            file_path: row.get(2)?,
+            relative_path: row.get(3)?,
+            file_size: row.get(4)?,
+            mime_type: row.get(5)?,
+            created_at: Self::timestamp_to_datetime(row.get(6)?),
+            content_hash: row.get(7)?,
+            width: row.get(8)?,
+            height: row.get(9)?,
+        })
+    }
+
     /// Unix timestampからDateTime<Utc>に変換
     fn timestamp_to_datetime(timestamp: i64) -> DateTime<Utc> {
         DateTime::from_timestamp(timestamp, 0).unwrap_or_else(|| Utc::now())
     }
 }
 
-impl<'a> AssetIndexRepository for SqliteAssetIndexRepository<'a> {
+impl<D: Borrow<IndexDatabase>> AssetIndexRepository for SqliteAssetIndexRepository<D> {
     fn get_asset(&self, asset_id: &str) -> SqliteResult<Option<AssetIndexEntry>> {
-        let mut stmt = self.db.connection().prepare(
-            "SELECT id, task_id, file_path, relative_path, file_size, mime_type, created_at
+        let mut stmt = self.connection().prepare(
+            "SELECT id, task_id, file_path, relative_path, file_size, mime_type, created_at, content_hash, width, height
              FROM assets_index
              WHERE id = ?1",
         )?;
 
-        let entry = stmt.query_row(params![asset_id], |row| {
-            Ok(AssetIndexEntry {
-                id: row.get(0)?,
-                task_id: row.get(1)?,
-                file_path: row.get(2)?,
-                relative_path: row.get(3)?,
-                file_size: row.get(4)?,
-                mime_type: row.get(5)?,
-                created_at: Self::timestamp_to_datetime(row.get(6)?),
-            })
-        });
+        let entry = stmt.query_row(params![asset_id], Self::row_to_entry);
 
         match entry {
             Ok(e) => Ok(Some(e)),
@@ -112,23 +261,13 @@ impl<'a> AssetIndexRepository for SqliteAssetIndexRepository<'a> {
     }
 
     fn list_assets(&self) -> SqliteResult<Vec<AssetIndexEntry>> {
-        let mut stmt = self.db.connection().prepare(
-            "SELECT id, task_id, file_path, relative_path, file_size, mime_type, created_at
+        let mut stmt = self.connection().prepare(
+            "SELECT id, task_id, file_path, relative_path, file_size, mime_type, created_at, content_hash, width, height
              FROM assets_index
              ORDER BY created_at DESC",
         )?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(AssetIndexEntry {
-                id: row.get(0)?,
-                task_id: row.get(1)?,
-                file_path: row.get(2)?,
-                relative_path: row.get(3)?,
-                file_size: row.get(4)?,
-                mime_type: row.get(5)?,
-                created_at: Self::timestamp_to_datetime(row.get(6)?),
-            })
-        })?;
+        let rows = stmt.query_map([], Self::row_to_entry)?;
 
         let mut entries = Vec::new();
         for row_result in rows {
@@ -139,24 +278,14 @@ impl<'a> AssetIndexRepository for SqliteAssetIndexRepository<'a> {
     }
 
     fn list_assets_by_task(&self, task_id: &str) -> SqliteResult<Vec<AssetIndexEntry>> {
-        let mut stmt = self.db.connection().prepare(
-            "SELECT id, task_id, file_path, relative_path, file_size, mime_type, created_at
+        let mut stmt = self.connection().prepare(
+            "SELECT id, task_id, file_path, relative_path, file_size, mime_type, created_at, content_hash, width, height
              FROM assets_index
              WHERE task_id = ?1
              ORDER BY created_at DESC",
         )?;
 
-        let rows = stmt.query_map(params![task_id], |row| {
-            Ok(AssetIndexEntry {
-                id: row.get(0)?,
-                task_id: row.get(1)?,
-                file_path: row.get(2)?,
-                relative_path: row.get(3)?,
-                file_size: row.get(4)?,
-                mime_type: row.get(5)?,
-                created_at: Self::timestamp_to_datetime(row.get(6)?),
-            })
-        })?;
+        let rows = stmt.query_map(params![task_id], Self::row_to_entry)?;
 
         let mut entries = Vec::new();
         for row_result in rows {
@@ -167,23 +296,13 @@ impl<'a> AssetIndexRepository for SqliteAssetIndexRepository<'a> {
     }
 
     fn get_asset_by_path(&self, file_path: &str) -> SqliteResult<Option<AssetIndexEntry>> {
-        let mut stmt = self.db.connection().prepare(
-            "SELECT id, task_id, file_path, relative_path, file_size, mime_type, created_at
+        let mut stmt = self.connection().prepare(
+            "SELECT id, task_id, file_path, relative_path, file_size, mime_type, created_at, content_hash, width, height
              FROM assets_index
              WHERE file_path = ?1",
         )?;
 
-        let entry = stmt.query_row(params![file_path], |row| {
-            Ok(AssetIndexEntry {
-                id: row.get(0)?,
-                task_id: row.get(1)?,
-                file_path: row.get(2)?,
-                relative_path: row.get(3)?,
-                file_size: row.get(4)?,
-                mime_type: row.get(5)?,
-                created_at: Self::timestamp_to_datetime(row.get(6)?),
-            })
-        });
+        let entry = stmt.query_row(params![file_path], Self::row_to_entry);
 
         match entry {
             Ok(e) => Ok(Some(e)),
@@ -213,6 +332,10 @@ mod tests {
             modified_at: Utc::now(),
             indexed_at: Utc::now(),
             created_at: Some(Utc::now()),
+            title: None,
+            body_hash: None,
+            archived: false,
+            computed_tags: HashMap::new(),
         }
     }
 
@@ -235,6 +358,9 @@ mod tests {
             file_size: Some(1024),
             mime_type: Some("image/png".to_string()),
             created_at: Utc::now(),
+            content_hash: None,
+            width: Some(640),
+            height: Some(480),
         };
 
         asset_repo.upsert_asset(&entry).unwrap();
@@ -242,6 +368,7 @@ mod tests {
 
         assert_eq!(retrieved.id, "asset-1");
         assert_eq!(retrieved.task_id, "task-1");
+        assert_eq!(retrieved.width, Some(640));
     }
 
     #[test]
@@ -263,6 +390,9 @@ mod tests {
             file_size: None,
             mime_type: None,
             created_at: Utc::now(),
+            content_hash: None,
+            width: None,
+            height: None,
         };
 
         asset_repo.upsert_asset(&entry).unwrap();
@@ -0,0 +1,302 @@
+// Connection management for concurrent index access
+
+use crate::repository::database::{IndexDatabase, IndexRecovery};
+use crate::repository::index_builder::IndexWriter;
+use rusqlite::Result as SqliteResult;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+/// 再利用のために保持する読み取り接続の最大数
+pub const DEFAULT_MAX_IDLE_READERS: usize = 4;
+
+/// 書き込み接続の取得を待つ最大時間
+const WRITER_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// ワークスペース1つ分の接続プール
+///
+/// WALモードでは読み取りと書き込みが互いをブロックしないため、
+/// 書き込みは単一の接続に直列化し、読み取りは複数の接続で並行に実行する。
+pub struct IndexPool {
+    workspace_root: PathBuf,
+    /// 書き込み接続（貸し出し中はNone）
+    writer: Mutex<Option<IndexDatabase>>,
+    writer_returned: Condvar,
+    /// 待機中の読み取り接続
+    idle_readers: Mutex<Vec<IndexDatabase>>,
+    max_idle_readers: usize,
+    /// 開く際にキャッシュを作り直した場合の情報（未通知のもの）
+    recovery: Mutex<Option<IndexRecovery>>,
+}
+
+impl IndexPool {
+    /// プールを作成
+    ///
+    /// 書き込み接続を最初に開くため、整合性チェック・マイグレーション・
+    /// 破損したキャッシュの作り直しはここで行われる。
+    ///
+    /// # Arguments
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    ///
+    /// # Returns
+    /// * `Result<Arc<IndexPool>, rusqlite::Error>` - 接続プール
+    pub fn open(workspace_root: &Path) -> SqliteResult<Arc<Self>> {
+        let writer = IndexDatabase::new(workspace_root)?;
+        let recovery = writer.recovery().cloned();
+        if let Some(ref recovery) = recovery {
+            log::warn!(
+                "Index cache was rebuilt ({}), previous cache moved to {}",
+                recovery.reason,
+                recovery.quarantined_path.display()
+            );
+        }
+
+        Ok(Arc::new(Self {
+            workspace_root: workspace_root.to_path_buf(),
+            writer: Mutex::new(Some(writer)),
+            writer_returned: Condvar::new(),
+            idle_readers: Mutex::new(Vec::new()),
+            max_idle_readers: DEFAULT_MAX_IDLE_READERS,
+            recovery: Mutex::new(recovery),
+        }))
+    }
+
+    /// キャッシュの作り直しが未通知であれば、その情報を取り出す
+    pub fn take_recovery(&self) -> Option<IndexRecovery> {
+        self.recovery.lock().ok().and_then(|mut recovery| recovery.take())
+    }
+
+    /// 読み取り接続を取得（待機中の接続がなければ新しく開く）
+    pub fn reader(self: &Arc<Self>) -> SqliteResult<PooledConnection> {
+        let idle = self.idle_readers.lock().ok().and_then(|mut readers| readers.pop());
+        let db = match idle {
+            Some(db) => db,
+            None => IndexDatabase::open_reader(&self.workspace_root)?,
+        };
+
+        Ok(PooledConnection {
+            pool: Arc::clone(self),
+            db: Some(db),
+            is_writer: false,
+        })
+    }
+
+    /// 書き込み接続を取得（他で使用中の場合は返却を待つ）
+    pub fn writer(self: &Arc<Self>) -> SqliteResult<PooledConnection> {
+        let guard = self.writer.lock().map_err(|_| Self::busy_error("writer lock poisoned"))?;
+        let (mut guard, _) = self
+            .writer_returned
+            .wait_timeout_while(guard, WRITER_WAIT_TIMEOUT, |writer| writer.is_none())
+            .map_err(|_| Self::busy_error("writer lock poisoned"))?;
+
+        let db = guard
+            .take()
+            .ok_or_else(|| Self::busy_error("timed out waiting for the index writer"))?;
+
+        Ok(PooledConnection {
+            pool: Arc::clone(self),
+            db: Some(db),
+            is_writer: true,
+        })
+    }
+
+    fn release(&self, db: IndexDatabase, is_writer: bool) {
+        if is_writer {
+            if let Ok(mut writer) = self.writer.lock() {
+                *writer = Some(db);
+            }
+            self.writer_returned.notify_one();
+        } else if let Ok(mut readers) = self.idle_readers.lock() {
+            if readers.len() < self.max_idle_readers {
+                readers.push(db);
+            }
+        }
+    }
+
+    fn busy_error(message: &str) -> rusqlite::Error {
+        rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            Some(message.to_string()),
+        )
+    }
+}
+
+/// インデックスビルダーにはトランザクションごとに書き込み接続を貸し出す
+impl IndexWriter for Arc<IndexPool> {
+    type Connection<'a> = PooledConnection;
+
+    fn acquire(&self) -> SqliteResult<PooledConnection> {
+        self.writer()
+    }
+}
+
+/// プールから貸し出された接続
+///
+/// 所有型のハンドルのためスレッド間で受け渡しでき、ドロップ時にプールへ返却される。
+pub struct PooledConnection {
+    pool: Arc<IndexPool>,
+    db: Option<IndexDatabase>,
+    is_writer: bool,
+}
+
+impl Deref for PooledConnection {
+    type Target = IndexDatabase;
+
+    fn deref(&self) -> &IndexDatabase {
+        self.db.as_ref().expect("pooled connection used after release")
+    }
+}
+
+impl Borrow<IndexDatabase> for PooledConnection {
+    fn borrow(&self) -> &IndexDatabase {
+        self
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(db) = self.db.take() {
+            self.pool.release(db, self.is_writer);
+        }
+    }
+}
+
+/// ワークスペースごとの接続プールを管理する（Tauriのマネージドステート）
+pub struct IndexConnectionManager {
+    pools: Mutex<HashMap<PathBuf, Arc<IndexPool>>>,
+}
+
+impl IndexConnectionManager {
+    pub fn new() -> Self {
+        Self {
+            pools: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// ワークスペースの接続プールを取得（初回は作成する）
+    ///
+    /// # Arguments
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    ///
+    /// # Returns
+    /// * `Result<Arc<IndexPool>, rusqlite::Error>` - 接続プール
+    pub fn pool(&self, workspace_root: &Path) -> SqliteResult<Arc<IndexPool>> {
+        let mut pools = self
+            .pools
+            .lock()
+            .map_err(|_| IndexPool::busy_error("connection manager lock poisoned"))?;
+
+        if let Some(pool) = pools.get(workspace_root) {
+            return Ok(Arc::clone(pool));
+        }
+
+        let pool = IndexPool::open(workspace_root)?;
+        pools.insert(workspace_root.to_path_buf(), Arc::clone(&pool));
+        Ok(pool)
+    }
+
+    /// ワークスペースの読み取り接続を取得
+    pub fn reader(&self, workspace_root: &Path) -> SqliteResult<PooledConnection> {
+        self.pool(workspace_root)?.reader()
+    }
+
+    /// ワークスペースの書き込み接続を取得
+    pub fn writer(&self, workspace_root: &Path) -> SqliteResult<PooledConnection> {
+        self.pool(workspace_root)?.writer()
+    }
+}
+
+impl Default for IndexConnectionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task::FrontMatter;
+    use crate::repository::task_index_repository::{
+        SqliteTaskIndexRepository, TaskIndexEntry, TaskIndexRepository,
+    };
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn entry(id: &str) -> TaskIndexEntry {
+        TaskIndexEntry {
+            id: id.to_string(),
+            file_path: format!("{}.md", id),
+            front_matter: FrontMatter::default(),
+            modified_at: Utc::now(),
+            indexed_at: Utc::now(),
+            created_at: None,
+            title: None,
+            body_hash: None,
+            archived: false,
+            computed_tags: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_readers_run_while_writer_is_held() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = IndexConnectionManager::new();
+        let pool = manager.pool(temp_dir.path()).unwrap();
+
+        // 書き込みトランザクション中でも、コミット済みの内容を別スレッドから読み取れる
+        let connection = pool.writer().unwrap();
+        let writer = SqliteTaskIndexRepository::new(&*connection);
+        writer.upsert_task(&entry("committed")).unwrap();
+        let transaction = connection.connection().unchecked_transaction().unwrap();
+        writer.upsert_task(&entry("pending")).unwrap();
+
+        let readers: Vec<_> = (0..3)
+            .map(|_| {
+                let repo = SqliteTaskIndexRepository::new(pool.reader().unwrap());
+                std::thread::spawn(move || repo.list_tasks().unwrap().len())
+            })
+            .collect();
+        for handle in readers {
+            assert_eq!(handle.join().unwrap(), 1);
+        }
+
+        transaction.commit().unwrap();
+        let repo = SqliteTaskIndexRepository::new(manager.reader(temp_dir.path()).unwrap());
+        assert_eq!(repo.list_tasks().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_writer_is_exclusive() {
+        let temp_dir = TempDir::new().unwrap();
+        let pool = IndexPool::open(temp_dir.path()).unwrap();
+
+        let first = pool.writer().unwrap();
+        let waiter = {
+            let pool = Arc::clone(&pool);
+            std::thread::spawn(move || {
+                let writer = SqliteTaskIndexRepository::new(pool.writer().unwrap());
+                writer.upsert_task(&entry("second")).unwrap();
+            })
+        };
+
+        SqliteTaskIndexRepository::new(&*first).upsert_task(&entry("first")).unwrap();
+        drop(first);
+        waiter.join().unwrap();
+
+        let repo = SqliteTaskIndexRepository::new(pool.reader().unwrap());
+        assert_eq!(repo.list_tasks().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_reader_is_query_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let pool = IndexPool::open(temp_dir.path()).unwrap();
+
+        let repo = SqliteTaskIndexRepository::new(pool.reader().unwrap());
+        assert!(repo.upsert_task(&entry("task")).is_err());
+    }
+}
@@ -1,30 +1,182 @@
 // Database connection and schema management
 
-use rusqlite::{Connection, Result as SqliteResult};
-use std::path::Path;
+use crate::repository::migrations::{self, MigrationError};
+use chrono::Utc;
+use rusqlite::{Connection, ErrorCode, Result as SqliteResult};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// キャッシュファイル名
+const CACHE_FILE_NAME: &str = "cache.sqlite";
+
+/// ロック中の場合に再試行を続ける時間
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// インデックスのデータを保持するテーブル（`clear`で全行を削除する）
+const DATA_TABLES: &[&str] = &[
+    "asset_references",
+    "assets_index",
+    "task_tags_index",
+    "task_folders_index",
+    "tags_index",
+    "folders_index",
+    "file_metadata",
+    "tasks_index",
+];
+
+/// スキーマの健全性チェックで必須とするテーブルと列
+const REQUIRED_COLUMNS: &[(&str, &[&str])] = &[
+    (
+        "tasks_index",
+        &["id", "file_path", "front_matter_json", "modified_at", "indexed_at", "created_at", "title", "body_hash", "archived"],
+    ),
+    ("task_tags_index", &["task_id", "category", "value"]),
+    (
+        "assets_index",
+        &["id", "task_id", "file_path", "relative_path", "file_size", "mime_type", "created_at", "content_hash", "width", "height"],
+    ),
+    ("asset_references", &["task_id", "asset_path"]),
+];
+
+/// 破損・非互換のため退避されたキャッシュの情報（`index-recovered`イベントのペイロード）
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexRecovery {
+    /// 退避した理由
+    pub reason: String,
+    /// 退避先のパス
+    pub quarantined_path: PathBuf,
+}
+
+/// 整合性チェックの結果
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityReport {
+    /// 問題がなければtrue
+    pub ok: bool,
+    /// 検出された問題（`PRAGMA integrity_check`の出力・スキーマの不整合）
+    pub problems: Vec<String>,
+}
+
+/// データベースを開く際のエラー
+enum OpenError {
+    /// キャッシュが破損・非互換のため作り直しが必要
+    Unhealthy(String),
+    /// それ以外のエラー（ロック中など。キャッシュには触れない）
+    Sqlite(rusqlite::Error),
+}
+
+impl From<rusqlite::Error> for OpenError {
+    fn from(err: rusqlite::Error) -> Self {
+        match err.sqlite_error_code() {
+            Some(ErrorCode::DatabaseCorrupt) | Some(ErrorCode::NotADatabase) => {
+                OpenError::Unhealthy(err.to_string())
+            }
+            _ => OpenError::Sqlite(err),
+        }
+    }
+}
+
+impl From<MigrationError> for OpenError {
+    fn from(err: MigrationError) -> Self {
+        match err {
+            MigrationError::Sqlite { source, .. } => source.into(),
+            other => OpenError::Unhealthy(other.to_string()),
+        }
+    }
+}
 
 /// SQLiteインデックスデータベース管理
 pub struct IndexDatabase {
     connection: Connection,
+    recovery: Option<IndexRecovery>,
 }
 
 impl IndexDatabase {
     /// 新しいデータベース接続を作成
     ///
+    /// 開く際に`PRAGMA quick_check`とスキーマの健全性を確認し、キャッシュが破損している場合や
+    /// 非互換の場合は退避してから空のキャッシュを作り直す（内容は次回の構築でMarkdownから復元される）。
+    ///
     /// # Arguments
     /// * `workspace_root` - ワークスペースのルートディレクトリ
     ///
     /// # Returns
     /// * `Result<IndexDatabase, rusqlite::Error>` - データベース接続
     pub fn new(workspace_root: &Path) -> SqliteResult<Self> {
+        let db_path = Self::cache_path(workspace_root)?;
+
+        match Self::open_checked(&db_path) {
+            Ok(db) => Ok(db),
+            Err(OpenError::Sqlite(e)) => Err(e),
+            Err(OpenError::Unhealthy(reason)) => {
+                log::warn!("Index cache is unusable ({}), rebuilding: {}", reason, db_path.display());
+                let quarantined_path = Self::quarantine(&db_path).map_err(|e| {
+                    rusqlite::Error::InvalidPath(format!("Failed to quarantine index cache: {}", e).into())
+                })?;
+
+                let mut db = match Self::open_checked(&db_path) {
+                    Ok(db) => db,
+                    Err(OpenError::Sqlite(e)) => return Err(e),
+                    Err(OpenError::Unhealthy(reason)) => {
+                        return Err(rusqlite::Error::InvalidPath(
+                            format!("Failed to recreate index cache: {}", reason).into(),
+                        ))
+                    }
+                };
+                db.recovery = Some(IndexRecovery {
+                    reason,
+                    quarantined_path,
+                });
+                Ok(db)
+            }
+        }
+    }
+
+    /// 読み取り専用の接続を開く
+    ///
+    /// スキーマの確認・マイグレーションは書き込み接続（`new`）で済んでいる前提とし、
+    /// ここでは行わない。
+    ///
+    /// # Arguments
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    pub fn open_reader(workspace_root: &Path) -> SqliteResult<Self> {
+        let connection = Connection::open(Self::cache_path(workspace_root)?)?;
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+        connection.execute("PRAGMA query_only = ON", [])?;
+
+        Ok(Self {
+            connection,
+            recovery: None,
+        })
+    }
+
+    /// インデックスの全データを削除（スキーマは保持する）
+    ///
+    /// 開いている他の接続に影響を与えずに、次回の構築で全件を作り直すために使う。
+    pub fn clear(&self) -> SqliteResult<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+        for table in DATA_TABLES {
+            transaction.execute(&format!("DELETE FROM {}", table), [])?;
+        }
+        transaction.commit()
+    }
+
+    /// キャッシュファイルのパスを取得（.hienmarkディレクトリを作成）
+    fn cache_path(workspace_root: &Path) -> SqliteResult<PathBuf> {
         // .hienmarkディレクトリを作成（存在しない場合）
         let hienmark_dir = workspace_root.join(".hienmark");
         std::fs::create_dir_all(&hienmark_dir).map_err(|e| {
             rusqlite::Error::InvalidPath(format!("Failed to create .hienmark directory: {}", e).into())
         })?;
 
-        let db_path = hienmark_dir.join("cache.sqlite");
-        let connection = Connection::open(&db_path)?;
+        Ok(hienmark_dir.join(CACHE_FILE_NAME))
+    }
+
+    /// 接続を開いて整合性とスキーマを確認
+    fn open_checked(db_path: &Path) -> Result<Self, OpenError> {
+        let connection = Connection::open(db_path)?;
+        connection.busy_timeout(BUSY_TIMEOUT)?;
 
         // 外部キー制約を有効化（結果を返さない）
         connection.execute("PRAGMA foreign_keys = ON", [])?;
@@ -40,248 +192,114 @@ impl IndexDatabase {
         // ページサイズを最適化（結果を返さない）
         connection.execute("PRAGMA page_size = 4096", [])?;
 
-        let db = Self { connection };
-        
-        // スキーマを初期化
-        db.initialize_schema()?;
-
-        Ok(db)
-    }
-
-    /// スキーマを初期化
-    fn initialize_schema(&self) -> SqliteResult<()> {
-        // まずschema_versionテーブルを作成（存在しない場合）
-        self.connection.execute(
-            "CREATE TABLE IF NOT EXISTS schema_version (
-                version INTEGER PRIMARY KEY,
-                applied_at INTEGER NOT NULL,
-                migration_sql TEXT
-            )",
-            [],
-        )?;
+        // 開くたびに実行するため、軽量なquick_checkを使う
+        let problems = Self::run_check(&connection, "quick_check")?;
+        if !problems.is_empty() {
+            return Err(OpenError::Unhealthy(format!("quick_check failed: {}", problems.join("; "))));
+        }
 
-        let current_version = self.get_current_version()?;
+        // スキーマを初期化（未適用のマイグレーションを順に適用）
+        migrations::run_migrations(&connection)?;
 
-        if current_version < 1 {
-            self.create_schema_v1()?;
-            self.set_version(1)?;
+        let problems = Self::schema_problems(&connection)?;
+        if !problems.is_empty() {
+            return Err(OpenError::Unhealthy(format!("schema check failed: {}", problems.join("; "))));
         }
 
-        // 将来のマイグレーションをここに追加
-        // if current_version < 2 {
-        //     self.migrate_to_v2()?;
-        //     self.set_version(2)?;
-        // }
-
-        Ok(())
+        Ok(Self {
+            connection,
+            recovery: None,
+        })
     }
 
-    /// スキーマバージョン1を作成
-    fn create_schema_v1(&self) -> SqliteResult<()> {
-        // 注意: schema_versionテーブルはinitialize_schema()で既に作成されている
-        
-        // タスクインデックス
-        self.connection.execute(
-            "CREATE TABLE IF NOT EXISTS tasks_index (
-                id TEXT PRIMARY KEY,
-                file_path TEXT UNIQUE NOT NULL,
-                front_matter_json TEXT NOT NULL,
-                modified_at INTEGER NOT NULL,
-                indexed_at INTEGER NOT NULL,
-                created_at INTEGER
-            )",
-            [],
-        )?;
-
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tasks_modified_at ON tasks_index(modified_at)",
-            [],
-        )?;
-
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tasks_indexed_at ON tasks_index(indexed_at)",
-            [],
-        )?;
-
-        // フォルダインデックス
-        self.connection.execute(
-            "CREATE TABLE IF NOT EXISTS folders_index (
-                id TEXT PRIMARY KEY,
-                parent_id TEXT,
-                name TEXT NOT NULL,
-                path TEXT UNIQUE NOT NULL,
-                created_at INTEGER NOT NULL
-            )",
-            [],
-        )?;
-
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_folders_parent_id ON folders_index(parent_id)",
-            [],
-        )?;
-
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_folders_path ON folders_index(path)",
-            [],
-        )?;
-
-        // タスク-フォルダ関連インデックス
-        self.connection.execute(
-            "CREATE TABLE IF NOT EXISTS task_folders_index (
-                task_id TEXT NOT NULL,
-                folder_id TEXT NOT NULL,
-                PRIMARY KEY (task_id, folder_id),
-                FOREIGN KEY (task_id) REFERENCES tasks_index(id) ON DELETE CASCADE,
-                FOREIGN KEY (folder_id) REFERENCES folders_index(id) ON DELETE CASCADE
-            )",
-            [],
-        )?;
-
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_task_folders_task_id ON task_folders_index(task_id)",
-            [],
-        )?;
-
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_task_folders_folder_id ON task_folders_index(folder_id)",
-            [],
-        )?;
-
-        // アセットインデックス
-        self.connection.execute(
-            "CREATE TABLE IF NOT EXISTS assets_index (
-                id TEXT PRIMARY KEY,
-                task_id TEXT NOT NULL,
-                file_path TEXT UNIQUE NOT NULL,
-                relative_path TEXT NOT NULL,
-                file_size INTEGER,
-                mime_type TEXT,
-                created_at INTEGER NOT NULL,
-                FOREIGN KEY (task_id) REFERENCES tasks_index(id) ON DELETE CASCADE
-            )",
-            [],
-        )?;
-
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_assets_task_id ON assets_index(task_id)",
-            [],
-        )?;
-
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_assets_file_path ON assets_index(file_path)",
-            [],
-        )?;
-
-        // タグインデックス
-        self.connection.execute(
-            "CREATE TABLE IF NOT EXISTS tags_index (
-                category TEXT NOT NULL,
-                value TEXT NOT NULL,
-                task_count INTEGER DEFAULT 0,
-                PRIMARY KEY (category, value)
-            )",
-            [],
-        )?;
-
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tags_category ON tags_index(category)",
-            [],
-        )?;
+    /// 整合性チェックのPRAGMAを実行し、問題の一覧を返す（問題がなければ空）
+    fn run_check(connection: &Connection, pragma: &str) -> SqliteResult<Vec<String>> {
+        let mut stmt = connection.prepare(&format!("PRAGMA {}", pragma))?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+        let mut problems = Vec::new();
+        for row_result in rows {
+            let message = row_result?;
+            if message != "ok" {
+                problems.push(message);
+            }
+        }
 
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tags_task_count ON tags_index(task_count)",
-            [],
-        )?;
+        Ok(problems)
+    }
 
-        // タスク-タグ関連インデックス
-        self.connection.execute(
-            "CREATE TABLE IF NOT EXISTS task_tags_index (
-                task_id TEXT NOT NULL,
-                category TEXT NOT NULL,
-                value TEXT NOT NULL,
-                PRIMARY KEY (task_id, category, value),
-                FOREIGN KEY (task_id) REFERENCES tasks_index(id) ON DELETE CASCADE
-            )",
-            [],
-        )?;
+    /// 必須のテーブル・列が揃っているかを確認し、不足の一覧を返す
+    fn schema_problems(connection: &Connection) -> SqliteResult<Vec<String>> {
+        let mut problems = Vec::new();
+
+        for (table, columns) in REQUIRED_COLUMNS {
+            let mut stmt = connection.prepare("SELECT name FROM pragma_table_info(?1)")?;
+            let existing = stmt
+                .query_map([table], |row| row.get::<_, String>(0))?
+                .collect::<SqliteResult<Vec<String>>>()?;
+
+            if existing.is_empty() {
+                problems.push(format!("missing table {}", table));
+                continue;
+            }
+            for column in *columns {
+                if !existing.iter().any(|name| name == column) {
+                    problems.push(format!("missing column {}.{}", table, column));
+                }
+            }
+        }
 
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_task_tags_task_id ON task_tags_index(task_id)",
-            [],
-        )?;
+        Ok(problems)
+    }
 
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_task_tags_category_value ON task_tags_index(category, value)",
-            [],
-        )?;
+    /// キャッシュ本体とWAL・共有メモリファイルのパス
+    fn cache_files(db_path: &Path) -> Vec<PathBuf> {
+        let base = db_path.to_string_lossy();
+        vec![
+            db_path.to_path_buf(),
+            PathBuf::from(format!("{}-wal", base)),
+            PathBuf::from(format!("{}-shm", base)),
+        ]
+    }
 
-        // ファイルメタデータ
-        self.connection.execute(
-            "CREATE TABLE IF NOT EXISTS file_metadata (
-                file_path TEXT PRIMARY KEY,
-                mtime INTEGER NOT NULL,
-                indexed_at INTEGER NOT NULL,
-                file_size INTEGER,
-                hash TEXT
-            )",
-            [],
-        )?;
+    /// キャッシュを`cache.corrupt-<日時>.sqlite`として退避
+    fn quarantine(db_path: &Path) -> std::io::Result<PathBuf> {
+        let quarantined = db_path.with_file_name(format!(
+            "cache.corrupt-{}.sqlite",
+            Utc::now().format("%Y%m%d-%H%M%S")
+        ));
+        let quarantined_files = Self::cache_files(&quarantined);
+
+        for (source, target) in Self::cache_files(db_path).iter().zip(quarantined_files.iter()) {
+            if source.exists() {
+                fs::rename(source, target)?;
+            }
+        }
 
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_file_metadata_mtime ON file_metadata(mtime)",
-            [],
-        )?;
+        Ok(quarantined)
+    }
 
-        self.connection.execute(
-            "CREATE INDEX IF NOT EXISTS idx_file_metadata_indexed_at ON file_metadata(indexed_at)",
-            [],
-        )?;
+    /// 完全な整合性チェック（`PRAGMA integrity_check`）とスキーマ確認を実行
+    ///
+    /// データベース全体を走査するため、開く際のチェックより時間がかかる。
+    pub fn check_integrity(&self) -> SqliteResult<IntegrityReport> {
+        let mut problems = Self::run_check(&self.connection, "integrity_check")?;
+        problems.extend(Self::schema_problems(&self.connection)?);
+
+        Ok(IntegrityReport {
+            ok: problems.is_empty(),
+            problems,
+        })
+    }
 
-        Ok(())
+    /// 開く際にキャッシュを作り直した場合、その情報を取得
+    pub fn recovery(&self) -> Option<&IndexRecovery> {
+        self.recovery.as_ref()
     }
 
     /// 現在のスキーマバージョンを取得
     pub fn get_current_version(&self) -> SqliteResult<i32> {
-        // schema_versionテーブルが存在するか確認
-        let table_exists: bool = match self.connection.query_row(
-            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='schema_version'",
-            [],
-            |row| {
-                let count: i32 = row.get(0)?;
-                Ok(count > 0)
-            },
-        ) {
-            Ok(exists) => exists,
-            Err(_) => false,
-        };
-
-        if !table_exists {
-            return Ok(0);
-        }
-
-        // schema_versionテーブルが存在する場合、最大バージョンを取得
-        match self.connection.query_row(
-            "SELECT MAX(version) FROM schema_version",
-            [],
-            |row| {
-                let v: Option<i32> = row.get(0)?;
-                Ok(v.unwrap_or(0))
-            },
-        ) {
-            Ok(v) => Ok(v),
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(0),
-            Err(e) => Err(e),
-        }
-    }
-
-    /// スキーマバージョンを設定
-    fn set_version(&self, version: i32) -> SqliteResult<()> {
-        let now = chrono::Utc::now().timestamp();
-        self.connection.execute(
-            "INSERT OR REPLACE INTO schema_version (version, applied_at, migration_sql) VALUES (?1, ?2, ?3)",
-            rusqlite::params![version, now, "-- Initial schema version 1"],
-        )?;
-        Ok(())
+        migrations::current_version(&self.connection)
     }
 
     /// データベース接続への参照を取得（内部使用）
@@ -309,7 +327,61 @@ mod tests {
     fn test_create_database() {
         let temp_dir = TempDir::new().unwrap();
         let db = IndexDatabase::new(temp_dir.path()).unwrap();
-        assert_eq!(db.get_current_version().unwrap(), 1);
+        assert_eq!(db.get_current_version().unwrap(), migrations::latest_version());
+    }
+
+    #[test]
+    fn test_open_newer_schema_is_quarantined_and_rebuilt() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let db = IndexDatabase::new(temp_dir.path()).unwrap();
+            assert!(db.recovery().is_none());
+            db.connection()
+                .execute(
+                    "INSERT INTO schema_version (version, applied_at) VALUES (?1, 0)",
+                    [migrations::latest_version() + 1],
+                )
+                .unwrap();
+        }
+
+        let db = IndexDatabase::new(temp_dir.path()).unwrap();
+        assert_eq!(db.get_current_version().unwrap(), migrations::latest_version());
+
+        // 元のキャッシュは退避先にそのまま残る
+        let recovery = db.recovery().unwrap();
+        let connection = Connection::open(&recovery.quarantined_path).unwrap();
+        assert_eq!(
+            migrations::current_version(&connection).unwrap(),
+            migrations::latest_version() + 1
+        );
+    }
+
+    #[test]
+    fn test_corrupt_cache_is_quarantined_and_rebuilt() {
+        let temp_dir = TempDir::new().unwrap();
+        let hienmark_dir = temp_dir.path().join(".hienmark");
+        std::fs::create_dir_all(&hienmark_dir).unwrap();
+        std::fs::write(hienmark_dir.join(CACHE_FILE_NAME), vec![0x42; 8192]).unwrap();
+
+        let db = IndexDatabase::new(temp_dir.path()).unwrap();
+        assert!(db.recovery().unwrap().quarantined_path.exists());
+        assert!(db.check_integrity().unwrap().ok);
+    }
+
+    #[test]
+    fn test_missing_table_is_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let db = IndexDatabase::new(temp_dir.path()).unwrap();
+            db.connection().execute("DROP TABLE asset_references", []).unwrap();
+            let report = db.check_integrity().unwrap();
+            assert!(!report.ok);
+            assert_eq!(report.problems, vec!["missing table asset_references".to_string()]);
+        }
+
+        let db = IndexDatabase::new(temp_dir.path()).unwrap();
+        assert!(db.recovery().is_some());
+        assert!(db.check_integrity().unwrap().ok);
     }
 
     #[test]
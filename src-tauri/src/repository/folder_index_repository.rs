@@ -2,7 +2,8 @@
 
 use crate::repository::database::IndexDatabase;
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Result as SqliteResult};
+use rusqlite::{params, Connection, Result as SqliteResult};
+use std::borrow::Borrow;
 use std::collections::HashMap;
 
 /// フォルダインデックスのリポジトリトレイト
@@ -34,19 +35,23 @@ pub struct FolderIndexEntry {
 }
 
 /// SQLite実装
-pub struct SqliteFolderIndexRepository<'a> {
-    db: &'a IndexDatabase,
+pub struct SqliteFolderIndexRepository<D: Borrow<IndexDatabase>> {
+    db: D,
 }
 
-impl<'a> SqliteFolderIndexRepository<'a> {
-    pub fn new(db: &'a IndexDatabase) -> Self {
+impl<D: Borrow<IndexDatabase>> SqliteFolderIndexRepository<D> {
+    pub fn new(db: D) -> Self {
         Self { db }
     }
 
+    fn connection(&self) -> &Connection {
+        self.db.borrow().connection()
+    }
+
     /// フォルダを追加または更新
     pub fn upsert_folder(&self, entry: &FolderIndexEntry) -> SqliteResult<()> {
         let created_at = entry.created_at.timestamp();
-        self.db.connection().execute(
+        self.connection().execute(
             "INSERT OR REPLACE INTO folders_index (id, parent_id, name, path, created_at)
              VALUES (?1, ?2, ?3, ?4, ?5)",
             params![entry.id, entry.parent_id, entry.name, entry.path, created_at],
@@ -56,7 +61,7 @@ impl<'a> SqliteFolderIndexRepository<'a> {
 
     /// フォルダを削除
     pub fn delete_folder(&self, folder_id: &str) -> SqliteResult<()> {
-        self.db.connection().execute(
+        self.connection().execute(
             "DELETE FROM folders_index WHERE id = ?1",
             params![folder_id],
         )?;
@@ -69,9 +74,9 @@ impl<'a> SqliteFolderIndexRepository<'a> {
     }
 }
 
-impl<'a> FolderIndexRepository for SqliteFolderIndexRepository<'a> {
+impl<D: Borrow<IndexDatabase>> FolderIndexRepository for SqliteFolderIndexRepository<D> {
     fn get_folder(&self, folder_id: &str) -> SqliteResult<Option<FolderIndexEntry>> {
-        let mut stmt = self.db.connection().prepare(
+        let mut stmt = self.connection().prepare(
             "SELECT id, parent_id, name, path, created_at
              FROM folders_index
              WHERE id = ?1",
@@ -95,7 +100,7 @@ impl<'a> FolderIndexRepository for SqliteFolderIndexRepository<'a> {
     }
 
     fn list_folders(&self) -> SqliteResult<Vec<FolderIndexEntry>> {
-        let mut stmt = self.db.connection().prepare(
+        let mut stmt = self.connection().prepare(
             "SELECT id, parent_id, name, path, created_at
              FROM folders_index
              ORDER BY path",
@@ -120,7 +125,7 @@ impl<'a> FolderIndexRepository for SqliteFolderIndexRepository<'a> {
     }
 
     fn get_folder_by_path(&self, path: &str) -> SqliteResult<Option<FolderIndexEntry>> {
-        let mut stmt = self.db.connection().prepare(
+        let mut stmt = self.connection().prepare(
             "SELECT id, parent_id, name, path, created_at
              FROM folders_index
              WHERE path = ?1",
@@ -147,7 +152,7 @@ impl<'a> FolderIndexRepository for SqliteFolderIndexRepository<'a> {
         let mut entries = Vec::new();
         
         if let Some(parent_id) = parent_id {
-            let mut stmt = self.db.connection().prepare(
+            let mut stmt = self.connection().prepare(
                 "SELECT id, parent_id, name, path, created_at
                  FROM folders_index
                  WHERE parent_id = ?1
@@ -168,7 +173,7 @@ impl<'a> FolderIndexRepository for SqliteFolderIndexRepository<'a> {
                 entries.push(row_result?);
             }
         } else {
-            let mut stmt = self.db.connection().prepare(
+            let mut stmt = self.connection().prepare(
                 "SELECT id, parent_id, name, path, created_at
                  FROM folders_index
                  WHERE parent_id IS NULL
@@ -1,20 +1,62 @@
 // Index builder service - builds index from Markdown files
 
-use crate::parser::FrontMatterParser;
+use crate::models::{FrontMatter, Severity, TagValue};
+use crate::parser::{AssetReferenceParser, ComputedFields, FrontMatterParser, ValidationRules};
+use crate::repository::asset_index_repository::{
+    AssetIndexEntry, AssetIndexRepository, SqliteAssetIndexRepository,
+};
 use crate::repository::database::IndexDatabase;
 use crate::repository::task_index_repository::{
     SqliteTaskIndexRepository, TaskIndexEntry, TaskIndexRepository,
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, Utc};
 use rusqlite::Result as SqliteResult;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::borrow::Borrow;
+use std::collections::HashSet;
 use std::fs;
 use std::io;
+use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 1トランザクションで処理するファイル数のデフォルト値
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+
+/// インデックスビルダーが書き込みに使う接続の取得元
+///
+/// 書き込み接続（`IndexDatabase`・`PooledConnection`・参照）はそのまま使い続ける。
+/// 接続プールの場合はトランザクションごとに書き込み接続を借りて返すため、
+/// 全件構築のバッチの合間にファイル監視などの書き込みが割り込める。
+pub trait IndexWriter {
+    type Connection<'a>: Deref<Target = IndexDatabase>
+    where
+        Self: 'a;
+
+    /// 書き込み接続を取得
+    fn acquire(&self) -> SqliteResult<Self::Connection<'_>>;
+}
+
+impl<D: Borrow<IndexDatabase>> IndexWriter for D {
+    type Connection<'a> = &'a IndexDatabase where D: 'a;
+
+    fn acquire(&self) -> SqliteResult<&IndexDatabase> {
+        Ok(self.borrow())
+    }
+}
 
 /// インデックスビルダーサービス
 /// MarkdownファイルからSQLiteインデックスを構築する
-pub struct IndexBuilder {
-    db: IndexDatabase,
+///
+/// 書き込み接続は所有型（`IndexDatabase`・`PooledConnection`）でも参照でもよく、
+/// 接続プール（`Arc<IndexPool>`）を渡すと書き込み接続を占有し続けない。
+pub struct IndexBuilder<D: IndexWriter = IndexDatabase> {
+    db: D,
+    batch_size: usize,
+    computed_fields: ComputedFields,
+    validation_rules: ValidationRules,
+    strict_mode: bool,
 }
 
 impl IndexBuilder {
@@ -27,7 +69,45 @@ impl IndexBuilder {
     /// * `Result<IndexBuilder, rusqlite::Error>` - インデックスビルダー
     pub fn new(workspace_root: &Path) -> SqliteResult<Self> {
         let db = IndexDatabase::new(workspace_root)?;
-        Ok(Self { db })
+        Ok(Self::with_database(db))
+    }
+}
+
+impl<D: IndexWriter> IndexBuilder<D> {
+    /// 既存の書き込み接続（または接続プール）を使うインデックスビルダーを作成
+    pub fn with_database(db: D) -> Self {
+        Self {
+            db,
+            batch_size: DEFAULT_BATCH_SIZE,
+            computed_fields: ComputedFields::default(),
+            validation_rules: ValidationRules::default(),
+            strict_mode: false,
+        }
+    }
+
+    /// バッチサイズを設定
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// タスクごとに評価する計算フィールドを設定
+    pub fn with_computed_fields(mut self, computed_fields: ComputedFields) -> Self {
+        self.computed_fields = computed_fields;
+        self
+    }
+
+    /// 全件構築の後に評価する検証規則を設定
+    ///
+    /// Errorになる違反は、解析エラーと同じく`BuildResult.errors`に報告する。
+    ///
+    /// # Arguments
+    /// * `validation_rules` - タグスキーマの検証規則
+    /// * `strict_mode` - タグ固定モードかどうか（規則の重大度の既定値に使う）
+    pub fn with_validation_rules(mut self, validation_rules: ValidationRules, strict_mode: bool) -> Self {
+        self.validation_rules = validation_rules;
+        self.strict_mode = strict_mode;
+        self
     }
 
     /// ワークスペース内の全Markdownファイルからインデックスを構築
@@ -38,33 +118,93 @@ impl IndexBuilder {
     /// # Returns
     /// * `Result<BuildResult, io::Error>` - 構築結果
     pub fn build_from_workspace(&mut self, workspace_root: &Path) -> Result<BuildResult, io::Error> {
+        let cancel_flag = AtomicBool::new(false);
+        self.build_from_workspace_with_progress(workspace_root, &cancel_flag, |_| {})
+    }
+
+    /// 進捗通知とキャンセルに対応したインデックス構築
+    ///
+    /// ファイルを`batch_size`件ずつ1トランザクションで処理する。
+    /// 書き込み接続はバッチごとに取得し直し、進捗の通知前に返す。
+    /// キャンセルはバッチの境界で判定され、コミット済みのバッチはそのまま残る
+    /// （次回の構築では未変更ファイルとしてスキップされる）。
+    ///
+    /// # Arguments
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    /// * `cancel_flag` - trueになると次のバッチの前で構築を中断する
+    /// * `on_progress` - バッチのコミットごとに呼ばれる進捗コールバック
+    ///
+    /// # Returns
+    /// * `Result<BuildResult, io::Error>` - 構築結果
+    pub fn build_from_workspace_with_progress<F>(
+        &mut self,
+        workspace_root: &Path,
+        cancel_flag: &AtomicBool,
+        mut on_progress: F,
+    ) -> Result<BuildResult, io::Error>
+    where
+        F: FnMut(&IndexProgress),
+    {
+        // .mdファイルをスキャン
+        let md_files = self.scan_markdown_files(workspace_root)?;
+
         let mut result = BuildResult {
+            total_files: md_files.len(),
             tasks_indexed: 0,
             tasks_updated: 0,
             tasks_skipped: 0,
+            tasks_removed: 0,
             errors: Vec::new(),
+            cancelled: false,
         };
 
-        // .mdファイルをスキャン
-        let md_files = self.scan_markdown_files(workspace_root)?;
+        let mut progress = IndexProgress {
+            processed: 0,
+            total: md_files.len(),
+        };
+        on_progress(&progress);
+
+        for batch in md_files.chunks(self.batch_size) {
+            if cancel_flag.load(Ordering::SeqCst) {
+                result.cancelled = true;
+                break;
+            }
+
+            {
+                // バッチ単位でトランザクションを張る（途中でクラッシュしても未コミット分は破棄される）
+                let db = self.writer()?;
+                let transaction = db.connection().unchecked_transaction().map_err(|e| {
+                    io::Error::new(io::ErrorKind::Other, format!("Database transaction error: {}", e))
+                })?;
+
+                let task_repo = SqliteTaskIndexRepository::new(&*db);
 
-        // トランザクションは将来的に実装（現時点では不要）
-        // 将来的な拡張用にコメントアウト
-        // let transaction = self.db.transaction().map_err(|e| {
-        //     io::Error::new(io::ErrorKind::Other, format!("Database transaction error: {}", e))
-        // })?;
-        
-        let task_repo = SqliteTaskIndexRepository::new(&self.db);
-
-        for file_path in md_files {
-            match self.process_task_file(&file_path, workspace_root, &task_repo) {
-                Ok(TaskProcessResult::Indexed) => result.tasks_indexed += 1,
-                Ok(TaskProcessResult::Updated) => result.tasks_updated += 1,
-                Ok(TaskProcessResult::Skipped) => result.tasks_skipped += 1,
-                Err(e) => {
-                    result.errors.push(format!("{}: {}", file_path.display(), e));
+                for file_path in batch {
+                    match self.process_task_file(file_path, workspace_root, &task_repo) {
+                        Ok(TaskProcessResult::Indexed) => result.tasks_indexed += 1,
+                        Ok(TaskProcessResult::Updated) => result.tasks_updated += 1,
+                        Ok(TaskProcessResult::Skipped) => result.tasks_skipped += 1,
+                        Err(e) => {
+                            result.errors.push(format!("{}: {}", file_path.display(), e));
+                        }
+                    }
                 }
+
+                transaction.commit().map_err(|e| {
+                    io::Error::new(io::ErrorKind::Other, format!("Database commit error: {}", e))
+                })?;
             }
+
+            progress.processed += batch.len();
+            on_progress(&progress);
+        }
+
+        // 全件を走査できた場合のみ、削除されたタスクをインデックスから取り除く
+        if !result.cancelled {
+            result.tasks_removed = self.remove_stale_tasks(workspace_root, &md_files)?;
+            // スキップしたタスクも日付やスキーマの変更に追従させる
+            self.refresh_computed_tags()?;
+            result.errors.extend(self.check_validation_rules(workspace_root)?);
         }
 
         Ok(result)
@@ -83,7 +223,115 @@ impl IndexBuilder {
         &self,
         file_path: &Path,
         workspace_root: &Path,
-        task_repo: &SqliteTaskIndexRepository,
+        task_repo: &SqliteTaskIndexRepository<&IndexDatabase>,
+    ) -> Result<TaskProcessResult, io::Error> {
+        self.index_task_file(file_path, workspace_root, task_repo, false)
+    }
+
+    /// 変更が分かっている単一のタスクファイルをインデックスに反映
+    ///
+    /// 更新日時は秒単位で記録されるため、同じ秒の中で保存し直された変更も
+    /// 取りこぼさないよう、更新日時の比較を行わずに書き込む。
+    ///
+    /// # Arguments
+    /// * `file_path` - タスクファイルのパス
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    ///
+    /// # Returns
+    /// * `Result<TaskProcessResult, io::Error>` - 処理結果（IndexedまたはUpdated）
+    pub fn update_task_file(
+        &self,
+        file_path: &Path,
+        workspace_root: &Path,
+    ) -> Result<TaskProcessResult, io::Error> {
+        let db = self.writer()?;
+        let transaction = db.connection().unchecked_transaction().map_err(|e| {
+            io::Error::new(io::ErrorKind::Other, format!("Database transaction error: {}", e))
+        })?;
+
+        let task_repo = SqliteTaskIndexRepository::new(&*db);
+        let result = self.index_task_file(file_path, workspace_root, &task_repo, true)?;
+
+        transaction.commit().map_err(|e| {
+            io::Error::new(io::ErrorKind::Other, format!("Database commit error: {}", e))
+        })?;
+        Ok(result)
+    }
+
+    /// インデックス済みの全タスクの計算フィールドを評価し直す
+    ///
+    /// ファイルは読み直さず、インデックスに保存されたFront Matterから評価する。
+    ///
+    /// # Returns
+    /// * `Result<usize, io::Error>` - 値が変わったタスクの数
+    pub fn refresh_computed_tags(&self) -> Result<usize, io::Error> {
+        let to_io_error = |e: rusqlite::Error| io::Error::other(format!("Database error: {}", e));
+
+        let db = self.writer()?;
+        let transaction = db.connection().unchecked_transaction().map_err(to_io_error)?;
+        let task_repo = SqliteTaskIndexRepository::new(&*db);
+        let today = Local::now().date_naive();
+
+        let mut updated = 0;
+        for mut entry in task_repo.list_tasks().map_err(to_io_error)? {
+            let computed_tags = self.computed_fields.evaluate(&entry.front_matter.tags, today);
+            if computed_tags != entry.computed_tags {
+                entry.computed_tags = computed_tags;
+                task_repo.update_computed_tags(&entry).map_err(to_io_error)?;
+                updated += 1;
+            }
+        }
+
+        transaction.commit().map_err(to_io_error)?;
+        Ok(updated)
+    }
+
+    /// インデックス内の全タスクに検証規則を適用する
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>, io::Error>` - 解析できない規則の式と、Errorになる違反
+    ///   （`ファイルパス: メッセージ (rule 規則ID)`の形式）
+    fn check_validation_rules(&self, workspace_root: &Path) -> Result<Vec<String>, io::Error> {
+        let mut diagnostics = self.validation_rules.errors().to_vec();
+        let db = self.writer()?;
+        let task_repo = SqliteTaskIndexRepository::new(&*db);
+        let today = Local::now().date_naive();
+
+        for entry in task_repo
+            .list_tasks()
+            .map_err(|e| io::Error::other(format!("Database error: {}", e)))?
+        {
+            let mut tags = entry.front_matter.tags;
+            tags.extend(entry.computed_tags);
+            for failure in self.validation_rules.evaluate(&tags, today) {
+                if failure.rule.severity_for(self.strict_mode) == Severity::Error {
+                    diagnostics.push(format!(
+                        "{}: {} (rule {})",
+                        workspace_root.join(&entry.file_path).display(),
+                        failure.message,
+                        failure.rule.id
+                    ));
+                }
+            }
+        }
+        Ok(diagnostics)
+    }
+
+    /// タスクをインデックスから削除
+    ///
+    /// 関連するタグ・アセット参照は外部キーのカスケードで削除される。
+    pub fn remove_task(&self, task_id: &str) -> Result<(), io::Error> {
+        SqliteTaskIndexRepository::new(&*self.writer()?)
+            .delete_task(task_id)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database delete error: {}", e)))
+    }
+
+    fn index_task_file(
+        &self,
+        file_path: &Path,
+        workspace_root: &Path,
+        task_repo: &SqliteTaskIndexRepository<&IndexDatabase>,
+        force: bool,
     ) -> Result<TaskProcessResult, io::Error> {
         // ファイルを読み込む
         let content = fs::read_to_string(file_path)?;
@@ -112,7 +360,7 @@ impl IndexBuilder {
             .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0));
 
         // Front Matterをパース
-        let (front_matter, _body, _tag_order) =
+        let (front_matter, body, _tag_order) =
             FrontMatterParser::parse_with_order(&content).map_err(|e| {
                 io::Error::new(io::ErrorKind::InvalidData, format!("Parse error: {}", e))
             })?;
@@ -135,14 +383,24 @@ impl IndexBuilder {
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_else(|_| file_path.to_string_lossy().to_string());
 
+        let title = Self::extract_title(&front_matter, &body);
+        let archived = matches!(front_matter.tags.get("archived"), Some(TagValue::Bool(true)));
+        let computed_tags = self
+            .computed_fields
+            .evaluate(&front_matter.tags, Local::now().date_naive());
+
         // TaskIndexEntryを作成
         let entry = TaskIndexEntry {
             id: task_id.clone(),
-            file_path: relative_path,
+            file_path: relative_path.clone(),
             front_matter,
             modified_at,
             indexed_at: Utc::now(),
             created_at,
+            title,
+            body_hash: Some(format!("{:x}", Sha256::digest(body.as_bytes()))),
+            archived,
+            computed_tags,
         };
 
         // 既存のエントリをチェック
@@ -152,11 +410,12 @@ impl IndexBuilder {
 
         let result = if let Some(existing_entry) = existing {
             // 既存エントリがある場合、更新日時を比較
-            if existing_entry.modified_at < modified_at {
+            if force || existing_entry.modified_at < modified_at {
                 // 更新されているのでインデックスを更新
                 task_repo.upsert_task(&entry).map_err(|e| {
                     io::Error::new(io::ErrorKind::Other, format!("Database update error: {}", e))
                 })?;
+                self.index_asset_references(task_repo.database(), &task_id, &relative_path, &body, &entry.front_matter, workspace_root)?;
                 TaskProcessResult::Updated
             } else {
                 // 変更なし
@@ -167,12 +426,96 @@ impl IndexBuilder {
             task_repo.upsert_task(&entry).map_err(|e| {
                 io::Error::new(io::ErrorKind::Other, format!("Database insert error: {}", e))
             })?;
+            self.index_asset_references(task_repo.database(), &task_id, &relative_path, &body, &entry.front_matter, workspace_root)?;
             TaskProcessResult::Indexed
         };
 
         Ok(result)
     }
 
+    /// 本文とタグの値（画像・ハイパーリンク）のアセット参照をインデックスに反映
+    ///
+    /// 参照先のファイルが存在する場合は`assets_index`にも登録する（所有タスクが未登録の場合のみ）。
+    fn index_asset_references(
+        &self,
+        db: &IndexDatabase,
+        task_id: &str,
+        task_relative_path: &str,
+        body: &str,
+        front_matter: &FrontMatter,
+        workspace_root: &Path,
+    ) -> Result<(), io::Error> {
+        let mut references = AssetReferenceParser::extract(body, task_relative_path);
+        references.extend(AssetReferenceParser::extract_from_tags(&front_matter.tags));
+        references.sort();
+        references.dedup();
+        let asset_repo = SqliteAssetIndexRepository::new(db);
+        let to_io_error =
+            |e: rusqlite::Error| io::Error::new(io::ErrorKind::Other, format!("Database asset error: {}", e));
+
+        asset_repo
+            .replace_task_references(task_id, &references)
+            .map_err(to_io_error)?;
+
+        for asset_path in &references {
+            // 登録済みのアセットは再解析しない
+            if asset_repo.get_asset_by_path(asset_path).map_err(to_io_error)?.is_some() {
+                continue;
+            }
+            let Ok(entry) = AssetIndexEntry::from_file(workspace_root, asset_path, task_id) else {
+                continue;
+            };
+            asset_repo.insert_asset_if_absent(&entry).map_err(to_io_error)?;
+        }
+
+        Ok(())
+    }
+
+    /// ファイルが存在しなくなったタスクをインデックスから削除
+    ///
+    /// 構築中に監視側で追加されたタスクは走査結果に含まれないため、ファイルの有無も確認する。
+    /// 関連するタグ・アセット参照は外部キーのカスケードで削除される。
+    fn remove_stale_tasks(&self, workspace_root: &Path, md_files: &[PathBuf]) -> Result<usize, io::Error> {
+        let existing: HashSet<String> = md_files
+            .iter()
+            .map(|path| {
+                path.strip_prefix(workspace_root)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_else(|_| path.to_string_lossy().to_string())
+            })
+            .collect();
+
+        let db = self.writer()?;
+        let task_repo = SqliteTaskIndexRepository::new(&*db);
+        let to_io_error =
+            |e: rusqlite::Error| io::Error::new(io::ErrorKind::Other, format!("Database cleanup error: {}", e));
+
+        let mut removed = 0;
+        for entry in task_repo.list_tasks().map_err(to_io_error)? {
+            if !existing.contains(&entry.file_path) && !workspace_root.join(&entry.file_path).exists() {
+                task_repo.delete_task(&entry.id).map_err(to_io_error)?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// タイトルを抽出（Front Matterの`title`を優先し、なければ本文の最初の見出し）
+    fn extract_title(front_matter: &FrontMatter, body: &str) -> Option<String> {
+        if let Some(TagValue::String(title)) = front_matter.tags.get("title") {
+            if !title.trim().is_empty() {
+                return Some(title.trim().to_string());
+            }
+        }
+
+        body.lines()
+            .map(str::trim)
+            .find(|line| line.starts_with('#'))
+            .map(|line| line.trim_start_matches('#').trim().to_string())
+            .filter(|title| !title.is_empty())
+    }
+
     /// ディレクトリ内の全.mdファイルをスキャン（再帰的）
     ///
     /// # Arguments
@@ -227,9 +570,18 @@ impl IndexBuilder {
         Ok(())
     }
 
+    /// 書き込み接続を取得
+    fn writer(&self) -> Result<D::Connection<'_>, io::Error> {
+        self.db
+            .acquire()
+            .map_err(|e| io::Error::other(format!("Database connection error: {}", e)))
+    }
+}
+
+impl<D: Borrow<IndexDatabase>> IndexBuilder<D> {
     /// データベース接続への参照を取得
     pub fn database(&self) -> &IndexDatabase {
-        &self.db
+        self.db.borrow()
     }
 }
 
@@ -244,23 +596,39 @@ pub enum TaskProcessResult {
     Skipped,
 }
 
+/// インデックス構築の進捗（`index-progress`イベントのペイロード）
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexProgress {
+    /// 処理済みファイル数
+    pub processed: usize,
+    /// 対象ファイル総数
+    pub total: usize,
+}
+
 /// インデックス構築結果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BuildResult {
+    /// 対象ファイル総数
+    pub total_files: usize,
     /// 新規にインデックスに追加されたタスク数
     pub tasks_indexed: usize,
     /// 更新されたタスク数
     pub tasks_updated: usize,
     /// スキップされたタスク数（変更なし）
     pub tasks_skipped: usize,
+    /// ファイルが削除されたためインデックスから除去されたタスク数
+    pub tasks_removed: usize,
     /// エラーリスト
     pub errors: Vec<String>,
+    /// 構築がキャンセルされたかどうか
+    pub cancelled: bool,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     // Note: FrontMatter, TagValue, HashMap are unused in tests but kept for future use
+    use std::sync::Arc;
     use tempfile::TempDir;
 
     #[test]
@@ -288,6 +656,90 @@ This is a test task.
         assert_eq!(result.errors.len(), 0);
     }
 
+    #[test]
+    fn test_build_reports_progress_per_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_root = temp_dir.path();
+
+        for i in 0..5 {
+            std::fs::write(
+                workspace_root.join(format!("task-{}.md", i)),
+                "---\nstatus: open\n---\n\n# Task\n",
+            )
+            .unwrap();
+        }
+
+        let mut builder = IndexBuilder::new(workspace_root).unwrap().with_batch_size(2);
+        let cancel_flag = AtomicBool::new(false);
+        let mut events = Vec::new();
+        let result = builder
+            .build_from_workspace_with_progress(workspace_root, &cancel_flag, |p| {
+                events.push((p.processed, p.total));
+            })
+            .unwrap();
+
+        assert_eq!(result.tasks_indexed, 5);
+        assert!(!result.cancelled);
+        assert_eq!(events, vec![(0, 5), (2, 5), (4, 5), (5, 5)]);
+    }
+
+    #[test]
+    fn test_build_cancelled_before_start() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_root = temp_dir.path();
+        std::fs::write(workspace_root.join("task.md"), "# Task\n").unwrap();
+
+        let mut builder = IndexBuilder::new(workspace_root).unwrap();
+        let cancel_flag = AtomicBool::new(true);
+        let result = builder
+            .build_from_workspace_with_progress(workspace_root, &cancel_flag, |_| {})
+            .unwrap();
+
+        assert!(result.cancelled);
+        assert_eq!(result.tasks_indexed, 0);
+        let task_repo = SqliteTaskIndexRepository::new(builder.database());
+        assert!(task_repo.list_tasks().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_writer_is_released_between_batches() {
+        use crate::repository::connection_manager::IndexPool;
+
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_root = temp_dir.path();
+        for i in 0..3 {
+            std::fs::write(
+                workspace_root.join(format!("task-{}.md", i)),
+                "---\nstatus: open\n---\n\n# Task\n",
+            )
+            .unwrap();
+        }
+
+        let pool = IndexPool::open(workspace_root).unwrap();
+        let mut builder = IndexBuilder::with_database(Arc::clone(&pool)).with_batch_size(1);
+        let cancel_flag = AtomicBool::new(false);
+        let result = builder
+            .build_from_workspace_with_progress(workspace_root, &cancel_flag, |progress| {
+                if progress.processed != 1 {
+                    return;
+                }
+                // 構築の途中で、ファイル監視が追加されたタスクを書き込む
+                let watched = workspace_root.join("watched.md");
+                std::fs::write(&watched, "---\nstatus: new\n---\n\n# Watched\n").unwrap();
+                let writer = pool.writer().unwrap();
+                IndexBuilder::with_database(writer)
+                    .update_task_file(&watched, workspace_root)
+                    .unwrap();
+            })
+            .unwrap();
+
+        assert_eq!(result.tasks_indexed, 3);
+        assert_eq!(result.tasks_removed, 0);
+        let task_repo = SqliteTaskIndexRepository::new(pool.reader().unwrap());
+        assert_eq!(task_repo.list_tasks().unwrap().len(), 4);
+        assert!(task_repo.get_task("watched").unwrap().is_some());
+    }
+
     #[test]
     fn test_process_task_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -311,11 +763,127 @@ status: pending
 
         assert_eq!(result, TaskProcessResult::Indexed);
 
+        let entry = task_repo.get_task("test-task").unwrap().unwrap();
+        assert_eq!(entry.title.as_deref(), Some("Test Task"));
+        assert!(entry.body_hash.is_some());
+        assert!(!entry.archived);
+
         // 再度処理するとスキップされる
         let result2 = builder
             .process_task_file(&task_file, workspace_root, &task_repo)
             .unwrap();
         assert_eq!(result2, TaskProcessResult::Skipped);
     }
+
+    #[test]
+    fn test_update_and_remove_single_task() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_root = temp_dir.path();
+        let task_file = workspace_root.join("watched.md");
+        std::fs::write(&task_file, "---\nstatus: open\n---\n\n# Watched\n").unwrap();
+
+        let builder = IndexBuilder::new(workspace_root).unwrap();
+        assert_eq!(
+            builder.update_task_file(&task_file, workspace_root).unwrap(),
+            TaskProcessResult::Indexed
+        );
+
+        // 更新日時が同じ秒のままでも書き込まれる
+        std::fs::write(&task_file, "---\nstatus: done\n---\n\n# Watched\n").unwrap();
+        assert_eq!(
+            builder.update_task_file(&task_file, workspace_root).unwrap(),
+            TaskProcessResult::Updated
+        );
+        let task_repo = SqliteTaskIndexRepository::new(builder.database());
+        let entry = task_repo.get_task("watched").unwrap().unwrap();
+        assert!(matches!(
+            entry.front_matter.tags.get("status"),
+            Some(TagValue::String(s)) if s == "done"
+        ));
+
+        builder.remove_task("watched").unwrap();
+        assert!(task_repo.get_task("watched").unwrap().is_none());
+    }
+
+    fn computed_fields(expression: &str) -> ComputedFields {
+        let schema: crate::models::TagSchema = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "tags": {"score": {"type": "Computed", "options": {"expression": expression}}}
+        }))
+        .unwrap();
+        ComputedFields::from_schema(&schema)
+    }
+
+    #[test]
+    fn test_computed_fields_follow_schema_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_root = temp_dir.path();
+        std::fs::write(
+            workspace_root.join("task.md"),
+            "---\nweight: 3\neffort: 4\n---\n\n# Task\n",
+        )
+        .unwrap();
+
+        let mut builder = IndexBuilder::new(workspace_root)
+            .unwrap()
+            .with_computed_fields(computed_fields("weight * effort"));
+        builder.build_from_workspace(workspace_root).unwrap();
+        let task_repo = SqliteTaskIndexRepository::new(builder.database());
+        let entry = task_repo.get_task("task").unwrap().unwrap();
+        assert_eq!(entry.computed_tags["score"], TagValue::Number(12));
+        assert!(!entry.front_matter.tags.contains_key("score"));
+
+        // ファイルが変わっていなくても、スキーマの変更は再構築時に反映される
+        let mut builder = IndexBuilder::with_database(builder.database())
+            .with_computed_fields(computed_fields("weight + effort"));
+        let result = builder.build_from_workspace(workspace_root).unwrap();
+        assert_eq!(result.tasks_skipped, 1);
+        let entry = task_repo.get_task("task").unwrap().unwrap();
+        assert_eq!(entry.computed_tags["score"], TagValue::Number(7));
+    }
+
+    #[test]
+    fn test_rule_violations_are_reported_as_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_root = temp_dir.path();
+        std::fs::write(
+            workspace_root.join("task.md"),
+            "---\nstatus: close\nstart_date: 2025-03-10\nend_date: 2025-03-01\n---\n\n# Task\n",
+        )
+        .unwrap();
+        let schema: crate::models::TagSchema = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "rules": [
+                {"id": "end-after-start", "assert": "end_date >= start_date",
+                 "message": "end_date must not be before start_date", "severity": "error"},
+                {"id": "close-needs-completion", "when": "status == close", "require": ["completed_at"]},
+                {"id": "broken", "assert": "(status"}
+            ]
+        }))
+        .unwrap();
+        let rules = ValidationRules::from_schema(&schema);
+
+        let mut builder = IndexBuilder::new(workspace_root)
+            .unwrap()
+            .with_validation_rules(rules.clone(), false);
+        let result = builder.build_from_workspace(workspace_root).unwrap();
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors[0].contains("rule broken"));
+        assert_eq!(
+            result.errors[1],
+            format!(
+                "{}: end_date must not be before start_date (rule end-after-start)",
+                workspace_root.join("task.md").display()
+            )
+        );
+
+        // タグ固定モードでは重大度を省略した規則もErrorになる（変更のないタスクも評価する）
+        let mut builder =
+            IndexBuilder::with_database(builder.database()).with_validation_rules(rules, true);
+        let result = builder.build_from_workspace(workspace_root).unwrap();
+        assert_eq!(result.tasks_skipped, 1);
+        assert_eq!(result.errors.len(), 3);
+        assert!(result.errors[2].ends_with("completed_at is required when status == close (rule close-needs-completion)"));
+    }
 }
 
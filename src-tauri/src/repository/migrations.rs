@@ -0,0 +1,537 @@
+// Schema migrations for the SQLite index
+
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// スキーママイグレーション定義
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// 適用後のスキーマバージョン
+    pub version: i32,
+    /// マイグレーションの説明
+    pub description: &'static str,
+    /// 適用するSQL（複数文可）
+    pub sql: &'static str,
+}
+
+impl Migration {
+    /// SQLのチェックサム（SHA-256の16進文字列）を計算
+    pub fn checksum(&self) -> String {
+        format!("{:x}", Sha256::digest(self.sql.as_bytes()))
+    }
+}
+
+/// バージョン1: 初期スキーマ
+const MIGRATION_V1_SQL: &str = "
+CREATE TABLE IF NOT EXISTS tasks_index (
+    id TEXT PRIMARY KEY,
+    file_path TEXT UNIQUE NOT NULL,
+    front_matter_json TEXT NOT NULL,
+    modified_at INTEGER NOT NULL,
+    indexed_at INTEGER NOT NULL,
+    created_at INTEGER
+);
+CREATE INDEX IF NOT EXISTS idx_tasks_modified_at ON tasks_index(modified_at);
+CREATE INDEX IF NOT EXISTS idx_tasks_indexed_at ON tasks_index(indexed_at);
+
+CREATE TABLE IF NOT EXISTS folders_index (
+    id TEXT PRIMARY KEY,
+    parent_id TEXT,
+    name TEXT NOT NULL,
+    path TEXT UNIQUE NOT NULL,
+    created_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_folders_parent_id ON folders_index(parent_id);
+CREATE INDEX IF NOT EXISTS idx_folders_path ON folders_index(path);
+
+CREATE TABLE IF NOT EXISTS task_folders_index (
+    task_id TEXT NOT NULL,
+    folder_id TEXT NOT NULL,
+    PRIMARY KEY (task_id, folder_id),
+    FOREIGN KEY (task_id) REFERENCES tasks_index(id) ON DELETE CASCADE,
+    FOREIGN KEY (folder_id) REFERENCES folders_index(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_task_folders_task_id ON task_folders_index(task_id);
+CREATE INDEX IF NOT EXISTS idx_task_folders_folder_id ON task_folders_index(folder_id);
+
+CREATE TABLE IF NOT EXISTS assets_index (
+    id TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL,
+    file_path TEXT UNIQUE NOT NULL,
+    relative_path TEXT NOT NULL,
+    file_size INTEGER,
+    mime_type TEXT,
+    created_at INTEGER NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks_index(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_assets_task_id ON assets_index(task_id);
+CREATE INDEX IF NOT EXISTS idx_assets_file_path ON assets_index(file_path);
+
+CREATE TABLE IF NOT EXISTS tags_index (
+    category TEXT NOT NULL,
+    value TEXT NOT NULL,
+    task_count INTEGER DEFAULT 0,
+    PRIMARY KEY (category, value)
+);
+CREATE INDEX IF NOT EXISTS idx_tags_category ON tags_index(category);
+CREATE INDEX IF NOT EXISTS idx_tags_task_count ON tags_index(task_count);
+
+CREATE TABLE IF NOT EXISTS task_tags_index (
+    task_id TEXT NOT NULL,
+    category TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (task_id, category, value),
+    FOREIGN KEY (task_id) REFERENCES tasks_index(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_task_tags_task_id ON task_tags_index(task_id);
+CREATE INDEX IF NOT EXISTS idx_task_tags_category_value ON task_tags_index(category, value);
+
+CREATE TABLE IF NOT EXISTS file_metadata (
+    file_path TEXT PRIMARY KEY,
+    mtime INTEGER NOT NULL,
+    indexed_at INTEGER NOT NULL,
+    file_size INTEGER,
+    hash TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_file_metadata_mtime ON file_metadata(mtime);
+CREATE INDEX IF NOT EXISTS idx_file_metadata_indexed_at ON file_metadata(indexed_at);
+";
+
+/// バージョン2: タスクの本文ハッシュ・タイトル・アーカイブフラグを追加
+const MIGRATION_V2_SQL: &str = "
+ALTER TABLE tasks_index ADD COLUMN body_hash TEXT;
+ALTER TABLE tasks_index ADD COLUMN title TEXT;
+ALTER TABLE tasks_index ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;
+CREATE INDEX IF NOT EXISTS idx_tasks_archived ON tasks_index(archived);
+";
+
+/// バージョン3: タスク本文からのアセット参照を追加
+///
+/// 既存のタスクは参照が未解析のため、次回の構築で再処理されるよう更新日時をリセットする。
+const MIGRATION_V3_SQL: &str = "
+CREATE TABLE IF NOT EXISTS asset_references (
+    task_id TEXT NOT NULL,
+    asset_path TEXT NOT NULL,
+    PRIMARY KEY (task_id, asset_path),
+    FOREIGN KEY (task_id) REFERENCES tasks_index(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_asset_references_asset_path ON asset_references(asset_path);
+UPDATE tasks_index SET modified_at = 0;
+";
+
+/// バージョン4: アセットのコンテンツハッシュと画像サイズを追加
+const MIGRATION_V4_SQL: &str = "
+ALTER TABLE assets_index ADD COLUMN content_hash TEXT;
+ALTER TABLE assets_index ADD COLUMN width INTEGER;
+ALTER TABLE assets_index ADD COLUMN height INTEGER;
+CREATE INDEX IF NOT EXISTS idx_assets_content_hash ON assets_index(content_hash);
+";
+
+/// バージョン5: 計算フィールドの値を追加
+///
+/// 値はインデックス構築のたびに評価し直されるため、既存のタスクは空のままでよい。
+const MIGRATION_V5_SQL: &str = "
+ALTER TABLE tasks_index ADD COLUMN computed_json TEXT NOT NULL DEFAULT '{}';
+";
+
+/// バージョン6: タスク-タグ関連インデックスを再構築
+///
+/// フィルターのSQL化以前のキャッシュには`task_tags_index`の行がないため、
+/// 次回の構築ですべてのタスクが再処理されるよう更新日時をリセットする。
+const MIGRATION_V6_SQL: &str = "
+UPDATE tasks_index SET modified_at = 0;
+";
+
+/// バージョン7: タグの値から参照されるアセットを登録
+///
+/// 画像・ハイパーリンクタグの参照は以前のキャッシュの`asset_references`にないため、
+/// 次回の構築ですべてのタスクが再処理されるよう更新日時をリセットする。
+const MIGRATION_V7_SQL: &str = "
+UPDATE tasks_index SET modified_at = 0;
+";
+
+/// 登録済みマイグレーション（バージョン昇順）
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Initial schema",
+        sql: MIGRATION_V1_SQL,
+    },
+    Migration {
+        version: 2,
+        description: "Add body_hash, title and archived to tasks_index",
+        sql: MIGRATION_V2_SQL,
+    },
+    Migration {
+        version: 3,
+        description: "Add asset_references",
+        sql: MIGRATION_V3_SQL,
+    },
+    Migration {
+        version: 4,
+        description: "Add content_hash, width and height to assets_index",
+        sql: MIGRATION_V4_SQL,
+    },
+    Migration {
+        version: 5,
+        description: "Add computed_json to tasks_index",
+        sql: MIGRATION_V5_SQL,
+    },
+    Migration {
+        version: 6,
+        description: "Reindex tasks to backfill task_tags_index",
+        sql: MIGRATION_V6_SQL,
+    },
+    Migration {
+        version: 7,
+        description: "Reindex tasks to add asset references from tags",
+        sql: MIGRATION_V7_SQL,
+    },
+];
+
+/// このアプリがサポートする最新のスキーマバージョン
+pub fn latest_version() -> i32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// マイグレーションエラー
+#[derive(Debug)]
+pub enum MigrationError {
+    /// キャッシュがより新しいバージョンのアプリで作成されている
+    UnsupportedVersion { found: i32, supported: i32 },
+    /// 適用済みマイグレーションのチェックサムが登録内容と一致しない
+    ChecksumMismatch { version: i32 },
+    /// SQLの実行に失敗（該当ステップはロールバック済み）
+    Sqlite { version: i32, source: rusqlite::Error },
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "Index schema version {} is newer than supported version {}",
+                found, supported
+            ),
+            MigrationError::ChecksumMismatch { version } => write!(
+                f,
+                "Checksum mismatch for applied schema migration v{}",
+                version
+            ),
+            MigrationError::Sqlite { version, source } => {
+                write!(f, "Schema migration v{} failed: {}", version, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+impl From<MigrationError> for rusqlite::Error {
+    fn from(err: MigrationError) -> Self {
+        match err {
+            MigrationError::Sqlite { source, .. } => source,
+            MigrationError::UnsupportedVersion { .. } => rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CANTOPEN),
+                Some(err.to_string()),
+            ),
+            MigrationError::ChecksumMismatch { .. } => rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CORRUPT),
+                Some(err.to_string()),
+            ),
+        }
+    }
+}
+
+/// 登録済みマイグレーションをすべて適用
+pub fn run_migrations(connection: &Connection) -> Result<i32, MigrationError> {
+    run_migrations_with(connection, MIGRATIONS)
+}
+
+/// 指定したマイグレーション列を適用
+///
+/// 各ステップは個別のトランザクションで実行され、失敗した場合はそのステップのみ
+/// ロールバックされる（それ以前に適用済みのステップは残る）。
+///
+/// # Returns
+/// * `Result<i32, MigrationError>` - 適用後のスキーマバージョン
+pub fn run_migrations_with(
+    connection: &Connection,
+    migrations: &[Migration],
+) -> Result<i32, MigrationError> {
+    let supported = migrations.last().map(|m| m.version).unwrap_or(0);
+    let bootstrap_error = |source| MigrationError::Sqlite { version: 0, source };
+
+    ensure_version_table(connection).map_err(bootstrap_error)?;
+
+    let current = current_version(connection).map_err(bootstrap_error)?;
+    if current > supported {
+        return Err(MigrationError::UnsupportedVersion {
+            found: current,
+            supported,
+        });
+    }
+
+    // 適用済みステップのチェックサムを検証
+    for migration in migrations.iter().filter(|m| m.version <= current) {
+        let recorded: Option<Option<String>> = connection
+            .query_row(
+                "SELECT checksum FROM schema_version WHERE version = ?1",
+                params![migration.version],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(bootstrap_error)?;
+
+        match recorded {
+            Some(Some(checksum)) if checksum != migration.checksum() => {
+                return Err(MigrationError::ChecksumMismatch {
+                    version: migration.version,
+                });
+            }
+            // チェックサム導入前に記録されたバージョンは現在の値で補完
+            Some(None) | None => {
+                connection
+                    .execute(
+                        "INSERT OR REPLACE INTO schema_version (version, applied_at, migration_sql, checksum)
+                         VALUES (?1, COALESCE((SELECT applied_at FROM schema_version WHERE version = ?1), ?2), ?3, ?4)",
+                        params![
+                            migration.version,
+                            chrono::Utc::now().timestamp(),
+                            migration.sql,
+                            migration.checksum()
+                        ],
+                    )
+                    .map_err(bootstrap_error)?;
+            }
+            Some(Some(_)) => {}
+        }
+    }
+
+    let mut version = current;
+    for migration in migrations.iter().filter(|m| m.version > current) {
+        apply_migration(connection, migration).map_err(|source| MigrationError::Sqlite {
+            version: migration.version,
+            source,
+        })?;
+        log::info!(
+            "Applied index schema migration v{}: {}",
+            migration.version,
+            migration.description
+        );
+        version = migration.version;
+    }
+
+    Ok(version)
+}
+
+/// 単一のマイグレーションをトランザクション内で適用
+fn apply_migration(connection: &Connection, migration: &Migration) -> rusqlite::Result<()> {
+    let transaction = connection.unchecked_transaction()?;
+    transaction.execute_batch(migration.sql)?;
+    transaction.execute(
+        "INSERT INTO schema_version (version, applied_at, migration_sql, checksum)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![
+            migration.version,
+            chrono::Utc::now().timestamp(),
+            migration.sql,
+            migration.checksum()
+        ],
+    )?;
+    transaction.commit()
+}
+
+/// schema_versionテーブルを作成（旧形式の場合はchecksum列を追加）
+fn ensure_version_table(connection: &Connection) -> rusqlite::Result<()> {
+    connection.execute(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            applied_at INTEGER NOT NULL,
+            migration_sql TEXT,
+            checksum TEXT
+        )",
+        [],
+    )?;
+
+    let has_checksum: bool = connection.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('schema_version') WHERE name = 'checksum'",
+        [],
+        |row| row.get::<_, i32>(0).map(|count| count > 0),
+    )?;
+
+    if !has_checksum {
+        connection.execute("ALTER TABLE schema_version ADD COLUMN checksum TEXT", [])?;
+    }
+
+    Ok(())
+}
+
+/// 記録されている最大のスキーマバージョンを取得
+pub fn current_version(connection: &Connection) -> rusqlite::Result<i32> {
+    let table_exists: bool = connection.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='schema_version'",
+        [],
+        |row| row.get::<_, i32>(0).map(|count| count > 0),
+    )?;
+
+    if !table_exists {
+        return Ok(0);
+    }
+
+    connection.query_row("SELECT MAX(version) FROM schema_version", [], |row| {
+        row.get::<_, Option<i32>>(0).map(|v| v.unwrap_or(0))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 旧バージョンのアプリが作成したv1データベースを再現
+    fn create_legacy_v1(connection: &Connection) {
+        connection
+            .execute_batch(
+                "CREATE TABLE schema_version (
+                    version INTEGER PRIMARY KEY,
+                    applied_at INTEGER NOT NULL,
+                    migration_sql TEXT
+                );",
+            )
+            .unwrap();
+        connection.execute_batch(MIGRATION_V1_SQL).unwrap();
+        connection
+            .execute(
+                "INSERT INTO schema_version (version, applied_at, migration_sql) VALUES (1, 0, '-- Initial schema version 1')",
+                [],
+            )
+            .unwrap();
+        connection
+            .execute(
+                "INSERT INTO tasks_index (id, file_path, front_matter_json, modified_at, indexed_at)
+                 VALUES ('task-1', 'task-1.md', '{}', 0, 0)",
+                [],
+            )
+            .unwrap();
+    }
+
+    fn column_exists(connection: &Connection, table: &str, column: &str) -> bool {
+        connection
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+                params![table, column],
+                |row| row.get::<_, i32>(0),
+            )
+            .unwrap()
+            > 0
+    }
+
+    #[test]
+    fn test_migrate_from_empty_database() {
+        let connection = Connection::open_in_memory().unwrap();
+        let version = run_migrations(&connection).unwrap();
+
+        assert_eq!(version, latest_version());
+        assert!(column_exists(&connection, "tasks_index", "body_hash"));
+        assert!(column_exists(&connection, "tasks_index", "title"));
+        assert!(column_exists(&connection, "tasks_index", "archived"));
+    }
+
+    #[test]
+    fn test_migrate_from_legacy_v1() {
+        let connection = Connection::open_in_memory().unwrap();
+        create_legacy_v1(&connection);
+
+        let version = run_migrations(&connection).unwrap();
+        assert_eq!(version, latest_version());
+
+        // 既存データは保持され、新しい列にはデフォルト値が入る
+        let archived: i32 = connection
+            .query_row("SELECT archived FROM tasks_index WHERE id = 'task-1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(archived, 0);
+
+        // v1のチェックサムが補完されている
+        let checksum: Option<String> = connection
+            .query_row("SELECT checksum FROM schema_version WHERE version = 1", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(checksum, Some(MIGRATIONS[0].checksum()));
+
+        // 再実行しても変化しない
+        assert_eq!(run_migrations(&connection).unwrap(), latest_version());
+    }
+
+    #[test]
+    fn test_existing_tasks_are_reindexed_for_task_tags() {
+        let connection = Connection::open_in_memory().unwrap();
+        run_migrations_with(&connection, &MIGRATIONS[..5]).unwrap();
+        connection
+            .execute(
+                "INSERT INTO tasks_index (id, file_path, front_matter_json, modified_at, indexed_at)
+                 VALUES ('task-1', 'task-1.md', '{}', 100, 100)",
+                [],
+            )
+            .unwrap();
+
+        assert_eq!(run_migrations(&connection).unwrap(), latest_version());
+        let modified_at: i64 = connection
+            .query_row("SELECT modified_at FROM tasks_index WHERE id = 'task-1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(modified_at, 0);
+    }
+
+    #[test]
+    fn test_newer_schema_is_rejected() {
+        let connection = Connection::open_in_memory().unwrap();
+        run_migrations(&connection).unwrap();
+        connection
+            .execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (?1, 0)",
+                params![latest_version() + 1],
+            )
+            .unwrap();
+
+        let result = run_migrations(&connection);
+        assert!(matches!(result, Err(MigrationError::UnsupportedVersion { .. })));
+    }
+
+    #[test]
+    fn test_checksum_mismatch_is_detected() {
+        let connection = Connection::open_in_memory().unwrap();
+        run_migrations(&connection).unwrap();
+        connection
+            .execute("UPDATE schema_version SET checksum = 'tampered' WHERE version = 2", [])
+            .unwrap();
+
+        let result = run_migrations(&connection);
+        assert!(matches!(result, Err(MigrationError::ChecksumMismatch { version: 2 })));
+    }
+
+    #[test]
+    fn test_failed_migration_is_rolled_back() {
+        let connection = Connection::open_in_memory().unwrap();
+        let broken = [
+            MIGRATIONS[0],
+            Migration {
+                version: 2,
+                description: "broken",
+                sql: "CREATE TABLE half_done (id INTEGER); SELECT * FROM missing_table;",
+            },
+        ];
+
+        let result = run_migrations_with(&connection, &broken);
+        assert!(matches!(result, Err(MigrationError::Sqlite { version: 2, .. })));
+
+        // v1は適用済み、v2の途中結果は残らない
+        assert_eq!(current_version(&connection).unwrap(), 1);
+        let half_done: i32 = connection
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'half_done'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(half_done, 0);
+    }
+}
@@ -1,6 +1,9 @@
 // Repository layer for SQLite index management
 pub mod database;
+pub mod connection_manager;
+pub mod migrations;
 pub mod task_index_repository;
+pub mod task_query;
 pub mod folder_index_repository;
 pub mod asset_index_repository;
 pub mod index_builder;
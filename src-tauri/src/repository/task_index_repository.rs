@@ -1,10 +1,15 @@
 // Task index repository
 
-use crate::models::task::FrontMatter;
+use crate::models::filter_sort::{CustomSort, FilterExpression};
+use crate::models::task::{FrontMatter, TagValue};
 use crate::repository::database::IndexDatabase;
+use crate::repository::task_query::TaskQueryCompiler;
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Result as SqliteResult};
+use rusqlite::{params, Connection, params_from_iter, Result as SqliteResult};
+use serde::Serialize;
 use serde_json;
+use std::borrow::Borrow;
+use std::collections::HashMap;
 
 /// タスクインデックスの読み取り専用リポジトリトレイト
 pub trait TaskIndexRepository {
@@ -22,7 +27,8 @@ pub trait TaskIndexRepository {
 }
 
 /// タスクインデックスエントリ（データベースから取得した情報）
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TaskIndexEntry {
     pub id: String,
     pub file_path: String,
@@ -30,52 +36,195 @@ pub struct TaskIndexEntry {
     pub modified_at: DateTime<Utc>,
     pub indexed_at: DateTime<Utc>,
     pub created_at: Option<DateTime<Utc>>,
+    /// タイトル（Front Matterの`title`または本文の最初の見出し）
+    pub title: Option<String>,
+    /// 本文のハッシュ値（SHA-256）
+    pub body_hash: Option<String>,
+    /// アーカイブ済みかどうか
+    pub archived: bool,
+    /// 計算フィールドの値（インデックス構築時に評価）
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub computed_tags: HashMap<String, TagValue>,
+}
+
+/// タスク検索結果のページ
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskQueryPage {
+    /// 該当ページのタスク
+    pub items: Vec<TaskIndexEntry>,
+    /// フィルター適用後の総件数
+    pub total: usize,
+    /// 取得開始位置
+    pub offset: usize,
+    /// 取得件数の上限
+    pub limit: usize,
 }
 
 /// SQLite実装
-pub struct SqliteTaskIndexRepository<'a> {
-    db: &'a IndexDatabase,
+///
+/// `db`には`&IndexDatabase`のほか、プールから取得した所有型の接続
+/// （`PooledConnection`）を渡せる。
+pub struct SqliteTaskIndexRepository<D: Borrow<IndexDatabase>> {
+    db: D,
 }
 
-impl<'a> SqliteTaskIndexRepository<'a> {
-    pub fn new(db: &'a IndexDatabase) -> Self {
+impl<D: Borrow<IndexDatabase>> SqliteTaskIndexRepository<D> {
+    pub fn new(db: D) -> Self {
         Self { db }
     }
 
+    /// リポジトリが使うデータベース接続
+    pub fn database(&self) -> &IndexDatabase {
+        self.db.borrow()
+    }
+
+    fn connection(&self) -> &Connection {
+        self.database().connection()
+    }
+
     /// タスクをインデックスに追加または更新
     pub fn upsert_task(&self, entry: &TaskIndexEntry) -> SqliteResult<()> {
-        let front_matter_json = serde_json::to_string(&entry.front_matter)
-            .map_err(|e| {
-                rusqlite::Error::InvalidColumnType(
-                    0,
-                    format!("JSON serialization error: {}", e),
-                    rusqlite::types::Type::Null,
-                )
-            })?;
+        let front_matter_json = Self::to_json(&entry.front_matter)?;
+        let computed_json = Self::to_json(&entry.computed_tags)?;
 
         let modified_at = entry.modified_at.timestamp();
         let indexed_at = entry.indexed_at.timestamp();
         let created_at = entry.created_at.map(|dt| dt.timestamp());
 
-        self.db.connection().execute(
-            "INSERT OR REPLACE INTO tasks_index (id, file_path, front_matter_json, modified_at, indexed_at, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        self.connection().execute(
+            // INSERT OR REPLACEは行を削除してから挿入するため、関連テーブルがカスケード削除されてしまう
+            "INSERT INTO tasks_index (id, file_path, front_matter_json, modified_at, indexed_at, created_at, title, body_hash, archived, computed_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(id) DO UPDATE SET
+                file_path = excluded.file_path,
+                front_matter_json = excluded.front_matter_json,
+                modified_at = excluded.modified_at,
+                indexed_at = excluded.indexed_at,
+                created_at = excluded.created_at,
+                title = excluded.title,
+                body_hash = excluded.body_hash,
+                archived = excluded.archived,
+                computed_json = excluded.computed_json",
             params![
                 entry.id,
                 entry.file_path,
                 front_matter_json,
                 modified_at,
                 indexed_at,
-                created_at
+                created_at,
+                entry.title,
+                entry.body_hash,
+                entry.archived,
+                computed_json
             ],
         )?;
 
+        self.replace_task_tags(entry)?;
+
         Ok(())
     }
 
+    /// 計算フィールドの値だけを置き換える（Front Matterと更新日時は変更しない）
+    pub fn update_computed_tags(&self, entry: &TaskIndexEntry) -> SqliteResult<()> {
+        self.connection().execute(
+            "UPDATE tasks_index SET computed_json = ?2 WHERE id = ?1",
+            params![entry.id, Self::to_json(&entry.computed_tags)?],
+        )?;
+        self.replace_task_tags(entry)
+    }
+
+    /// タスク-タグ関連インデックスを置き換える（配列は要素ごとに1行）
+    ///
+    /// 計算フィールドは同名のFront Matterのタグより優先される。
+    fn replace_task_tags(&self, entry: &TaskIndexEntry) -> SqliteResult<()> {
+        let connection = self.connection();
+        connection.execute(
+            "DELETE FROM task_tags_index WHERE task_id = ?1",
+            params![entry.id],
+        )?;
+
+        let mut stmt = connection.prepare(
+            "INSERT OR IGNORE INTO task_tags_index (task_id, category, value) VALUES (?1, ?2, ?3)",
+        )?;
+        let front_matter = entry
+            .front_matter
+            .tags
+            .iter()
+            .filter(|(category, _)| !entry.computed_tags.contains_key(*category));
+        for (category, value) in front_matter.chain(&entry.computed_tags) {
+            match value {
+                TagValue::Array(items) => {
+                    for item in items {
+                        stmt.execute(params![entry.id, category, item])?;
+                    }
+                }
+                other => {
+                    stmt.execute(params![entry.id, category, other.to_string_value()])?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// フィルター・ソートを適用してタスクをページ単位で取得
+    ///
+    /// # Arguments
+    /// * `filter` - フィルター式（Noneの場合は全件）
+    /// * `sort` - カスタムソート（Noneの場合はID順）
+    /// * `separator` - 階層タグの区切り文字
+    /// * `offset` - 取得開始位置
+    /// * `limit` - 取得件数
+    ///
+    /// # Returns
+    /// * `SqliteResult<TaskQueryPage>` - 該当ページと総件数
+    pub fn query_tasks(
+        &self,
+        filter: Option<&FilterExpression>,
+        sort: Option<&CustomSort>,
+        separator: &str,
+        offset: usize,
+        limit: usize,
+    ) -> SqliteResult<TaskQueryPage> {
+        let compiled = TaskQueryCompiler::compile(filter, sort, separator);
+        let connection = self.connection();
+
+        let total: i64 = connection.query_row(
+            &format!("SELECT COUNT(*) FROM tasks_index t WHERE {}", compiled.where_clause),
+            params_from_iter(compiled.where_params.iter()),
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = connection.prepare(&format!(
+            "SELECT t.id, t.file_path, t.front_matter_json, t.modified_at, t.indexed_at, t.created_at, t.title, t.body_hash, t.archived, t.computed_json
+             FROM tasks_index t
+             WHERE {}
+             ORDER BY {}
+             LIMIT {} OFFSET {}",
+            compiled.where_clause, compiled.order_by, limit, offset
+        ))?;
+
+        let rows = stmt.query_map(
+            params_from_iter(compiled.where_params.iter().chain(compiled.order_params.iter())),
+            Self::row_to_entry,
+        )?;
+
+        let mut items = Vec::new();
+        for row_result in rows {
+            items.push(row_result?);
+        }
+
+        Ok(TaskQueryPage {
+            items,
+            total: total as usize,
+            offset,
+            limit,
+        })
+    }
+
     /// タスクを削除
     pub fn delete_task(&self, task_id: &str) -> SqliteResult<()> {
-        self.db.connection().execute(
+        self.connection().execute(
             "DELETE FROM tasks_index WHERE id = ?1",
             params![task_id],
         )?;
@@ -84,15 +233,26 @@ impl<'a> SqliteTaskIndexRepository<'a> {
 
     /// ファイルパスでタスクを削除
     pub fn delete_task_by_path(&self, file_path: &str) -> SqliteResult<()> {
-        self.db.connection().execute(
+        self.connection().execute(
             "DELETE FROM tasks_index WHERE file_path = ?1",
             params![file_path],
         )?;
         Ok(())
     }
 
-    /// Front MatterをJSON文字列からデシリアライズ
-    fn deserialize_front_matter(json: &str) -> SqliteResult<FrontMatter> {
+    /// JSON文字列にシリアライズ
+    fn to_json<T: Serialize>(value: &T) -> SqliteResult<String> {
+        serde_json::to_string(value).map_err(|e| {
+            rusqlite::Error::InvalidColumnType(
+                0,
+                format!("JSON serialization error: {}", e),
+                rusqlite::types::Type::Null,
+            )
+        })
+    }
+
+    /// Front Matter・計算フィールドをJSON文字列からデシリアライズ
+    fn deserialize_json<T: serde::de::DeserializeOwned>(json: &str) -> SqliteResult<T> {
         serde_json::from_str(json).map_err(|e| {
             rusqlite::Error::InvalidColumnType(
                 0,
@@ -102,32 +262,37 @@ impl<'a> SqliteTaskIndexRepository<'a> {
         })
     }
 
+    /// 検索結果の行をTaskIndexEntryに変換
+    fn row_to_entry(row: &rusqlite::Row) -> SqliteResult<TaskIndexEntry> {
+        Ok(TaskIndexEntry {
+            id: row.get(0)?,
+            file_path: row.get(1)?,
+            front_matter: Self::deserialize_json(row.get::<_, String>(2)?.as_str())?,
+            modified_at: Self::timestamp_to_datetime(row.get(3)?),
+            indexed_at: Self::timestamp_to_datetime(row.get(4)?),
+            created_at: row.get::<_, Option<i64>>(5)?.map(Self::timestamp_to_datetime),
+            title: row.get(6)?,
+            body_hash: row.get(7)?,
+            archived: row.get(8)?,
+            computed_tags: Self::deserialize_json(row.get::<_, String>(9)?.as_str())?,
+        })
+    }
+
     /// Unix timestampからDateTime<Utc>に変換
     fn timestamp_to_datetime(timestamp: i64) -> DateTime<Utc> {
         DateTime::from_timestamp(timestamp, 0).unwrap_or_else(|| Utc::now())
     }
 }
 
-impl<'a> TaskIndexRepository for SqliteTaskIndexRepository<'a> {
+impl<D: Borrow<IndexDatabase>> TaskIndexRepository for SqliteTaskIndexRepository<D> {
     fn get_task(&self, task_id: &str) -> SqliteResult<Option<TaskIndexEntry>> {
-        let mut stmt = self.db.connection().prepare(
-            "SELECT id, file_path, front_matter_json, modified_at, indexed_at, created_at
+        let mut stmt = self.connection().prepare(
+            "SELECT id, file_path, front_matter_json, modified_at, indexed_at, created_at, title, body_hash, archived, computed_json
              FROM tasks_index
              WHERE id = ?1",
         )?;
 
-        let entry = stmt.query_row(params![task_id], |row| {
-            Ok(TaskIndexEntry {
-                id: row.get(0)?,
-                file_path: row.get(1)?,
-                front_matter: Self::deserialize_front_matter(
-                    row.get::<_, String>(2)?.as_str(),
-                )?,
-                modified_at: Self::timestamp_to_datetime(row.get(3)?),
-                indexed_at: Self::timestamp_to_datetime(row.get(4)?),
-                created_at: row.get::<_, Option<i64>>(5)?.map(Self::timestamp_to_datetime),
-            })
-        });
+        let entry = stmt.query_row(params![task_id], Self::row_to_entry);
 
         match entry {
             Ok(e) => Ok(Some(e)),
@@ -137,24 +302,13 @@ impl<'a> TaskIndexRepository for SqliteTaskIndexRepository<'a> {
     }
 
     fn list_tasks(&self) -> SqliteResult<Vec<TaskIndexEntry>> {
-        let mut stmt = self.db.connection().prepare(
-            "SELECT id, file_path, front_matter_json, modified_at, indexed_at, created_at
+        let mut stmt = self.connection().prepare(
+            "SELECT id, file_path, front_matter_json, modified_at, indexed_at, created_at, title, body_hash, archived, computed_json
              FROM tasks_index
              ORDER BY id",
         )?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(TaskIndexEntry {
-                id: row.get(0)?,
-                file_path: row.get(1)?,
-                front_matter: Self::deserialize_front_matter(
-                    row.get::<_, String>(2)?.as_str(),
-                )?,
-                modified_at: Self::timestamp_to_datetime(row.get(3)?),
-                indexed_at: Self::timestamp_to_datetime(row.get(4)?),
-                created_at: row.get::<_, Option<i64>>(5)?.map(Self::timestamp_to_datetime),
-            })
-        })?;
+        let rows = stmt.query_map([], Self::row_to_entry)?;
 
         let mut entries = Vec::new();
         for row_result in rows {
@@ -165,24 +319,13 @@ impl<'a> TaskIndexRepository for SqliteTaskIndexRepository<'a> {
     }
 
     fn get_task_by_path(&self, file_path: &str) -> SqliteResult<Option<TaskIndexEntry>> {
-        let mut stmt = self.db.connection().prepare(
-            "SELECT id, file_path, front_matter_json, modified_at, indexed_at, created_at
+        let mut stmt = self.connection().prepare(
+            "SELECT id, file_path, front_matter_json, modified_at, indexed_at, created_at, title, body_hash, archived, computed_json
              FROM tasks_index
              WHERE file_path = ?1",
         )?;
 
-        let entry = stmt.query_row(params![file_path], |row| {
-            Ok(TaskIndexEntry {
-                id: row.get(0)?,
-                file_path: row.get(1)?,
-                front_matter: Self::deserialize_front_matter(
-                    row.get::<_, String>(2)?.as_str(),
-                )?,
-                modified_at: Self::timestamp_to_datetime(row.get(3)?),
-                indexed_at: Self::timestamp_to_datetime(row.get(4)?),
-                created_at: row.get::<_, Option<i64>>(5)?.map(Self::timestamp_to_datetime),
-            })
-        });
+        let entry = stmt.query_row(params![file_path], Self::row_to_entry);
 
         match entry {
             Ok(e) => Ok(Some(e)),
@@ -194,32 +337,21 @@ impl<'a> TaskIndexRepository for SqliteTaskIndexRepository<'a> {
     fn list_tasks_by_modified_at(&self, limit: Option<i64>) -> SqliteResult<Vec<TaskIndexEntry>> {
         let query = if let Some(limit) = limit {
             format!(
-                "SELECT id, file_path, front_matter_json, modified_at, indexed_at, created_at
+                "SELECT id, file_path, front_matter_json, modified_at, indexed_at, created_at, title, body_hash, archived, computed_json
                  FROM tasks_index
                  ORDER BY modified_at DESC
                  LIMIT {}",
                 limit
             )
         } else {
-            "SELECT id, file_path, front_matter_json, modified_at, indexed_at, created_at
+            "SELECT id, file_path, front_matter_json, modified_at, indexed_at, created_at, title, body_hash, archived, computed_json
              FROM tasks_index
              ORDER BY modified_at DESC"
                 .to_string()
         };
 
-        let mut stmt = self.db.connection().prepare(&query)?;
-        let rows = stmt.query_map([], |row| {
-            Ok(TaskIndexEntry {
-                id: row.get(0)?,
-                file_path: row.get(1)?,
-                front_matter: Self::deserialize_front_matter(
-                    row.get::<_, String>(2)?.as_str(),
-                )?,
-                modified_at: Self::timestamp_to_datetime(row.get(3)?),
-                indexed_at: Self::timestamp_to_datetime(row.get(4)?),
-                created_at: row.get::<_, Option<i64>>(5)?.map(Self::timestamp_to_datetime),
-            })
-        })?;
+        let mut stmt = self.connection().prepare(&query)?;
+        let rows = stmt.query_map([], Self::row_to_entry)?;
 
         let mut entries = Vec::new();
         for row_result in rows {
@@ -257,6 +389,10 @@ mod tests {
             modified_at: Utc::now(),
             indexed_at: Utc::now(),
             created_at: Some(Utc::now()),
+            title: Some("Test Task".to_string()),
+            body_hash: None,
+            archived: false,
+            computed_tags: HashMap::new(),
         };
 
         repo.upsert_task(&entry).unwrap();
@@ -279,6 +415,10 @@ mod tests {
             modified_at: Utc::now(),
             indexed_at: Utc::now(),
             created_at: Some(Utc::now()),
+            title: Some("Test Task".to_string()),
+            body_hash: None,
+            archived: false,
+            computed_tags: HashMap::new(),
         };
 
         repo.upsert_task(&entry).unwrap();
@@ -286,4 +426,139 @@ mod tests {
         let retrieved = repo.get_task("test-task").unwrap();
         assert!(retrieved.is_none());
     }
+
+    fn insert_query_fixture(repo: &SqliteTaskIndexRepository<&IndexDatabase>) {
+        let tasks = vec![
+            ("a", "pending", 3, vec!["ui", "bug"], "Fix login", "area/backend/api"),
+            ("b", "done", 1, vec!["api"], "Add endpoint", "area/backend"),
+            ("c", "in_progress", 5, vec!["ui"], "Redesign login page", "area/backend-old"),
+            ("d", "pending", 2, vec![], "Write docs", "docs"),
+        ];
+
+        for (id, status, points, labels, summary, area) in tasks {
+            let mut tags = HashMap::new();
+            tags.insert("area".to_string(), TagValue::String(area.to_string()));
+            tags.insert("status".to_string(), TagValue::String(status.to_string()));
+            tags.insert("points".to_string(), TagValue::Number(points));
+            tags.insert("summary".to_string(), TagValue::String(summary.to_string()));
+            if !labels.is_empty() {
+                tags.insert(
+                    "labels".to_string(),
+                    TagValue::Array(labels.iter().map(|l| l.to_string()).collect()),
+                );
+            }
+            repo.upsert_task(&TaskIndexEntry {
+                id: id.to_string(),
+                file_path: format!("tasks/{}.md", id),
+                front_matter: FrontMatter { tags },
+                modified_at: Utc::now(),
+                indexed_at: Utc::now(),
+                created_at: None,
+                title: None,
+                body_hash: None,
+                archived: false,
+                computed_tags: HashMap::from([(
+                    "score".to_string(),
+                    TagValue::Number(points * 10),
+                )]),
+            })
+            .unwrap();
+        }
+    }
+
+    fn filter(json: serde_json::Value) -> FilterExpression {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn ids(page: &TaskQueryPage) -> Vec<&str> {
+        page.items.iter().map(|item| item.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_query_tasks_conditions() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = IndexDatabase::new(temp_dir.path()).unwrap();
+        let repo = SqliteTaskIndexRepository::new(&db);
+        insert_query_fixture(&repo);
+
+        // 配列タグは要素単位で一致
+        let f = filter(serde_json::json!({"condition": {"tagKey": "labels", "operator": "==", "value": "ui"}}));
+        assert_eq!(ids(&repo.query_tasks(Some(&f), None, "/", 0, 10).unwrap()), vec!["a", "c"]);
+
+        let f = filter(serde_json::json!({"condition": {"tagKey": "points", "operator": ">", "value": 2}}));
+        assert_eq!(ids(&repo.query_tasks(Some(&f), None, "/", 0, 10).unwrap()), vec!["a", "c"]);
+
+        let f = filter(serde_json::json!({"condition": {"tagKey": "summary", "operator": "contains", "value": "login"}}));
+        assert_eq!(ids(&repo.query_tasks(Some(&f), None, "/", 0, 10).unwrap()), vec!["a", "c"]);
+
+        let f = filter(serde_json::json!({"condition": {"tagKey": "summary", "operator": "ends_with", "value": "docs"}}));
+        assert_eq!(ids(&repo.query_tasks(Some(&f), None, "/", 0, 10).unwrap()), vec!["d"]);
+
+        // 計算フィールドも通常のタグと同じように検索できる
+        let f = filter(serde_json::json!({"condition": {"tagKey": "score", "operator": ">", "value": 25}}));
+        assert_eq!(ids(&repo.query_tasks(Some(&f), None, "/", 0, 10).unwrap()), vec!["a", "c"]);
+
+        let f = filter(serde_json::json!({"condition": {"tagKey": "score", "operator": "==", "value": 10}}));
+        let page = repo.query_tasks(Some(&f), None, "/", 0, 10).unwrap();
+        assert_eq!(ids(&page), vec!["b"]);
+        assert_eq!(page.items[0].computed_tags["score"], TagValue::Number(10));
+
+        // 階層タグは区切り文字の境界でのみ前方一致する
+        let f = filter(serde_json::json!({"condition": {"tagKey": "area", "operator": "under", "value": "area/backend"}}));
+        assert_eq!(ids(&repo.query_tasks(Some(&f), None, "/", 0, 10).unwrap()), vec!["a", "b"]);
+        assert_eq!(ids(&repo.query_tasks(Some(&f), None, "", 0, 10).unwrap()), vec!["b"]);
+    }
+
+    #[test]
+    fn test_query_tasks_logical_operators() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = IndexDatabase::new(temp_dir.path()).unwrap();
+        let repo = SqliteTaskIndexRepository::new(&db);
+        insert_query_fixture(&repo);
+
+        let f = filter(serde_json::json!({
+            "logicalOperator": "OR",
+            "expressions": [
+                {"condition": {"tagKey": "status", "operator": "==", "value": "done"}},
+                {"condition": {"tagKey": "points", "operator": ">=", "value": 5}}
+            ]
+        }));
+        assert_eq!(ids(&repo.query_tasks(Some(&f), None, "/", 0, 10).unwrap()), vec!["b", "c"]);
+
+        let f = filter(serde_json::json!({
+            "logicalOperator": "NOT",
+            "expressions": [
+                {"condition": {"tagKey": "status", "operator": "==", "value": "pending"}}
+            ]
+        }));
+        assert_eq!(ids(&repo.query_tasks(Some(&f), None, "/", 0, 10).unwrap()), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_query_tasks_sort_and_pagination() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = IndexDatabase::new(temp_dir.path()).unwrap();
+        let repo = SqliteTaskIndexRepository::new(&db);
+        insert_query_fixture(&repo);
+
+        let sort: CustomSort = serde_json::from_value(serde_json::json!({
+            "name": "status",
+            "description": null,
+            "sortKeys": [
+                {"tagKey": "status", "order": "Asc", "customOrder": ["in_progress", "pending", "done"]},
+                {"tagKey": "points", "order": "Desc", "customOrder": null}
+            ],
+            "handleMissing": "last",
+            "createdAt": "",
+            "updatedAt": ""
+        }))
+        .unwrap();
+
+        let page = repo.query_tasks(None, Some(&sort), "/", 0, 2).unwrap();
+        assert_eq!(page.total, 4);
+        assert_eq!(ids(&page), vec!["c", "a"]);
+
+        let page = repo.query_tasks(None, Some(&sort), "/", 2, 2).unwrap();
+        assert_eq!(ids(&page), vec!["d", "b"]);
+    }
 }
@@ -0,0 +1,241 @@
+// FilterExpression / CustomSort to SQL compiler
+
+use crate::models::filter_sort::{
+    ComparisonOperator, CustomSort, FilterCondition, FilterExpression, LogicalOperator, SortOrder,
+};
+use rusqlite::types::Value;
+
+/// 検索対象のタグ（Front Matterに計算フィールドを上書きしたJSON）
+const TAGS_JSON: &str = "json_patch(t.front_matter_json, t.computed_json)";
+
+/// SQLにコンパイルされたタスク検索条件
+///
+/// `tasks_index`を別名`t`として参照するWHERE句・ORDER BY句と、
+/// それぞれに対応する位置パラメータを保持する。
+#[derive(Debug, Clone)]
+pub struct CompiledTaskQuery {
+    pub where_clause: String,
+    pub where_params: Vec<Value>,
+    pub order_by: String,
+    pub order_params: Vec<Value>,
+}
+
+/// フィルター式・カスタムソートをSQLに変換するコンパイラ
+///
+/// 評価結果が`Metric::evaluate`（メモリ上の評価）と一致するように変換する。
+/// ただし等価比較と文字列比較は`task_tags_index`上で行うため、配列タグは要素単位で一致判定される。
+/// 計算フィールドは通常のタグと同じ名前空間で参照できる。
+pub struct TaskQueryCompiler;
+
+impl TaskQueryCompiler {
+    /// フィルター式とソートをコンパイル
+    ///
+    /// `separator`は階層タグの区切り文字（`under`演算子で使用）
+    pub fn compile(
+        filter: Option<&FilterExpression>,
+        sort: Option<&CustomSort>,
+        separator: &str,
+    ) -> CompiledTaskQuery {
+        let mut where_params = Vec::new();
+        let where_clause = match filter {
+            Some(expression) => Self::compile_expression(expression, separator, &mut where_params),
+            None => "1".to_string(),
+        };
+
+        let mut order_params = Vec::new();
+        let order_by = Self::compile_sort(sort, &mut order_params);
+
+        CompiledTaskQuery {
+            where_clause,
+            where_params,
+            order_by,
+            order_params,
+        }
+    }
+
+    /// フィルター式をWHERE句に変換
+    fn compile_expression(
+        expression: &FilterExpression,
+        separator: &str,
+        params: &mut Vec<Value>,
+    ) -> String {
+        // 単一条件
+        if let Some(ref condition) = expression.condition {
+            return Self::compile_condition(condition, separator, params);
+        }
+
+        // 複合式
+        let Some(ref expressions) = expression.expressions else {
+            return "1".to_string();
+        };
+
+        // NOTは先頭の式のみを否定する（メモリ上の評価と同じ）
+        if let Some(LogicalOperator::NOT) = expression.logical_operator {
+            return match expressions.first() {
+                Some(first) => format!("(NOT {})", Self::compile_expression(first, separator, params)),
+                None => "1".to_string(),
+            };
+        }
+
+        let parts: Vec<String> = expressions
+            .iter()
+            .map(|expr| Self::compile_expression(expr, separator, params))
+            .collect();
+
+        match expression.logical_operator {
+            Some(LogicalOperator::OR) if parts.is_empty() => "0".to_string(),
+            Some(LogicalOperator::OR) => format!("({})", parts.join(" OR ")),
+            // デフォルトはAND
+            _ if parts.is_empty() => "1".to_string(),
+            _ => format!("({})", parts.join(" AND ")),
+        }
+    }
+
+    /// 条件を述語に変換
+    fn compile_condition(
+        condition: &FilterCondition,
+        separator: &str,
+        params: &mut Vec<Value>,
+    ) -> String {
+        let path = Self::json_path(&condition.tag_key);
+
+        match condition.operator {
+            ComparisonOperator::Equal | ComparisonOperator::NotEqual => {
+                let Some(value) = Self::scalar_to_string(&condition.value) else {
+                    return "0".to_string();
+                };
+                params.push(Value::Text(condition.tag_key.clone()));
+                params.push(Value::Text(value));
+                let exists = "EXISTS (SELECT 1 FROM task_tags_index tt \
+                     WHERE tt.task_id = t.id AND tt.category = ? AND tt.value = ?)";
+                if matches!(condition.operator, ComparisonOperator::Equal) {
+                    exists.to_string()
+                } else {
+                    format!("NOT {}", exists)
+                }
+            }
+            ComparisonOperator::Under => {
+                let Some(prefix) = condition.value.as_str() else {
+                    return "0".to_string();
+                };
+                params.push(Value::Text(condition.tag_key.clone()));
+                params.push(Value::Text(prefix.to_string()));
+                if separator.is_empty() {
+                    return "EXISTS (SELECT 1 FROM task_tags_index tt \
+                         WHERE tt.task_id = t.id AND tt.category = ? AND tt.value = ?)"
+                        .to_string();
+                }
+                params.push(Value::Text(format!("{}{}", prefix, separator)));
+                "EXISTS (SELECT 1 FROM task_tags_index tt \
+                     WHERE tt.task_id = t.id AND tt.category = ? AND (tt.value = ? OR instr(tt.value, ?) = 1))"
+                    .to_string()
+            }
+            ComparisonOperator::GreaterThan
+            | ComparisonOperator::LessThan
+            | ComparisonOperator::GreaterThanOrEqual
+            | ComparisonOperator::LessThanOrEqual => {
+                let Some(number) = condition.value.as_f64() else {
+                    return "0".to_string();
+                };
+                let op = match condition.operator {
+                    ComparisonOperator::GreaterThan => ">",
+                    ComparisonOperator::LessThan => "<",
+                    ComparisonOperator::GreaterThanOrEqual => ">=",
+                    _ => "<=",
+                };
+                params.push(Value::Text(path.clone()));
+                params.push(Value::Text(path));
+                params.push(Value::Real(number));
+                format!(
+                    "(json_type({TAGS_JSON}, ?) IN ('integer', 'real') AND json_extract({TAGS_JSON}, ?) {} ?)",
+                    op
+                )
+            }
+            ComparisonOperator::Contains
+            | ComparisonOperator::StartsWith
+            | ComparisonOperator::EndsWith
+            | ComparisonOperator::Regex => {
+                let Some(needle) = condition.value.as_str() else {
+                    return "0".to_string();
+                };
+                params.push(Value::Text(path.clone()));
+                params.push(Value::Text(path));
+                params.push(Value::Text(needle.to_string()));
+
+                let value = format!("json_extract({TAGS_JSON}, ?)");
+                let predicate = match condition.operator {
+                    ComparisonOperator::StartsWith => format!("instr({}, ?) = 1", value),
+                    ComparisonOperator::EndsWith => {
+                        params.push(Value::Text(needle.to_string()));
+                        format!("substr({}, -length(?)) = ?", value)
+                    }
+                    // Regexは簡易実装としてcontainsと同じ扱い
+                    _ => format!("instr({}, ?) > 0", value),
+                };
+
+                // 文字列値のみが対象（配列・数値は一致しない）
+                format!("(json_type({TAGS_JSON}, ?) = 'text' AND {})", predicate)
+            }
+        }
+    }
+
+    /// カスタムソートをORDER BY句に変換
+    fn compile_sort(sort: Option<&CustomSort>, params: &mut Vec<Value>) -> String {
+        let mut terms = Vec::new();
+
+        if let Some(sort) = sort {
+            let missing_direction = if sort.handle_missing == "first" { "DESC" } else { "ASC" };
+
+            for key in &sort.sort_keys {
+                let path = Self::json_path(&key.tag_key);
+                let direction = match key.order {
+                    SortOrder::Asc => "ASC",
+                    SortOrder::Desc => "DESC",
+                };
+
+                // 値が存在しないタスクの位置
+                params.push(Value::Text(path.clone()));
+                terms.push(format!(
+                    "(json_extract({TAGS_JSON}, ?) IS NULL) {}",
+                    missing_direction
+                ));
+
+                // カスタム順序（リストにない値はその後ろに辞書順で並ぶ）
+                if let Some(ref custom_order) = key.custom_order {
+                    if !custom_order.is_empty() {
+                        let mut case_sql = format!("CASE CAST(json_extract({TAGS_JSON}, ?) AS TEXT)");
+                        params.push(Value::Text(path.clone()));
+                        for (index, value) in custom_order.iter().enumerate() {
+                            case_sql.push_str(&format!(" WHEN ? THEN {}", index));
+                            params.push(Value::Text(value.clone()));
+                        }
+                        case_sql.push_str(&format!(" ELSE {} END {}", custom_order.len(), direction));
+                        terms.push(case_sql);
+                    }
+                }
+
+                params.push(Value::Text(path));
+                terms.push(format!("json_extract({TAGS_JSON}, ?) {}", direction));
+            }
+        }
+
+        // 安定した順序のためIDを最後に使用
+        terms.push("t.id ASC".to_string());
+        terms.join(", ")
+    }
+
+    /// タグキーからJSONパスを生成
+    fn json_path(tag_key: &str) -> String {
+        format!("$.\"{}\"", tag_key.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    /// 条件値を文字列に変換（メモリ上の評価と同じ規則）
+    fn scalar_to_string(value: &serde_json::Value) -> Option<String> {
+        match value {
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            serde_json::Value::Bool(b) => Some(b.to_string()),
+            _ => None,
+        }
+    }
+}
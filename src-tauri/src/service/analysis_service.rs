@@ -6,8 +6,10 @@ use crate::models::chart::{
     PieChart, LineChart, BarChart,
     CategoryCount, TimeSeriesPoint,
 };
+use crate::models::tag::{canonical_elements, truncate_tag_path};
+use crate::models::tag_config::TagType;
 use chrono::{NaiveDate, Utc};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// 分析サービス
 pub struct AnalysisService;
@@ -189,7 +191,7 @@ impl AnalysisService {
     
     /// 日付文字列を抽出（検証付き）
     fn extract_validated_date_string(task: &Task, field: &str) -> Option<String> {
-        task.front_matter.tags.get(field).and_then(|v| match v {
+        task.tag(field).and_then(|v| match v {
             crate::models::TagValue::String(s) => {
                 // 日付形式を検証（YYYY-MM-DD）
                 if NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
@@ -204,7 +206,7 @@ impl AnalysisService {
     
     /// 日付文字列を抽出（非検証版、折線グラフ用）
     fn extract_date_string(task: &Task, field: &str) -> Option<String> {
-        task.front_matter.tags.get(field).and_then(|v| match v {
+        task.tag(field).and_then(|v| match v {
             crate::models::TagValue::String(s) => {
                 // パース可能な日付のみ返す
                 if NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
@@ -218,14 +220,14 @@ impl AnalysisService {
     }
 
     fn extract_tag_value_string(task: &Task, field: &str) -> Option<String> {
-        task.front_matter.tags.get(field).and_then(|v| match v {
+        task.tag(field).and_then(|v| match v {
             crate::models::TagValue::String(s) => Some(s.clone()),
             _ => None,
         })
     }
 
     fn extract_tag_value_array(task: &Task, field: &str) -> Option<Vec<String>> {
-        task.front_matter.tags.get(field).and_then(|v| match v {
+        task.tag(field).and_then(|v| match v {
             crate::models::TagValue::Array(arr) => Some(arr.clone()),
             _ => None,
         })
@@ -355,8 +357,14 @@ impl AnalysisService {
     }
 
     /// 円グラフDSLを生成し、MermaidとJSON両方を返す
-    pub fn generate_pie_chart_with_dsl(workspace: &Workspace, category: &str) -> Result<ChartOutput, String> {
-        let chart_dsl = Self::generate_pie_dsl(workspace, category)?;
+    ///
+    /// `depth`を指定すると、階層タグを先頭からその階層までで集計する
+    pub fn generate_pie_chart_with_dsl(
+        workspace: &Workspace,
+        category: &str,
+        depth: Option<usize>,
+    ) -> Result<ChartOutput, String> {
+        let chart_dsl = Self::generate_pie_dsl(workspace, category, depth)?;
         let mermaid = Self::dsl_to_mermaid_pie(&chart_dsl);
         
         Ok(ChartOutput {
@@ -382,8 +390,14 @@ impl AnalysisService {
     }
 
     /// 棒グラフDSLを生成し、MermaidとJSON両方を返す
-    pub fn generate_bar_chart_with_dsl(workspace: &Workspace, category: &str) -> Result<ChartOutput, String> {
-        let chart_dsl = Self::generate_bar_dsl(workspace, category)?;
+    ///
+    /// `depth`を指定すると、階層タグを先頭からその階層までで集計する
+    pub fn generate_bar_chart_with_dsl(
+        workspace: &Workspace,
+        category: &str,
+        depth: Option<usize>,
+    ) -> Result<ChartOutput, String> {
+        let chart_dsl = Self::generate_bar_dsl(workspace, category, depth)?;
         let mermaid = Self::dsl_to_mermaid_bar(&chart_dsl);
         
         Ok(ChartOutput {
@@ -401,8 +415,9 @@ impl AnalysisService {
         filter_date_field: Option<&str>,
         start_date: Option<&str>,
         end_date: Option<&str>,
+        depth: Option<usize>,
     ) -> Result<ChartOutput, String> {
-        let chart_dsl = Self::generate_pie_dsl_filtered(workspace, category, filter_date_field, start_date, end_date)?;
+        let chart_dsl = Self::generate_pie_dsl_filtered(workspace, category, filter_date_field, start_date, end_date, depth)?;
         let mermaid = Self::dsl_to_mermaid_pie(&chart_dsl);
 
         Ok(ChartOutput {
@@ -418,8 +433,9 @@ impl AnalysisService {
         filter_date_field: Option<&str>,
         start_date: Option<&str>,
         end_date: Option<&str>,
+        depth: Option<usize>,
     ) -> Result<ChartOutput, String> {
-        let chart_dsl = Self::generate_bar_dsl_filtered(workspace, category, filter_date_field, start_date, end_date)?;
+        let chart_dsl = Self::generate_bar_dsl_filtered(workspace, category, filter_date_field, start_date, end_date, depth)?;
         let mermaid = Self::dsl_to_mermaid_bar(&chart_dsl);
 
         Ok(ChartOutput {
@@ -701,16 +717,14 @@ impl AnalysisService {
         Ok(GanttChart { title: "Task Schedule".to_string(), date_format: "YYYY-MM-DD".to_string(), sections })
     }
 
-    fn generate_pie_dsl(workspace: &Workspace, category: &str) -> Result<PieChart, String> {
+    fn generate_pie_dsl(workspace: &Workspace, category: &str, depth: Option<usize>) -> Result<PieChart, String> {
         let category_data = workspace.tag_index.categories
             .get(category)
             .ok_or_else(|| format!("カテゴリ '{}' が見つかりませんでした。", category))?;
 
-        let categories: Vec<CategoryCount> = category_data.values.iter()
-            .map(|(label, count)| CategoryCount {
-                label: label.clone(),
-                count: *count,
-            })
+        let counts = Self::roll_up_counts(category_data.values.clone(), workspace, depth);
+        let categories: Vec<CategoryCount> = counts.into_iter()
+            .map(|(label, count)| CategoryCount { label, count })
             .collect();
 
         Ok(PieChart {
@@ -726,26 +740,18 @@ impl AnalysisService {
         filter_date_field: Option<&str>,
         start_date: Option<&str>,
         end_date: Option<&str>,
+        depth: Option<usize>,
     ) -> Result<PieChart, String> {
         let tasks = Self::iter_tasks_filtered(workspace, filter_date_field, start_date, end_date)?;
         let mut counts: HashMap<String, usize> = HashMap::new();
 
         for task in tasks {
-            if let Some(val) = task.front_matter.tags.get(category) {
-                match val {
-                    crate::models::TagValue::String(s) => {
-                        *counts.entry(s.clone()).or_insert(0) += 1;
-                    }
-                    crate::models::TagValue::Array(arr) => {
-                        for s in arr {
-                            *counts.entry(s.clone()).or_insert(0) += 1;
-                        }
-                    }
-                    _ => {}
-                }
+            for value in Self::canonical_tag_values(workspace, task, category) {
+                *counts.entry(value).or_insert(0) += 1;
             }
         }
 
+        let counts = Self::roll_up_counts(counts, workspace, depth);
         if counts.is_empty() {
             // 既存動作に合わせて空配列でもOKとする
             return Ok(PieChart { title: format!("{} Distribution", category), categories: vec![] });
@@ -777,23 +783,7 @@ impl AnalysisService {
         }
 
         // メトリックに基づいて値を計算
-        let mut series: Vec<TimeSeriesPoint> = date_values
-            .into_iter()
-            .map(|(date, tasks)| {
-                let value = if let Some(metric) = metric {
-                    metric.evaluate(&tasks)
-                } else {
-                    // デフォルト: タスク数
-                    tasks.len() as f64
-                };
-                TimeSeriesPoint {
-                    date: date.format("%Y-%m-%d").to_string(),
-                    value,
-                }
-            })
-            .collect();
-
-        series.sort_by_key(|p| p.date.clone());
+        let (series, currency_series) = Self::metric_series(workspace, date_values, metric);
 
         // タイトルを生成（メトリック名に基づく）
         let title = if let Some(metric) = metric {
@@ -806,6 +796,7 @@ impl AnalysisService {
             title,
             y_axis_label: y_axis_label.unwrap_or("Count").to_string(),
             series,
+            currency_series,
         })
     }
 
@@ -835,23 +826,7 @@ impl AnalysisService {
         }
 
         // メトリックに基づいて値を計算
-        let mut series: Vec<TimeSeriesPoint> = date_values
-            .into_iter()
-            .map(|(date, tasks)| {
-                let value = if let Some(metric) = metric {
-                    metric.evaluate(&tasks)
-                } else {
-                    // デフォルト: タスク数
-                    tasks.len() as f64
-                };
-                TimeSeriesPoint { 
-                    date: date.format("%Y-%m-%d").to_string(), 
-                    value 
-                }
-            })
-            .collect();
-
-        series.sort_by_key(|p| p.date.clone());
+        let (series, currency_series) = Self::metric_series(workspace, date_values, metric);
 
         // タイトルを生成（メトリック名に基づく）
         let title = if let Some(metric) = metric {
@@ -863,17 +838,75 @@ impl AnalysisService {
         Ok(LineChart { 
             title, 
             y_axis_label: y_axis_label.unwrap_or("Count").to_string(),
-            series 
+            series,
+            currency_series,
         })
     }
 
-    fn generate_bar_dsl(workspace: &Workspace, category: &str) -> Result<BarChart, String> {
+    /// 日付ごとのタスクからメトリックの系列を計算
+    ///
+    /// メトリックの集計元が通貨タグの場合は、通貨コード別の系列も返す
+    /// （異なる通貨の金額は合算しない）。
+    fn metric_series(
+        workspace: &Workspace,
+        date_values: HashMap<NaiveDate, Vec<&Task>>,
+        metric: Option<&Metric>,
+    ) -> (Vec<TimeSeriesPoint>, BTreeMap<String, Vec<TimeSeriesPoint>>) {
+        let separator = &workspace.config.tag_separator;
+        let currency_config = metric
+            .and_then(|m| m.source_tag.as_ref())
+            .and_then(|tag| workspace.config.tag_configs.configs.get(tag))
+            .filter(|config| matches!(config.tag_type, TagType::Currency));
+
+        let mut dates: Vec<_> = date_values.into_iter().collect();
+        dates.sort_by_key(|(date, _)| *date);
+
+        let mut series = Vec::new();
+        let mut by_currency: Vec<BTreeMap<String, f64>> = Vec::new();
+        for (date, tasks) in &dates {
+            let value = if let Some(metric) = metric {
+                metric.evaluate(tasks, separator)
+            } else {
+                // デフォルト: タスク数
+                tasks.len() as f64
+            };
+            series.push(TimeSeriesPoint {
+                date: date.format("%Y-%m-%d").to_string(),
+                value,
+            });
+            if let (Some(metric), Some(config)) = (metric, currency_config) {
+                by_currency.push(metric.evaluate_by_currency(tasks, separator, config.currency.as_deref()));
+            }
+        }
+
+        // 日付を揃えるため、その日に金額のない通貨は0とする
+        let mut currency_series: BTreeMap<String, Vec<TimeSeriesPoint>> = BTreeMap::new();
+        for currency in by_currency.iter().flat_map(|values| values.keys()) {
+            if currency_series.contains_key(currency) {
+                continue;
+            }
+            let points = series
+                .iter()
+                .zip(&by_currency)
+                .map(|(point, values)| TimeSeriesPoint {
+                    date: point.date.clone(),
+                    value: values.get(currency).copied().unwrap_or(0.0),
+                })
+                .collect();
+            currency_series.insert(currency.clone(), points);
+        }
+
+        (series, currency_series)
+    }
+
+    fn generate_bar_dsl(workspace: &Workspace, category: &str, depth: Option<usize>) -> Result<BarChart, String> {
         let category_data = workspace.tag_index.categories
             .get(category)
             .ok_or_else(|| format!("カテゴリ '{}' が見つかりませんでした。", category))?;
 
-        let x_axis: Vec<String> = category_data.values.keys().cloned().collect();
-        let values: Vec<usize> = category_data.values.values().cloned().collect();
+        let counts = Self::roll_up_counts(category_data.values.clone(), workspace, depth);
+        let x_axis: Vec<String> = counts.keys().cloned().collect();
+        let values: Vec<usize> = x_axis.iter().map(|k| counts[k]).collect();
         let max_value = values.iter().max().cloned().unwrap_or(0);
 
         Ok(BarChart {
@@ -892,22 +925,18 @@ impl AnalysisService {
         filter_date_field: Option<&str>,
         start_date: Option<&str>,
         end_date: Option<&str>,
+        depth: Option<usize>,
     ) -> Result<BarChart, String> {
         let tasks = Self::iter_tasks_filtered(workspace, filter_date_field, start_date, end_date)?;
         let mut counts: HashMap<String, usize> = HashMap::new();
 
         for task in tasks {
-            if let Some(val) = task.front_matter.tags.get(category) {
-                match val {
-                    crate::models::TagValue::String(s) => { *counts.entry(s.clone()).or_insert(0) += 1; }
-                    crate::models::TagValue::Array(arr) => {
-                        for s in arr { *counts.entry(s.clone()).or_insert(0) += 1; }
-                    }
-                    _ => {}
-                }
+            for value in Self::canonical_tag_values(workspace, task, category) {
+                *counts.entry(value).or_insert(0) += 1;
             }
         }
 
+        let counts = Self::roll_up_counts(counts, workspace, depth);
         let mut x_axis: Vec<String> = counts.keys().cloned().collect();
         x_axis.sort();
         let values: Vec<usize> = x_axis.iter().map(|k| counts.get(k).cloned().unwrap_or(0)).collect();
@@ -916,6 +945,39 @@ impl AnalysisService {
         Ok(BarChart { title: category.to_string(), x_axis, y_axis_label: "Count".to_string(), values, max_value })
     }
 
+    /// タスクの文字列・配列タグの値を、タグ設定の正規化ルールで正規の値にして取得
+    fn canonical_tag_values(workspace: &Workspace, task: &Task, category: &str) -> Vec<String> {
+        match task.tag(category) {
+            Some(value @ (crate::models::TagValue::String(_) | crate::models::TagValue::Array(_))) => {
+                let normalization = workspace
+                    .config
+                    .tag_configs
+                    .configs
+                    .get(category)
+                    .and_then(|config| config.normalization.as_ref());
+                canonical_elements(value, normalization)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// 階層タグの値を先頭から`depth`階層までで集計し直す（Noneならそのまま）
+    fn roll_up_counts(
+        counts: HashMap<String, usize>,
+        workspace: &Workspace,
+        depth: Option<usize>,
+    ) -> HashMap<String, usize> {
+        let Some(depth) = depth else {
+            return counts;
+        };
+        let mut rolled: HashMap<String, usize> = HashMap::new();
+        for (label, count) in counts {
+            let label = truncate_tag_path(&label, &workspace.config.tag_separator, depth);
+            *rolled.entry(label).or_insert(0) += count;
+        }
+        rolled
+    }
+
     /// 任意の日付フィールド + 範囲でタスクをフィルタ
     fn iter_tasks_filtered<'a>(
         workspace: &'a Workspace,
@@ -1007,19 +1069,31 @@ impl AnalysisService {
         lines.push(format!("        {}", date_labels.join(", ")));
         lines.push("    ]".to_string());
 
-        let max_value = dsl.series.iter().map(|p| p.value).fold(0.0f64, f64::max);
+        // 通貨別の系列がある場合は通貨ごとに線を引く
+        let series_list: Vec<&Vec<TimeSeriesPoint>> = if dsl.currency_series.is_empty() {
+            vec![&dsl.series]
+        } else {
+            dsl.currency_series.values().collect()
+        };
+
+        let max_value = series_list.iter()
+            .flat_map(|series| series.iter())
+            .map(|p| p.value)
+            .fold(0.0f64, f64::max);
         let max_int = max_value.ceil() as usize;
         lines.push(format!("    y-axis \"{}\" 0 --> {}", dsl.y_axis_label, max_int + 5));
         
-        let values: Vec<String> = dsl.series.iter().map(|p| {
-            // 整数値の場合は整数として表示、小数の場合は小数点以下1桁まで表示
-            if p.value.fract() == 0.0 {
-                (p.value as usize).to_string()
-            } else {
-                format!("{:.1}", p.value)
-            }
-        }).collect();
-        lines.push(format!("    line [{}]", values.join(", ")));
+        for series in series_list {
+            let values: Vec<String> = series.iter().map(|p| {
+                // 整数値の場合は整数として表示、小数の場合は小数点以下1桁まで表示
+                if p.value.fract() == 0.0 {
+                    (p.value as i64).to_string()
+                } else {
+                    format!("{:.1}", p.value)
+                }
+            }).collect();
+            lines.push(format!("    line [{}]", values.join(", ")));
+        }
 
         lines.join("\n")
     }
@@ -1106,6 +1180,7 @@ mod tests {
             content: "Test task 1".to_string(),
             modified_at: Utc::now(),
             tag_order: None,
+            computed_tags: HashMap::new(),
         };
         
         // テストタスク2: 無効な日付形式
@@ -1120,6 +1195,7 @@ mod tests {
             content: "Test task 2".to_string(),
             modified_at: Utc::now(),
             tag_order: None,
+            computed_tags: HashMap::new(),
         };
         
         workspace.tasks.insert("task-1".to_string(), task1);
@@ -1144,6 +1220,7 @@ mod tests {
             content: "Test task 3".to_string(),
             modified_at: Utc::now(),
             tag_order: None,
+            computed_tags: HashMap::new(),
         };
         
         let mut front_matter4 = FrontMatter::default();
@@ -1158,6 +1235,7 @@ mod tests {
             content: "Test task 4".to_string(),
             modified_at: Utc::now(),
             tag_order: None,
+            computed_tags: HashMap::new(),
         };
         
         workspace.tasks.insert("task-3".to_string(), task3);
@@ -1178,6 +1256,7 @@ mod tests {
             content: "".to_string(),
             modified_at: Utc::now(),
             tag_order: None,
+            computed_tags: HashMap::new(),
         };
         
         // 有効な日付
@@ -1197,6 +1276,7 @@ mod tests {
             content: "".to_string(),
             modified_at: Utc::now(),
             tag_order: None,
+            computed_tags: HashMap::new(),
         };
         
         assert_eq!(
@@ -1252,5 +1332,80 @@ mod tests {
         let error_msg = result.unwrap_err();
         assert!(error_msg.contains("循環依存"));
     }
-}
 
+    #[test]
+    fn test_bar_chart_aggregates_hierarchical_tags_by_depth() {
+        let mut workspace = Workspace::new(PathBuf::from("/test"));
+        for (id, area) in [("a", "area/backend/api"), ("b", "area/backend/db"), ("c", "area/frontend"), ("d", "client")] {
+            let mut front_matter = FrontMatter::default();
+            front_matter.tags.insert("area".to_string(), TagValue::String(area.to_string()));
+            workspace.tag_index.index_task(id, &front_matter.tags);
+            workspace.tasks.insert(id.to_string(), Task {
+                id: id.to_string(),
+                file_path: PathBuf::from(format!("/test/{}.md", id)),
+                front_matter,
+                content: String::new(),
+                modified_at: Utc::now(),
+                tag_order: None,
+                computed_tags: HashMap::new(),
+            });
+        }
+
+        let output = AnalysisService::generate_bar_chart_with_dsl_filtered(&workspace, "area", None, None, None, Some(2)).unwrap();
+        let Chart::Bar(bar) = output.data else { panic!("expected bar chart") };
+        assert_eq!(bar.x_axis, vec!["area/backend", "area/frontend", "client"]);
+        assert_eq!(bar.values, vec![2, 1, 1]);
+
+        let output = AnalysisService::generate_pie_chart_with_dsl(&workspace, "area", Some(1)).unwrap();
+        let Chart::Pie(pie) = output.data else { panic!("expected pie chart") };
+        let area = pie.categories.iter().find(|c| c.label == "area").unwrap();
+        assert_eq!(area.count, 3);
+        assert_eq!(pie.categories.len(), 2);
+    }
+
+    #[test]
+    fn test_line_chart_sums_currency_per_code() {
+        let mut workspace = Workspace::new(PathBuf::from("/test"));
+        let mut cost = crate::models::tag_config::TagConfig::new(TagType::Currency);
+        cost.currency = Some("JPY".to_string());
+        workspace.config.tag_configs.set_config("cost".to_string(), cost);
+        for (id, date, cost) in [
+            ("a", "2025-01-01", TagValue::Number(1000)),
+            ("b", "2025-01-01", TagValue::String("12.50 USD".to_string())),
+            ("c", "2025-01-01", TagValue::String("7.50 USD".to_string())),
+            ("d", "2025-01-02", TagValue::String("500 JPY".to_string())),
+        ] {
+            let mut front_matter = FrontMatter::default();
+            front_matter.tags.insert("due".to_string(), TagValue::String(date.to_string()));
+            front_matter.tags.insert("cost".to_string(), cost);
+            workspace.tasks.insert(id.to_string(), Task {
+                id: id.to_string(),
+                file_path: PathBuf::from(format!("/test/{}.md", id)),
+                front_matter,
+                content: String::new(),
+                modified_at: Utc::now(),
+                tag_order: None,
+                computed_tags: HashMap::new(),
+            });
+        }
+        let metric = Metric {
+            id: "cost".to_string(),
+            name: "Cost".to_string(),
+            calculation_type: crate::models::metric::MetricCalculationType::Sum,
+            source_tag: Some("cost".to_string()),
+            filter_expression: None,
+            is_default: None,
+        };
+
+        let output = AnalysisService::generate_line_chart_with_dsl(&workspace, "due", None, Some(&metric)).unwrap();
+        let Chart::Line(line) = output.data else { panic!("expected line chart") };
+        let values = |currency: &str| -> Vec<f64> {
+            line.currency_series[currency].iter().map(|p| p.value).collect()
+        };
+        assert_eq!(line.currency_series.len(), 2);
+        assert_eq!(values("JPY"), vec![1000.0, 500.0]);
+        assert_eq!(values("USD"), vec![20.0, 0.0]);
+        assert_eq!(output.mermaid.matches("line [").count(), 2);
+    }
+
+}
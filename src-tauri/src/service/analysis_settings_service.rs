@@ -19,7 +19,7 @@ impl AnalysisSettingsService {
     ///
     /// # Returns
     /// * `PathBuf` - 設定ファイルのパス
-    fn settings_path(workspace_path: &PathBuf) -> PathBuf {
+    pub fn settings_path(workspace_path: &PathBuf) -> PathBuf {
         workspace_path.join(".hienmark").join("analysis_settings.json")
     }
 
@@ -0,0 +1,474 @@
+use crate::models::{AssetGcOptions, AssetGcReport, OrphanedAsset};
+use crate::parser::asset_refs::ASSETS_DIR;
+use crate::repository::asset_index_repository::{
+    AssetIndexEntry, AssetReference, SqliteAssetIndexRepository,
+};
+use crate::repository::database::IndexDatabase;
+use crate::repository::task_index_repository::{SqliteTaskIndexRepository, TaskIndexRepository};
+use crate::utils::media::{extension_for_mime_type, MediaInfo};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// アセットのゴミ箱ディレクトリ（ワークスペースルートからの相対パス）
+const TRASH_DIR: &str = ".hienmark/trash/assets";
+
+/// アセットのファイル名に使うハッシュの桁数
+const SHORT_HASH_LEN: usize = 16;
+
+/// アセット管理サービス
+///
+/// 参照情報はインデックス（`asset_references`）を使用するため、
+/// 呼び出し側で事前にインデックスを最新化しておくこと。
+pub struct AssetService;
+
+impl AssetService {
+    /// ファイルをコンテンツアドレス方式でアセットディレクトリに取り込む
+    ///
+    /// ファイル名は内容のSHA-256から決まるため、同じ内容のファイルは1つだけ保存される。
+    /// 既存のアセット（旧形式のファイル名を含む）に同じ内容があればそれを再利用する。
+    ///
+    /// # Arguments
+    /// * `db` - インデックスの書き込み接続（Noneの場合はインデックスを使わずに取り込む）
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    /// * `source_path` - 取り込むファイルのパス
+    /// * `task_id` - アセットを関連付けるタスクID
+    ///
+    /// # Returns
+    /// * `Result<String, io::Error>` - アセットの相対パス（Markdown記法用）
+    pub fn import_asset(
+        db: Option<&IndexDatabase>,
+        workspace_root: &Path,
+        source_path: &Path,
+        task_id: &str,
+    ) -> Result<String, io::Error> {
+        let bytes = fs::read(source_path)?;
+        let content_hash = format!("{:x}", Sha256::digest(&bytes));
+
+        let existing = match db {
+            Some(db) => Self::find_existing_asset(db, workspace_root, &content_hash)?,
+            None => None,
+        };
+
+        let asset_path = match existing {
+            Some(asset_path) => asset_path,
+            None => {
+                let extension = MediaInfo::inspect(&bytes)
+                    .mime_type
+                    .and_then(extension_for_mime_type)
+                    .map(str::to_string)
+                    .or_else(|| {
+                        source_path
+                            .extension()
+                            .and_then(|e| e.to_str())
+                            .map(|e| e.to_ascii_lowercase())
+                    })
+                    .unwrap_or_else(|| "bin".to_string());
+                Self::store_content(workspace_root, &bytes, &content_hash, &extension)?
+            }
+        };
+
+        // インデックスへの登録はベストエフォート（失敗しても取り込み自体は成功扱い）
+        if let Some(db) = db {
+            if let Err(e) = Self::register_asset(db, workspace_root, &asset_path, task_id) {
+                log::warn!("Failed to register asset {} for task {}: {}", asset_path, task_id, e);
+            }
+        }
+
+        Ok(asset_path)
+    }
+
+    /// アセットをインデックスに登録（所有タスクとして関連付け）
+    ///
+    /// タスクがまだインデックスに存在しない場合や、アセットが登録済みの場合は何もしない
+    /// （未登録のタスクは次回のインデックス構築で本文の参照から関連付けられる）。
+    ///
+    /// # Arguments
+    /// * `db` - インデックスデータベース
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    /// * `asset_path` - アセットの相対パス
+    /// * `task_id` - 関連付けるタスクID
+    ///
+    /// # Returns
+    /// * `Result<bool, io::Error>` - 登録を試みた場合はtrue
+    pub fn register_asset(
+        db: &IndexDatabase,
+        workspace_root: &Path,
+        asset_path: &str,
+        task_id: &str,
+    ) -> Result<bool, io::Error> {
+        let task = SqliteTaskIndexRepository::new(db)
+            .get_task(task_id)
+            .map_err(Self::db_error)?;
+        if task.is_none() {
+            return Ok(false);
+        }
+
+        let entry = AssetIndexEntry::from_file(workspace_root, asset_path, task_id)?;
+        SqliteAssetIndexRepository::new(db)
+            .insert_asset_if_absent(&entry)
+            .map_err(Self::db_error)?;
+
+        Ok(true)
+    }
+
+    /// 同じ内容の既存アセットを検索（ファイルが現存し内容が一致するもののみ）
+    fn find_existing_asset(
+        db: &IndexDatabase,
+        workspace_root: &Path,
+        content_hash: &str,
+    ) -> Result<Option<String>, io::Error> {
+        let candidates = SqliteAssetIndexRepository::new(db)
+            .find_by_content_hash(content_hash)
+            .map_err(Self::db_error)?;
+
+        for candidate in candidates {
+            let path = workspace_root.join(&candidate.file_path);
+            // インデックス作成後にファイルが差し替えられている可能性があるため再検証する
+            if path.is_file() && Self::hash_file(&path)? == content_hash {
+                return Ok(Some(candidate.file_path));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// 内容をハッシュ名のファイルとして保存
+    ///
+    /// 短いハッシュ名が別の内容で使われている場合（衝突）は完全なハッシュ名を使う。
+    fn store_content(
+        workspace_root: &Path,
+        bytes: &[u8],
+        content_hash: &str,
+        extension: &str,
+    ) -> Result<String, io::Error> {
+        let assets_dir = workspace_root.join(ASSETS_DIR);
+        fs::create_dir_all(&assets_dir)?;
+
+        for name_len in [SHORT_HASH_LEN, content_hash.len()] {
+            let file_name = format!("{}.{}", &content_hash[..name_len], extension);
+            let asset_path = format!("{}/{}", ASSETS_DIR, file_name);
+            let destination = assets_dir.join(&file_name);
+
+            if destination.is_file() {
+                if Self::hash_file(&destination)? == content_hash {
+                    return Ok(asset_path);
+                }
+                continue;
+            }
+
+            // 一時ファイルに書き込んでからリネーム（書き込み途中のファイルを参照させない）
+            let temp = assets_dir.join(format!(
+                ".{}.{}.tmp",
+                file_name,
+                Utc::now().timestamp_nanos_opt().unwrap_or_default()
+            ));
+            fs::write(&temp, bytes)?;
+            if let Err(e) = fs::rename(&temp, &destination) {
+                let _ = fs::remove_file(&temp);
+                // 同じ内容が同時に保存された場合はそれを使う
+                if destination.is_file() && Self::hash_file(&destination)? == content_hash {
+                    return Ok(asset_path);
+                }
+                return Err(e);
+            }
+            return Ok(asset_path);
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("Asset name collision for content hash {}", content_hash),
+        ))
+    }
+
+    /// ファイル内容のSHA-256を計算
+    fn hash_file(path: &Path) -> Result<String, io::Error> {
+        Ok(format!("{:x}", Sha256::digest(fs::read(path)?)))
+    }
+
+    /// どのタスクからも参照されていないアセットを列挙
+    ///
+    /// # Arguments
+    /// * `db` - インデックスデータベース
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    ///
+    /// # Returns
+    /// * `Result<Vec<OrphanedAsset>, io::Error>` - 孤立アセット（パス順）
+    pub fn find_orphaned_assets(
+        db: &IndexDatabase,
+        workspace_root: &Path,
+    ) -> Result<Vec<OrphanedAsset>, io::Error> {
+        let referenced: HashSet<String> = SqliteAssetIndexRepository::new(db)
+            .list_references()
+            .map_err(Self::db_error)?
+            .into_iter()
+            .map(|reference| reference.asset_path)
+            .collect();
+
+        Ok(Self::scan_asset_files(workspace_root)?
+            .into_iter()
+            .filter(|asset| !referenced.contains(&asset.path))
+            .collect())
+    }
+
+    /// 存在しないアセットへの参照を列挙
+    ///
+    /// # Arguments
+    /// * `db` - インデックスデータベース
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    ///
+    /// # Returns
+    /// * `Result<Vec<AssetReference>, io::Error>` - 参照先が存在しない参照
+    pub fn find_missing_references(
+        db: &IndexDatabase,
+        workspace_root: &Path,
+    ) -> Result<Vec<AssetReference>, io::Error> {
+        Ok(SqliteAssetIndexRepository::new(db)
+            .list_references()
+            .map_err(Self::db_error)?
+            .into_iter()
+            .filter(|reference| !workspace_root.join(&reference.asset_path).is_file())
+            .collect())
+    }
+
+    /// 孤立アセットを削除（またはゴミ箱へ移動）
+    ///
+    /// # Arguments
+    /// * `db` - インデックスデータベース
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    /// * `options` - ドライラン・ゴミ箱・猶予期間の設定
+    ///
+    /// # Returns
+    /// * `Result<AssetGcReport, io::Error>` - 実行結果
+    pub fn collect_garbage(
+        db: &IndexDatabase,
+        workspace_root: &Path,
+        options: &AssetGcOptions,
+    ) -> Result<AssetGcReport, io::Error> {
+        let now = Utc::now();
+        let threshold = now.timestamp() - options.min_age_secs as i64;
+
+        let (targets, skipped_recent): (Vec<OrphanedAsset>, Vec<OrphanedAsset>) =
+            Self::find_orphaned_assets(db, workspace_root)?
+                .into_iter()
+                .partition(|asset| asset.modified_at <= threshold);
+
+        let bytes_freed = targets.iter().map(|asset| asset.file_size).sum();
+        let trash_dir = if options.use_trash {
+            Some(format!("{}/{}", TRASH_DIR, now.format("%Y%m%d-%H%M%S")))
+        } else {
+            None
+        };
+
+        if !options.dry_run {
+            let asset_repo = SqliteAssetIndexRepository::new(db);
+            let assets_prefix = format!("{}/", ASSETS_DIR);
+
+            for asset in &targets {
+                let source = workspace_root.join(&asset.path);
+                match trash_dir {
+                    Some(ref trash_dir) => {
+                        // アセットディレクトリ内の構造を保ったまま移動
+                        let inner = asset.path.strip_prefix(&assets_prefix).unwrap_or(&asset.path);
+                        let destination = workspace_root.join(trash_dir).join(inner);
+                        if let Some(parent) = destination.parent() {
+                            fs::create_dir_all(parent)?;
+                        }
+                        fs::rename(&source, &destination)?;
+                    }
+                    None => fs::remove_file(&source)?,
+                }
+                asset_repo.delete_asset_by_path(&asset.path).map_err(Self::db_error)?;
+            }
+        }
+
+        Ok(AssetGcReport {
+            dry_run: options.dry_run,
+            removed: targets,
+            skipped_recent,
+            bytes_freed,
+            trash_dir: if options.dry_run { None } else { trash_dir },
+        })
+    }
+
+    /// アセットディレクトリ内の全ファイルを列挙（再帰的）
+    fn scan_asset_files(workspace_root: &Path) -> Result<Vec<OrphanedAsset>, io::Error> {
+        let mut assets = Vec::new();
+        let assets_dir = workspace_root.join(ASSETS_DIR);
+        if assets_dir.is_dir() {
+            Self::scan_recursive(&assets_dir, ASSETS_DIR, &mut assets)?;
+        }
+        assets.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(assets)
+    }
+
+    fn scan_recursive(dir: &Path, relative_dir: &str, assets: &mut Vec<OrphanedAsset>) -> Result<(), io::Error> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            // 保存途中の一時ファイルなど隠しファイルは対象外
+            if name.starts_with('.') {
+                continue;
+            }
+            let relative_path = format!("{}/{}", relative_dir, name);
+
+            if path.is_dir() {
+                Self::scan_recursive(&path, &relative_path, assets)?;
+            } else if path.is_file() {
+                let metadata = entry.metadata()?;
+                let modified_at = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs() as i64)
+                    .unwrap_or(0);
+                assets.push(OrphanedAsset {
+                    path: relative_path,
+                    file_size: metadata.len(),
+                    modified_at,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn db_error(e: rusqlite::Error) -> io::Error {
+        io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::asset_index_repository::AssetIndexRepository;
+    use crate::repository::IndexBuilder;
+    use tempfile::TempDir;
+
+    fn setup_workspace() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join(ASSETS_DIR)).unwrap();
+        fs::write(root.join(ASSETS_DIR).join("used.png"), b"used").unwrap();
+        fs::write(root.join(ASSETS_DIR).join("orphan.png"), b"orphan").unwrap();
+        fs::write(
+            root.join("task-1.md"),
+            "---\nstatus: open\n---\n\n![used](.hienmark/assets/used.png)\n![gone](.hienmark/assets/gone.png)\n",
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_find_orphaned_and_missing() {
+        let temp_dir = setup_workspace();
+        let root = temp_dir.path();
+        let mut builder = IndexBuilder::new(root).unwrap();
+        builder.build_from_workspace(root).unwrap();
+        let db = builder.database();
+
+        let orphaned = AssetService::find_orphaned_assets(db, root).unwrap();
+        assert_eq!(orphaned.len(), 1);
+        assert_eq!(orphaned[0].path, ".hienmark/assets/orphan.png");
+
+        let missing = AssetService::find_missing_references(db, root).unwrap();
+        assert_eq!(
+            missing,
+            vec![AssetReference {
+                task_id: "task-1".to_string(),
+                asset_path: ".hienmark/assets/gone.png".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_collect_garbage_dry_run_and_trash() {
+        let temp_dir = setup_workspace();
+        let root = temp_dir.path();
+        let mut builder = IndexBuilder::new(root).unwrap();
+        builder.build_from_workspace(root).unwrap();
+        let db = builder.database();
+
+        // 猶予期間内の孤立アセットは対象外
+        let report = AssetService::collect_garbage(db, root, &AssetGcOptions::default()).unwrap();
+        assert!(report.removed.is_empty());
+        assert_eq!(report.skipped_recent.len(), 1);
+
+        let mut options = AssetGcOptions {
+            min_age_secs: 0,
+            ..AssetGcOptions::default()
+        };
+        let report = AssetService::collect_garbage(db, root, &options).unwrap();
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.bytes_freed, 6);
+        assert!(root.join(ASSETS_DIR).join("orphan.png").exists());
+
+        options.dry_run = false;
+        let report = AssetService::collect_garbage(db, root, &options).unwrap();
+        let trash_dir = report.trash_dir.unwrap();
+        assert!(!root.join(ASSETS_DIR).join("orphan.png").exists());
+        assert!(root.join(trash_dir).join("orphan.png").exists());
+        assert!(root.join(ASSETS_DIR).join("used.png").exists());
+    }
+
+    #[test]
+    fn test_collect_garbage_keeps_assets_referenced_by_tags() {
+        let temp_dir = setup_workspace();
+        let root = temp_dir.path();
+        fs::write(root.join(ASSETS_DIR).join("cover.png"), b"cover").unwrap();
+        fs::write(root.join(ASSETS_DIR).join("spec.pdf"), b"spec").unwrap();
+        fs::write(
+            root.join("task-2.md"),
+            "---\ncover: .hienmark/assets/cover.png\nspec: \"[Spec](.hienmark/assets/spec.pdf)\"\n---\n\nNo links in the body\n",
+        )
+        .unwrap();
+        let mut builder = IndexBuilder::new(root).unwrap();
+        builder.build_from_workspace(root).unwrap();
+        let db = builder.database();
+
+        let options = AssetGcOptions {
+            min_age_secs: 0,
+            dry_run: false,
+            ..AssetGcOptions::default()
+        };
+        let report = AssetService::collect_garbage(db, root, &options).unwrap();
+        assert_eq!(report.removed.len(), 1);
+        assert!(!root.join(ASSETS_DIR).join("orphan.png").exists());
+        assert!(root.join(ASSETS_DIR).join("cover.png").exists());
+        assert!(root.join(ASSETS_DIR).join("spec.pdf").exists());
+    }
+
+    #[test]
+    fn test_import_asset_deduplicates_by_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("task-1.md"), "---\nstatus: open\n---\n\nbody\n").unwrap();
+        IndexBuilder::new(root).unwrap().build_from_workspace(root).unwrap();
+
+        // 拡張子はマジックバイトから決まる
+        let source_dir = TempDir::new().unwrap();
+        let first = source_dir.path().join("screenshot.dat");
+        let second = source_dir.path().join("copy.png");
+        fs::write(&first, b"GIF89a\x02\x00\x03\x00rest").unwrap();
+        fs::write(&second, b"GIF89a\x02\x00\x03\x00rest").unwrap();
+
+        let db = IndexDatabase::new(root).unwrap();
+        let path1 = AssetService::import_asset(Some(&db), root, &first, "task-1").unwrap();
+        let path2 = AssetService::import_asset(Some(&db), root, &second, "task-1").unwrap();
+        assert_eq!(path1, path2);
+        assert!(path1.starts_with(".hienmark/assets/") && path1.ends_with(".gif"));
+        assert_eq!(fs::read_dir(root.join(ASSETS_DIR)).unwrap().count(), 1);
+
+        let entry = SqliteAssetIndexRepository::new(&db)
+            .get_asset_by_path(&path1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.task_id, "task-1");
+        assert_eq!(entry.mime_type.as_deref(), Some("image/gif"));
+        assert_eq!(entry.file_size, Some(14));
+        assert_eq!((entry.width, entry.height), (Some(2), Some(3)));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::models::{CustomFiltersAndSorts, TagSchema, WorkspaceConfig};
+use crate::service::TagSchemaService;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 監視対象のワークスペース設定ファイル
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigFileKind {
+    /// `.hienmark.json`
+    WorkspaceConfig,
+    /// `.hienmark/tag_schema.json`
+    TagSchema,
+    /// `.hienmark/filters_and_sorts.json`
+    FiltersAndSorts,
+    /// `.hienmark/analysis_settings.json`
+    AnalysisSettings,
+}
+
+impl ConfigFileKind {
+    pub const ALL: [ConfigFileKind; 4] = [
+        ConfigFileKind::WorkspaceConfig,
+        ConfigFileKind::TagSchema,
+        ConfigFileKind::FiltersAndSorts,
+        ConfigFileKind::AnalysisSettings,
+    ];
+
+    /// 設定ファイルのパス
+    pub fn path(self, workspace_root: &Path) -> PathBuf {
+        match self {
+            ConfigFileKind::WorkspaceConfig => workspace_root.join(".hienmark.json"),
+            ConfigFileKind::TagSchema => workspace_root.join(".hienmark").join("tag_schema.json"),
+            ConfigFileKind::FiltersAndSorts => workspace_root
+                .join(".hienmark")
+                .join("filters_and_sorts.json"),
+            ConfigFileKind::AnalysisSettings => workspace_root
+                .join(".hienmark")
+                .join("analysis_settings.json"),
+        }
+    }
+
+    /// パスがどの設定ファイルに当たるかを判定
+    pub fn from_path(workspace_root: &Path, path: &Path) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.path(workspace_root) == path)
+    }
+}
+
+/// 検証済みの設定内容
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ConfigContent {
+    WorkspaceConfig(Box<WorkspaceConfig>),
+    FiltersAndSorts(CustomFiltersAndSorts),
+    TagSchema(TagSchema),
+    /// スキーマを持たないJSON設定（分析設定）
+    Json(Value),
+}
+
+/// 設定ファイルの変更（`config-changed`イベントのペイロード）
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigChangedEvent {
+    /// 変更された設定ファイルの種類
+    pub kind: ConfigFileKind,
+
+    /// 設定ファイルのパス
+    pub path: String,
+
+    /// ファイルが削除されたかどうか（削除時はデフォルトの設定に戻る）
+    pub removed: bool,
+
+    /// 検証済みの内容（検証に失敗した場合はNone）
+    pub content: Option<ConfigContent>,
+
+    /// 検証エラー（不正な内容は反映されない）
+    pub error: Option<String>,
+}
+
+/// 外部で変更された設定ファイルの読み込み・検証を担当
+pub struct ConfigReloadService;
+
+impl ConfigReloadService {
+    /// 設定ファイルを読み込んで検証
+    ///
+    /// # Arguments
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    /// * `kind` - 変更された設定ファイルの種類
+    ///
+    /// # Returns
+    /// * `ConfigChangedEvent` - 検証結果（エラーの場合も通知できるようにイベントとして返す）
+    pub fn load_change(workspace_root: &Path, kind: ConfigFileKind) -> ConfigChangedEvent {
+        let path = kind.path(workspace_root);
+        let removed = !path.exists();
+
+        let result = if removed {
+            Ok(Self::default_content(kind))
+        } else {
+            fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+                .and_then(|content| Self::validate(kind, &content))
+        };
+
+        let (content, error) = match result {
+            Ok(content) => (Some(content), None),
+            Err(e) => (None, Some(e)),
+        };
+
+        ConfigChangedEvent {
+            kind,
+            path: path.to_string_lossy().to_string(),
+            removed,
+            content,
+            error,
+        }
+    }
+
+    /// 設定ファイルの内容を検証
+    ///
+    /// # Arguments
+    /// * `kind` - 設定ファイルの種類
+    /// * `content` - ファイルの内容
+    ///
+    /// # Returns
+    /// * `Result<ConfigContent, String>` - 検証済みの内容
+    pub fn validate(kind: ConfigFileKind, content: &str) -> Result<ConfigContent, String> {
+        match kind {
+            ConfigFileKind::WorkspaceConfig => {
+                let config: WorkspaceConfig = serde_json::from_str(content)
+                    .map_err(|e| format!("Failed to parse config: {}", e))?;
+                Ok(ConfigContent::WorkspaceConfig(Box::new(config)))
+            }
+            ConfigFileKind::FiltersAndSorts => {
+                let filters_and_sorts: CustomFiltersAndSorts = serde_json::from_str(content)
+                    .map_err(|e| format!("Failed to parse filters and sorts config: {}", e))?;
+
+                // 保存済みフィルター/ソートは名前で参照されるため重複を許可しない
+                let mut filter_names = HashSet::new();
+                if let Some(filter) = filters_and_sorts
+                    .filters
+                    .iter()
+                    .find(|f| !filter_names.insert(&f.name))
+                {
+                    return Err(format!("Duplicate filter name: {}", filter.name));
+                }
+                let mut sort_names = HashSet::new();
+                if let Some(sort) = filters_and_sorts
+                    .sorts
+                    .iter()
+                    .find(|s| !sort_names.insert(&s.name))
+                {
+                    return Err(format!("Duplicate sort name: {}", sort.name));
+                }
+
+                Ok(ConfigContent::FiltersAndSorts(filters_and_sorts))
+            }
+            ConfigFileKind::TagSchema => {
+                // 旧形式のファイルは次回の読み込み時に移行されるため、ここでは内容だけを検証する
+                let (schema, _) = TagSchemaService::parse_schema(content)?;
+                schema.validate()?;
+                Ok(ConfigContent::TagSchema(schema))
+            }
+            ConfigFileKind::AnalysisSettings => {
+                let value: Value =
+                    serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+                if !value.is_object() {
+                    return Err("Expected a JSON object".to_string());
+                }
+                Ok(ConfigContent::Json(value))
+            }
+        }
+    }
+
+    /// ファイルが存在しない場合の設定（各読み込みコマンドの既定値と同じ）
+    fn default_content(kind: ConfigFileKind) -> ConfigContent {
+        match kind {
+            ConfigFileKind::WorkspaceConfig => ConfigContent::WorkspaceConfig(Box::default()),
+            ConfigFileKind::FiltersAndSorts => {
+                ConfigContent::FiltersAndSorts(CustomFiltersAndSorts {
+                    filters: Vec::new(),
+                    sorts: Vec::new(),
+                })
+            }
+            ConfigFileKind::TagSchema => ConfigContent::TagSchema(TagSchema::default()),
+            ConfigFileKind::AnalysisSettings => {
+                ConfigContent::Json(Value::Object(Default::default()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_config_kind_from_path() {
+        let root = Path::new("/workspace");
+        assert_eq!(
+            ConfigFileKind::from_path(root, Path::new("/workspace/.hienmark/tag_schema.json")),
+            Some(ConfigFileKind::TagSchema)
+        );
+        assert_eq!(
+            ConfigFileKind::from_path(root, Path::new("/workspace/tag_schema.json")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_load_change_validates_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join(".hienmark")).unwrap();
+
+        let path = ConfigFileKind::FiltersAndSorts.path(root);
+        let sort = r#"{"name": "s", "description": null, "sortKeys": [], "handleMissing": "last", "createdAt": "", "updatedAt": ""}"#;
+        fs::write(
+            &path,
+            format!(r#"{{"filters": [], "sorts": [{}, {}]}}"#, sort, sort),
+        )
+        .unwrap();
+        let event = ConfigReloadService::load_change(root, ConfigFileKind::FiltersAndSorts);
+        assert!(event.content.is_none());
+        assert_eq!(event.error.as_deref(), Some("Duplicate sort name: s"));
+
+        fs::write(ConfigFileKind::TagSchema.path(root), "[1, 2]").unwrap();
+        let event = ConfigReloadService::load_change(root, ConfigFileKind::TagSchema);
+        assert!(event.error.is_some());
+
+        fs::write(
+            ConfigFileKind::TagSchema.path(root),
+            r#"{"version": 1, "tags": {"n": {"type": "Number", "options": {"min": 5, "max": 1}}}}"#,
+        )
+        .unwrap();
+        let event = ConfigReloadService::load_change(root, ConfigFileKind::TagSchema);
+        assert_eq!(
+            event.error.as_deref(),
+            Some("n: min (5) is greater than max (1)")
+        );
+
+        // 削除された場合はデフォルトの設定に戻る
+        let event = ConfigReloadService::load_change(root, ConfigFileKind::WorkspaceConfig);
+        assert!(event.removed);
+        assert!(matches!(
+            event.content,
+            Some(ConfigContent::WorkspaceConfig(ref config)) if config.watch_enabled
+        ));
+    }
+}
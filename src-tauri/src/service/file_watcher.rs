@@ -1,20 +1,437 @@
-use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::Path;
-use std::sync::mpsc::{channel, Receiver};
+use crate::models::{FrontMatter, WatchBackend, WorkspaceConfig};
+use crate::parser::FrontMatterParser;
+use crate::service::config_reload_service::ConfigFileKind;
+use crate::service::polling_watcher::PollingWatcher;
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
 /// ファイル変更イベントの種類
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct FileChangeEvent {
-    /// イベントタイプ（created, modified, removed）
+    /// イベントタイプ（created, modified, removed, renamed）
+    #[serde(rename = "eventType")]
     pub event_type: String,
-    /// 変更されたファイルのパス
+    /// 変更されたファイルのパス（renamedの場合は変更後のパス）
     pub path: String,
+    /// 変更前のパス（renamedの場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// 対象タスクのID（ファイル名、拡張子なし）
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+    /// 変更前のタスクID（renamedの場合のみ）
+    #[serde(rename = "previousTaskId", skip_serializing_if = "Option::is_none")]
+    pub previous_task_id: Option<String>,
+    /// Front Matterが変更されたかどうか（作成・削除の場合は常にtrue）
+    #[serde(rename = "frontMatterChanged")]
+    pub front_matter_changed: bool,
+}
+
+impl FileChangeEvent {
+    fn new(event_type: &str, path: &Path, front_matter_changed: bool) -> Self {
+        Self {
+            event_type: event_type.to_string(),
+            path: path.to_string_lossy().to_string(),
+            from: None,
+            task_id: task_id_of(path),
+            previous_task_id: None,
+            front_matter_changed,
+        }
+    }
+
+    fn renamed(from: &Path, to: &Path, front_matter_changed: bool) -> Self {
+        Self {
+            from: Some(from.to_string_lossy().to_string()),
+            previous_task_id: Some(task_id_of(from)),
+            ..Self::new("renamed", to, front_matter_changed)
+        }
+    }
+}
+
+/// まとめられた変更（タスクファイルまたは設定ファイル）
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchEvent {
+    Task(FileChangeEvent),
+    Config(ConfigFileKind),
+}
+
+/// 変更検知のために保持するタスクファイルの状態
+#[derive(Debug, Clone, PartialEq)]
+struct FileSnapshot {
+    /// ファイル内容のハッシュ
+    content_hash: u64,
+    /// パース結果（パースに失敗した場合はNone）
+    front_matter: Option<FrontMatter>,
+}
+
+impl FileSnapshot {
+    fn read(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+
+        Some(Self {
+            content_hash: content_hash(&content),
+            front_matter: FrontMatterParser::parse(&content).ok().map(|(fm, _)| fm),
+        })
+    }
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 設定ファイルの内容のハッシュ（存在しない場合はNone）
+fn config_hash(path: &Path) -> Option<u64> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|content| content_hash(&content))
+}
+
+/// 生のファイルシステムイベントを一定時間まとめ、タスク単位の変更に変換する
+///
+/// 1回の保存で発生する複数のイベントは、最後のイベントから`window`の間
+/// 新しいイベントが来なくなった時点で1件にまとめられる。最終的な種類は
+/// イベントの並びではなく、既知の状態と現在のファイルの有無から判定する。
+struct EventDebouncer {
+    workspace_root: PathBuf,
+    window: Duration,
+    last_event_at: Option<Instant>,
+    /// 窓内に変更されたパス（到着順）
+    touched: Vec<PathBuf>,
+    /// 対になったリネーム（変更前, 変更後）
+    renames: Vec<(PathBuf, PathBuf)>,
+    /// 対になる変更後パスを待っているリネーム元（トラッカー, パス）
+    pending_from: Vec<(Option<usize>, PathBuf)>,
+    /// 既知のタスクファイルの状態
+    known: HashMap<PathBuf, FileSnapshot>,
+    /// 既知の設定ファイルの内容のハッシュ
+    known_configs: HashMap<ConfigFileKind, Option<u64>>,
+}
+
+impl EventDebouncer {
+    fn new(workspace_root: &Path, window: Duration) -> Self {
+        Self {
+            workspace_root: workspace_root.to_path_buf(),
+            window,
+            last_event_at: None,
+            touched: Vec::new(),
+            renames: Vec::new(),
+            pending_from: Vec::new(),
+            known: HashMap::new(),
+            known_configs: HashMap::new(),
+        }
+    }
+
+    /// ワークスペース内の既存タスクファイル・設定ファイルの状態を記録
+    fn load_snapshots(&mut self) {
+        for kind in ConfigFileKind::ALL {
+            let hash = config_hash(&kind.path(&self.workspace_root));
+            self.known_configs.insert(kind, hash);
+        }
+
+        let mut files = Vec::new();
+        if let Err(e) = scan_task_files(&self.workspace_root, &mut files) {
+            log::error!("Failed to scan workspace for watcher: {}", e);
+        }
+        for path in files {
+            if let Some(snapshot) = FileSnapshot::read(&path) {
+                self.known.insert(path, snapshot);
+            }
+        }
+    }
+
+    /// 生のイベントを追加
+    fn push(&mut self, event: Event, now: Instant) {
+        let tracker = event.attrs.tracker();
+        match event.kind {
+            EventKind::Access(_) => return,
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+                self.renames
+                    .push((event.paths[0].clone(), event.paths[1].clone()));
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                for path in event.paths {
+                    self.pending_from.push((tracker, path));
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                for path in event.paths {
+                    self.pair_rename_target(tracker, path);
+                }
+            }
+            // 変更前か変更後かが通知されないバックエンドでは、ファイルの有無で判断する
+            EventKind::Modify(ModifyKind::Name(_)) => {
+                for path in event.paths {
+                    if path.exists() {
+                        self.pair_rename_target(tracker, path);
+                    } else {
+                        self.pending_from.push((tracker, path));
+                    }
+                }
+            }
+            _ => self.touched.extend(event.paths),
+        }
+        self.last_event_at = Some(now);
+    }
+
+    fn pair_rename_target(&mut self, tracker: Option<usize>, to: PathBuf) {
+        let position = match tracker {
+            Some(_) => self.pending_from.iter().position(|(t, _)| *t == tracker),
+            None => (!self.pending_from.is_empty()).then_some(0),
+        };
+        match position {
+            Some(index) => {
+                let (_, from) = self.pending_from.remove(index);
+                self.renames.push((from, to));
+            }
+            None => self.touched.push(to),
+        }
+    }
+
+    /// 次にまとめて通知するまでの残り時間（保留中のイベントがなければNone）
+    fn time_until_flush(&self, now: Instant) -> Option<Duration> {
+        self.last_event_at
+            .map(|last| (last + self.window).saturating_duration_since(now))
+    }
+
+    /// 保留中のイベントをタスク・設定ファイル単位の変更にまとめて取り出す
+    fn flush(&mut self) -> Vec<WatchEvent> {
+        self.last_event_at = None;
+        let mut touched = std::mem::take(&mut self.touched);
+        touched.extend(self.pending_from.drain(..).map(|(_, path)| path));
+
+        let mut changes = Vec::new();
+        let mut handled = HashSet::new();
+
+        // 設定ファイルは一時ファイル経由で保存されることが多いため、リネームの両端も確認する
+        let config_kinds: HashSet<_> = touched
+            .iter()
+            .chain(self.renames.iter().flat_map(|(from, to)| [from, to]))
+            .filter_map(|path| ConfigFileKind::from_path(&self.workspace_root, path))
+            .collect();
+        for kind in ConfigFileKind::ALL {
+            if !config_kinds.contains(&kind) {
+                continue;
+            }
+            let hash = config_hash(&kind.path(&self.workspace_root));
+            if self.known_configs.insert(kind, hash) != Some(hash) {
+                changes.push(WatchEvent::Config(kind));
+            }
+        }
+
+        for (from, to) in std::mem::take(&mut self.renames) {
+            let from_is_task = self.is_task_file(&from);
+            let to_is_task = self.is_task_file(&to);
+            let snapshot = if to_is_task {
+                FileSnapshot::read(&to)
+            } else {
+                None
+            };
+
+            match (from_is_task, snapshot) {
+                (true, Some(snapshot)) => {
+                    let previous = self.known.remove(&from);
+                    let front_matter_changed = previous
+                        .map_or(true, |previous| previous.front_matter != snapshot.front_matter);
+                    changes.push(WatchEvent::Task(FileChangeEvent::renamed(
+                        &from,
+                        &to,
+                        front_matter_changed,
+                    )));
+                    self.known.insert(to.clone(), snapshot);
+                    handled.insert(from);
+                    handled.insert(to);
+                }
+                // タスクファイル以外との間のリネーム（一時ファイル経由の保存など）は
+                // それぞれのパスの作成・削除として扱う
+                _ => {
+                    touched.push(from);
+                    touched.push(to);
+                }
+            }
+        }
+
+        let mut task_changes = Vec::new();
+        // 内容のハッシュ付きの作成・削除（変更一覧での位置, ハッシュ）
+        let mut created = Vec::new();
+        let mut removed = Vec::new();
+
+        for path in touched {
+            if !handled.insert(path.clone()) || !self.is_task_file(&path) {
+                continue;
+            }
+            let current = FileSnapshot::read(&path);
+            let previous = self.known.get(&path);
+
+            let change = match (previous, &current) {
+                (None, Some(current)) => {
+                    created.push((task_changes.len(), current.content_hash));
+                    FileChangeEvent::new("created", &path, true)
+                }
+                (Some(previous), Some(current)) if previous != current => {
+                    let front_matter_changed = previous.front_matter != current.front_matter;
+                    FileChangeEvent::new("modified", &path, front_matter_changed)
+                }
+                (Some(previous), None) => {
+                    removed.push((task_changes.len(), previous.content_hash));
+                    FileChangeEvent::new("removed", &path, true)
+                }
+                // 内容が変わっていない、または窓内で作成・削除された一時ファイル
+                _ => continue,
+            };
+
+            match current {
+                Some(snapshot) => self.known.insert(path, snapshot),
+                None => self.known.remove(&path),
+            };
+            task_changes.push(change);
+        }
+
+        // リネームが通知されないバックエンド（ポーリングなど）では、
+        // 同じ内容のファイルの削除と作成をリネームとみなす
+        let mut merged = HashSet::new();
+        for (created_index, hash) in created {
+            let Some(position) = removed.iter().position(|(_, h)| *h == hash) else {
+                continue;
+            };
+            let (removed_index, _) = removed.remove(position);
+            let from = PathBuf::from(&task_changes[removed_index].path);
+            let to = PathBuf::from(&task_changes[created_index].path);
+            task_changes[created_index] = FileChangeEvent::renamed(&from, &to, false);
+            merged.insert(removed_index);
+        }
+
+        changes.extend(
+            task_changes
+                .into_iter()
+                .enumerate()
+                .filter(|(index, _)| !merged.contains(index))
+                .map(|(_, change)| WatchEvent::Task(change)),
+        );
+        changes
+    }
+
+    /// ワークスペース内の（隠しディレクトリ外の）.mdファイルかどうか
+    fn is_task_file(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.workspace_root) else {
+            return false;
+        };
+        path.extension().is_some_and(|ext| ext == "md")
+            && !relative
+                .components()
+                .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+    }
+}
+
+/// パスからタスクIDを取得（ファイル名、拡張子なし）
+fn task_id_of(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// 隠しディレクトリを除いて.mdファイルを再帰的に収集
+pub(crate) fn scan_task_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if !path
+                .file_name()
+                .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+            {
+                scan_task_files(&path, files)?;
+            }
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 監視の設定
+#[derive(Debug, Clone, Copy)]
+pub struct WatchOptions {
+    /// 連続するイベントをまとめる待機時間
+    pub debounce: Duration,
+    /// 監視の方式
+    pub backend: WatchBackend,
+    /// ポーリング監視の間隔
+    pub poll_interval: Duration,
+}
+
+impl WatchOptions {
+    /// ワークスペース設定から監視の設定を作成
+    pub fn from_config(config: &WorkspaceConfig) -> Self {
+        Self {
+            debounce: Duration::from_millis(config.watch_debounce_ms),
+            backend: config.watch_backend,
+            poll_interval: Duration::from_millis(config.watch_poll_interval_ms.max(100)),
+        }
+    }
+}
+
+/// 他のマシンからの変更がOSのファイル通知に届かないファイルシステムの種別
+#[cfg(any(target_os = "linux", test))]
+const NETWORK_FS_TYPES: &[&str] = &[
+    "nfs", "nfs4", "cifs", "smb3", "smbfs", "9p", "afs", "ceph", "glusterfs",
+    "fuse.sshfs", "fuse.rclone", "davfs", "fuse.davfs2",
+];
+
+/// パスがネットワークファイルシステム上にあるかどうか
+///
+/// マウント情報から判定できるのはLinuxのみで、他のOSでは常にfalseを返す。
+fn is_network_filesystem(path: &Path) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        let Ok(mounts) = fs::read_to_string("/proc/self/mounts") else {
+            return false;
+        };
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        mount_fs_type(&mounts, &path).is_some_and(|fs_type| NETWORK_FS_TYPES.contains(&fs_type))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+/// マウント一覧（`/proc/self/mounts`の形式）から、パスを含む最も深いマウントのファイルシステム種別を取得
+#[cfg(any(target_os = "linux", test))]
+fn mount_fs_type<'a>(mounts: &'a str, path: &Path) -> Option<&'a str> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            // マウントポイントの空白は`\040`にエスケープされている
+            let mount_point = PathBuf::from(fields.nth(1)?.replace("\\040", " "));
+            let fs_type = fields.next()?;
+            Some((mount_point, fs_type))
+        })
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        // 同じ深さでは後からマウントしたものが優先される
+        .max_by_key(|(mount_point, _)| mount_point.components().count())
+        .map(|(_, fs_type)| fs_type)
+}
+
+/// 実行中のウォッチャー（ドロップすると監視が止まる）
+enum ActiveWatcher {
+    Native { _watcher: RecommendedWatcher },
+    Polling { _watcher: PollingWatcher },
 }
 
 /// ファイルウォッチャーサービス
 pub struct FileWatcherService {
-    watcher: Option<RecommendedWatcher>,
+    watcher: Option<ActiveWatcher>,
 }
 
 impl FileWatcherService {
@@ -27,17 +444,69 @@ impl FileWatcherService {
     /// # Arguments
     /// * `app_handle` - Tauriアプリハンドル
     /// * `workspace_path` - 監視するワークスペースのパス
+    /// * `options` - まとめる待機時間・監視の方式
+    /// * `on_change` - 変更ごとに呼ばれるコールバック（タスクの変更は`file-change`の通知後）
     ///
     /// # Returns
-    /// * `Result<Receiver<notify::Result<Event>>, String>` - イベント受信チャネル
-    pub fn start_watching(
+    /// * `Result<(), String>` - 開始結果
+    pub fn start_watching<F>(
         &mut self,
         app_handle: AppHandle,
         workspace_path: &Path,
-    ) -> Result<(), String> {
+        options: WatchOptions,
+        on_change: F,
+    ) -> Result<(), String>
+    where
+        F: FnMut(&WatchEvent) + Send + 'static,
+    {
         // チャネルを作成
         let (tx, rx) = channel();
 
+        let watcher = match options.backend {
+            WatchBackend::Polling => ActiveWatcher::Polling {
+                _watcher: PollingWatcher::start(workspace_path, options.poll_interval, tx),
+            },
+            WatchBackend::Native => ActiveWatcher::Native {
+                _watcher: Self::start_native(workspace_path, tx)?,
+            },
+            WatchBackend::Auto if is_network_filesystem(workspace_path) => {
+                log::info!(
+                    "{} is on a network file system, using polling",
+                    workspace_path.display()
+                );
+                ActiveWatcher::Polling {
+                    _watcher: PollingWatcher::start(workspace_path, options.poll_interval, tx),
+                }
+            }
+            WatchBackend::Auto => match Self::start_native(workspace_path, tx.clone()) {
+                Ok(watcher) => ActiveWatcher::Native { _watcher: watcher },
+                Err(e) => {
+                    log::warn!("Native file watching unavailable ({}), falling back to polling", e);
+                    ActiveWatcher::Polling {
+                        _watcher: PollingWatcher::start(workspace_path, options.poll_interval, tx),
+                    }
+                }
+            },
+        };
+        if let ActiveWatcher::Polling { .. } = watcher {
+            log::info!("Polling {} every {:?}", workspace_path.display(), options.poll_interval);
+        }
+        self.watcher = Some(watcher);
+
+        // バックグラウンドスレッドでイベントを処理
+        let debouncer = EventDebouncer::new(workspace_path, options.debounce);
+        std::thread::spawn(move || {
+            Self::handle_events(app_handle, rx, debouncer, on_change);
+        });
+
+        Ok(())
+    }
+
+    /// OSのファイル通知による監視を開始
+    fn start_native(
+        workspace_path: &Path,
+        tx: Sender<notify::Result<Event>>,
+    ) -> Result<RecommendedWatcher, String> {
         // ウォッチャーを作成
         let mut watcher = RecommendedWatcher::new(
             move |res: Result<Event, notify::Error>| {
@@ -54,59 +523,53 @@ impl FileWatcherService {
             .watch(workspace_path, RecursiveMode::Recursive)
             .map_err(|e| format!("Failed to watch directory: {}", e))?;
 
-        self.watcher = Some(watcher);
-
-        // バックグラウンドスレッドでイベントを処理
-        let workspace_path_clone = workspace_path.to_path_buf();
-        std::thread::spawn(move || {
-            Self::handle_events(app_handle, rx, workspace_path_clone);
-        });
-
-        Ok(())
+        Ok(watcher)
     }
 
-    /// ファイルイベントを処理してフロントエンドに通知
-    fn handle_events(
+    /// ファイルイベントをまとめてフロントエンドに通知
+    ///
+    /// ウォッチャーが停止されて送信側が破棄されるとループを抜ける。
+    fn handle_events<F>(
         app_handle: AppHandle,
         rx: Receiver<notify::Result<Event>>,
-        workspace_path: std::path::PathBuf,
-    ) {
-        for res in rx {
-            match res {
-                Ok(event) => {
-                    // .mdファイルの変更のみを処理
-                    for path in &event.paths {
-                        if let Some(ext) = path.extension() {
-                            if ext == "md" && path.starts_with(&workspace_path) {
-                                let event_type = match event.kind {
-                                    notify::EventKind::Create(_) => "created",
-                                    notify::EventKind::Modify(_) => "modified",
-                                    notify::EventKind::Remove(_) => "removed",
-                                    _ => continue,
-                                };
-
-                                let change_event = FileChangeEvent {
-                                    event_type: event_type.to_string(),
-                                    path: path.to_string_lossy().to_string(),
-                                };
-
-                                // フロントエンドにイベントを送信
-                                if let Err(e) = app_handle.emit("file-change", &change_event) {
-                                    eprintln!("Failed to emit file-change event: {}", e);
-                                }
-
-                                log::info!(
-                                    "File {} detected: {}",
-                                    event_type,
-                                    path.display()
-                                );
-                            }
-                        }
+        mut debouncer: EventDebouncer,
+        mut on_change: F,
+    ) where
+        F: FnMut(&WatchEvent),
+    {
+        debouncer.load_snapshots();
+
+        loop {
+            let received = match debouncer.time_until_flush(Instant::now()) {
+                Some(timeout) => rx.recv_timeout(timeout),
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match received {
+                Ok(Ok(event)) => debouncer.push(event, Instant::now()),
+                Ok(Err(e)) => log::error!("Watch error: {:?}", e),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            if debouncer.time_until_flush(Instant::now()) != Some(Duration::ZERO) {
+                continue;
+            }
+
+            for watch_event in debouncer.flush() {
+                if let WatchEvent::Task(change_event) = &watch_event {
+                    // フロントエンドにイベントを送信
+                    if let Err(e) = app_handle.emit("file-change", change_event) {
+                        eprintln!("Failed to emit file-change event: {}", e);
                     }
+
+                    log::info!(
+                        "File {} detected: {}",
+                        change_event.event_type,
+                        change_event.path
+                    );
                 }
-                Err(e) => {
-                    log::error!("Watch error: {:?}", e);
-                }
+                on_change(&watch_event);
             }
         }
     }
@@ -135,6 +598,38 @@ impl Drop for FileWatcherService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use notify::event::{CreateKind, DataChange, RemoveKind};
+    use tempfile::TempDir;
+
+    const TASK: &str = "---\nstatus: open\n---\n\n# Task\n";
+
+    fn debouncer(temp_dir: &TempDir) -> EventDebouncer {
+        let mut debouncer = EventDebouncer::new(temp_dir.path(), Duration::from_millis(50));
+        debouncer.load_snapshots();
+        debouncer
+    }
+
+    fn event(kind: EventKind, path: &Path) -> Event {
+        Event::new(kind).add_path(path.to_path_buf())
+    }
+
+    fn task_changes(debouncer: &mut EventDebouncer) -> Vec<FileChangeEvent> {
+        debouncer
+            .flush()
+            .into_iter()
+            .filter_map(|event| match event {
+                WatchEvent::Task(change) => Some(change),
+                WatchEvent::Config(_) => None,
+            })
+            .collect()
+    }
+
+    fn modify(path: &Path) -> Event {
+        event(
+            EventKind::Modify(ModifyKind::Data(DataChange::Content)),
+            path,
+        )
+    }
 
     #[test]
     fn test_file_watcher_creation() {
@@ -142,15 +637,219 @@ mod tests {
         assert!(watcher.watcher.is_none());
     }
 
+    #[test]
+    fn test_network_mounts_are_detected() {
+        let mounts = "\
+/dev/sda1 / ext4 rw,relatime 0 0
+server:/export /mnt/share nfs4 rw,relatime 0 0
+//nas/My\\040Docs /mnt/my\\040docs cifs rw 0 0
+/dev/sdb1 /mnt/share/local ext4 rw 0 0
+";
+        let fs_type = |path: &str| mount_fs_type(mounts, Path::new(path));
+        assert_eq!(fs_type("/home/user/tasks"), Some("ext4"));
+        assert_eq!(fs_type("/mnt/share/tasks"), Some("nfs4"));
+        assert_eq!(fs_type("/mnt/my docs/tasks"), Some("cifs"));
+        assert_eq!(fs_type("/mnt/share/local/tasks"), Some("ext4"));
+        assert_eq!(fs_type("/mnt/shared"), Some("ext4"));
+        assert!(NETWORK_FS_TYPES.contains(&"nfs4"));
+    }
+
     #[test]
     fn test_file_change_event_serialization() {
-        let event = FileChangeEvent {
-            event_type: "modified".to_string(),
-            path: "/test/path.md".to_string(),
-        };
+        let event =
+            FileChangeEvent::renamed(Path::new("/test/old.md"), Path::new("/test/path.md"), false);
 
         let json = serde_json::to_string(&event).unwrap();
-        assert!(json.contains("modified"));
-        assert!(json.contains("/test/path.md"));
+        assert!(json.contains("\"eventType\":\"renamed\""));
+        assert!(json.contains("\"from\":\"/test/old.md\""));
+        assert!(json.contains("\"taskId\":\"path\""));
+        assert!(json.contains("\"previousTaskId\":\"old\""));
+        assert!(json.contains("\"frontMatterChanged\":false"));
+    }
+
+    #[test]
+    fn test_burst_is_coalesced_until_window_elapses() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut debouncer = debouncer(&temp_dir);
+        let path = temp_dir.path().join("task.md");
+        let start = Instant::now();
+
+        fs::write(&path, TASK).unwrap();
+        debouncer.push(event(EventKind::Create(CreateKind::File), &path), start);
+        debouncer.push(modify(&path), start);
+        debouncer.push(modify(&path), start + Duration::from_millis(30));
+
+        assert_eq!(
+            debouncer.time_until_flush(start + Duration::from_millis(60)),
+            Some(Duration::from_millis(20))
+        );
+        let changes = task_changes(&mut debouncer);
+        assert_eq!(changes, vec![FileChangeEvent::new("created", &path, true)]);
+        assert_eq!(changes[0].task_id, "task");
+        assert_eq!(debouncer.time_until_flush(start), None);
+    }
+
+    #[test]
+    fn test_modified_reports_front_matter_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("task.md");
+        fs::write(&path, TASK).unwrap();
+        let mut debouncer = debouncer(&temp_dir);
+        let now = Instant::now();
+
+        // 本文のみの変更
+        fs::write(&path, format!("{}\nmore text\n", TASK)).unwrap();
+        debouncer.push(modify(&path), now);
+        assert_eq!(
+            task_changes(&mut debouncer),
+            vec![FileChangeEvent::new("modified", &path, false)]
+        );
+
+        // 内容が変わらない通知は無視される
+        debouncer.push(modify(&path), now);
+        assert!(task_changes(&mut debouncer).is_empty());
+
+        fs::write(&path, "---\nstatus: done\n---\n\n# Task\n").unwrap();
+        debouncer.push(modify(&path), now);
+        assert_eq!(
+            task_changes(&mut debouncer),
+            vec![FileChangeEvent::new("modified", &path, true)]
+        );
+    }
+
+    #[test]
+    fn test_rename_halves_are_paired() {
+        let temp_dir = TempDir::new().unwrap();
+        let old_path = temp_dir.path().join("old.md");
+        let new_path = temp_dir.path().join("new.md");
+        fs::write(&old_path, TASK).unwrap();
+        let mut debouncer = debouncer(&temp_dir);
+        let now = Instant::now();
+
+        fs::rename(&old_path, &new_path).unwrap();
+        debouncer.push(
+            event(
+                EventKind::Modify(ModifyKind::Name(RenameMode::From)),
+                &old_path,
+            )
+            .set_tracker(7),
+            now,
+        );
+        debouncer.push(
+            event(
+                EventKind::Modify(ModifyKind::Name(RenameMode::To)),
+                &new_path,
+            )
+            .set_tracker(7),
+            now,
+        );
+
+        let changes = task_changes(&mut debouncer);
+        assert_eq!(
+            changes,
+            vec![FileChangeEvent::renamed(&old_path, &new_path, false)]
+        );
+        assert_eq!(changes[0].previous_task_id.as_deref(), Some("old"));
+        assert_eq!(changes[0].task_id, "new");
+    }
+
+    #[test]
+    fn test_remove_and_create_with_same_content_is_rename() {
+        let temp_dir = TempDir::new().unwrap();
+        let old_path = temp_dir.path().join("old.md");
+        let new_path = temp_dir.path().join("sub").join("new.md");
+        fs::write(&old_path, TASK).unwrap();
+        let mut debouncer = debouncer(&temp_dir);
+        let now = Instant::now();
+
+        // ポーリングではリネームが削除と作成として届く
+        fs::create_dir_all(new_path.parent().unwrap()).unwrap();
+        fs::rename(&old_path, &new_path).unwrap();
+        debouncer.push(event(EventKind::Remove(RemoveKind::File), &old_path), now);
+        debouncer.push(event(EventKind::Create(CreateKind::File), &new_path), now);
+
+        assert_eq!(
+            task_changes(&mut debouncer),
+            vec![FileChangeEvent::renamed(&old_path, &new_path, false)]
+        );
+    }
+
+    #[test]
+    fn test_atomic_save_and_temporary_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("task.md");
+        let temp_path = temp_dir.path().join("task.md.tmp");
+        fs::write(&path, TASK).unwrap();
+        let mut debouncer = debouncer(&temp_dir);
+        let now = Instant::now();
+
+        // 一時ファイルに書き込んでから置き換える保存方式は更新として扱う
+        fs::write(&temp_path, "---\nstatus: done\n---\n").unwrap();
+        debouncer.push(event(EventKind::Create(CreateKind::File), &temp_path), now);
+        fs::rename(&temp_path, &path).unwrap();
+        debouncer.push(
+            Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+                .add_path(temp_path.clone())
+                .add_path(path.clone()),
+            now,
+        );
+        assert_eq!(
+            task_changes(&mut debouncer),
+            vec![FileChangeEvent::new("modified", &path, true)]
+        );
+
+        // 窓内で作成・削除されたファイルは通知しない
+        let scratch = temp_dir.path().join("scratch.md");
+        debouncer.push(event(EventKind::Create(CreateKind::File), &scratch), now);
+        debouncer.push(event(EventKind::Remove(RemoveKind::File), &scratch), now);
+        assert!(task_changes(&mut debouncer).is_empty());
+
+        fs::remove_file(&path).unwrap();
+        debouncer.push(event(EventKind::Remove(RemoveKind::File), &path), now);
+        assert_eq!(
+            task_changes(&mut debouncer),
+            vec![FileChangeEvent::new("removed", &path, true)]
+        );
+    }
+
+    #[test]
+    fn test_config_file_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join(".hienmark");
+        fs::create_dir_all(&config_dir).unwrap();
+        let schema_path = config_dir.join("tag_schema.json");
+        fs::write(&schema_path, "{}").unwrap();
+        let mut debouncer = debouncer(&temp_dir);
+        let now = Instant::now();
+
+        // 内容が変わらない書き込みは通知しない
+        debouncer.push(modify(&schema_path), now);
+        assert!(debouncer.flush().is_empty());
+
+        // 一時ファイルからの置き換えでも検知する
+        let temp_path = config_dir.join("tag_schema.json.tmp");
+        fs::write(&temp_path, r#"{"status": {}}"#).unwrap();
+        fs::rename(&temp_path, &schema_path).unwrap();
+        debouncer.push(
+            Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+                .add_path(temp_path)
+                .add_path(schema_path.clone()),
+            now,
+        );
+        assert_eq!(
+            debouncer.flush(),
+            vec![WatchEvent::Config(ConfigFileKind::TagSchema)]
+        );
+
+        let config_path = temp_dir.path().join(".hienmark.json");
+        fs::write(&config_path, "{}").unwrap();
+        debouncer.push(
+            event(EventKind::Create(CreateKind::File), &config_path),
+            now,
+        );
+        assert_eq!(
+            debouncer.flush(),
+            vec![WatchEvent::Config(ConfigFileKind::WorkspaceConfig)]
+        );
     }
 }
@@ -1,11 +1,18 @@
 // Business logic services
 pub mod workspace_service;
 pub mod file_watcher;
+pub mod polling_watcher;
 pub mod tag_service;
 pub mod template_service;
 pub mod tag_schema_service;
 pub mod analysis_service;
 pub mod analysis_settings_service;
+pub mod asset_service;
+pub mod task_sync_service;
+pub mod config_reload_service;
+pub mod schema_validator;
+pub mod tag_bundle_service;
+pub mod schema_inference_service;
 
 pub use workspace_service::WorkspaceService;
 pub use file_watcher::FileWatcherService;
@@ -14,3 +21,9 @@ pub use template_service::TemplateService;
 pub use tag_schema_service::TagSchemaService;
 pub use analysis_service::AnalysisService;
 pub use analysis_settings_service::AnalysisSettingsService;
+pub use asset_service::AssetService;
+pub use task_sync_service::TaskSyncService;
+pub use config_reload_service::ConfigReloadService;
+pub use schema_validator::SchemaValidator;
+pub use tag_bundle_service::TagBundleService;
+pub use schema_inference_service::SchemaInferenceService;
@@ -0,0 +1,148 @@
+use crate::service::config_reload_service::ConfigFileKind;
+use crate::service::file_watcher::scan_task_files;
+use notify::event::{CreateKind, DataChange, ModifyKind, RemoveKind};
+use notify::{Event, EventKind};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::time::{Duration, SystemTime};
+
+/// ポーリングで比較するファイルの状態
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// 更新日時とサイズを定期的に比較するウォッチャー
+///
+/// ネットワークドライブやクラウド同期フォルダではOSのファイル通知が届かないため、
+/// ワークスペースを一定間隔で走査し、差分をnotifyと同じ形式のイベントとして送る。
+/// ドロップすると走査スレッドが停止する。
+pub struct PollingWatcher {
+    _stop: Sender<()>,
+}
+
+impl PollingWatcher {
+    /// ポーリングを開始
+    ///
+    /// # Arguments
+    /// * `workspace_root` - 監視するワークスペースのルートディレクトリ
+    /// * `interval` - 走査の間隔
+    /// * `tx` - イベントの送信先
+    ///
+    /// # Returns
+    /// * `PollingWatcher` - 停止用のハンドル
+    pub fn start(
+        workspace_root: &Path,
+        interval: Duration,
+        tx: Sender<notify::Result<Event>>,
+    ) -> Self {
+        let (stop_tx, stop_rx) = channel::<()>();
+        let workspace_root = workspace_root.to_path_buf();
+
+        std::thread::spawn(move || {
+            let mut previous = scan_stamps(&workspace_root);
+
+            // 送信側（ハンドル）がドロップされるとDisconnectedになり終了する
+            while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(interval) {
+                let current = scan_stamps(&workspace_root);
+                for event in diff_stamps(&previous, &current) {
+                    if tx.send(Ok(event)).is_err() {
+                        return;
+                    }
+                }
+                previous = current;
+            }
+        });
+
+        Self { _stop: stop_tx }
+    }
+}
+
+/// タスクファイルと設定ファイルの状態を取得
+fn scan_stamps(workspace_root: &Path) -> HashMap<PathBuf, FileStamp> {
+    let mut paths = Vec::new();
+    if let Err(e) = scan_task_files(workspace_root, &mut paths) {
+        log::error!("Failed to scan workspace for polling: {}", e);
+    }
+    paths.extend(ConfigFileKind::ALL.iter().map(|kind| kind.path(workspace_root)));
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let metadata = fs::metadata(&path).ok()?;
+            let stamp = FileStamp {
+                modified: metadata.modified().ok(),
+                len: metadata.len(),
+            };
+            Some((path, stamp))
+        })
+        .collect()
+}
+
+/// 2回の走査結果の差分をイベントに変換
+fn diff_stamps(
+    previous: &HashMap<PathBuf, FileStamp>,
+    current: &HashMap<PathBuf, FileStamp>,
+) -> Vec<Event> {
+    let mut events = Vec::new();
+
+    for (path, stamp) in current {
+        let kind = match previous.get(path) {
+            None => EventKind::Create(CreateKind::File),
+            Some(old) if old != stamp => EventKind::Modify(ModifyKind::Data(DataChange::Any)),
+            Some(_) => continue,
+        };
+        events.push(Event::new(kind).add_path(path.clone()));
+    }
+    for path in previous.keys().filter(|path| !current.contains_key(*path)) {
+        events.push(Event::new(EventKind::Remove(RemoveKind::File)).add_path(path.clone()));
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_diff_detects_size_and_removal() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let task = root.join("task.md");
+        fs::write(&task, "a").unwrap();
+        fs::create_dir_all(root.join(".hienmark")).unwrap();
+        fs::write(root.join(".hienmark").join("notes.md"), "hidden").unwrap();
+
+        let before = scan_stamps(root);
+        assert_eq!(before.len(), 1);
+
+        // 同じ秒の中の書き込みでもサイズの違いで検知する
+        fs::write(&task, "ab").unwrap();
+        let config = ConfigFileKind::WorkspaceConfig.path(root);
+        fs::write(&config, "{}").unwrap();
+        let after = scan_stamps(root);
+
+        let mut kinds: Vec<_> = diff_stamps(&before, &after)
+            .into_iter()
+            .map(|event| (event.kind, event.paths[0].clone()))
+            .collect();
+        kinds.sort_by(|a, b| a.1.cmp(&b.1));
+        assert_eq!(
+            kinds,
+            vec![
+                (EventKind::Create(CreateKind::File), config),
+                (EventKind::Modify(ModifyKind::Data(DataChange::Any)), task.clone()),
+            ]
+        );
+
+        fs::remove_file(&task).unwrap();
+        let removed = diff_stamps(&after, &scan_stamps(root));
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].kind, EventKind::Remove(RemoveKind::File));
+    }
+}
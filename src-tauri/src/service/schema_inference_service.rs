@@ -0,0 +1,442 @@
+use crate::models::schema_inference::{
+    InferredTag, OutlierKind, SchemaInference, TagOutlier, ValueCount,
+};
+use crate::models::tag_config::{
+    AllowedValueType, CaseFolding, TagConfig, TagType, ValueNormalization,
+};
+use crate::models::tag_schema::{TagDefinition, TagSchema};
+use crate::models::{TagValue, Workspace};
+use crate::service::schema_validator::{as_number, is_empty, type_matches, type_name};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+/// 型を推定するのに必要な、その型に合う値の割合
+const DOMINANT_SHARE: f64 = 0.8;
+
+/// 必須と推定するのに必要な、タグを持つタスクの割合
+const REQUIRED_COVERAGE: f64 = 0.9;
+
+/// 選択型と推定する値の種類の上限
+const MAX_SELECT_OPTIONS: usize = 10;
+
+/// 1度しか使われていない値を外れ値とみなすのに必要な出現回数の合計
+const RARE_VALUE_MIN_OCCURRENCES: usize = 10;
+
+/// 提案に含める値の上限
+const MAX_REPORTED_VALUES: usize = 20;
+
+/// タスクのタグ（タスクID・値）
+type Observation<'a> = (&'a str, &'a TagValue);
+
+/// 既存タスクからタグスキーマを推定するサービス
+pub struct SchemaInferenceService;
+
+impl SchemaInferenceService {
+    /// 全タスクのタグを走査し、タグごとの定義を提案
+    ///
+    /// # Arguments
+    /// * `workspace` - ワークスペース
+    /// * `schema` - 現在のタグスキーマ（定義済みのタグの判定に使う）
+    ///
+    /// # Returns
+    /// * `SchemaInference` - タグごとの提案（型・選択肢・必須・外れ値）
+    pub fn infer(workspace: &Workspace, schema: &TagSchema) -> SchemaInference {
+        let mut task_ids: Vec<&str> = workspace.tasks.keys().map(String::as_str).collect();
+        task_ids.sort();
+
+        // 空の値は未入力として扱う
+        let mut observed: BTreeMap<&str, Vec<Observation>> = BTreeMap::new();
+        for task_id in &task_ids {
+            for (name, value) in &workspace.tasks[*task_id].front_matter.tags {
+                if !is_empty(value) {
+                    observed.entry(name).or_default().push((task_id, value));
+                }
+            }
+        }
+
+        let tags = observed
+            .into_iter()
+            .map(|(name, values)| {
+                Self::infer_tag(name, &values, &task_ids, schema.tags.contains_key(name))
+            })
+            .collect();
+
+        SchemaInference {
+            tasks_scanned: task_ids.len(),
+            tags,
+        }
+    }
+
+    /// 推定した定義をタグスキーマに追加
+    ///
+    /// # Arguments
+    /// * `schema` - 追加先のタグスキーマ
+    /// * `definitions` - タグ名 → 確認済みのタグ定義
+    /// * `overwrite` - 既存の定義を置き換えるか（falseの場合は未定義のタグだけ追加）
+    ///
+    /// # Returns
+    /// * `usize` - 追加・置き換えたタグの数
+    pub fn merge_definitions(
+        schema: &mut TagSchema,
+        definitions: BTreeMap<String, TagDefinition>,
+        overwrite: bool,
+    ) -> usize {
+        let mut merged = 0;
+        for (name, definition) in definitions {
+            if overwrite || !schema.tags.contains_key(&name) {
+                schema.tags.insert(name, definition);
+                merged += 1;
+            }
+        }
+        merged
+    }
+
+    fn infer_tag(
+        name: &str,
+        values: &[Observation],
+        task_ids: &[&str],
+        existing: bool,
+    ) -> InferredTag {
+        let mut config = TagConfig::new(Self::infer_type(values));
+        let mut outliers = Vec::new();
+
+        for (task_id, value) in values {
+            if !type_matches(&config, value) {
+                outliers.push(TagOutlier {
+                    task_id: task_id.to_string(),
+                    kind: OutlierKind::TypeMismatch,
+                    value: Some((*value).clone()),
+                    message: format!("{} is expected to be {}", name, type_name(&config.tag_type)),
+                });
+            }
+        }
+        let matching: Vec<Observation> = values
+            .iter()
+            .filter(|(_, value)| type_matches(&config, value))
+            .copied()
+            .collect();
+
+        match config.tag_type {
+            TagType::String | TagType::MultiSelect => {
+                Self::infer_options(name, &matching, &mut config, &mut outliers)
+            }
+            TagType::Number => Self::find_numeric_outliers(name, &matching, &mut outliers),
+            _ => {}
+        }
+
+        let coverage = if task_ids.is_empty() {
+            0.0
+        } else {
+            values.len() as f64 / task_ids.len() as f64
+        };
+        config.required = coverage >= REQUIRED_COVERAGE;
+        if config.required {
+            for task_id in task_ids {
+                if !values.iter().any(|(id, _)| id == task_id) {
+                    outliers.push(TagOutlier {
+                        task_id: task_id.to_string(),
+                        kind: OutlierKind::Missing,
+                        value: None,
+                        message: format!("{} is missing", name),
+                    });
+                }
+            }
+        }
+        outliers.sort_by(|a, b| a.task_id.cmp(&b.task_id));
+
+        InferredTag {
+            name: name.to_string(),
+            definition: TagDefinition::from(&config),
+            coverage,
+            task_count: values.len(),
+            values: value_counts(values)
+                .into_iter()
+                .take(MAX_REPORTED_VALUES)
+                .collect(),
+            outliers,
+            existing,
+        }
+    }
+
+    /// 値の大半が合う型を推定（どれにも当てはまらなければ文字列）
+    fn infer_type(values: &[Observation]) -> TagType {
+        let share = |tag_type: &TagType| {
+            let config = TagConfig::new(tag_type.clone());
+            let matched = values.iter().filter(|(_, value)| type_matches(&config, value)).count();
+            matched as f64 / values.len() as f64
+        };
+
+        // 時刻付きの値は日付としても解釈できるため、日時を先に確認する
+        let scalar_types = [
+            TagType::Boolean,
+            TagType::Number,
+            TagType::Datetime,
+            TagType::Date,
+            TagType::Currency,
+            TagType::Hyperlink,
+        ];
+        if let Some(tag_type) = scalar_types.iter().find(|t| share(t) >= DOMINANT_SHARE) {
+            return tag_type.clone();
+        }
+        let has_arrays = values.iter().any(|(_, value)| matches!(value, TagValue::Array(_)));
+        if has_arrays && share(&TagType::MultiSelect) >= DOMINANT_SHARE {
+            return TagType::MultiSelect;
+        }
+        TagType::String
+    }
+
+    /// 値の種類が少なければ選択肢を提案し、表記ゆれと1度しか使われていない値を報告
+    fn infer_options(
+        name: &str,
+        values: &[Observation],
+        config: &mut TagConfig,
+        outliers: &mut Vec<TagOutlier>,
+    ) {
+        // 大文字・小文字を区別せずにまとめ、最も多い表記を正規の値とする
+        let mut spellings: BTreeMap<String, BTreeMap<&str, usize>> = BTreeMap::new();
+        for (_, value) in values {
+            for element in elements(value) {
+                *spellings
+                    .entry(element.to_lowercase())
+                    .or_default()
+                    .entry(element)
+                    .or_insert(0) += 1;
+            }
+        }
+        let canonical: HashMap<&str, (&str, usize)> = spellings
+            .iter()
+            .map(|(folded, variants)| {
+                let (spelling, _) = variants
+                    .iter()
+                    .max_by_key(|(spelling, count)| (**count, Reverse(**spelling)))
+                    .unwrap();
+                (folded.as_str(), (*spelling, variants.values().sum()))
+            })
+            .collect();
+
+        let occurrences: usize = canonical.values().map(|(_, count)| count).sum();
+        let is_rare = |count: usize| occurrences >= RARE_VALUE_MIN_OCCURRENCES && count == 1;
+        let mut options: Vec<(&str, usize)> = canonical
+            .values()
+            .filter(|(_, count)| !is_rare(*count))
+            .copied()
+            .collect();
+        options.sort_by_key(|(value, count)| (Reverse(*count), *value));
+        let rare = canonical.values().filter(|(_, count)| is_rare(*count)).count();
+
+        let is_select = !options.is_empty()
+            && options.len() <= MAX_SELECT_OPTIONS
+            && occurrences >= options.len() * 2
+            && rare * 10 <= occurrences;
+        if !is_select {
+            return;
+        }
+
+        if matches!(config.tag_type, TagType::String) {
+            config.tag_type = TagType::Select;
+        }
+        config.allowed_value_type = Some(AllowedValueType::List(
+            options.iter().map(|(value, _)| value.to_string()).collect(),
+        ));
+
+        let mut has_variants = false;
+        for (task_id, value) in values {
+            for element in elements(value) {
+                let (spelling, count) = canonical[element.to_lowercase().as_str()];
+                let (kind, message) = if is_rare(count) {
+                    (OutlierKind::RareValue, format!("{} is used only once in {}", element, name))
+                } else if element != spelling {
+                    has_variants = true;
+                    (
+                        OutlierKind::CaseVariant,
+                        format!("{} differs only in case from {}", element, spelling),
+                    )
+                } else {
+                    continue;
+                };
+                outliers.push(TagOutlier {
+                    task_id: task_id.to_string(),
+                    kind,
+                    value: Some((*value).clone()),
+                    message,
+                });
+            }
+        }
+
+        // 表記ゆれは大文字・小文字を区別しない正規化でまとめられるようにする
+        if has_variants {
+            config.normalization = Some(ValueNormalization {
+                synonyms: options
+                    .iter()
+                    .map(|(value, _)| (value.to_string(), Vec::new()))
+                    .collect(),
+                case_folding: CaseFolding::Insensitive,
+            });
+        }
+    }
+
+    /// 四分位範囲から大きく外れた数値を報告
+    fn find_numeric_outliers(name: &str, values: &[Observation], outliers: &mut Vec<TagOutlier>) {
+        let mut numbers: Vec<f64> = values.iter().filter_map(|(_, value)| as_number(value)).collect();
+        if numbers.len() < 4 {
+            return;
+        }
+        numbers.sort_by(f64::total_cmp);
+        let q1 = numbers[numbers.len() / 4];
+        let q3 = numbers[numbers.len() * 3 / 4];
+        let iqr = q3 - q1;
+        if iqr <= 0.0 {
+            return;
+        }
+        let (low, high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        for (task_id, value) in values {
+            let Some(number) = as_number(value) else {
+                continue;
+            };
+            if number < low || number > high {
+                outliers.push(TagOutlier {
+                    task_id: task_id.to_string(),
+                    kind: OutlierKind::OutOfRange,
+                    value: Some((*value).clone()),
+                    message: format!("{} is far outside the usual range of {} ({} to {})", number, name, q1, q3),
+                });
+            }
+        }
+    }
+}
+
+/// 値を要素ごとの文字列に分解（配列以外は1要素）
+fn elements(value: &TagValue) -> Vec<&str> {
+    match value {
+        TagValue::Array(items) => items.iter().map(String::as_str).collect(),
+        TagValue::String(s) => vec![s.as_str()],
+        _ => Vec::new(),
+    }
+}
+
+/// 値（配列は要素ごと）の出現回数（多い順、同数は値の順）
+fn value_counts(values: &[Observation]) -> Vec<ValueCount> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for (_, value) in values {
+        let texts = match value {
+            TagValue::Array(items) => items.clone(),
+            other => vec![other.to_string_value()],
+        };
+        for text in texts {
+            *counts.entry(text).or_insert(0) += 1;
+        }
+    }
+    let mut counts: Vec<ValueCount> = counts
+        .into_iter()
+        .map(|(value, count)| ValueCount { value, count })
+        .collect();
+    counts.sort_by_key(|c| Reverse(c.count));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tag_schema::TagAttribute;
+    use crate::models::{FrontMatter, Task};
+    use std::path::PathBuf;
+
+    fn workspace(rows: &[&[(&str, TagValue)]]) -> Workspace {
+        let mut workspace = Workspace::new(PathBuf::from("/test"));
+        for (i, tags) in rows.iter().enumerate() {
+            let id = format!("task{:02}", i);
+            workspace.tasks.insert(id.clone(), Task {
+                file_path: PathBuf::from(format!("/test/{}.md", id)),
+                id,
+                front_matter: FrontMatter {
+                    tags: tags.iter().map(|(k, v)| (k.to_string(), v.clone())).collect(),
+                },
+                content: String::new(),
+                modified_at: chrono::Utc::now(),
+                tag_order: None,
+                computed_tags: HashMap::new(),
+            });
+        }
+        workspace
+    }
+
+    #[test]
+    fn test_infer_types_options_and_outliers() {
+        let text = |s: &str| TagValue::String(s.to_string());
+        let statuses = ["done", "open", "done", "Done", "open", "done", "open", "done", "done", "open"];
+        let estimates = [
+            TagValue::Number(1),
+            TagValue::Number(2),
+            TagValue::Number(2),
+            TagValue::Number(3),
+            TagValue::Number(2),
+            TagValue::Number(1),
+            TagValue::Number(3),
+            TagValue::Number(2),
+            TagValue::Number(100),
+            text("large"),
+        ];
+        let notes: Vec<String> = (0..10).map(|i| format!("note {}", i)).collect();
+        let rows: Vec<Vec<(&str, TagValue)>> = (0..10)
+            .map(|i| {
+                let mut row = vec![
+                    ("status", text(statuses[i])),
+                    ("estimate", estimates[i].clone()),
+                    ("note", text(&notes[i])),
+                ];
+                if i < 8 {
+                    row.push(("due", text(&format!("2025-01-{:02}", i + 1))));
+                }
+                row
+            })
+            .collect();
+        let rows: Vec<&[(&str, TagValue)]> = rows.iter().map(Vec::as_slice).collect();
+
+        let mut schema = TagSchema::default();
+        schema.tags.insert("note".to_string(), TagDefinition::from(&TagConfig::new(TagType::String)));
+        let inference = SchemaInferenceService::infer(&workspace(&rows), &schema);
+        assert_eq!(inference.tasks_scanned, 10);
+        let tag = |name: &str| inference.tags.iter().find(|t| t.name == name).unwrap();
+        let kinds = |name: &str| tag(name).outliers.iter().map(|o| (o.task_id.as_str(), o.kind)).collect::<Vec<_>>();
+
+        // 値の種類が少ない文字列は選択型になり、表記ゆれは正規化で吸収する
+        let status = tag("status");
+        let TagAttribute::Select(options) = &status.definition.attribute else {
+            panic!("expected select: {:?}", status.definition.attribute);
+        };
+        assert_eq!(options.options_list, vec!["done", "open"]);
+        assert!(status.definition.required);
+        assert_eq!(
+            status.definition.normalization.as_ref().map(|n| n.case_folding),
+            Some(CaseFolding::Insensitive)
+        );
+        assert_eq!(kinds("status"), vec![("task03", OutlierKind::CaseVariant)]);
+        assert_eq!(status.values[0], ValueCount { value: "done".to_string(), count: 5 });
+
+        let estimate = tag("estimate");
+        assert!(matches!(estimate.definition.attribute, TagAttribute::Number(_)));
+        assert_eq!(
+            kinds("estimate"),
+            vec![("task08", OutlierKind::OutOfRange), ("task09", OutlierKind::TypeMismatch)]
+        );
+
+        let due = tag("due");
+        assert!(matches!(due.definition.attribute, TagAttribute::Datetime(_)));
+        assert_eq!((due.coverage, due.definition.required), (0.8, false));
+
+        let note = tag("note");
+        assert!(matches!(note.definition.attribute, TagAttribute::String(_)));
+        assert!(note.outliers.is_empty() && note.existing);
+
+        // 既存の定義は置き換えを指定した場合だけ上書きする
+        let definitions: BTreeMap<String, TagDefinition> = inference
+            .tags
+            .iter()
+            .map(|t| (t.name.clone(), t.definition.clone()))
+            .collect();
+        assert_eq!(SchemaInferenceService::merge_definitions(&mut schema, definitions.clone(), false), 3);
+        assert_eq!(SchemaInferenceService::merge_definitions(&mut schema, definitions, true), 4);
+        assert!(schema.validate().is_ok());
+    }
+}
@@ -0,0 +1,652 @@
+use crate::models::tag_config::{AllowedValueType, TagConfig, TagType};
+use crate::models::typed_value::{is_url, CurrencyAmount, Hyperlink};
+use crate::models::validation::{Severity, TagViolation, ValidationReport, ViolationKind};
+use crate::models::{FrontMatter, TagValue, Workspace, WorkspaceConfig};
+use crate::parser::asset_refs::ASSETS_DIR;
+use crate::parser::AssetReferenceParser;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// タスクのFront Matterを`TagConfigCollection`とタグ固定モードの設定に照らして検証する
+///
+/// パターンは作成時に一度だけコンパイルする。違反の重大度はタグ固定モードなら
+/// Error（保存を拒否）、そうでなければWarningになる。
+pub struct SchemaValidator<'a> {
+    config: &'a WorkspaceConfig,
+    patterns: HashMap<&'a str, Regex>,
+    schema_errors: Vec<String>,
+    workspace_root: Option<&'a Path>,
+}
+
+impl<'a> SchemaValidator<'a> {
+    /// ワークスペース設定から検証器を作成
+    pub fn new(config: &'a WorkspaceConfig) -> Self {
+        let mut patterns = HashMap::new();
+        let mut schema_errors = Vec::new();
+
+        for (category, tag_config) in &config.tag_configs.configs {
+            if let Some(AllowedValueType::Pattern(pattern)) = &tag_config.allowed_value_type {
+                // 部分一致ではなく値全体に一致させる
+                match Regex::new(&format!("^(?:{})$", pattern)) {
+                    Ok(regex) => {
+                        patterns.insert(category.as_str(), regex);
+                    }
+                    Err(e) => {
+                        schema_errors.push(format!("Invalid pattern for {}: {}", category, e))
+                    }
+                }
+            }
+        }
+        schema_errors.extend(config.validation_rules.errors().iter().cloned());
+        schema_errors.sort();
+
+        Self {
+            config,
+            patterns,
+            schema_errors,
+            workspace_root: None,
+        }
+    }
+
+    /// 画像タグの参照先の存在確認に使うワークスペースのルートを設定
+    pub fn with_workspace_root(mut self, workspace_root: &'a Path) -> Self {
+        self.workspace_root = Some(workspace_root);
+        self
+    }
+
+    /// タグ設定自体の問題
+    pub fn schema_errors(&self) -> &[String] {
+        &self.schema_errors
+    }
+
+    fn severity(&self) -> Severity {
+        if self.config.strict_tag_mode {
+            Severity::Error
+        } else {
+            Severity::Warning
+        }
+    }
+
+    /// 1タスク分のFront Matterを検証
+    ///
+    /// # Arguments
+    /// * `task_id` - タスクID
+    /// * `front_matter` - 検証するFront Matter
+    ///
+    /// # Returns
+    /// * `Vec<TagViolation>` - 検出された違反（カテゴリ名順）
+    pub fn validate_task(&self, task_id: &str, front_matter: &FrontMatter) -> Vec<TagViolation> {
+        let mut violations = Vec::new();
+        let violation =
+            |category: &str, kind, value: Option<&TagValue>, message: String| TagViolation {
+                task_id: task_id.to_string(),
+                category: category.to_string(),
+                kind,
+                severity: self.severity(),
+                value: value.cloned(),
+                message,
+                rule: None,
+            };
+
+        let mut categories: Vec<_> = self.config.tag_configs.configs.iter().collect();
+        categories.sort_by_key(|(name, _)| name.as_str());
+
+        for (category, tag_config) in categories {
+            let value = front_matter.tags.get(category);
+            let Some(value) = value.filter(|v| !is_empty(v)) else {
+                if tag_config.required {
+                    violations.push(violation(
+                        category,
+                        ViolationKind::MissingRequired,
+                        value,
+                        format!("{} is required", category),
+                    ));
+                }
+                continue;
+            };
+
+            if !type_matches(tag_config, value) {
+                violations.push(violation(
+                    category,
+                    ViolationKind::TypeMismatch,
+                    Some(value),
+                    format!("{} must be {}", category, type_name(&tag_config.tag_type)),
+                ));
+                continue;
+            }
+
+            if let (TagType::Image, TagValue::String(path)) = (&tag_config.tag_type, value) {
+                if let Some(message) = self.check_image(path) {
+                    violations.push(violation(
+                        category,
+                        ViolationKind::MissingAsset,
+                        Some(value),
+                        format!("{} {}", category, message),
+                    ));
+                }
+            }
+
+            match &tag_config.allowed_value_type {
+                Some(AllowedValueType::List(options)) => {
+                    // 同義語は正規の値として扱う（書き換えは`normalize_tags`で行う）
+                    let normalization = tag_config.normalization.as_ref();
+                    let invalid: Vec<_> = elements(value)
+                        .into_iter()
+                        .filter(|element| {
+                            let canonical = normalization.map(|rules| rules.canonical(element));
+                            !options.contains(canonical.as_ref().unwrap_or(element))
+                        })
+                        .collect();
+                    if !invalid.is_empty() {
+                        violations.push(violation(
+                            category,
+                            ViolationKind::NotInList,
+                            Some(value),
+                            format!(
+                                "{} has values not in the list: {}",
+                                category,
+                                invalid.join(", ")
+                            ),
+                        ));
+                    }
+                }
+                Some(AllowedValueType::Pattern(pattern)) => {
+                    let Some(regex) = self.patterns.get(category.as_str()) else {
+                        continue;
+                    };
+                    let invalid: Vec<_> = elements(value)
+                        .into_iter()
+                        .filter(|element| !regex.is_match(element))
+                        .collect();
+                    if !invalid.is_empty() {
+                        violations.push(violation(
+                            category,
+                            ViolationKind::PatternMismatch,
+                            Some(value),
+                            format!(
+                                "{} does not match {}: {}",
+                                category,
+                                pattern,
+                                invalid.join(", ")
+                            ),
+                        ));
+                    }
+                }
+                Some(AllowedValueType::Range { min, max }) => match as_amount(tag_config, value) {
+                    Some(number) if number < *min || number > *max => {
+                        violations.push(violation(
+                            category,
+                            ViolationKind::OutOfRange,
+                            Some(value),
+                            format!("{} must be between {} and {}", category, min, max),
+                        ));
+                    }
+                    Some(_) => {}
+                    None => violations.push(violation(
+                        category,
+                        ViolationKind::TypeMismatch,
+                        Some(value),
+                        format!("{} must be a number", category),
+                    )),
+                },
+                Some(AllowedValueType::DirectInput) | None => {}
+            }
+        }
+
+        // 複数のタグにまたがる検証規則（計算フィールドの値も参照できる）
+        let today = Local::now().date_naive();
+        let mut tags = front_matter.tags.clone();
+        tags.extend(self.config.computed_fields.evaluate(&front_matter.tags, today));
+        for failure in self.config.validation_rules.evaluate(&tags, today) {
+            violations.push(TagViolation {
+                task_id: task_id.to_string(),
+                category: failure.category,
+                kind: ViolationKind::RuleViolation,
+                severity: failure.rule.severity_for(self.config.strict_tag_mode),
+                value: failure.value,
+                message: failure.message,
+                rule: Some(failure.rule.id.clone()),
+            });
+        }
+
+        let mut read_only: Vec<_> = self
+            .config
+            .computed_fields
+            .names()
+            .filter_map(|name| front_matter.tags.get_key_value(name))
+            .collect();
+        read_only.sort_by_key(|(category, _)| category.as_str());
+        for (category, value) in read_only {
+            violations.push(violation(
+                category,
+                ViolationKind::ReadOnly,
+                Some(value),
+                format!("{} is a computed field and cannot be set", category),
+            ));
+        }
+
+        if self.config.strict_tag_mode {
+            // タグ設定のあるカテゴリも許可されたカテゴリとして扱う
+            let allowed: HashSet<&str> = self
+                .config
+                .allowed_categories
+                .iter()
+                .map(String::as_str)
+                .chain(self.config.tag_configs.configs.keys().map(String::as_str))
+                .chain(self.config.computed_fields.names())
+                .collect();
+
+            let mut unknown: Vec<_> = front_matter
+                .tags
+                .iter()
+                .filter(|(category, _)| !allowed.contains(category.as_str()))
+                .collect();
+            unknown.sort_by_key(|(category, _)| category.as_str());
+
+            for (category, value) in unknown {
+                violations.push(violation(
+                    category,
+                    ViolationKind::UnknownCategory,
+                    Some(value),
+                    format!("{} is not an allowed category", category),
+                ));
+            }
+        }
+
+        violations
+    }
+
+    /// 画像のパスがアセットディレクトリ内の既存のファイルを指しているか確認
+    ///
+    /// # Returns
+    /// * `Option<String>` - 問題がある場合のメッセージ（カテゴリ名に続く部分）
+    fn check_image(&self, path: &str) -> Option<String> {
+        let Some(asset_path) = AssetReferenceParser::resolve(path.trim(), "") else {
+            return Some(format!("must point to a file in {}: {}", ASSETS_DIR, path));
+        };
+        match self.workspace_root {
+            Some(root) if !root.join(&asset_path).is_file() => {
+                Some(format!("refers to a missing asset: {}", asset_path))
+            }
+            _ => None,
+        }
+    }
+
+    /// ワークスペース内の全タスクを検証
+    ///
+    /// # Arguments
+    /// * `workspace` - 検証するワークスペース
+    ///
+    /// # Returns
+    /// * `ValidationReport` - 検証結果
+    pub fn validate_workspace(workspace: &Workspace) -> ValidationReport {
+        let validator =
+            SchemaValidator::new(&workspace.config).with_workspace_root(&workspace.root_path);
+
+        let mut task_ids: Vec<_> = workspace.tasks.keys().collect();
+        task_ids.sort();
+
+        let violations = task_ids
+            .iter()
+            .flat_map(|task_id| {
+                validator.validate_task(task_id, &workspace.tasks[*task_id].front_matter)
+            })
+            .collect();
+
+        ValidationReport {
+            tasks_checked: task_ids.len(),
+            strict_mode: workspace.config.strict_tag_mode,
+            violations,
+            schema_errors: validator.schema_errors().to_vec(),
+        }
+    }
+}
+
+/// 空文字列・空配列は未入力として扱う
+pub(crate) fn is_empty(value: &TagValue) -> bool {
+    match value {
+        TagValue::String(s) => s.trim().is_empty(),
+        TagValue::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+/// 値を要素ごとの文字列に分解（配列以外は1要素）
+fn elements(value: &TagValue) -> Vec<String> {
+    match value {
+        TagValue::Array(items) => items.clone(),
+        other => vec![other.to_string_value()],
+    }
+}
+
+pub(crate) fn as_number(value: &TagValue) -> Option<f64> {
+    match value {
+        TagValue::Number(n) => Some(*n as f64),
+        TagValue::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// 範囲の確認に使う数値（通貨型は通貨コード付きの金額も対象）
+fn as_amount(tag_config: &TagConfig, value: &TagValue) -> Option<f64> {
+    match tag_config.tag_type {
+        TagType::Currency => CurrencyAmount::from_tag_value(value, tag_config.currency.as_deref())
+            .map(|amount| amount.amount)
+            .or_else(|| as_number(value)),
+        _ => as_number(value),
+    }
+}
+
+fn is_date(value: &str) -> bool {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+        || DateTime::parse_from_rfc3339(value).is_ok()
+}
+
+/// 時刻付きの日時かどうか（タイムゾーンは省略可）
+fn is_datetime(value: &str) -> bool {
+    DateTime::parse_from_rfc3339(value).is_ok()
+        || ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+            .iter()
+            .any(|format| NaiveDateTime::parse_from_str(value, format).is_ok())
+}
+
+pub(crate) fn type_matches(tag_config: &TagConfig, value: &TagValue) -> bool {
+    match tag_config.tag_type {
+        // 単一値の型は配列を受け付けない
+        TagType::String | TagType::Select => !matches!(value, TagValue::Array(_)),
+        // 1つだけの値は配列に書かれていなくてもよい
+        TagType::MultiSelect | TagType::Array => {
+            matches!(value, TagValue::Array(_) | TagValue::String(_))
+        }
+        TagType::Number => as_number(value).is_some(),
+        TagType::Boolean => matches!(value, TagValue::Bool(_)),
+        TagType::Date => matches!(value, TagValue::String(s) if is_date(s)),
+        TagType::Datetime => matches!(value, TagValue::String(s) if is_datetime(s)),
+        // 通貨コードのない数値は既定の通貨が設定されている場合のみ
+        TagType::Currency => {
+            CurrencyAmount::from_tag_value(value, tag_config.currency.as_deref()).is_some()
+        }
+        TagType::Hyperlink => {
+            matches!(value, TagValue::String(s) if is_url(&Hyperlink::parse(s).url))
+        }
+        TagType::Image => matches!(value, TagValue::String(_)),
+    }
+}
+
+pub(crate) fn type_name(tag_type: &TagType) -> &'static str {
+    match tag_type {
+        TagType::String => "a single value",
+        TagType::Select => "a single option",
+        TagType::MultiSelect | TagType::Array => "a list of values",
+        TagType::Number => "a number",
+        TagType::Boolean => "true or false",
+        TagType::Date => "a date (YYYY-MM-DD)",
+        TagType::Datetime => "a date and time (YYYY-MM-DDTHH:MM)",
+        TagType::Currency => "an amount with a currency code (e.g. 1200.50 USD)",
+        TagType::Hyperlink => "a URL or [text](URL)",
+        TagType::Image => "an image path",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tag_config::TagConfig;
+
+    fn front_matter(entries: &[(&str, TagValue)]) -> FrontMatter {
+        FrontMatter {
+            tags: entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+        }
+    }
+
+    fn kinds(violations: &[TagViolation]) -> Vec<(&str, ViolationKind)> {
+        violations
+            .iter()
+            .map(|v| (v.category.as_str(), v.kind))
+            .collect()
+    }
+
+    fn config() -> WorkspaceConfig {
+        let mut config = WorkspaceConfig::default();
+        let configs = &mut config.tag_configs;
+        configs.set_config(
+            "estimate".to_string(),
+            TagConfig::new(TagType::Number).with_allowed_value_type(AllowedValueType::Range {
+                min: 0.0,
+                max: 40.0,
+            }),
+        );
+        configs.set_config(
+            "ticket".to_string(),
+            TagConfig::new(TagType::String)
+                .with_allowed_value_type(AllowedValueType::Pattern("[A-Z]+-\\d+".to_string())),
+        );
+        configs.configs.get_mut("status").unwrap().required = true;
+        config
+    }
+
+    #[test]
+    fn test_validate_task_rules() {
+        let config = config();
+        let validator = SchemaValidator::new(&config);
+
+        let violations = validator.validate_task(
+            "task",
+            &front_matter(&[
+                ("estimate", TagValue::Number(80)),
+                ("ticket", TagValue::String("HM-12x".into())),
+                ("tags", TagValue::Array(vec!["bug".into(), "chore".into()])),
+                ("due_date", TagValue::String("next week".into())),
+                ("extra", TagValue::Bool(true)),
+            ]),
+        );
+
+        assert_eq!(
+            kinds(&violations),
+            vec![
+                ("due_date", ViolationKind::TypeMismatch),
+                ("estimate", ViolationKind::OutOfRange),
+                ("status", ViolationKind::MissingRequired),
+                ("tags", ViolationKind::NotInList),
+                ("ticket", ViolationKind::PatternMismatch),
+            ]
+        );
+        assert!(violations.iter().all(|v| v.severity == Severity::Warning));
+
+        let valid = validator.validate_task(
+            "task",
+            &front_matter(&[
+                ("status", TagValue::String("open".into())),
+                ("estimate", TagValue::Float(1.5)),
+                ("ticket", TagValue::String("HM-12".into())),
+                ("tags", TagValue::String("bug".into())),
+                ("due_date", TagValue::String("2025-01-31".into())),
+            ]),
+        );
+        assert!(valid.is_empty());
+    }
+
+    #[test]
+    fn test_validate_typed_values() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join(ASSETS_DIR)).unwrap();
+        std::fs::write(root.join(ASSETS_DIR).join("cover.png"), b"png").unwrap();
+
+        let mut config = WorkspaceConfig::default();
+        let configs = &mut config.tag_configs;
+        let mut budget = TagConfig::new(TagType::Currency)
+            .with_allowed_value_type(AllowedValueType::Range { min: 0.0, max: f64::INFINITY });
+        budget.currency = Some("JPY".to_string());
+        configs.set_config("budget".to_string(), budget);
+        configs.set_config("fee".to_string(), TagConfig::new(TagType::Currency));
+        configs.set_config("link".to_string(), TagConfig::new(TagType::Hyperlink));
+        configs.set_config("cover".to_string(), TagConfig::new(TagType::Image));
+        configs.set_config("started".to_string(), TagConfig::new(TagType::Datetime));
+        let validator = SchemaValidator::new(&config).with_workspace_root(root);
+
+        let valid = validator.validate_task(
+            "task",
+            &front_matter(&[
+                ("status", TagValue::String("open".into())),
+                ("budget", TagValue::Number(5000)),
+                ("fee", TagValue::String("12.50 USD".into())),
+                ("link", TagValue::String("[Spec](https://example.com/spec)".into())),
+                ("cover", TagValue::String(".hienmark/assets/cover.png".into())),
+                ("started", TagValue::String("2025-01-31T09:30".into())),
+            ]),
+        );
+        assert!(valid.is_empty(), "{:?}", valid);
+
+        let violations = validator.validate_task(
+            "task",
+            &front_matter(&[
+                ("budget", TagValue::String("-10 EUR".into())),
+                ("fee", TagValue::Float(12.5)),
+                ("link", TagValue::String("example.com".into())),
+                ("cover", TagValue::String(".hienmark/assets/missing.png".into())),
+                ("started", TagValue::String("2025-01-31".into())),
+            ]),
+        );
+        assert_eq!(
+            kinds(&violations),
+            vec![
+                ("budget", ViolationKind::OutOfRange),
+                ("cover", ViolationKind::MissingAsset),
+                ("fee", ViolationKind::TypeMismatch),
+                ("link", ViolationKind::TypeMismatch),
+                ("started", ViolationKind::TypeMismatch),
+            ]
+        );
+
+        let outside = validator.validate_task(
+            "task",
+            &front_matter(&[("cover", TagValue::String("images/cover.png".into()))]),
+        );
+        assert!(outside.iter().any(|v| v.kind == ViolationKind::MissingAsset));
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_unknown_categories() {
+        let mut config = config();
+        config.strict_tag_mode = true;
+        config.computed_fields = crate::parser::ComputedFields::from_schema(
+            &serde_json::from_value(serde_json::json!({
+                "version": 1,
+                "tags": {"score": {"type": "Computed", "options": {"expression": "estimate * 2"}}}
+            }))
+            .unwrap(),
+        );
+        config.tag_configs.set_config(
+            "code".to_string(),
+            TagConfig::new(TagType::String)
+                .with_allowed_value_type(AllowedValueType::Pattern("(".to_string())),
+        );
+        let validator = SchemaValidator::new(&config);
+        assert_eq!(validator.schema_errors().len(), 1);
+
+        let violations = validator.validate_task(
+            "task",
+            &front_matter(&[
+                ("status", TagValue::String("open".into())),
+                ("assignee", TagValue::String("me".into())),
+                ("extra", TagValue::Bool(true)),
+                ("score", TagValue::Number(3)),
+            ]),
+        );
+        assert_eq!(
+            kinds(&violations),
+            vec![
+                ("score", ViolationKind::ReadOnly),
+                ("extra", ViolationKind::UnknownCategory)
+            ]
+        );
+        let unknown = violations
+            .iter()
+            .find(|v| v.kind == ViolationKind::UnknownCategory)
+            .unwrap();
+        assert_eq!(unknown.severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_cross_field_rules() {
+        let schema: crate::models::TagSchema = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "tags": {
+                "status": {"type": "Select", "options": {"optionsList": ["open", "inprogress", "close"]}},
+                "start_date": {"type": "Datetime", "options": {}},
+                "end_date": {"type": "Datetime", "options": {}},
+                "completed_at": {"type": "Datetime", "options": {}},
+                "assignee": {"type": "String", "options": {}},
+                "duration": {"type": "Computed", "options": {"expression": "end_date - start_date"}}
+            },
+            "rules": [
+                {"id": "end-after-start", "assert": "end_date >= start_date",
+                 "message": "end_date must not be before start_date", "severity": "error"},
+                {"id": "close-needs-completion", "when": "status == close", "require": ["completed_at"]},
+                {"id": "assignee-in-progress", "when": "status == inprogress", "require": ["assignee"]},
+                {"id": "short-tasks", "assert": "duration <= 30"},
+                {"id": "broken", "assert": "(status"}
+            ]
+        }))
+        .unwrap();
+        let mut config = WorkspaceConfig::default();
+        config.apply_tag_schema(&schema);
+        let validator = SchemaValidator::new(&config);
+        assert_eq!(validator.schema_errors().len(), 1);
+        assert!(validator.schema_errors()[0].contains("rule broken"));
+
+        let text = |s: &str| TagValue::String(s.into());
+        fn rules(violations: &[TagViolation]) -> Vec<(&str, &str, Severity)> {
+            violations
+                .iter()
+                .map(|v| (v.rule.as_deref().unwrap(), v.category.as_str(), v.severity))
+                .collect()
+        }
+
+        let closed = validator.validate_task(
+            "closed",
+            &front_matter(&[
+                ("status", text("close")),
+                ("start_date", text("2025-01-10")),
+                ("end_date", text("2025-01-05")),
+            ]),
+        );
+        assert!(closed.iter().all(|v| v.kind == ViolationKind::RuleViolation));
+        assert_eq!(
+            rules(&closed),
+            vec![
+                ("end-after-start", "end_date", Severity::Error),
+                ("close-needs-completion", "completed_at", Severity::Warning),
+            ]
+        );
+        assert_eq!(closed[0].message, "end_date must not be before start_date");
+        assert_eq!(closed[1].message, "completed_at is required when status == close");
+
+        let in_progress = validator.validate_task(
+            "in_progress",
+            &front_matter(&[
+                ("status", text("inprogress")),
+                ("assignee", text(" ")),
+                ("start_date", text("2025-01-01")),
+                ("end_date", text("2025-03-01")),
+            ]),
+        );
+        assert_eq!(
+            rules(&in_progress),
+            vec![
+                ("assignee-in-progress", "assignee", Severity::Warning),
+                ("short-tasks", "duration", Severity::Warning),
+            ]
+        );
+
+        // 参照するタグがなく評価できない規則は違反としない
+        let open = validator.validate_task("open", &front_matter(&[("status", text("open"))]));
+        assert!(open.is_empty());
+    }
+}
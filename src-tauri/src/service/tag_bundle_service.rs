@@ -0,0 +1,440 @@
+use crate::models::tag_bundle::{
+    BundleFormat, BundleItemKind, BundleItemPreview, BundleItemStatus, ImportAction,
+    ImportPreview, ImportReport, ImportResolution, TagBundle, TAG_BUNDLE_VERSION,
+};
+use crate::models::tag_schema::TagAttribute;
+use crate::models::TagTemplate;
+use crate::parser::expression::ExpressionParser;
+use crate::service::{TagSchemaService, WorkspaceService};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// タグ定義バンドルサービス
+/// タグ定義とテンプレートのエクスポート、インポートのプレビューと適用を担当
+pub struct TagBundleService;
+
+impl TagBundleService {
+    /// 選択したタグ定義とテンプレートをバンドルにまとめる
+    ///
+    /// # Arguments
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    /// * `tag_names` - エクスポートするタグ（`None`はすべて）
+    /// * `template_names` - エクスポートするテンプレート（`None`はすべて）
+    ///
+    /// # Returns
+    /// * `Result<TagBundle, String>` - バンドル（存在しない名前が指定された場合はエラー）
+    pub fn export_bundle(
+        workspace_root: &Path,
+        tag_names: Option<&[String]>,
+        template_names: Option<&[String]>,
+    ) -> Result<TagBundle, String> {
+        let schema = TagSchemaService::load_schema(workspace_root)?;
+        let config = WorkspaceService::load_config(&workspace_root.to_string_lossy())?;
+
+        let tags = match tag_names {
+            None => schema.tags,
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    schema
+                        .tags
+                        .get(name)
+                        .map(|definition| (name.clone(), definition.clone()))
+                        .ok_or_else(|| format!("Tag '{}' not found", name))
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        let templates = match template_names {
+            None => config.templates.templates.into_iter().collect(),
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    config
+                        .templates
+                        .get_template(name)
+                        .map(|template| (name.clone(), template.clone()))
+                        .ok_or_else(|| format!("Template '{}' not found", name))
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(TagBundle {
+            version: TAG_BUNDLE_VERSION,
+            exported_at: chrono::Utc::now(),
+            tags,
+            templates,
+        })
+    }
+
+    /// バンドルを文字列に変換
+    pub fn serialize_bundle(bundle: &TagBundle, format: BundleFormat) -> Result<String, String> {
+        match format {
+            BundleFormat::Json => serde_json::to_string_pretty(bundle)
+                .map_err(|e| format!("Failed to serialize bundle: {}", e)),
+            BundleFormat::Yaml => serde_yaml::to_string(bundle)
+                .map_err(|e| format!("Failed to serialize bundle: {}", e)),
+        }
+    }
+
+    /// バンドルを解析
+    ///
+    /// JSONはYAMLとしても読めるため、形式は内容から判断する。
+    ///
+    /// # Arguments
+    /// * `content` - バンドルファイルの内容（JSONまたはYAML）
+    ///
+    /// # Returns
+    /// * `Result<TagBundle, String>` - バンドル（サポートより新しいバージョンはエラー）
+    pub fn parse_bundle(content: &str) -> Result<TagBundle, String> {
+        let value: serde_yaml::Value =
+            serde_yaml::from_str(content).map_err(|e| format!("Invalid bundle: {}", e))?;
+        let Some(version) = value.get("version").and_then(serde_yaml::Value::as_u64) else {
+            return Err("Bundle has no version".to_string());
+        };
+        if version > TAG_BUNDLE_VERSION as u64 {
+            return Err(format!(
+                "Bundle version {} is newer than supported version {}",
+                version, TAG_BUNDLE_VERSION
+            ));
+        }
+
+        serde_yaml::from_value(value).map_err(|e| format!("Failed to parse bundle: {}", e))
+    }
+
+    /// インポートした場合の各項目の扱いを確認する（ファイルは変更しない）
+    ///
+    /// # Arguments
+    /// * `workspace_root` - インポート先のワークスペース
+    /// * `bundle` - インポートするバンドル
+    ///
+    /// # Returns
+    /// * `Result<ImportPreview, String>` - 項目ごとの比較結果
+    pub fn preview_import(workspace_root: &Path, bundle: &TagBundle) -> Result<ImportPreview, String> {
+        let schema = TagSchemaService::load_schema(workspace_root)?;
+        let config = WorkspaceService::load_config(&workspace_root.to_string_lossy())?;
+
+        let tags = bundle.tags.iter().map(|(name, definition)| BundleItemPreview {
+            kind: BundleItemKind::Tag,
+            name: name.clone(),
+            status: match schema.tags.get(name) {
+                None => BundleItemStatus::New,
+                Some(existing) if existing == definition => BundleItemStatus::Identical,
+                Some(_) => BundleItemStatus::Conflict,
+            },
+        });
+        let templates = bundle.templates.iter().map(|(name, template)| BundleItemPreview {
+            kind: BundleItemKind::Template,
+            name: name.clone(),
+            status: match config.templates.get_template(name) {
+                None => BundleItemStatus::New,
+                Some(existing) if same_template(existing, template) => BundleItemStatus::Identical,
+                Some(_) => BundleItemStatus::Conflict,
+            },
+        });
+
+        Ok(ImportPreview {
+            version: bundle.version,
+            exported_at: bundle.exported_at,
+            items: tags.chain(templates).collect(),
+        })
+    }
+
+    /// バンドルをインポート
+    ///
+    /// 新しい項目はそのまま追加し、内容の異なる同名の項目は`resolutions`の指定
+    /// （指定がなければ`default_action`）に従う。別名でインポートしたタグは、
+    /// 同じバンドルのテンプレートと計算式からの参照も新しい名前に置き換える。
+    ///
+    /// # Arguments
+    /// * `workspace_root` - インポート先のワークスペース
+    /// * `bundle` - インポートするバンドル
+    /// * `resolutions` - 項目ごとのインポート方法
+    /// * `default_action` - 指定のない競合項目のインポート方法（`Rename`は指定できない）
+    ///
+    /// # Returns
+    /// * `Result<ImportReport, String>` - インポート結果（スキーマが不正になる場合は何も保存しない）
+    pub fn import_bundle(
+        workspace_root: &Path,
+        bundle: &TagBundle,
+        resolutions: &[ImportResolution],
+        default_action: ImportAction,
+    ) -> Result<ImportReport, String> {
+        if default_action == ImportAction::Rename {
+            return Err("Rename requires a new name for each item".to_string());
+        }
+
+        let workspace_path = workspace_root.to_string_lossy();
+        let mut schema = TagSchemaService::load_schema(workspace_root)?;
+        let mut config = WorkspaceService::load_config(&workspace_path)?;
+        let preview = Self::preview_import(workspace_root, bundle)?;
+        let resolution_of = |item: &BundleItemPreview| {
+            resolutions
+                .iter()
+                .find(|r| r.kind == item.kind && r.name == item.name)
+        };
+
+        // 項目ごとのインポート後の名前（スキップする項目は含まない）
+        let mut tag_targets: BTreeMap<&str, String> = BTreeMap::new();
+        let mut template_targets: BTreeMap<&str, String> = BTreeMap::new();
+        let mut report = ImportReport::default();
+        for item in &preview.items {
+            let resolution = resolution_of(item);
+            let action = match (item.status, resolution) {
+                (BundleItemStatus::Identical, _) => ImportAction::Skip,
+                (_, Some(resolution)) => resolution.action,
+                (BundleItemStatus::New, None) => ImportAction::Overwrite,
+                (BundleItemStatus::Conflict, None) => default_action,
+            };
+            let target = match action {
+                ImportAction::Skip => {
+                    report.skipped.push(item.clone());
+                    continue;
+                }
+                ImportAction::Overwrite => item.name.clone(),
+                ImportAction::Rename => {
+                    let new_name = resolution
+                        .and_then(|r| r.new_name.as_deref())
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .ok_or_else(|| format!("No new name given for '{}'", item.name))?;
+                    let taken = match item.kind {
+                        BundleItemKind::Tag => {
+                            schema.tags.contains_key(new_name)
+                                || bundle.tags.contains_key(new_name)
+                                || tag_targets.values().any(|t| t == new_name)
+                        }
+                        BundleItemKind::Template => {
+                            config.templates.templates.contains_key(new_name)
+                                || bundle.templates.contains_key(new_name)
+                                || template_targets.values().any(|t| t == new_name)
+                        }
+                    };
+                    if taken {
+                        return Err(format!("'{}' already exists", new_name));
+                    }
+                    new_name.to_string()
+                }
+            };
+            match item.kind {
+                BundleItemKind::Tag => tag_targets.insert(&item.name, target),
+                BundleItemKind::Template => template_targets.insert(&item.name, target),
+            };
+        }
+
+        let renamed_tags: Vec<(&str, &str)> = tag_targets
+            .iter()
+            .filter(|(from, to)| **from != to.as_str())
+            .map(|(from, to)| (*from, to.as_str()))
+            .collect();
+
+        for (name, target) in &tag_targets {
+            let mut definition = bundle.tags[*name].clone();
+            if let TagAttribute::Computed(options) = &mut definition.attribute {
+                for (from, to) in &renamed_tags {
+                    if let Ok(Some(expression)) =
+                        ExpressionParser::rename_reference(&options.expression, from, to)
+                    {
+                        options.expression = expression;
+                    }
+                }
+            }
+            schema.tags.insert(target.clone(), definition);
+            report.tags_imported.push(target.clone());
+        }
+
+        for (name, target) in &template_targets {
+            let mut template = bundle.templates[*name].clone();
+            template.name = target.clone();
+            for (from, to) in &renamed_tags {
+                if let Some(value) = template.tags.remove(*from) {
+                    template.tags.insert(to.to_string(), value);
+                }
+            }
+            config.templates.add_template(template);
+            report.templates_imported.push(target.clone());
+        }
+
+        // スキーマは保存時に検証されるため、先に保存して不正ならテンプレートも保存しない
+        if !report.tags_imported.is_empty() {
+            TagSchemaService::save_schema(workspace_root, &schema)?;
+        }
+        if !report.templates_imported.is_empty() {
+            WorkspaceService::save_config(&workspace_path, &config)?;
+        }
+
+        Ok(report)
+    }
+}
+
+/// 作成・更新日時を除いてテンプレートの内容を比較
+fn same_template(a: &TagTemplate, b: &TagTemplate) -> bool {
+    a.description == b.description && a.tags == b.tags && a.body == b.body
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TagValue, WorkspaceConfig};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn setup_workspace(schema_json: &str, templates: Vec<TagTemplate>) -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let (schema, _) = TagSchemaService::parse_schema(schema_json).unwrap();
+        TagSchemaService::save_schema(root, &schema).unwrap();
+
+        let mut config = WorkspaceConfig::default();
+        for template in templates {
+            config.templates.add_template(template);
+        }
+        WorkspaceService::save_config(&root.to_string_lossy(), &config).unwrap();
+        temp_dir
+    }
+
+    fn template(name: &str, tags: &[(&str, &str)]) -> TagTemplate {
+        let tags: HashMap<String, TagValue> = tags
+            .iter()
+            .map(|(k, v)| (k.to_string(), TagValue::String(v.to_string())))
+            .collect();
+        TagTemplate::new(name.to_string(), None, tags)
+    }
+
+    #[test]
+    fn test_export_and_import_round_trip() {
+        let source = setup_workspace(
+            r#"{"version": 1, "tags": {
+                "status": {"type": "Select", "options": {"optionsList": ["todo", "done"]}},
+                "owner": {"type": "String", "options": {}}
+            }}"#,
+            vec![template("bug", &[("status", "todo")])],
+        );
+        let names = vec!["status".to_string()];
+        let bundle = TagBundleService::export_bundle(source.path(), Some(&names), None).unwrap();
+        assert_eq!(bundle.tags.len(), 1);
+        assert_eq!(bundle.templates.len(), 1);
+
+        for format in [BundleFormat::Json, BundleFormat::Yaml] {
+            let content = TagBundleService::serialize_bundle(&bundle, format).unwrap();
+            let parsed = TagBundleService::parse_bundle(&content).unwrap();
+            assert_eq!(parsed.tags, bundle.tags);
+
+            let target = setup_workspace(r#"{"version": 1, "tags": {}}"#, vec![]);
+            let report =
+                TagBundleService::import_bundle(target.path(), &parsed, &[], ImportAction::Skip)
+                    .unwrap();
+            assert_eq!(report.tags_imported, vec!["status"]);
+            assert_eq!(report.templates_imported, vec!["bug"]);
+
+            let schema = TagSchemaService::load_schema(target.path()).unwrap();
+            assert_eq!(schema.tags.get("status"), bundle.tags.get("status"));
+            let config = WorkspaceService::load_config(&target.path().to_string_lossy()).unwrap();
+            assert_eq!(
+                config.templates.get_template("bug").unwrap().tags.get("status"),
+                Some(&TagValue::String("todo".to_string()))
+            );
+
+            // 同じバンドルをもう一度インポートしても変更はない
+            let preview = TagBundleService::preview_import(target.path(), &parsed).unwrap();
+            assert!(preview
+                .items
+                .iter()
+                .all(|item| item.status == BundleItemStatus::Identical));
+        }
+
+        let newer = format!(r#"{{"version": {}, "exportedAt": "2024-01-01T00:00:00Z"}}"#, TAG_BUNDLE_VERSION + 1);
+        assert!(TagBundleService::parse_bundle(&newer).is_err());
+    }
+
+    #[test]
+    fn test_import_resolves_conflicts() {
+        let source = setup_workspace(
+            r#"{"version": 1, "tags": {
+                "status": {"type": "Select", "options": {"optionsList": ["open", "closed"]}},
+                "priority": {"type": "Number", "options": {}},
+                "closed": {"type": "Computed", "options": {"expression": "status == \"closed\""}}
+            }}"#,
+            vec![template("bug", &[("status", "open")])],
+        );
+        let bundle = TagBundleService::export_bundle(source.path(), None, None).unwrap();
+
+        let target = setup_workspace(
+            r#"{"version": 1, "tags": {
+                "status": {"type": "Select", "options": {"optionsList": ["todo", "done"]}},
+                "priority": {"type": "String", "options": {}}
+            }}"#,
+            vec![template("bug", &[("status", "todo")])],
+        );
+        let preview = TagBundleService::preview_import(target.path(), &bundle).unwrap();
+        let status_of = |kind, name: &str| {
+            preview
+                .items
+                .iter()
+                .find(|item| item.kind == kind && item.name == name)
+                .map(|item| item.status)
+        };
+        assert_eq!(status_of(BundleItemKind::Tag, "status"), Some(BundleItemStatus::Conflict));
+        assert_eq!(status_of(BundleItemKind::Tag, "closed"), Some(BundleItemStatus::New));
+        assert_eq!(status_of(BundleItemKind::Template, "bug"), Some(BundleItemStatus::Conflict));
+
+        let resolutions = vec![
+            ImportResolution {
+                kind: BundleItemKind::Tag,
+                name: "status".to_string(),
+                action: ImportAction::Rename,
+                new_name: Some("ticket_status".to_string()),
+            },
+            ImportResolution {
+                kind: BundleItemKind::Template,
+                name: "bug".to_string(),
+                action: ImportAction::Rename,
+                new_name: Some("imported_bug".to_string()),
+            },
+        ];
+        let report = TagBundleService::import_bundle(
+            target.path(),
+            &bundle,
+            &resolutions,
+            ImportAction::Skip,
+        )
+        .unwrap();
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].name, "priority");
+
+        let schema = TagSchemaService::load_schema(target.path()).unwrap();
+        assert!(matches!(schema.tags["priority"].attribute, TagAttribute::String(_)));
+        assert!(matches!(schema.tags["status"].attribute, TagAttribute::Select(_)));
+        assert_eq!(schema.tags["ticket_status"], bundle.tags["status"]);
+        match &schema.tags["closed"].attribute {
+            TagAttribute::Computed(options) => {
+                assert_eq!(options.expression, "ticket_status == \"closed\"")
+            }
+            other => panic!("unexpected attribute: {:?}", other),
+        }
+
+        let config = WorkspaceService::load_config(&target.path().to_string_lossy()).unwrap();
+        let imported = config.templates.get_template("imported_bug").unwrap();
+        assert_eq!(
+            imported.tags.get("ticket_status"),
+            Some(&TagValue::String("open".to_string()))
+        );
+        assert_eq!(
+            config.templates.get_template("bug").unwrap().tags.get("status"),
+            Some(&TagValue::String("todo".to_string()))
+        );
+
+        // 既存の名前への変更は拒否される
+        let clash = vec![ImportResolution {
+            kind: BundleItemKind::Tag,
+            name: "priority".to_string(),
+            action: ImportAction::Rename,
+            new_name: Some("status".to_string()),
+        }];
+        assert!(
+            TagBundleService::import_bundle(target.path(), &bundle, &clash, ImportAction::Skip)
+                .is_err()
+        );
+    }
+}
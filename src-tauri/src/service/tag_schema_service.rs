@@ -1,10 +1,18 @@
-use chrono::Local;
-use regex::Regex;
-use std::ops::Add;
-use chrono::Duration;
+use crate::models::tag_config::TagConfigCollection;
+use crate::models::tag_schema::TagDefinition;
+use crate::models::{TagSchema, TAG_SCHEMA_VERSION};
+use crate::models::TagValue;
+use crate::parser::formula::FormulaValue;
+use crate::parser::{ComputedFields, FormulaParser};
+use chrono::{DateTime, Local};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 /// タグスキーマサービス
 /// タグスキーマの読み込み、保存、検証、動的デフォルト値の計算を担当
+/// （計算式の構文は`parser::formula`を参照）
 pub struct TagSchemaService;
 
 impl TagSchemaService {
@@ -12,6 +20,183 @@ impl TagSchemaService {
         Self
     }
 
+    /// タグスキーマファイルのパス
+    pub fn schema_path(workspace_root: &Path) -> PathBuf {
+        workspace_root.join(".hienmark").join("tag_schema.json")
+    }
+
+    /// スキーマのJSONを解析
+    ///
+    /// バージョンを持たない旧形式（タグ名 → `{type, options}`のオブジェクト）も受け付ける。
+    ///
+    /// # Arguments
+    /// * `content` - `tag_schema.json`の内容
+    ///
+    /// # Returns
+    /// * `Result<(TagSchema, bool), String>` - スキーマと、旧形式だったかどうか
+    pub fn parse_schema(content: &str) -> Result<(TagSchema, bool), String> {
+        let value: Value =
+            serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+        let Some(object) = value.as_object() else {
+            return Err("Tag schema must be a JSON object".to_string());
+        };
+
+        // 旧形式ではトップレベルの値はすべてタグ定義（オブジェクト）
+        let Some(version) = object.get("version").and_then(Value::as_u64) else {
+            let tags: BTreeMap<String, TagDefinition> = serde_json::from_value(value)
+                .map_err(|e| format!("Failed to parse legacy tag schema: {}", e))?;
+            return Ok((
+                TagSchema {
+                    version: TAG_SCHEMA_VERSION,
+                    tags,
+                    rules: Vec::new(),
+                },
+                true,
+            ));
+        };
+        if version > TAG_SCHEMA_VERSION as u64 {
+            return Err(format!(
+                "Tag schema version {} is newer than supported version {}",
+                version, TAG_SCHEMA_VERSION
+            ));
+        }
+
+        let schema: TagSchema = serde_json::from_value(value)
+            .map_err(|e| format!("Failed to parse tag schema: {}", e))?;
+        Ok((schema, false))
+    }
+
+    /// タグスキーマで定義された計算フィールドを読み込む
+    ///
+    /// インデックス構築から使われるため、スキーマが読めない場合はログに残して計算フィールドなしとする。
+    pub fn load_computed_fields(workspace_root: &Path) -> ComputedFields {
+        match Self::load_schema(workspace_root) {
+            Ok(schema) => ComputedFields::from_schema(&schema),
+            Err(e) => {
+                log::warn!("Failed to load computed fields: {}", e);
+                ComputedFields::default()
+            }
+        }
+    }
+
+    /// タグスキーマを読み込む
+    ///
+    /// 旧形式の`tag_schema.json`や`.hienmark.json`の`tag_configs`は、現在の形式に変換した内容を返す
+    /// （ファイルは変更しない。書き換えは`migrate_schema`で行う）。同じタグが両方にある場合は
+    /// `tag_schema.json`を優先する。どちらのファイルもない新しいワークスペースでは既定のタグ設定を返す。
+    ///
+    /// # Arguments
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    ///
+    /// # Returns
+    /// * `Result<TagSchema, String>` - タグスキーマ
+    pub fn load_schema(workspace_root: &Path) -> Result<TagSchema, String> {
+        Self::read_schema(workspace_root).map(|(schema, _)| schema)
+    }
+
+    /// 旧形式のタグスキーマ・タグ設定を現在の形式に移行して保存
+    ///
+    /// ワークスペースを開くときと、設定やスキーマを書き込む前に呼ばれる。
+    /// 旧形式の`tag_schema.json`は`tag_schema.json.bak`に残す。
+    ///
+    /// # Arguments
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    ///
+    /// # Returns
+    /// * `Result<bool, String>` - 移行を行ったかどうか
+    pub fn migrate_schema(workspace_root: &Path) -> Result<bool, String> {
+        let (schema, Some(migration)) = Self::read_schema(workspace_root)? else {
+            return Ok(false);
+        };
+
+        if let Some(content) = migration.legacy_schema {
+            let backup_path = Self::schema_path(workspace_root).with_extension("json.bak");
+            fs::write(&backup_path, content)
+                .map_err(|e| format!("Failed to back up legacy tag schema: {}", e))?;
+        }
+        // スキーマを先に書き込むことで、途中で失敗しても次回の移行でやり直せる
+        Self::write_schema(workspace_root, &schema)?;
+        if let Some(value) = migration.config {
+            let config_json = serde_json::to_string_pretty(&value)
+                .map_err(|e| format!("Failed to serialize config: {}", e))?;
+            fs::write(workspace_root.join(".hienmark.json"), config_json)
+                .map_err(|e| format!("Failed to write config: {}", e))?;
+        }
+        log::info!("Migrated tag schema to version {}", TAG_SCHEMA_VERSION);
+
+        Ok(true)
+    }
+
+    /// タグスキーマを読み込み、旧形式から移行が必要な場合はその内容も返す
+    fn read_schema(workspace_root: &Path) -> Result<(TagSchema, Option<SchemaMigration>), String> {
+        let schema_path = Self::schema_path(workspace_root);
+        let config_path = workspace_root.join(".hienmark.json");
+
+        let (mut schema, legacy_schema) = if schema_path.exists() {
+            let content = fs::read_to_string(&schema_path)
+                .map_err(|e| format!("Failed to read tag schema: {}", e))?;
+            let (schema, legacy) = Self::parse_schema(&content)?;
+            (schema, legacy.then_some(content))
+        } else if config_path.exists() {
+            (TagSchema::default(), None)
+        } else {
+            return Ok((TagSchema::from(&TagConfigCollection::create_default_configs()), None));
+        };
+
+        let mut config: Option<Value> = None;
+        if config_path.exists() {
+            let content = fs::read_to_string(&config_path)
+                .map_err(|e| format!("Failed to read config: {}", e))?;
+            let mut value: Value = serde_json::from_str(&content)
+                .map_err(|e| format!("Failed to parse config: {}", e))?;
+            if let Some(tag_configs) = value.as_object_mut().and_then(|o| o.remove("tag_configs")) {
+                let collection: TagConfigCollection = serde_json::from_value(tag_configs)
+                    .map_err(|e| format!("Failed to parse tag_configs: {}", e))?;
+                for (name, config) in &collection.configs {
+                    schema
+                        .tags
+                        .entry(name.clone())
+                        .or_insert_with(|| TagDefinition::from(config));
+                }
+                config = Some(value);
+            }
+        }
+
+        let migration = (legacy_schema.is_some() || config.is_some()).then_some(SchemaMigration {
+            legacy_schema,
+            config,
+        });
+        Ok((schema, migration))
+    }
+
+    /// タグスキーマを検証して保存
+    ///
+    /// # Arguments
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    /// * `schema` - 保存するスキーマ
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - 保存結果
+    pub fn save_schema(workspace_root: &Path, schema: &TagSchema) -> Result<(), String> {
+        schema.validate()?;
+        // 旧形式のタグ設定が後から移行されて保存内容に混ざらないよう、先に移行しておく
+        Self::migrate_schema(workspace_root)?;
+        Self::write_schema(workspace_root, schema)
+    }
+
+    fn write_schema(workspace_root: &Path, schema: &TagSchema) -> Result<(), String> {
+        let schema_path = Self::schema_path(workspace_root);
+        if let Some(dir) = schema_path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create .hienmark directory: {}", e))?;
+        }
+
+        let schema_json = serde_json::to_string_pretty(schema)
+            .map_err(|e| format!("Failed to serialize tag schema: {}", e))?;
+        fs::write(&schema_path, schema_json)
+            .map_err(|e| format!("Failed to write tag schema: {}", e))
+    }
+
     /// 動的デフォルト値を計算
     /// 
     /// # Arguments
@@ -22,44 +207,169 @@ impl TagSchemaService {
     /// 
     /// # Examples
     /// * `=[TODAY]+7` → 今日から7日後の日付
-    /// * `=[TODAY]` → 今日の日付
-    /// * `=[TODAY]+365` → 今日から365日後（1年後）
+    /// * `=[NOW]+2w` → 2週間後の現在時刻
+    /// * `=[TODAY]+1m end of month` → 来月末
+    /// * `=next monday` → 次の月曜日
+    /// * `=[TODAY]+5bd` → 5営業日後
     pub fn calculate_dynamic_default_value(&self, formula: &str) -> Result<String, String> {
-        // 式から `=` を除去
-        let expression = formula.trim_start_matches('=').trim();
-        
-        // `[TODAY]` を現在の日時に置き換え
-        let base_date = Local::now();
-        
-        // `+N` または `-N` の形式のオフセットをパース
-        let result = if expression.contains("[TODAY]") {
-            let offset_regex = Regex::new(r"\[TODAY\]\s*([\+\-])\s*(\d+)")
-                .map_err(|e| format!("Failed to create regex: {}", e))?;
-            
-            if let Some(captures) = offset_regex.captures(expression) {
-                let sign = &captures[1];
-                let days: i64 = captures[2].parse()
-                    .map_err(|e| format!("Failed to parse days offset: {}", e))?;
-                
-                let duration = if sign == "+" {
-                    Duration::days(days)
-                } else {
-                    Duration::days(-days)
-                };
-                
-                let result_date = base_date.add(duration);
-                result_date.to_rfc3339()
-            } else if expression.trim() == "[TODAY]" {
-                // 今日の日付のみ
-                base_date.to_rfc3339()
-            } else {
-                return Err("Invalid formula format. Supported format: `=[TODAY]+N` or `=[TODAY]-N`".to_string());
+        self.calculate_dynamic_default_value_with(formula, &HashMap::new())
+    }
+
+    /// 他のタグの値を参照して動的デフォルト値を計算
+    ///
+    /// # Arguments
+    /// * `formula` - 計算式（例: `=[start_date]+14`）
+    /// * `tags` - 参照できるタグの値（計算式を含んでいてもよい）
+    ///
+    /// # Returns
+    /// * `Result<String, String>` - 計算結果のISO 8601形式の文字列
+    pub fn calculate_dynamic_default_value_with(
+        &self,
+        formula: &str,
+        tags: &HashMap<String, TagValue>,
+    ) -> Result<String, String> {
+        let parsed = FormulaParser::parse(formula).map_err(|e| e.to_string())?;
+        let mut resolver = FormulaResolver::new(tags, Local::now());
+        parsed
+            .evaluate(resolver.now, |field| resolver.field_value(field))
+            .map(|value| value.to_rfc3339())
+    }
+
+    /// 計算式を評価してタグの値と同じ形式（`YYYY-MM-DD`またはRFC 3339）の文字列を返す
+    ///
+    /// テンプレート本文の`{{=...}}`プレースホルダーの展開に使う。
+    ///
+    /// # Arguments
+    /// * `formula` - 計算式（例: `=[TODAY]+7`）
+    /// * `tags` - 参照できるタグの値（計算式を含んでいてもよい）
+    /// * `now` - 現在の日時
+    pub fn evaluate_formula(
+        formula: &str,
+        tags: &HashMap<String, TagValue>,
+        now: DateTime<Local>,
+    ) -> Result<String, String> {
+        let parsed = FormulaParser::parse(formula).map_err(|e| e.to_string())?;
+        let mut resolver = FormulaResolver::new(tags, now);
+        parsed
+            .evaluate(now, |field| resolver.field_value(field))
+            .map(|value| value.to_tag_string())
+    }
+
+    /// タグの値に含まれる計算式（`=`で始まる文字列）を評価して置き換える
+    ///
+    /// テンプレートからタスクを作成するときに呼ばれる。計算式は他のタグの値や
+    /// 他の計算式の結果を参照でき、循環参照はエラーになる。
+    ///
+    /// # Arguments
+    /// * `tags` - 評価対象のタグ
+    /// * `now` - 現在の日時
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - 評価結果（エラーにはタグ名と位置を含む）
+    pub fn resolve_formulas(
+        tags: &mut HashMap<String, TagValue>,
+        now: DateTime<Local>,
+    ) -> Result<(), String> {
+        let names: Vec<String> = tags.keys().cloned().collect();
+        Self::resolve_formulas_of(tags, &names, now)
+    }
+
+    /// 指定したタグの計算式だけを評価して置き換える
+    ///
+    /// 既存タスクにテンプレートを適用するときに、テンプレートから追加したタグだけを評価するために使う
+    /// （タスクにもともと`=`で始まる文字列があっても、参照されない限り評価しない）。
+    ///
+    /// # Arguments
+    /// * `tags` - 評価対象のタグ（指定外のタグも計算式から参照できる）
+    /// * `names` - 評価するタグ名
+    /// * `now` - 現在の日時
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - 評価結果（エラーにはタグ名と位置を含む）
+    pub fn resolve_formulas_of(
+        tags: &mut HashMap<String, TagValue>,
+        names: &[String],
+        now: DateTime<Local>,
+    ) -> Result<(), String> {
+        let mut names: Vec<String> = names
+            .iter()
+            .filter(|name| matches!(tags.get(*name), Some(TagValue::String(s)) if FormulaParser::is_formula(s)))
+            .cloned()
+            .collect();
+        if names.is_empty() {
+            return Ok(());
+        }
+        names.sort();
+
+        let mut resolver = FormulaResolver::new(tags, now);
+        for name in &names {
+            resolver.field_value(name)?;
+        }
+        let resolved = resolver.resolved;
+
+        for name in names {
+            tags.insert(name.clone(), TagValue::String(resolved[&name].to_tag_string()));
+        }
+        Ok(())
+    }
+}
+
+/// 旧形式から移行する内容
+struct SchemaMigration {
+    /// 旧形式の`tag_schema.json`の内容（バックアップ用）
+    legacy_schema: Option<String>,
+    /// `tag_configs`を取り除いた`.hienmark.json`
+    config: Option<Value>,
+}
+
+/// 計算式から参照されるタグの値を解決する（計算式同士の参照は再帰的に評価する）
+struct FormulaResolver<'a> {
+    tags: &'a HashMap<String, TagValue>,
+    now: DateTime<Local>,
+    resolved: HashMap<String, FormulaValue>,
+    evaluating: Vec<String>,
+}
+
+impl<'a> FormulaResolver<'a> {
+    fn new(tags: &'a HashMap<String, TagValue>, now: DateTime<Local>) -> Self {
+        Self {
+            tags,
+            now,
+            resolved: HashMap::new(),
+            evaluating: Vec::new(),
+        }
+    }
+
+    fn field_value(&mut self, name: &str) -> Result<FormulaValue, String> {
+        if let Some(value) = self.resolved.get(name) {
+            return Ok(*value);
+        }
+
+        let value = match self.tags.get(name) {
+            Some(TagValue::String(text)) if FormulaParser::is_formula(text) => {
+                if self.evaluating.iter().any(|n| n == name) {
+                    return Err(format!(
+                        "Circular reference: {} -> {}",
+                        self.evaluating.join(" -> "),
+                        name
+                    ));
+                }
+                let formula = FormulaParser::parse(text).map_err(|e| format!("{}: {}", name, e))?;
+
+                self.evaluating.push(name.to_string());
+                let now = self.now;
+                let result = formula.evaluate(now, |field| self.field_value(field));
+                self.evaluating.pop();
+                result?
             }
-        } else {
-            return Err("Formula must contain [TODAY]".to_string());
+            Some(value) => FormulaValue::parse(&value.to_string_value()).ok_or_else(|| {
+                format!("Tag '{}' is not a date: {}", name, value.to_string_value())
+            })?,
+            None => return Err(format!("Tag '{}' is not set", name)),
         };
-        
-        Ok(result)
+
+        self.resolved.insert(name.to_string(), value);
+        Ok(value)
     }
 }
 
@@ -97,6 +407,85 @@ mod tests {
         assert!(result_str.contains("+") || result_str.contains("-"));
     }
 
+    #[test]
+    fn test_migrate_schema_from_legacy_locations() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join(".hienmark")).unwrap();
+        fs::write(
+            TagSchemaService::schema_path(root),
+            r#"{"status": {"type": "Select", "options": {"optionsList": ["todo", "done"]}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join(".hienmark.json"),
+            r#"{
+                "strictTagMode": false, "allowedCategories": [], "watchEnabled": true,
+                "tag_configs": {"configs": {
+                    "status": {"tag_type": "String"},
+                    "ticket": {"tag_type": "String", "allowed_value_type": {"Pattern": "[A-Z]+-\\d+"}, "required": true}
+                }}
+            }"#,
+        )
+        .unwrap();
+
+        // 読み込みではファイルを変更しない
+        let schema = TagSchemaService::load_schema(root).unwrap();
+        assert!(fs::read_to_string(root.join(".hienmark.json")).unwrap().contains("tag_configs"));
+        assert!(!root.join(".hienmark").join("tag_schema.json.bak").exists());
+
+        assert!(TagSchemaService::migrate_schema(root).unwrap());
+        assert_eq!(schema.version, TAG_SCHEMA_VERSION);
+        // tag_schema.jsonの定義が優先される
+        assert!(matches!(
+            schema.tags["status"].attribute,
+            crate::models::tag_schema::TagAttribute::Select(_)
+        ));
+        assert!(schema.tags["ticket"].required);
+
+        // 移行後のファイルは現在の形式で、.hienmark.jsonからtag_configsが取り除かれている
+        let saved = fs::read_to_string(TagSchemaService::schema_path(root)).unwrap();
+        assert_eq!(TagSchemaService::parse_schema(&saved).unwrap(), (schema.clone(), false));
+        let config = fs::read_to_string(root.join(".hienmark.json")).unwrap();
+        assert!(!config.contains("tag_configs"));
+        assert!(root.join(".hienmark").join("tag_schema.json.bak").exists());
+
+        assert_eq!(TagSchemaService::load_schema(root).unwrap(), schema);
+        assert!(!TagSchemaService::migrate_schema(root).unwrap());
+    }
+
+    #[test]
+    fn test_parse_schema_rejects_newer_version() {
+        let result = TagSchemaService::parse_schema(r#"{"version": 99, "tags": {}}"#);
+        assert!(result.unwrap_err().contains("newer than supported"));
+    }
+
+    #[test]
+    fn test_resolve_formulas_follows_references() {
+        let now = Local::now();
+        let mut tags = HashMap::new();
+        tags.insert("start_date".to_string(), TagValue::String("2025-02-10".to_string()));
+        tags.insert("review".to_string(), TagValue::String("=[due_date]-1bd".to_string()));
+        tags.insert("due_date".to_string(), TagValue::String("=[start_date]+14".to_string()));
+        tags.insert("status".to_string(), TagValue::String("open".to_string()));
+
+        TagSchemaService::resolve_formulas(&mut tags, now).unwrap();
+        assert_eq!(tags["due_date"], TagValue::String("2025-02-24".to_string()));
+        assert_eq!(tags["review"], TagValue::String("2025-02-21".to_string()));
+        assert_eq!(tags["status"], TagValue::String("open".to_string()));
+
+        let mut cyclic = HashMap::new();
+        cyclic.insert("a".to_string(), TagValue::String("=[b]+1".to_string()));
+        cyclic.insert("b".to_string(), TagValue::String("=[a]+1".to_string()));
+        let error = TagSchemaService::resolve_formulas(&mut cyclic, now).unwrap_err();
+        assert!(error.contains("Circular reference: a -> b -> a"), "{}", error);
+
+        let mut invalid = HashMap::new();
+        invalid.insert("due".to_string(), TagValue::String("=[TODAY]+2x".to_string()));
+        let error = TagSchemaService::resolve_formulas(&mut invalid, now).unwrap_err();
+        assert!(error.starts_with("due: Unknown unit 'x'"), "{}", error);
+    }
+
     #[test]
     fn test_invalid_formula() {
         let service = TagSchemaService::new();
@@ -1,6 +1,39 @@
-use crate::models::{TagValue, Workspace};
-use crate::service::WorkspaceService;
+use crate::models::tag_schema::TagAttribute;
+use crate::models::tag::canonical_elements;
+use crate::models::tag_config::ValueNormalization;
+use crate::models::{
+    CategoryConflictPolicy, CategoryRenameReport, NormalizationReport, TagValue, TagValueChange,
+    Workspace,
+};
+use crate::parser::expression::ExpressionParser;
+use crate::service::{AnalysisSettingsService, TagSchemaService, WorkspaceService};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
 use std::io;
+use std::path::{Path, PathBuf};
+
+/// チャートのマッピング（`chartMappings.*.mapping`）のうち、値がタグ名を指すキー
+const CHART_MAPPING_TAG_KEYS: [&str; 7] = [
+    "category",
+    "title",
+    "startDate",
+    "endDate",
+    "dependsOn",
+    "dateField",
+    "seriesBy",
+];
+
+/// タグマップ内でのカテゴリの移動結果
+#[derive(Debug, PartialEq)]
+enum CategoryMove {
+    /// 移動元のカテゴリがない
+    Absent,
+    /// 移動した（競合があった場合は方針に従って解決した）
+    Moved { conflict: bool },
+    /// 競合のため変更しなかった
+    Skipped,
+}
 
 /// タグ管理サービス
 pub struct TagService;
@@ -8,6 +41,8 @@ pub struct TagService;
 impl TagService {
     /// タグをリネーム（全タスクのFront Matterを一括更新）
     ///
+    /// 配列の値は要素単位で置き換える。
+    ///
     /// # Arguments
     /// * `workspace` - ワークスペース
     /// * `category` - タグカテゴリ名
@@ -21,77 +56,143 @@ impl TagService {
         category: &str,
         old_value: &str,
         new_value: &str,
+    ) -> Result<usize, io::Error> {
+        Self::merge_tag_values(workspace, category, &[old_value], new_value)
+    }
+
+    /// 複数のタグ値を1つの値に統合（全タスクのFront Matterを一括更新）
+    ///
+    /// 単一の値は型を維持したまま置き換え、配列は一致する要素だけを置き換える。
+    /// 配列内で統合先の値と重複した要素は1つにまとめる。
+    ///
+    /// # Arguments
+    /// * `workspace` - ワークスペース
+    /// * `category` - タグカテゴリ名
+    /// * `old_values` - 統合元の値
+    /// * `new_value` - 統合先の値
+    ///
+    /// # Returns
+    /// * `Result<usize, io::Error>` - 更新されたタスク数
+    pub fn merge_tag_values(
+        workspace: &mut Workspace,
+        category: &str,
+        old_values: &[&str],
+        new_value: &str,
     ) -> Result<usize, io::Error> {
         let mut updated_count = 0;
         let workspace_service = WorkspaceService::new();
 
         // 該当カテゴリを持つタスクIDを取得
         let task_ids: Vec<String> = if let Some(tag_category) = workspace.tag_index.categories.get(category) {
-            tag_category.task_ids.clone()
+            tag_category.task_ids.iter().cloned().collect()
         } else {
             return Ok(0); // カテゴリが存在しない場合は0を返す
         };
 
-        // 各タスクを更新
+        // 値が変わるタスクだけを書き換え、タグインデックスは差分で更新する
         for task_id in task_ids {
-            if let Some(task) = workspace.tasks.get_mut(&task_id) {
-                // 該当カテゴリの値をチェック
-                if let Some(value) = task.front_matter.tags.get(category) {
-                    let value_str = value.to_string_value();
-
-                    // 古い値と一致する場合のみ更新
-                    if value_str == old_value {
-                        // 新しい値を設定（型を維持）
-                        let new_tag_value = match value {
-                            TagValue::String(_) => TagValue::String(new_value.to_string()),
-                            TagValue::Number(_) => {
-                                // 数値の場合は変換を試みる
-                                if let Ok(num) = new_value.parse::<i64>() {
-                                    TagValue::Number(num)
-                                } else {
-                                    TagValue::String(new_value.to_string())
-                                }
-                            }
-                            TagValue::Float(_) => {
-                                // 浮動小数点の場合は変換を試みる
-                                if let Ok(num) = new_value.parse::<f64>() {
-                                    TagValue::Float(num)
-                                } else {
-                                    TagValue::String(new_value.to_string())
-                                }
-                            }
-                            TagValue::Bool(_) => {
-                                // 真偽値の場合は変換を試みる
-                                if let Ok(b) = new_value.parse::<bool>() {
-                                    TagValue::Bool(b)
-                                } else {
-                                    TagValue::String(new_value.to_string())
-                                }
-                            }
-                            TagValue::Array(_) => TagValue::String(new_value.to_string()),
-                        };
-
-                        task.front_matter.tags.insert(category.to_string(), new_tag_value);
-
-                        // タスクを保存
-                        workspace_service.save_task(task)?;
-                        updated_count += 1;
-                    }
-                }
-            }
-        }
+            let Some(task) = workspace.tasks.get(&task_id) else {
+                continue;
+            };
+            let Some(new_tag_value) = task
+                .front_matter
+                .tags
+                .get(category)
+                .and_then(|value| replace_value(value, old_values, new_value))
+            else {
+                continue;
+            };
 
-        // タグインデックスを再構築
-        workspace.tag_index = crate::models::TagIndex::new();
-        for (task_id, task) in &workspace.tasks {
-            workspace.tag_index.index_task(task_id, &task.front_matter.tags);
+            let mut task = task.clone();
+            task.front_matter.tags.insert(category.to_string(), new_tag_value);
+
+            // タスクを保存
+            workspace_service.save_task(&task)?;
+            workspace.upsert_task(task);
+            updated_count += 1;
         }
 
         Ok(updated_count)
     }
 
+    /// タグ値を正規の値に書き換える（全タスクのFront Matterを一括更新）
+    ///
+    /// タグ設定の正規化ルール（同義語・大文字小文字の扱い）を持つカテゴリが対象。
+    /// 配列は要素ごとに置き換え、正規の値が重複した要素は1つにまとめる。
+    ///
+    /// # Arguments
+    /// * `workspace` - ワークスペース
+    /// * `categories` - 対象のカテゴリ（Noneの場合はルールを持つすべてのカテゴリ）
+    /// * `dry_run` - trueの場合はファイルを書き換えずに差分だけを返す
+    ///
+    /// # Returns
+    /// * `Result<NormalizationReport, io::Error>` - 書き換える値の一覧と更新されたタスク数
+    pub fn normalize_tags(
+        workspace: &mut Workspace,
+        categories: Option<&[String]>,
+        dry_run: bool,
+    ) -> Result<NormalizationReport, io::Error> {
+        let mut rules: Vec<(String, ValueNormalization)> = workspace
+            .config
+            .tag_configs
+            .normalization_rules()
+            .into_iter()
+            .filter(|(name, _)| categories.map_or(true, |c| c.contains(name)))
+            .collect();
+        rules.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut report = NormalizationReport {
+            dry_run,
+            ..Default::default()
+        };
+        let workspace_service = WorkspaceService::new();
+
+        let mut task_ids: Vec<String> = workspace.tasks.keys().cloned().collect();
+        task_ids.sort();
+        for task_id in task_ids {
+            let Some(task) = workspace.tasks.get(&task_id) else {
+                continue;
+            };
+            let changes: Vec<TagValueChange> = rules
+                .iter()
+                .filter_map(|(category, normalization)| {
+                    let before = task.front_matter.tags.get(category)?;
+                    let after = normalize_value(before, normalization)?;
+                    Some(TagValueChange {
+                        task_id: task_id.clone(),
+                        category: category.clone(),
+                        before: before.clone(),
+                        after,
+                    })
+                })
+                .collect();
+            if changes.is_empty() {
+                continue;
+            }
+
+            if !dry_run {
+                let mut task = task.clone();
+                for change in &changes {
+                    task.front_matter
+                        .tags
+                        .insert(change.category.clone(), change.after.clone());
+                }
+
+                // タスクを保存
+                workspace_service.save_task(&task)?;
+                workspace.upsert_task(task);
+                report.tasks_updated += 1;
+            }
+            report.changes.extend(changes);
+        }
+
+        Ok(report)
+    }
+
     /// タグを削除（全タスクのFront Matterから削除）
     ///
+    /// 値を指定した場合、配列からはその要素だけを取り除く（空になったらカテゴリごと削除）。
+    ///
     /// # Arguments
     /// * `workspace` - ワークスペース
     /// * `category` - タグカテゴリ名
@@ -109,41 +210,451 @@ impl TagService {
 
         // 該当カテゴリを持つタスクIDを取得
         let task_ids: Vec<String> = if let Some(tag_category) = workspace.tag_index.categories.get(category) {
-            tag_category.task_ids.clone()
+            tag_category.task_ids.iter().cloned().collect()
         } else {
             return Ok(0); // カテゴリが存在しない場合は0を返す
         };
 
-        // 各タスクを更新
+        // 値が変わるタスクだけを書き換え、タグインデックスは差分で更新する
         for task_id in task_ids {
-            if let Some(task) = workspace.tasks.get_mut(&task_id) {
-                let should_remove = if let Some(target_value) = value {
+            if let Some(task) = workspace.tasks.get(&task_id) {
+                let mut task = task.clone();
+                let tags = &mut task.front_matter.tags;
+                let updated = match (tags.get(category), value) {
+                    (None, _) => false,
+                    // カテゴリごと削除
+                    (Some(_), None) => tags.remove(category).is_some(),
+                    // 配列は一致する要素だけを削除
+                    (Some(TagValue::Array(items)), Some(target))
+                        if items.iter().any(|item| item == target) =>
+                    {
+                        let remaining: Vec<String> =
+                            items.iter().filter(|item| *item != target).cloned().collect();
+                        if remaining.is_empty() {
+                            tags.remove(category);
+                        } else {
+                            tags.insert(category.to_string(), TagValue::Array(remaining));
+                        }
+                        true
+                    }
                     // 特定の値のみ削除
-                    if let Some(tag_value) = task.front_matter.tags.get(category) {
-                        tag_value.to_string_value() == target_value
-                    } else {
-                        false
+                    (Some(tag_value), Some(target)) => {
+                        tag_value.to_string_value() == target && tags.remove(category).is_some()
                     }
-                } else {
-                    // カテゴリごと削除
-                    task.front_matter.tags.contains_key(category)
                 };
 
-                if should_remove {
-                    task.front_matter.tags.remove(category);
-                    workspace_service.save_task(task)?;
+                if updated {
+                    workspace_service.save_task(&task)?;
+                    workspace.upsert_task(task);
                     updated_count += 1;
                 }
             }
         }
 
-        // タグインデックスを再構築
-        workspace.tag_index = crate::models::TagIndex::new();
-        for (task_id, task) in &workspace.tasks {
-            workspace.tag_index.index_task(task_id, &task.front_matter.tags);
+        Ok(updated_count)
+    }
+
+    /// タグカテゴリ（キー）の名前を変更する
+    ///
+    /// 全タスクのFront Matterとタグ順序に加え、テンプレート、許可カテゴリ、タグスキーマ
+    /// （計算フィールドの式を含む）、保存済みフィルタ・ソート、分析設定の参照も書き換える。
+    /// 移動先のカテゴリを既に持つタスクは`policy`に従って統合する。
+    ///
+    /// # Arguments
+    /// * `workspace` - ワークスペース
+    /// * `from` - 変更前のカテゴリ名
+    /// * `to` - 変更後のカテゴリ名
+    /// * `policy` - 両方のカテゴリを持つタスク・テンプレートの扱い
+    ///
+    /// # Returns
+    /// * `Result<CategoryRenameReport, io::Error>` - 更新内容（途中で書き込みに失敗した場合は、
+    ///   書き換えたファイルとワークスペースを元に戻してエラーを返す）
+    pub fn rename_category(
+        workspace: &mut Workspace,
+        from: &str,
+        to: &str,
+        policy: CategoryConflictPolicy,
+    ) -> Result<CategoryRenameReport, io::Error> {
+        let from = from.trim();
+        let to = to.trim();
+        if from.is_empty() || to.is_empty() || from == to {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid category name: '{}' -> '{}'", from, to),
+            ));
         }
 
-        Ok(updated_count)
+        let original = workspace.clone();
+        let mut journal = FileJournal::default();
+        let result = Self::move_category_everywhere(workspace, from, to, policy, &mut journal);
+        if result.is_err() {
+            journal.restore();
+            *workspace = original;
+        }
+        result
+    }
+
+    /// `rename_category`の本体（書き換えるファイルは`journal`に元の内容を記録する）
+    fn move_category_everywhere(
+        workspace: &mut Workspace,
+        from: &str,
+        to: &str,
+        policy: CategoryConflictPolicy,
+        journal: &mut FileJournal,
+    ) -> Result<CategoryRenameReport, io::Error> {
+        let mut report = CategoryRenameReport::default();
+        let workspace_service = WorkspaceService::new();
+
+        let mut task_ids: Vec<String> = workspace.tasks.keys().cloned().collect();
+        task_ids.sort();
+        for task_id in task_ids {
+            let Some(task) = workspace.tasks.get(&task_id) else {
+                continue;
+            };
+            let mut task = task.clone();
+            match move_category(&mut task.front_matter.tags, from, to, policy) {
+                CategoryMove::Absent => continue,
+                CategoryMove::Skipped => {
+                    report.conflicts.push(task_id.clone());
+                    report.skipped_tasks.push(task_id);
+                    continue;
+                }
+                CategoryMove::Moved { conflict } => {
+                    if conflict {
+                        report.conflicts.push(task_id.clone());
+                    }
+                }
+            }
+
+            if let Some(order) = task.tag_order.as_mut() {
+                rename_in_order(order, from, to);
+            }
+            journal.record(&task.file_path)?;
+            workspace_service.save_task(&task)?;
+            workspace.upsert_task(task);
+            report.tasks_updated += 1;
+        }
+
+        // テンプレートと許可カテゴリ
+        let mut config_changed = false;
+        for template in workspace.config.templates.templates.values_mut() {
+            // 移動先のタグを既に持ち、Skipで変更しなかったテンプレートは数えない
+            if matches!(move_category(&mut template.tags, from, to, policy), CategoryMove::Moved { .. }) {
+                report.templates_updated += 1;
+                config_changed = true;
+            }
+        }
+        // 変更しなかったタスクが残る場合は移動元も許可したままにする
+        let allowed = &mut workspace.config.allowed_categories;
+        if allowed.iter().any(|c| c == from) {
+            if report.skipped_tasks.is_empty() {
+                rename_in_order(allowed, from, to);
+                config_changed = true;
+            } else if !allowed.iter().any(|c| c == to) {
+                allowed.push(to.to_string());
+                config_changed = true;
+            }
+        }
+        let root = workspace.root_path.clone();
+        if config_changed {
+            journal.record(&root.join(".hienmark.json"))?;
+            WorkspaceService::save_config(&root.to_string_lossy(), &workspace.config)
+                .map_err(io::Error::other)?;
+        }
+
+        // タグスキーマ（変更しなかったタスクが残る場合は移動元の定義も残す）
+        let mut schema = TagSchemaService::load_schema(&root).map_err(io::Error::other)?;
+        if let Some(definition) = schema.tags.get(from).cloned() {
+            if report.skipped_tasks.is_empty() {
+                schema.tags.remove(from);
+            }
+            schema.tags.entry(to.to_string()).or_insert(definition);
+            report.schema_updated = true;
+        }
+        for definition in schema.tags.values_mut() {
+            if let TagAttribute::Computed(options) = &mut definition.attribute {
+                if let Ok(Some(expression)) =
+                    ExpressionParser::rename_reference(&options.expression, from, to)
+                {
+                    options.expression = expression;
+                    report.schema_updated = true;
+                }
+            }
+        }
+        for rule in &mut schema.rules {
+            for expression in [&mut rule.when, &mut rule.assert].into_iter().flatten() {
+                if let Ok(Some(renamed)) = ExpressionParser::rename_reference(expression, from, to) {
+                    *expression = renamed;
+                    report.schema_updated = true;
+                }
+            }
+            for name in rule.require.iter_mut().filter(|name| *name == from) {
+                *name = to.to_string();
+                report.schema_updated = true;
+            }
+        }
+        if report.schema_updated {
+            journal.record(&TagSchemaService::schema_path(&root))?;
+            TagSchemaService::save_schema(&root, &schema).map_err(io::Error::other)?;
+            workspace.apply_tag_schema(&schema);
+        }
+
+        report.filters_updated = Self::rename_in_filters_and_sorts(&root, from, to, journal)?;
+        report.analysis_settings_updated =
+            Self::rename_in_analysis_settings(&root, from, to, journal)?;
+
+        workspace.refresh_computed_tags();
+
+        Ok(report)
+    }
+
+    /// 保存済みフィルタ・ソートのタグ参照を書き換え、更新した件数を返す
+    fn rename_in_filters_and_sorts(
+        root: &Path,
+        from: &str,
+        to: &str,
+        journal: &mut FileJournal,
+    ) -> Result<usize, io::Error> {
+        let path = root.join(".hienmark").join("filters_and_sorts.json");
+        if !path.exists() {
+            return Ok(0);
+        }
+        // フロントエンドが書いた値をそのまま残すため、型付きモデルを経由せずに書き換える
+        let content = fs::read_to_string(&path)?;
+        let mut filters_and_sorts: Value =
+            serde_json::from_str(&content).map_err(io::Error::other)?;
+
+        let mut updated = 0;
+        for list in ["filters", "sorts"] {
+            if let Some(Value::Array(items)) = filters_and_sorts.get_mut(list) {
+                for item in items {
+                    if rename_in_json(item, &["tagKey"], from, to) {
+                        updated += 1;
+                    }
+                }
+            }
+        }
+
+        if updated > 0 {
+            let json = serde_json::to_string_pretty(&filters_and_sorts).map_err(io::Error::other)?;
+            journal.record(&path)?;
+            fs::write(&path, json)?;
+        }
+        Ok(updated)
+    }
+
+    /// 分析設定（チャートのマッピングとメトリック）のタグ参照を書き換える
+    ///
+    /// タグ名を指すフィールドだけを対象にし、チャートのタイトルなどの文字列は変更しない。
+    fn rename_in_analysis_settings(
+        root: &Path,
+        from: &str,
+        to: &str,
+        journal: &mut FileJournal,
+    ) -> Result<bool, io::Error> {
+        let root = root.to_path_buf();
+        let content = AnalysisSettingsService::load_settings(&root).map_err(io::Error::other)?;
+        let mut settings: Value = serde_json::from_str(&content).map_err(io::Error::other)?;
+
+        let mut changed = false;
+        if let Some(Value::Object(charts)) = settings.get_mut("chartMappings") {
+            for chart in charts.values_mut() {
+                if let Some(mapping) = chart.get_mut("mapping") {
+                    changed |= rename_fields(mapping, &CHART_MAPPING_TAG_KEYS, from, to);
+                }
+            }
+        }
+        if let Some(Value::Array(metrics)) = settings.get_mut("metrics") {
+            for metric in metrics {
+                changed |= rename_fields(metric, &["sourceTag"], from, to);
+                if let Some(expression) = metric.get_mut("filterExpression") {
+                    changed |= rename_in_json(expression, &["tagKey"], from, to);
+                }
+            }
+        }
+        if !changed {
+            return Ok(false);
+        }
+
+        let json = serde_json::to_string_pretty(&settings).map_err(io::Error::other)?;
+        journal.record(&AnalysisSettingsService::settings_path(&root))?;
+        AnalysisSettingsService::save_settings(&root, &json).map_err(io::Error::other)?;
+        Ok(true)
+    }
+}
+
+/// 値が`old_values`のいずれかに一致すれば`new_value`に置き換えた値を返す
+fn replace_value(value: &TagValue, old_values: &[&str], new_value: &str) -> Option<TagValue> {
+    if let TagValue::Array(items) = value {
+        if !items.iter().any(|item| old_values.contains(&item.as_str())) {
+            return None;
+        }
+        let mut replaced: Vec<String> = Vec::with_capacity(items.len());
+        for item in items {
+            let item = if old_values.contains(&item.as_str()) {
+                new_value.to_string()
+            } else {
+                item.clone()
+            };
+            if !replaced.contains(&item) {
+                replaced.push(item);
+            }
+        }
+        return Some(TagValue::Array(replaced));
+    }
+
+    if !old_values.contains(&value.to_string_value().as_str()) {
+        return None;
+    }
+    // 新しい値を設定（型を維持、変換できなければ文字列）
+    let as_string = || TagValue::String(new_value.to_string());
+    Some(match value {
+        TagValue::Number(_) => new_value.parse().map(TagValue::Number).unwrap_or_else(|_| as_string()),
+        TagValue::Float(_) => new_value.parse().map(TagValue::Float).unwrap_or_else(|_| as_string()),
+        TagValue::Bool(_) => new_value.parse().map(TagValue::Bool).unwrap_or_else(|_| as_string()),
+        _ => as_string(),
+    })
+}
+
+/// 文字列・配列の値を正規の値にした結果を返す（変わらなければNone）
+fn normalize_value(value: &TagValue, normalization: &ValueNormalization) -> Option<TagValue> {
+    let normalized = match value {
+        TagValue::String(s) => TagValue::String(normalization.canonical(s)),
+        TagValue::Array(_) => TagValue::Array(canonical_elements(value, Some(normalization))),
+        _ => return None,
+    };
+    (normalized != *value).then_some(normalized)
+}
+
+/// タグマップ内のカテゴリを`from`から`to`へ移す
+fn move_category(
+    tags: &mut HashMap<String, TagValue>,
+    from: &str,
+    to: &str,
+    policy: CategoryConflictPolicy,
+) -> CategoryMove {
+    if !tags.contains_key(from) {
+        return CategoryMove::Absent;
+    }
+    let conflict = tags.contains_key(to);
+    if conflict && policy == CategoryConflictPolicy::Skip {
+        return CategoryMove::Skipped;
+    }
+
+    let source = tags.remove(from).expect("checked above");
+    match (tags.get_mut(to), policy) {
+        (None, _) | (Some(_), CategoryConflictPolicy::Overwrite) => {
+            tags.insert(to.to_string(), source);
+        }
+        (Some(target), CategoryConflictPolicy::Combine) => {
+            *target = combine_values(target, &source);
+        }
+        (Some(_), _) => {}
+    }
+    CategoryMove::Moved { conflict }
+}
+
+/// 2つの値を重複なしの配列にまとめる（結果が1要素なら移動先の値をそのまま残す）
+fn combine_values(target: &TagValue, source: &TagValue) -> TagValue {
+    let elements = |value: &TagValue| match value {
+        TagValue::Array(items) => items.clone(),
+        other => vec![other.to_string_value()],
+    };
+
+    let mut combined = elements(target);
+    for item in elements(source) {
+        if !combined.contains(&item) {
+            combined.push(item);
+        }
+    }
+    if combined.len() == 1 && !matches!(target, TagValue::Array(_)) {
+        return target.clone();
+    }
+    TagValue::Array(combined)
+}
+
+/// 順序付きのカテゴリ一覧で名前を置き換える（`to`が既にあれば`from`を取り除く）
+fn rename_in_order(order: &mut Vec<String>, from: &str, to: &str) {
+    if order.iter().any(|key| key == to) {
+        order.retain(|key| key != from);
+    } else {
+        for key in order.iter_mut().filter(|key| *key == from) {
+            *key = to.to_string();
+        }
+    }
+}
+
+/// JSONオブジェクト直下の`keys`のフィールドのうち、`from`と一致するものを書き換える
+fn rename_fields(value: &mut Value, keys: &[&str], from: &str, to: &str) -> bool {
+    let Value::Object(map) = value else {
+        return false;
+    };
+    let mut changed = false;
+    for key in keys {
+        if let Some(field) = map.get_mut(*key).filter(|field| field.as_str() == Some(from)) {
+            *field = Value::String(to.to_string());
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// JSON中の`keys`のいずれかのキーを持つ値のうち、`from`と一致するものを書き換える
+fn rename_in_json(value: &mut Value, keys: &[&str], from: &str, to: &str) -> bool {
+    let mut changed = false;
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if keys.contains(&key.as_str()) && child.as_str() == Some(from) {
+                    *child = Value::String(to.to_string());
+                    changed = true;
+                } else {
+                    changed |= rename_in_json(child, keys, from, to);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                changed |= rename_in_json(item, keys, from, to);
+            }
+        }
+        _ => {}
+    }
+    changed
+}
+
+/// 書き換える前のファイルの内容（複数ファイルの更新が途中で失敗した場合に元に戻す）
+#[derive(Default)]
+struct FileJournal {
+    originals: Vec<(PathBuf, Option<Vec<u8>>)>,
+}
+
+impl FileJournal {
+    /// 書き換える前の内容を記録する（同じファイルは最初の1回だけ）
+    fn record(&mut self, path: &Path) -> Result<(), io::Error> {
+        if self.originals.iter().any(|(recorded, _)| recorded == path) {
+            return Ok(());
+        }
+        let original = match fs::read(path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        self.originals.push((path.to_path_buf(), original));
+        Ok(())
+    }
+
+    /// 記録したファイルを元の内容に戻す（元々なかったファイルは削除する）
+    fn restore(self) {
+        for (path, original) in self.originals.into_iter().rev() {
+            let result = match original {
+                Some(content) => fs::write(&path, content),
+                None => fs::remove_file(&path),
+            };
+            if let Err(e) = result {
+                log::error!("Failed to restore {}: {}", path.display(), e);
+            }
+        }
     }
 }
 
@@ -182,6 +693,7 @@ mod tests {
             content: "Task 1 content".to_string(),
             modified_at: chrono::Utc::now(),
             tag_order: None,
+            computed_tags: HashMap::new(),
         };
 
         let task2 = Task {
@@ -191,6 +703,7 @@ mod tests {
             content: "Task 2 content".to_string(),
             modified_at: chrono::Utc::now(),
             tag_order: None,
+            computed_tags: HashMap::new(),
         };
 
         // タスクをワークスペースに追加
@@ -226,6 +739,11 @@ mod tests {
             workspace.tasks["task2"].front_matter.tags["status"].to_string_value(),
             "in_progress"
         );
+
+        // 読み込み直さなくてもタグインデックスが更新されている
+        let status = &workspace.tag_index.categories["status"];
+        assert_eq!(status.values.get("in_progress"), Some(&2));
+        assert!(!status.values.contains_key("pending"));
     }
 
     #[test]
@@ -270,4 +788,330 @@ mod tests {
         assert!(!workspace.tasks["task1"].front_matter.tags.contains_key("priority"));
         assert!(!workspace.tasks["task2"].front_matter.tags.contains_key("priority"));
     }
+
+    #[test]
+    fn test_rename_category_updates_all_references() {
+        let (_temp_dir, mut workspace) = create_test_workspace();
+        let root = workspace.root_path.clone();
+        let hienmark_dir = root.join(".hienmark");
+        fs::create_dir_all(&hienmark_dir).unwrap();
+
+        let task1 = workspace.tasks.get_mut("task1").unwrap();
+        task1.tag_order = Some(vec!["status".to_string(), "priority".to_string()]);
+        let task2 = workspace.tasks.get_mut("task2").unwrap();
+        task2.front_matter.tags.insert("level".to_string(), TagValue::String("medium".to_string()));
+        let workspace_service = WorkspaceService::new();
+        for task in workspace.tasks.values() {
+            workspace_service.save_task(task).unwrap();
+        }
+
+        let mut template_tags = HashMap::new();
+        template_tags.insert("priority".to_string(), TagValue::String("low".to_string()));
+        workspace.config.templates.templates.insert(
+            "bug".to_string(),
+            crate::models::TagTemplate::new("bug".to_string(), None, template_tags),
+        );
+        fs::write(
+            hienmark_dir.join("tag_schema.json"),
+            r#"{"version": 1, "tags": {
+                "priority": {"type": "Select", "options": {"optionsList": ["high", "low"]}},
+                "urgent": {"type": "Computed", "options": {"expression": "priority == \"high\""}}
+            }, "rules": [
+                {"id": "owner", "when": "priority == high", "require": ["priority", "assignee"]}
+            ]}"#,
+        )
+        .unwrap();
+        fs::write(
+            hienmark_dir.join("filters_and_sorts.json"),
+            r#"{"filters": [{"name": "f", "description": null, "createdAt": "", "updatedAt": "",
+                "expression": {"condition": null, "logicalOperator": "AND", "expressions": [
+                    {"condition": {"tagKey": "priority", "operator": "==", "value": "high"}}
+                ]}}],
+               "sorts": [{"name": "s", "description": null, "handleMissing": "last", "createdAt": "", "updatedAt": "",
+                "sortKeys": [{"tagKey": "priority", "order": "asc", "customOrder": null}]}]}"#,
+        )
+        .unwrap();
+        fs::write(
+            hienmark_dir.join("analysis_settings.json"),
+            r#"{"chartMappings": {"pie": {"type": "pie", "mapping": {"category": "priority", "value": "priority"}},
+                                  "gantt": {"type": "gantt", "title": "priority", "mapping": {"title": "priority"}}},
+                "metrics": [{"id": "m", "name": "m", "calculationType": "sum", "sourceTag": "priority",
+                             "category": "priority",
+                             "filterExpression": {"condition": {"tagKey": "priority", "operator": "==", "value": "high"}}}]}"#,
+        )
+        .unwrap();
+
+        let report = TagService::rename_category(
+            &mut workspace,
+            "priority",
+            "level",
+            CategoryConflictPolicy::Combine,
+        )
+        .unwrap();
+
+        assert_eq!(report.tasks_updated, 2);
+        assert_eq!(report.conflicts, vec!["task2"]);
+        assert!(report.skipped_tasks.is_empty());
+        assert_eq!(report.templates_updated, 1);
+        assert!(report.schema_updated);
+        assert_eq!(report.filters_updated, 2);
+        assert!(report.analysis_settings_updated);
+
+        // 保存されたファイルから読み直して確認
+        let reloaded = workspace_service.load_workspace(root.clone()).unwrap();
+        let task1 = &reloaded.tasks["task1"];
+        assert_eq!(task1.front_matter.tags["level"], TagValue::String("high".to_string()));
+        assert!(!task1.front_matter.tags.contains_key("priority"));
+        assert_eq!(task1.tag_order.as_deref(), Some(&["status".to_string(), "level".to_string()][..]));
+        assert_eq!(
+            reloaded.tasks["task2"].front_matter.tags["level"],
+            TagValue::Array(vec!["medium".to_string(), "low".to_string()])
+        );
+        assert!(reloaded.config.templates.templates["bug"].tags.contains_key("level"));
+        assert_eq!(reloaded.tasks["task1"].computed_tags["urgent"], TagValue::Bool(true));
+        assert!(!workspace.tag_index.categories.contains_key("priority"));
+
+        let schema = TagSchemaService::load_schema(&root).unwrap();
+        assert!(!schema.tags.contains_key("priority"));
+        assert!(schema.tags.contains_key("level"));
+        assert_eq!(schema.rules[0].when.as_deref(), Some("level == high"));
+        assert_eq!(schema.rules[0].require, vec!["level", "assignee"]);
+
+        let filters = fs::read_to_string(hienmark_dir.join("filters_and_sorts.json")).unwrap();
+        assert!(!filters.contains("priority"));
+        let settings = fs::read_to_string(hienmark_dir.join("analysis_settings.json")).unwrap();
+        let settings: Value = serde_json::from_str(&settings).unwrap();
+        assert_eq!(settings["chartMappings"]["pie"]["mapping"]["category"], "level");
+        // メトリックIDなど、タグ名を指さないキーは変更しない
+        assert_eq!(settings["chartMappings"]["pie"]["mapping"]["value"], "priority");
+        assert_eq!(settings["metrics"][0]["sourceTag"], "level");
+        assert_eq!(settings["metrics"][0]["filterExpression"]["condition"]["tagKey"], "level");
+        assert_eq!(settings["chartMappings"]["gantt"]["mapping"]["title"], "level");
+        // マッピングの外にある同名のフィールドはタグ参照ではない
+        assert_eq!(settings["chartMappings"]["gantt"]["title"], "priority");
+        assert_eq!(settings["metrics"][0]["category"], "priority");
+    }
+
+    #[test]
+    fn test_rename_category_skip_leaves_templates_untouched() {
+        let (_temp_dir, mut workspace) = create_test_workspace();
+        let root = workspace.root_path.clone();
+        let workspace_service = WorkspaceService::new();
+        for task in workspace.tasks.values() {
+            workspace_service.save_task(task).unwrap();
+        }
+        let mut template_tags = HashMap::new();
+        template_tags.insert("priority".to_string(), TagValue::String("low".to_string()));
+        template_tags.insert("status".to_string(), TagValue::String("pending".to_string()));
+        workspace.config.templates.templates.insert(
+            "bug".to_string(),
+            crate::models::TagTemplate::new("bug".to_string(), None, template_tags),
+        );
+
+        // 前後の空白は無視される
+        let report = TagService::rename_category(
+            &mut workspace,
+            " priority ",
+            "status",
+            CategoryConflictPolicy::Skip,
+        )
+        .unwrap();
+
+        assert_eq!(report.tasks_updated, 0);
+        assert_eq!(report.skipped_tasks, vec!["task1", "task2"]);
+        assert_eq!(report.templates_updated, 0);
+        // 変更がないので設定も保存しない
+        assert!(!root.join(".hienmark.json").exists());
+    }
+
+    #[test]
+    fn test_rename_category_rolls_back_on_failure() {
+        let (_temp_dir, mut workspace) = create_test_workspace();
+        let root = workspace.root_path.clone();
+        let workspace_service = WorkspaceService::new();
+        for task in workspace.tasks.values() {
+            workspace_service.save_task(task).unwrap();
+        }
+        let original_task = fs::read_to_string(root.join("task1.md")).unwrap();
+        // タスクを書き換えた後、保存済みフィルタの読み込みで失敗させる
+        fs::create_dir_all(root.join(".hienmark").join("filters_and_sorts.json")).unwrap();
+
+        let result = TagService::rename_category(
+            &mut workspace,
+            "priority",
+            "level",
+            CategoryConflictPolicy::KeepTarget,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(root.join("task1.md")).unwrap(), original_task);
+        assert!(!TagSchemaService::schema_path(&root).exists());
+        assert!(workspace.tasks["task1"].front_matter.tags.contains_key("priority"));
+        assert!(workspace.tag_index.categories.contains_key("priority"));
+    }
+
+    #[test]
+    fn test_move_category_conflict_policies() {
+        let tags = || {
+            let mut tags = HashMap::new();
+            tags.insert("due".to_string(), TagValue::String("2025-01-31".to_string()));
+            tags.insert("due_date".to_string(), TagValue::String("2025-02-01".to_string()));
+            tags
+        };
+        let due_date = |policy| {
+            let mut tags = tags();
+            let result = move_category(&mut tags, "due", "due_date", policy);
+            (result, tags.get("due_date").cloned(), tags.contains_key("due"))
+        };
+
+        assert_eq!(
+            due_date(CategoryConflictPolicy::KeepTarget),
+            (
+                CategoryMove::Moved { conflict: true },
+                Some(TagValue::String("2025-02-01".to_string())),
+                false
+            )
+        );
+        assert_eq!(
+            due_date(CategoryConflictPolicy::Overwrite).1,
+            Some(TagValue::String("2025-01-31".to_string()))
+        );
+        assert_eq!(
+            due_date(CategoryConflictPolicy::Combine).1,
+            Some(TagValue::Array(vec!["2025-02-01".to_string(), "2025-01-31".to_string()]))
+        );
+        assert_eq!(
+            due_date(CategoryConflictPolicy::Skip),
+            (
+                CategoryMove::Skipped,
+                Some(TagValue::String("2025-02-01".to_string())),
+                true
+            )
+        );
+    }
+
+    #[test]
+    fn test_array_values_are_handled_per_element() {
+        let (_temp_dir, mut workspace) = create_test_workspace();
+        let labels = |items: &[&str]| TagValue::Array(items.iter().map(|s| s.to_string()).collect());
+        workspace.tasks.get_mut("task1").unwrap().front_matter.tags.insert("labels".to_string(), labels(&["bug", "ui"]));
+        workspace.tasks.get_mut("task2").unwrap().front_matter.tags.insert("labels".to_string(), labels(&["defect", "bug"]));
+        let workspace_service = WorkspaceService::new();
+        for (task_id, task) in &workspace.tasks {
+            workspace.tag_index.index_task(task_id, &task.front_matter.tags);
+            workspace_service.save_task(task).unwrap();
+        }
+        let labels_of = |workspace: &Workspace, task_id: &str| {
+            workspace.tasks[task_id].front_matter.tags.get("labels").cloned()
+        };
+
+        // 要素単位でリネームし、配列のまま残す
+        let count = TagService::rename_tag(&mut workspace, "labels", "ui", "frontend").unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(labels_of(&workspace, "task1"), Some(labels(&["bug", "frontend"])));
+        assert_eq!(labels_of(&workspace, "task2"), Some(labels(&["defect", "bug"])));
+
+        // 統合で重複した要素は1つにまとめる
+        let count = TagService::merge_tag_values(&mut workspace, "labels", &["defect"], "bug").unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(labels_of(&workspace, "task2"), Some(labels(&["bug"])));
+
+        // 値を指定した削除は要素だけを取り除き、空になったらカテゴリごと削除
+        let count = TagService::delete_tag(&mut workspace, "labels", Some("bug")).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(labels_of(&workspace, "task1"), Some(labels(&["frontend"])));
+        assert_eq!(labels_of(&workspace, "task2"), None);
+
+        // 保存されたファイルにも反映されている
+        let reloaded = workspace_service.load_workspace(workspace.root_path.clone()).unwrap();
+        assert_eq!(labels_of(&reloaded, "task1"), Some(labels(&["frontend"])));
+        assert_eq!(labels_of(&reloaded, "task2"), None);
+    }
+
+    #[test]
+    fn test_normalize_tags_rewrites_synonyms() {
+        use crate::models::tag_config::{CaseFolding, TagConfig, TagType};
+
+        let (_temp_dir, mut workspace) = create_test_workspace();
+        let tags_of = |workspace: &Workspace, task_id: &str, category: &str| {
+            workspace.tasks[task_id].front_matter.tags[category].clone()
+        };
+        let task1 = workspace.tasks.get_mut("task1").unwrap();
+        task1.front_matter.tags.insert("status".to_string(), TagValue::String("Done".to_string()));
+        task1.front_matter.tags.insert("priority".to_string(), TagValue::String("高".to_string()));
+        let task2 = workspace.tasks.get_mut("task2").unwrap();
+        task2.front_matter.tags.insert("status".to_string(), TagValue::String("closed".to_string()));
+        task2.front_matter.tags.insert(
+            "labels".to_string(),
+            TagValue::Array(vec!["UI".to_string(), "ui".to_string()]),
+        );
+        let workspace_service = WorkspaceService::new();
+        for task in workspace.tasks.values() {
+            workspace_service.save_task(task).unwrap();
+        }
+
+        let rules = |synonyms: &[(&str, &[&str])], case_folding| ValueNormalization {
+            synonyms: synonyms
+                .iter()
+                .map(|(canonical, synonyms)| {
+                    (canonical.to_string(), synonyms.iter().map(|s| s.to_string()).collect())
+                })
+                .collect(),
+            case_folding,
+        };
+        for (category, normalization) in [
+            ("status", rules(&[("done", &["closed", "close"])], CaseFolding::Insensitive)),
+            ("priority", rules(&[("high", &["高"])], CaseFolding::Sensitive)),
+            ("labels", rules(&[], CaseFolding::Lower)),
+        ] {
+            workspace.config.tag_configs.set_config(
+                category.to_string(),
+                TagConfig {
+                    normalization: Some(normalization),
+                    ..TagConfig::new(TagType::Select)
+                },
+            );
+        }
+        workspace.reindex_tags();
+
+        // インデックスは正規の値で数える
+        assert_eq!(workspace.tag_index.categories["status"].values["done"], 2);
+        assert_eq!(workspace.tag_index.categories["labels"].values["ui"], 1);
+
+        // ドライランは差分だけを返し、ファイルを書き換えない
+        let report = TagService::normalize_tags(&mut workspace, None, true).unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.tasks_updated, 0);
+        assert_eq!(
+            report
+                .changes
+                .iter()
+                .map(|c| (c.task_id.as_str(), c.category.as_str(), c.after.to_string_value()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("task1", "priority", "high".to_string()),
+                ("task1", "status", "done".to_string()),
+                ("task2", "labels", "[ui]".to_string()),
+                ("task2", "status", "done".to_string()),
+            ]
+        );
+        assert_eq!(tags_of(&workspace, "task1", "status"), TagValue::String("Done".to_string()));
+
+        let report = TagService::normalize_tags(&mut workspace, Some(&["status".to_string()]), false).unwrap();
+        assert_eq!((report.changes.len(), report.tasks_updated), (2, 2));
+        let reloaded = workspace_service.load_workspace(workspace.root_path.clone()).unwrap();
+        assert_eq!(tags_of(&reloaded, "task1", "status"), TagValue::String("done".to_string()));
+        assert_eq!(tags_of(&reloaded, "task2", "status"), TagValue::String("done".to_string()));
+        assert_eq!(tags_of(&reloaded, "task1", "priority"), TagValue::String("高".to_string()));
+    }
+
+    #[test]
+    fn test_replace_value_keeps_scalar_types() {
+        assert_eq!(replace_value(&TagValue::Number(1), &["1"], "2"), Some(TagValue::Number(2)));
+        assert_eq!(
+            replace_value(&TagValue::Number(1), &["1"], "one"),
+            Some(TagValue::String("one".to_string()))
+        );
+        assert_eq!(replace_value(&TagValue::Bool(true), &["false"], "x"), None);
+    }
 }
@@ -0,0 +1,165 @@
+use crate::models::{Task, Workspace};
+use crate::repository::database::IndexDatabase;
+use crate::repository::IndexBuilder;
+use crate::service::file_watcher::FileChangeEvent;
+use crate::service::{TagSchemaService, WorkspaceService};
+use serde::Serialize;
+use std::io;
+use std::path::Path;
+
+/// 外部で変更されたタスクの内容（`task-updated`イベントのペイロード）
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskUpdatedEvent {
+    /// 対象タスクのID
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+
+    /// リネーム前のタスクID（リネームの場合のみ）
+    #[serde(rename = "previousTaskId", skip_serializing_if = "Option::is_none")]
+    pub previous_task_id: Option<String>,
+
+    /// タスクが削除されたかどうか
+    pub deleted: bool,
+
+    /// 読み込み直したタスク（削除の場合はNone）
+    pub task: Option<Task>,
+}
+
+/// ウォッチャーが検知した1ファイル分の変更を、インデックスとメモリ上のワークスペースに反映する
+pub struct TaskSyncService;
+
+impl TaskSyncService {
+    /// 変更されたタスクファイルを読み込む
+    ///
+    /// # Arguments
+    /// * `change` - ウォッチャーが検知した変更
+    ///
+    /// # Returns
+    /// * `Result<TaskUpdatedEvent, io::Error>` - 変更後のタスク（Front Matterが不正な場合はエラー）
+    pub fn load_update(change: &FileChangeEvent) -> Result<TaskUpdatedEvent, io::Error> {
+        let deleted = change.event_type == "removed";
+        let task = if deleted {
+            None
+        } else {
+            Some(WorkspaceService::new().load_task(Path::new(&change.path))?)
+        };
+
+        Ok(TaskUpdatedEvent {
+            task_id: change.task_id.clone(),
+            previous_task_id: change.previous_task_id.clone(),
+            deleted,
+            task,
+        })
+    }
+
+    /// 変更をメモリ上のワークスペースとタグインデックスに反映
+    ///
+    /// 通知されるタスクにも計算フィールドの値が入るよう、`update`のタスクを直接更新する。
+    ///
+    /// # Arguments
+    /// * `workspace` - 更新するワークスペース
+    /// * `update` - `load_update`で読み込んだ変更
+    pub fn apply_to_workspace(workspace: &mut Workspace, update: &mut TaskUpdatedEvent) {
+        if let Some(previous_task_id) = &update.previous_task_id {
+            workspace.remove_task(previous_task_id);
+        }
+
+        match &mut update.task {
+            Some(task) => *task = workspace.upsert_task(task.clone()).clone(),
+            None => {
+                workspace.remove_task(&update.task_id);
+            }
+        }
+    }
+
+    /// 変更をSQLiteインデックスに反映
+    ///
+    /// # Arguments
+    /// * `db` - 書き込み接続
+    /// * `workspace_root` - ワークスペースのルートディレクトリ
+    /// * `change` - ウォッチャーが検知した変更
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - 反映結果
+    pub fn apply_to_index(
+        db: &IndexDatabase,
+        workspace_root: &Path,
+        change: &FileChangeEvent,
+    ) -> Result<(), io::Error> {
+        let builder = IndexBuilder::with_database(db)
+            .with_computed_fields(TagSchemaService::load_computed_fields(workspace_root));
+
+        if let Some(previous_task_id) = &change.previous_task_id {
+            builder.remove_task(previous_task_id)?;
+        }
+        if change.event_type == "removed" {
+            builder.remove_task(&change.task_id)
+        } else {
+            builder
+                .update_task_file(Path::new(&change.path), workspace_root)
+                .map(|_| ())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TagValue;
+    use crate::repository::task_index_repository::{
+        SqliteTaskIndexRepository, TaskIndexRepository,
+    };
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn change(event_type: &str, path: &Path, previous_task_id: Option<&str>) -> FileChangeEvent {
+        FileChangeEvent {
+            event_type: event_type.to_string(),
+            path: path.to_string_lossy().to_string(),
+            from: None,
+            task_id: path.file_stem().unwrap().to_string_lossy().to_string(),
+            previous_task_id: previous_task_id.map(str::to_string),
+            front_matter_changed: true,
+        }
+    }
+
+    #[test]
+    fn test_rename_and_delete_update_workspace_and_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("old.md"), "---\nstatus: open\n---\n\n# Task\n").unwrap();
+
+        let mut workspace = WorkspaceService::new().load_workspace(root.to_path_buf()).unwrap();
+        let db = IndexDatabase::new(root).unwrap();
+        IndexBuilder::with_database(&db).build_from_workspace(root).unwrap();
+
+        let new_path = root.join("new.md");
+        fs::rename(root.join("old.md"), &new_path).unwrap();
+        fs::write(&new_path, "---\nstatus: done\n---\n\n# Task\n").unwrap();
+        let renamed = change("renamed", &new_path, Some("old"));
+
+        let mut update = TaskSyncService::load_update(&renamed).unwrap();
+        TaskSyncService::apply_to_workspace(&mut workspace, &mut update);
+        TaskSyncService::apply_to_index(&db, root, &renamed).unwrap();
+
+        assert!(!workspace.tasks.contains_key("old"));
+        let task = update.task.as_ref().unwrap();
+        assert_eq!(task.front_matter.tags.get("status"), Some(&TagValue::String("done".into())));
+        assert_eq!(workspace.tag_index.categories["status"].values.get("done"), Some(&1));
+        assert_eq!(workspace.tag_index.categories["status"].values.get("open"), None);
+
+        let repo = SqliteTaskIndexRepository::new(&db);
+        let ids: Vec<_> = repo.list_tasks().unwrap().into_iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec!["new".to_string()]);
+
+        fs::remove_file(&new_path).unwrap();
+        let removed = change("removed", &new_path, None);
+        let mut update = TaskSyncService::load_update(&removed).unwrap();
+        assert!(update.deleted && update.task.is_none());
+        TaskSyncService::apply_to_workspace(&mut workspace, &mut update);
+        TaskSyncService::apply_to_index(&db, root, &removed).unwrap();
+
+        assert!(workspace.tasks.is_empty());
+        assert!(repo.list_tasks().unwrap().is_empty());
+    }
+}
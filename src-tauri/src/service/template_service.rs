@@ -1,5 +1,9 @@
-use crate::models::{TagTemplate, FrontMatter};
+use crate::models::{TagTemplate, TagValue, FrontMatter, TemplateRenderContext};
 use crate::parser::frontmatter::FrontMatterParser;
+use crate::parser::{FormulaParser, Placeholder, PlaceholderParser};
+use crate::service::TagSchemaService;
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
 
 /// テンプレート関連の操作を提供するサービス
 pub struct TemplateService;
@@ -10,15 +14,29 @@ impl TemplateService {
     /// # Arguments
     /// * `template` - 適用するテンプレート
     /// * `content` - タスクの本文（オプション、空文字列でもOK）
+    /// * `context` - 本文のプレースホルダーに埋め込む値
     ///
     /// # Returns
-    /// フロントマターを含む完全なMarkdown文字列
-    pub fn apply_template_to_new_task(template: &TagTemplate, content: &str) -> Result<String, String> {
-        let front_matter = FrontMatter {
+    /// フロントマターを含む完全なMarkdown文字列（タグの計算式と本文のプレースホルダーは評価済み）。
+    /// テンプレートの本文は`content`の後ろに追加され、未入力の`{{prompt:...}}`は空文字列になる
+    pub fn apply_template_to_new_task(
+        template: &TagTemplate,
+        content: &str,
+        context: &TemplateRenderContext,
+    ) -> Result<String, String> {
+        let now = Local::now();
+        let mut front_matter = FrontMatter {
             tags: template.tags.clone(),
         };
+        TagSchemaService::resolve_formulas(&mut front_matter.tags, now)?;
+
+        let body = match Self::render_body(template, &front_matter.tags, context, now, false)? {
+            Some(body) if content.trim().is_empty() => body,
+            Some(body) => format!("{}\n\n{}", content.trim_end(), body),
+            None => content.to_string(),
+        };
 
-        FrontMatterParser::serialize(&front_matter, content)
+        FrontMatterParser::serialize(&front_matter, &body)
             .map_err(|e| format!("Failed to serialize front matter: {}", e))
     }
 
@@ -39,15 +57,23 @@ impl TemplateService {
         let (mut front_matter, body) = FrontMatterParser::parse(task_content)
             .map_err(|e| format!("Failed to parse front matter: {}", e))?;
 
+        // テンプレートから設定したタグ（計算式の評価対象）
+        let mut applied: Vec<String> = Vec::new();
         if overwrite {
             // 上書きモード: テンプレートのタグで完全に置き換え
             front_matter.tags = template.tags.clone();
+            applied.extend(template.tags.keys().cloned());
         } else {
             // マージモード: 既存タグを保持し、テンプレートのタグを追加（既存タグが優先）
             for (key, value) in &template.tags {
-                front_matter.tags.entry(key.clone()).or_insert(value.clone());
+                if !front_matter.tags.contains_key(key) {
+                    front_matter.tags.insert(key.clone(), value.clone());
+                    applied.push(key.clone());
+                }
             }
         }
+        // 計算式は既存タスクのタグも参照できる（例: `=[start_date]+14`）
+        TagSchemaService::resolve_formulas_of(&mut front_matter.tags, &applied, Local::now())?;
 
         FrontMatterParser::serialize(&front_matter, &body)
             .map_err(|e| format!("Failed to serialize front matter: {}", e))
@@ -77,22 +103,70 @@ impl TemplateService {
         Ok(TagTemplate::new(template_name, description, front_matter.tags))
     }
 
-    /// テンプレートのプレビュー（YAML形式の文字列を生成）
+    /// テンプレートのプレビュー（新規タスクに適用した結果を生成）
     ///
     /// # Arguments
     /// * `template` - プレビューするテンプレート
+    /// * `context` - 本文のプレースホルダーに埋め込む値
     ///
     /// # Returns
-    /// YAML形式のフロントマター文字列（区切り記号含む）
-    pub fn preview_template(template: &TagTemplate) -> Result<String, String> {
-        let front_matter = FrontMatter {
+    /// フロントマターと本文を含むMarkdown文字列（計算式は現在の日時で評価）。
+    /// 未入力の`{{prompt:...}}`は入力欄の位置がわかるようにそのまま残す
+    pub fn preview_template(
+        template: &TagTemplate,
+        context: &TemplateRenderContext,
+    ) -> Result<String, String> {
+        let now = Local::now();
+        let mut front_matter = FrontMatter {
             tags: template.tags.clone(),
         };
+        TagSchemaService::resolve_formulas(&mut front_matter.tags, now)?;
 
-        FrontMatterParser::serialize(&front_matter, "")
+        let body = Self::render_body(template, &front_matter.tags, context, now, true)?;
+        FrontMatterParser::serialize(&front_matter, body.as_deref().unwrap_or(""))
             .map_err(|e| format!("Failed to serialize template: {}", e))
     }
 
+    /// テンプレート本文で入力を求める項目（`{{prompt:...}}`のラベル、出現順）
+    pub fn template_prompts(template: &TagTemplate) -> Vec<String> {
+        template
+            .body
+            .as_deref()
+            .map(PlaceholderParser::prompts)
+            .unwrap_or_default()
+    }
+
+    /// テンプレート本文のプレースホルダーを展開
+    ///
+    /// `{{=...}}`の計算式は評価済みのタグの値を参照できる。
+    /// `keep_unanswered`がtrueの場合、未入力の`{{prompt:...}}`を置換せずに残す。
+    fn render_body(
+        template: &TagTemplate,
+        tags: &HashMap<String, TagValue>,
+        context: &TemplateRenderContext,
+        now: DateTime<Local>,
+        keep_unanswered: bool,
+    ) -> Result<Option<String>, String> {
+        let Some(body) = template.body.as_deref() else {
+            return Ok(None);
+        };
+
+        PlaceholderParser::render(body, |placeholder| match placeholder {
+            Placeholder::Title => Ok(Some(context.title.clone())),
+            Placeholder::Date => Ok(Some(now.format("%Y-%m-%d").to_string())),
+            Placeholder::User => Ok(Some(context.user.clone())),
+            Placeholder::Prompt(label) => Ok(match context.prompts.get(label) {
+                Some(value) => Some(value.clone()),
+                None if keep_unanswered => None,
+                None => Some(String::new()),
+            }),
+            Placeholder::Formula(formula) => {
+                TagSchemaService::evaluate_formula(formula, tags, now).map(Some)
+            }
+        })
+        .map(Some)
+    }
+
     /// テンプレートのバリデーション
     ///
     /// # Arguments
@@ -107,8 +181,9 @@ impl TemplateService {
             errors.push("Template name cannot be empty".to_string());
         }
 
-        if template.tags.is_empty() {
-            errors.push("Template must have at least one tag".to_string());
+        let has_body = template.body.as_deref().is_some_and(|body| !body.trim().is_empty());
+        if template.tags.is_empty() && !has_body {
+            errors.push("Template must have at least one tag or a body".to_string());
         }
 
         // タグキーの検証
@@ -121,6 +196,25 @@ impl TemplateService {
             }
         }
 
+        // 計算式の構文は保存時に確認する（評価はタスク作成時）
+        for (key, value) in &template.tags {
+            if let TagValue::String(text) = value {
+                if FormulaParser::is_formula(text) {
+                    if let Err(e) = FormulaParser::parse(text) {
+                        errors.push(format!("Invalid formula in '{}': {}", key, e));
+                    }
+                }
+            }
+        }
+
+        if let Some(body) = &template.body {
+            errors.extend(
+                PlaceholderParser::validate(body)
+                    .into_iter()
+                    .map(|e| format!("Invalid placeholder in body: {}", e)),
+            );
+        }
+
         errors
     }
 }
@@ -144,7 +238,7 @@ mod tests {
             tags,
         );
 
-        let result = TemplateService::apply_template_to_new_task(&template, "# My Task\n\nTask content");
+        let result = TemplateService::apply_template_to_new_task(&template, "# My Task\n\nTask content", &TemplateRenderContext::default());
         assert!(result.is_ok());
 
         let content = result.unwrap();
@@ -206,6 +300,75 @@ mod tests {
         assert!(!content.contains("tags:"));
     }
 
+    #[test]
+    fn test_apply_template_evaluates_formulas() {
+        use std::collections::HashMap;
+
+        let mut tags = HashMap::new();
+        tags.insert("due_date".to_string(), TagValue::String("=[start_date]+2w".to_string()));
+
+        let template = TagTemplate::new("sprint".to_string(), None, tags);
+        assert!(TemplateService::validate_template(&template).is_empty());
+
+        let existing_content = "---\nstart_date: 2025-03-03\n---\n# Task";
+        let content = TemplateService::apply_template_to_existing_task(&template, existing_content, false).unwrap();
+        assert!(content.contains("due_date: 2025-03-17"));
+
+        // タスクにもともとある`=`で始まる値は計算式として評価しない
+        let existing_content = "---\nstart_date: 2025-03-03\nnote: =TODO\n---\n# Task";
+        let content = TemplateService::apply_template_to_existing_task(&template, existing_content, false).unwrap();
+        assert!(content.contains("due_date: 2025-03-17"));
+        assert!(content.contains("note: =TODO"));
+
+        // 参照先のない新規タスクではエラーになる
+        let error = TemplateService::apply_template_to_new_task(&template, "", &TemplateRenderContext::default()).unwrap_err();
+        assert_eq!(error, "Tag 'start_date' is not set");
+
+        let mut invalid = HashMap::new();
+        invalid.insert("due_date".to_string(), TagValue::String("=[TODAY]+".to_string()));
+        let errors = TemplateService::validate_template(&TagTemplate::new("bad".to_string(), None, invalid));
+        assert_eq!(errors, vec!["Invalid formula in 'due_date': Expected a number after '+' (column 10)"]);
+    }
+
+    #[test]
+    fn test_apply_template_renders_body() {
+        use std::collections::HashMap;
+
+        let mut tags = HashMap::new();
+        tags.insert("start_date".to_string(), TagValue::String("2025-03-03".to_string()));
+        tags.insert("due_date".to_string(), TagValue::String("=[start_date]+1w".to_string()));
+
+        let mut template = TagTemplate::new("bug".to_string(), None, tags);
+        template.body = Some(
+            "# {{title}}\n\nReported by {{user}}\n\n## Component\n{{prompt:Component}}\n\nFix by {{=[due_date]-1}}\n".to_string(),
+        );
+        assert!(TemplateService::validate_template(&template).is_empty());
+        assert_eq!(TemplateService::template_prompts(&template), vec!["Component"]);
+
+        let mut prompts = HashMap::new();
+        prompts.insert("Component".to_string(), "editor".to_string());
+        let context = TemplateRenderContext {
+            title: "Crash on save".to_string(),
+            user: "alice".to_string(),
+            prompts,
+        };
+        let content = TemplateService::apply_template_to_new_task(&template, "", &context).unwrap();
+        assert!(content.contains("due_date: 2025-03-10"));
+        assert!(content.ends_with(
+            "---\n\n# Crash on save\n\nReported by alice\n\n## Component\neditor\n\nFix by 2025-03-09\n"
+        ));
+
+        // プレビューでは未入力の項目をそのまま残す
+        let preview = TemplateService::preview_template(&template, &TemplateRenderContext::default()).unwrap();
+        assert!(preview.contains("## Component\n{{prompt:Component}}"));
+
+        template.body = Some("{{owner}}".to_string());
+        assert_eq!(
+            TemplateService::validate_template(&template),
+            vec!["Invalid placeholder in body: Unknown placeholder '{{owner}}'"]
+        );
+    }
+
     #[test]
     fn test_create_template_from_task() {
         let task_content = "---\nstatus: pending\npriority: high\n---\n# Task\n\nContent";
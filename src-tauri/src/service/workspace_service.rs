@@ -1,6 +1,9 @@
 use crate::models::{Task, Workspace, WorkspaceConfig};
 use crate::parser::FrontMatterParser;
+use crate::repository::database::IndexDatabase;
+use crate::service::{AssetService, TagSchemaService};
 use chrono::Utc;
+use std::collections::HashMap;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
@@ -41,12 +44,12 @@ impl WorkspaceService {
                     .unwrap_or("unknown")
                     .to_string();
 
-                // タグインデックスを更新
-                workspace.tag_index.index_task(&task_id, &task.front_matter.tags);
-
                 workspace.tasks.insert(task_id, task);
             }
         }
+        // タグインデックスはタグ設定の正規化ルールに合わせて作成する
+        workspace.reindex_tags();
+        workspace.refresh_computed_tags();
 
         Ok(workspace)
     }
@@ -88,6 +91,7 @@ impl WorkspaceService {
             content: body,
             modified_at,
             tag_order: if tag_order.is_empty() { None } else { Some(tag_order) },
+            computed_tags: HashMap::new(),
         })
     }
 
@@ -214,6 +218,7 @@ impl WorkspaceService {
             content: body,
             modified_at: Utc::now(),
             tag_order: if tag_order.is_empty() { None } else { Some(tag_order) },
+            computed_tags: HashMap::new(),
         };
 
         // ファイルに保存
@@ -224,49 +229,24 @@ impl WorkspaceService {
 
     /// アセットファイルをワークスペースにコピー
     ///
+    /// 内容のハッシュをファイル名とし、同じ内容のアセットが既にあれば再利用する。
+    ///
     /// # Arguments
+    /// * `db` - インデックスの書き込み接続（重複検出と所有タスクの登録に使用）
     /// * `workspace_root` - ワークスペースのルートディレクトリ
     /// * `source_path` - コピー元ファイルのパス
-    /// * `task_id` - アセットを関連付けるタスクID
+    /// * `task_id` - アセットを関連付けるタスクID（インデックスに所有タスクとして登録する）
     ///
     /// # Returns
     /// * `Result<String, io::Error>` - 相対パス（Markdown記法用）
     pub fn copy_asset_to_workspace(
         &self,
+        db: Option<&IndexDatabase>,
         workspace_root: &Path,
         source_path: &Path,
-        _task_id: &str,
+        task_id: &str,
     ) -> Result<String, io::Error> {
-        // .hienmark/assetsディレクトリのパス
-        let assets_dir = workspace_root.join(".hienmark").join("assets");
-        
-        // ディレクトリが存在しない場合は作成
-        if !assets_dir.exists() {
-            fs::create_dir_all(&assets_dir)?;
-        }
-        
-        // 一意なファイル名を生成（ファイル名_タイムスタンプ.拡張子）
-        let file_stem = source_path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("file");
-        let extension = source_path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|e| format!(".{}", e))
-            .unwrap_or_default();
-        let timestamp = chrono::Utc::now().timestamp();
-        let unique_name = format!("{}_{}{}", file_stem, timestamp, extension);
-        let dest_path = assets_dir.join(&unique_name);
-        
-        // ファイルをコピー
-        fs::copy(source_path, &dest_path)?;
-        
-        // 相対パスを生成（Markdown記法用）
-        // .hienmark/assets/file_123.png のような形式
-        let relative_path = format!(".hienmark/assets/{}", unique_name);
-        
-        Ok(relative_path)
+        AssetService::import_asset(db, workspace_root, source_path, task_id)
     }
 
     /// タスクをリネーム（ファイル名変更 + depends_on参照の更新）
@@ -277,13 +257,13 @@ impl WorkspaceService {
     /// * `new_task_id` - 新しいタスクID
     ///
     /// # Returns
-    /// * `Result<(), io::Error>` - リネーム結果
+    /// * `Result<Vec<PathBuf>, io::Error>` - depends_on参照を書き換えたタスクファイル
     pub fn rename_task(
         &self,
         workspace_root: &Path,
         old_task_id: &str,
         new_task_id: &str,
-    ) -> Result<(), io::Error> {
+    ) -> Result<Vec<PathBuf>, io::Error> {
         let old_file_path = workspace_root.join(format!("{}.md", old_task_id));
         let new_file_path = workspace_root.join(format!("{}.md", new_task_id));
 
@@ -307,9 +287,7 @@ impl WorkspaceService {
         fs::rename(&old_file_path, &new_file_path)?;
 
         // depends_on参照を更新
-        self.update_depends_on_references(workspace_root, old_task_id, new_task_id)?;
-
-        Ok(())
+        self.update_depends_on_references(workspace_root, old_task_id, new_task_id)
     }
 
     /// depends_on参照を更新
@@ -320,15 +298,16 @@ impl WorkspaceService {
     /// * `new_task_id` - 新しいタスクID
     ///
     /// # Returns
-    /// * `Result<(), io::Error>` - 更新結果
+    /// * `Result<Vec<PathBuf>, io::Error>` - 書き換えたタスクファイル
     fn update_depends_on_references(
         &self,
         workspace_root: &Path,
         old_task_id: &str,
         new_task_id: &str,
-    ) -> Result<(), io::Error> {
+    ) -> Result<Vec<PathBuf>, io::Error> {
         // 全タスクファイルをスキャン
         let md_files = self.scan_markdown_files(workspace_root)?;
+        let mut rewritten = Vec::new();
 
         for file_path in md_files {
             let content = fs::read_to_string(&file_path)?;
@@ -342,10 +321,11 @@ impl WorkspaceService {
             // 内容が変更された場合のみ書き込み
             if updated_content != content {
                 fs::write(&file_path, updated_content)?;
+                rewritten.push(file_path);
             }
         }
 
-        Ok(())
+        Ok(rewritten)
     }
 
     /// ディレクトリを作成
@@ -447,13 +427,20 @@ impl WorkspaceService {
             let config_json = fs::read_to_string(&config_path)
                 .map_err(|e| format!("Failed to read config: {}", e))?;
 
-            let config: WorkspaceConfig = serde_json::from_str(&config_json)
+            let mut config: WorkspaceConfig = serde_json::from_str(&config_json)
                 .map_err(|e| format!("Failed to parse config: {}", e))?;
 
+            // タグ設定はタグスキーマから再構成する（旧形式のtag_configsも読み込まれる）
+            config.apply_tag_schema(&TagSchemaService::load_schema(&root_path)?);
+
             Ok(config)
         } else {
             // デフォルト設定を返す
-            Ok(WorkspaceConfig::default())
+            let mut config = WorkspaceConfig::default();
+            if TagSchemaService::schema_path(&root_path).exists() {
+                config.apply_tag_schema(&TagSchemaService::load_schema(&root_path)?);
+            }
+            Ok(config)
         }
     }
 
@@ -469,6 +456,9 @@ impl WorkspaceService {
         let root_path = PathBuf::from(workspace_path);
         let config_path = root_path.join(".hienmark.json");
 
+        // 旧形式のtag_configsは保存する設定に含まれないため、上書きする前にタグスキーマへ移行する
+        TagSchemaService::migrate_schema(&root_path)?;
+
         // 設定をJSON形式で保存
         let config_json = serde_json::to_string_pretty(&config)
             .map_err(|e| format!("Failed to serialize config: {}", e))?;
@@ -556,6 +546,34 @@ This is a test task."#;
         assert!(!task.file_path.exists());
     }
 
+    #[test]
+    fn test_rename_task_updates_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("old.md"), "---\nstatus: open\n---\n\n# Old\n").unwrap();
+        fs::write(root.join("child.md"), "---\ndepends_on: old\n---\n\n# Child\n").unwrap();
+
+        let service = WorkspaceService::new();
+        let mut workspace = service.load_workspace(root.to_path_buf()).unwrap();
+
+        let rewritten = service.rename_task(root, "old", "new").unwrap();
+        assert_eq!(rewritten, vec![root.join("child.md")]);
+
+        // メモリ上のワークスペースとタグインデックスに反映
+        workspace.remove_task("old");
+        workspace.upsert_task(service.load_task(&root.join("new.md")).unwrap());
+        for file_path in &rewritten {
+            workspace.upsert_task(service.load_task(file_path).unwrap());
+        }
+
+        assert!(!workspace.tasks.contains_key("old"));
+        assert!(workspace.tasks.contains_key("new"));
+        assert_eq!(workspace.tag_index.categories["status"].task_ids.len(), 1);
+        assert!(workspace.tag_index.categories["status"].task_ids.contains("new"));
+        assert_eq!(workspace.tag_index.categories["depends_on"].values.get("new"), Some(&1));
+        assert_eq!(workspace.tag_index.categories["depends_on"].values.get("old"), None);
+    }
+
     #[test]
     fn test_save_task() {
         let temp_dir = TempDir::new().unwrap();
@@ -580,6 +598,7 @@ Content has been updated."#;
             content: body,
             modified_at: Utc::now(),
             tag_order: None,
+            computed_tags: HashMap::new(),
         };
 
         // 保存
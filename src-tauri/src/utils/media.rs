@@ -0,0 +1,269 @@
+// Media type detection from file contents
+
+/// ファイル内容から判定したメディア情報
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaInfo {
+    /// MIMEタイプ（判定できない場合はNone）
+    pub mime_type: Option<&'static str>,
+    /// 画像の幅（ピクセル）
+    pub width: Option<u32>,
+    /// 画像の高さ（ピクセル）
+    pub height: Option<u32>,
+}
+
+impl MediaInfo {
+    /// ファイル内容を解析してメディア情報を取得
+    pub fn inspect(bytes: &[u8]) -> Self {
+        let mime_type = detect_mime_type(bytes);
+        let dimensions = mime_type.and_then(|mime| image_dimensions(bytes, mime));
+
+        Self {
+            mime_type,
+            width: dimensions.map(|(w, _)| w),
+            height: dimensions.map(|(_, h)| h),
+        }
+    }
+}
+
+/// マジックバイトからMIMEタイプを判定
+pub fn detect_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    let starts = |magic: &[u8]| bytes.starts_with(magic);
+    let at = |offset: usize, magic: &[u8]| bytes.get(offset..offset + magic.len()) == Some(magic);
+
+    let mime = if starts(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if starts(b"\xFF\xD8\xFF") {
+        "image/jpeg"
+    } else if starts(b"GIF87a") || starts(b"GIF89a") {
+        "image/gif"
+    } else if starts(b"RIFF") && at(8, b"WEBP") {
+        "image/webp"
+    } else if starts(b"RIFF") && at(8, b"WAVE") {
+        "audio/wav"
+    } else if starts(b"BM") && bytes.len() >= 26 {
+        "image/bmp"
+    } else if starts(b"II*\0") || starts(b"MM\0*") {
+        "image/tiff"
+    } else if starts(b"\0\0\x01\0") {
+        "image/x-icon"
+    } else if at(4, b"ftyp") {
+        match bytes.get(8..12) {
+            Some(b"avif") => "image/avif",
+            Some(b"heic") | Some(b"heix") => "image/heic",
+            Some(b"qt  ") => "video/quicktime",
+            _ => "video/mp4",
+        }
+    } else if starts(b"\x1A\x45\xDF\xA3") {
+        "video/webm"
+    } else if starts(b"OggS") {
+        "audio/ogg"
+    } else if starts(b"ID3") || starts(b"\xFF\xFB") {
+        "audio/mpeg"
+    } else if starts(b"%PDF-") {
+        "application/pdf"
+    } else if starts(b"PK\x03\x04") {
+        "application/zip"
+    } else if is_svg(bytes) {
+        "image/svg+xml"
+    } else {
+        return None;
+    };
+
+    Some(mime)
+}
+
+/// MIMEタイプに対応する拡張子（ドットなし）
+pub fn extension_for_mime_type(mime_type: &str) -> Option<&'static str> {
+    let ext = match mime_type {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/bmp" => "bmp",
+        "image/tiff" => "tiff",
+        "image/x-icon" => "ico",
+        "image/avif" => "avif",
+        "image/heic" => "heic",
+        "image/svg+xml" => "svg",
+        "video/mp4" => "mp4",
+        "video/quicktime" => "mov",
+        "video/webm" => "webm",
+        "audio/wav" => "wav",
+        "audio/ogg" => "ogg",
+        "audio/mpeg" => "mp3",
+        "application/pdf" => "pdf",
+        "application/zip" => "zip",
+        _ => return None,
+    };
+    Some(ext)
+}
+
+/// 先頭付近に`<svg`要素を含むテキストかどうか
+fn is_svg(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(1024)];
+    let Ok(text) = std::str::from_utf8(head) else {
+        return false;
+    };
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    (text.starts_with("<?xml") || text.starts_with("<svg") || text.starts_with("<!--"))
+        && text.contains("<svg")
+}
+
+/// 画像の幅・高さをヘッダーから取得
+pub fn image_dimensions(bytes: &[u8], mime_type: &str) -> Option<(u32, u32)> {
+    match mime_type {
+        "image/png" => {
+            // IHDRチャンクはシグネチャの直後に必ず置かれる
+            if bytes.get(12..16)? != b"IHDR" {
+                return None;
+            }
+            Some((read_u32_be(bytes, 16)?, read_u32_be(bytes, 20)?))
+        }
+        "image/gif" => Some((read_u16_le(bytes, 6)? as u32, read_u16_le(bytes, 8)? as u32)),
+        "image/bmp" => {
+            let width = read_u32_le(bytes, 18)? as i32;
+            // 高さが負の場合はトップダウン形式
+            let height = read_u32_le(bytes, 22)? as i32;
+            Some((width.unsigned_abs(), height.unsigned_abs()))
+        }
+        "image/jpeg" => jpeg_dimensions(bytes),
+        "image/webp" => webp_dimensions(bytes),
+        _ => None,
+    }
+}
+
+/// JPEGのSOFセグメントからサイズを取得
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut offset = 2;
+    loop {
+        // マーカー前のフィルバイトを読み飛ばす
+        while *bytes.get(offset)? == 0xFF && *bytes.get(offset + 1)? == 0xFF {
+            offset += 1;
+        }
+        if *bytes.get(offset)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(offset + 1)?;
+        offset += 2;
+
+        match marker {
+            // スタンドアロンマーカー（長さフィールドなし）
+            0x01 | 0xD0..=0xD7 => continue,
+            // SOF0-SOF15（DHT・JPG・DACを除く）
+            0xC0..=0xCF if marker != 0xC4 && marker != 0xC8 && marker != 0xCC => {
+                let height = read_u16_be(bytes, offset + 3)? as u32;
+                let width = read_u16_be(bytes, offset + 5)? as u32;
+                return Some((width, height));
+            }
+            // EOI・SOS以降にはSOFは現れない
+            0xD9 | 0xDA => return None,
+            _ => {
+                let length = read_u16_be(bytes, offset)? as usize;
+                offset += length;
+            }
+        }
+    }
+}
+
+/// WebP（VP8・VP8L・VP8X）からサイズを取得
+fn webp_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    match bytes.get(12..16)? {
+        b"VP8 " => {
+            // キーフレームの開始コードの後に14bitずつ格納
+            if bytes.get(23..26)? != [0x9D, 0x01, 0x2A] {
+                return None;
+            }
+            let width = read_u16_le(bytes, 26)? & 0x3FFF;
+            let height = read_u16_le(bytes, 28)? & 0x3FFF;
+            Some((width as u32, height as u32))
+        }
+        b"VP8L" => {
+            if *bytes.get(20)? != 0x2F {
+                return None;
+            }
+            let bits = read_u32_le(bytes, 21)?;
+            let width = (bits & 0x3FFF) + 1;
+            let height = ((bits >> 14) & 0x3FFF) + 1;
+            Some((width, height))
+        }
+        b"VP8X" => {
+            let width = read_u24_le(bytes, 24)? + 1;
+            let height = read_u24_le(bytes, 27)? + 1;
+            Some((width, height))
+        }
+        _ => None,
+    }
+}
+
+fn read_u16_be(bytes: &[u8], offset: usize) -> Option<u16> {
+    let b = bytes.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_u16_le(bytes: &[u8], offset: usize) -> Option<u16> {
+    let b = bytes.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u24_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    let b = bytes.get(offset..offset + 3)?;
+    Some(u32::from_le_bytes([b[0], b[1], b[2], 0]))
+}
+
+fn read_u32_be(bytes: &[u8], offset: usize) -> Option<u32> {
+    let b = bytes.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    let b = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn test_inspect_png() {
+        let info = MediaInfo::inspect(&png_header(640, 480));
+        assert_eq!(info.mime_type, Some("image/png"));
+        assert_eq!((info.width, info.height), (Some(640), Some(480)));
+    }
+
+    #[test]
+    fn test_inspect_jpeg_and_gif() {
+        // SOI + APP0(長さ4) + SOF0
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00,
+            0x78, 0x00, 0xA0,
+        ];
+        let info = MediaInfo::inspect(&jpeg);
+        assert_eq!(info.mime_type, Some("image/jpeg"));
+        assert_eq!((info.width, info.height), (Some(160), Some(120)));
+
+        let gif = b"GIF89a\x20\x00\x10\x00";
+        let info = MediaInfo::inspect(gif);
+        assert_eq!(info.mime_type, Some("image/gif"));
+        assert_eq!((info.width, info.height), (Some(32), Some(16)));
+    }
+
+    #[test]
+    fn test_detect_non_image_types() {
+        assert_eq!(detect_mime_type(b"%PDF-1.7\n"), Some("application/pdf"));
+        assert_eq!(
+            detect_mime_type(b"<?xml version=\"1.0\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\"/>"),
+            Some("image/svg+xml")
+        );
+        assert_eq!(detect_mime_type(b"plain text"), None);
+        assert_eq!(MediaInfo::inspect(b"%PDF-1.7\n").width, None);
+    }
+}
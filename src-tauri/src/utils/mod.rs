@@ -2,3 +2,4 @@
 // pub mod file_watcher;
 // pub mod cache;
 // pub mod path_validator;
+pub mod media;
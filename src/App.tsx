@@ -11,6 +11,7 @@ import { TagSettingsView } from './components/TagManagement/TagSettingsView';
 import { Settings } from './components/TagManagement/Settings';
 import { TemplateManager } from './components/TemplateManagement';
 import { TemplateSelector } from './components/TemplateSelector';
+import type { TemplateInputValues } from './components/TemplateManagement/TemplatePromptFields';
 import { AnalysisDashboard } from './components/Analysis';
 import { FolderTree } from './components/FolderTree';
 import { Resizer } from './components/Resizer';
@@ -39,7 +40,7 @@ function AppContent() {
     ready: t.app.ready,
   });
   const { tagIndex, tagConfigs } = useTags(workspace?.rootPath || null);
-  const { templates, defaultTemplate, applyToNewTask } = useTemplates(workspace?.rootPath || null);
+  const { templates, defaultTemplate, applyToNewTask, getTemplatePrompts } = useTemplates(workspace?.rootPath || null);
   const { theme, updateTheme } = useTheme(workspace?.rootPath || null);
   
   // タブUI状態管理
@@ -182,14 +183,23 @@ function AppContent() {
     }
   };
 
-  const handleTemplateSelect = async (templateName: string | null) => {
+  const handleTemplateSelect = async (templateName: string | null, values: TemplateInputValues) => {
     if (!pendingTaskId) return;
 
     let initialContent = '# New Task\n\nStart writing...';
     
     if (templateName) {
       try {
-        initialContent = await applyToNewTask(templateName, initialContent);
+        // 本文の{{title}}と{{prompt:...}}の値はテンプレート選択モーダルで入力してもらう
+        // （タイトルの初期値はタスクID）
+        // 本文を持つテンプレートはその本文をそのまま使う
+        const hasBody = !!templates.find((template) => template.name === templateName)?.body;
+        initialContent = await applyToNewTask(
+          templateName,
+          hasBody ? '' : initialContent,
+          values.title,
+          values.prompts
+        );
       } catch (err) {
         console.warn('Failed to apply template:', err);
         // テンプレート適用に失敗した場合は空のタスクで作成
@@ -572,6 +582,8 @@ function AppContent() {
         <TemplateSelector
           templates={templates}
           defaultTemplate={defaultTemplate}
+          defaultTitle={pendingTaskId ?? ''}
+          loadPrompts={getTemplatePrompts}
           onSelect={handleTemplateSelect}
           onCancel={handleTemplateSelectorCancel}
         />
@@ -224,10 +224,12 @@ export const AnalysisDashboard: React.FC<AnalysisDashboardProps> = ({
                   effFilterDateField || undefined,
                   effStartDate || undefined,
                   effEndDate || undefined,
+                  pieMapping.depth,
                 )
               : await AnalysisService.generatePieChartWithDsl(
                   workspacePath,
-                  pieMapping.category
+                  pieMapping.category,
+                  pieMapping.depth
                 );
             code = output.mermaid;
             chartData = output.data;
@@ -248,10 +250,12 @@ export const AnalysisDashboard: React.FC<AnalysisDashboardProps> = ({
                   effFilterDateField || undefined,
                   effStartDate || undefined,
                   effEndDate || undefined,
+                  barMapping.depth,
                 )
               : await AnalysisService.generateBarChartWithDsl(
                   workspacePath,
-                  barMapping.category
+                  barMapping.category,
+                  barMapping.depth
                 );
             code = output.mermaid;
             chartData = output.data;
@@ -1,8 +1,11 @@
 // import React from 'react';
+import type { TagValue } from '../../types/task';
+import { parseCurrency, toCurrencyTagValue } from '../../utils/typedValues';
 
 interface CurrencyInputProps {
-  value: number | undefined;
-  onChange: (value: number) => void;
+  /** 既定の通貨の金額、または`1200.50 USD`形式の文字列 */
+  value: number | string | undefined;
+  onChange: (value: TagValue) => void;
   schema?: {
     type: 'Currency';
     options: {
@@ -20,39 +23,55 @@ export function CurrencyInput({ value, onChange, schema }: CurrencyInputProps) {
   const max = schema?.options?.max;
   const decimalPlaces = schema?.options?.decimalPlaces || 2;
   const currencyFormat = schema?.options?.currencyFormat || 'JPY';
+  // 既定と異なる通貨の金額は通貨コード付きで保存する
+  const parsed = parseCurrency(value, currencyFormat);
+  const amount = parsed?.amount;
+  const currency = parsed?.currency ?? currencyFormat;
   
   const step = Math.pow(10, -decimalPlaces);
 
   const formatCurrency = (amount: number): string => {
-    if (currencyFormat === 'JPY') {
+    if (currency === 'JPY') {
       return `¥${amount.toLocaleString()}`;
-    } else if (currencyFormat === 'USD') {
+    } else if (currency === 'USD') {
       return `$${amount.toFixed(2)}`;
-    } else if (currencyFormat === 'EUR') {
+    } else if (currency === 'EUR') {
       return `€${amount.toFixed(2)}`;
     }
-    return `${amount.toFixed(2)}`;
+    return `${amount.toFixed(2)} ${currency}`;
   };
 
   return (
     <div className="dynamic-input currency-input">
       <input
         type="number"
-        value={value ?? ''}
+        value={amount ?? ''}
         min={min}
         max={max}
         step={step}
         onChange={(e) => {
           const numValue = parseFloat(e.target.value);
           if (!isNaN(numValue)) {
-            onChange(numValue);
+            onChange(toCurrencyTagValue({ amount: numValue, currency }, currencyFormat));
           }
         }}
         placeholder={min !== undefined && max !== undefined 
           ? `${min}~${max}の数値を入力` 
           : '金額を入力'}
       />
-      <span className="input-suffix">{formatCurrency(value || 0)}</span>
+      <input
+        type="text"
+        className="currency-code"
+        value={currency}
+        maxLength={3}
+        onChange={(e) => {
+          const code = e.target.value.toUpperCase();
+          if (/^[A-Z]{3}$/.test(code)) {
+            onChange(toCurrencyTagValue({ amount: amount ?? 0, currency: code }, currencyFormat));
+          }
+        }}
+      />
+      <span className="input-suffix">{formatCurrency(amount || 0)}</span>
       {(min !== undefined || max !== undefined) && (
         <div className="input-hint">
           範囲: {min !== undefined ? formatCurrency(min) : '∞'} ~ {max !== undefined ? formatCurrency(max) : '∞'}
@@ -29,7 +29,9 @@ export function DateInput({ value, onChange, schema }: DateInputProps) {
         } else if (defaultValue.type === 'dynamic') {
           try {
             const calculatedValue = await tagSchemaService.getDynamicDefaultValue(defaultValue.formula);
-            setDefaultValueResolved(calculatedValue);
+            // ISO 8601の結果を入力欄の形式（YYYY-MM-DD / YYYY-MM-DDTHH:mm）に合わせる
+            const dateOnlyFormat = schema.options.format === 'dateOnly';
+            setDefaultValueResolved(calculatedValue.slice(0, dateOnlyFormat ? 10 : 16));
           } catch (err) {
             console.error('Failed to calculate dynamic default value:', err);
             setDefaultValueResolved(null);
@@ -39,7 +41,7 @@ export function DateInput({ value, onChange, schema }: DateInputProps) {
     };
 
     resolveDefaultValue();
-  }, [schema?.options?.defaultValue]);
+  }, [schema?.options?.defaultValue, schema?.options?.format]);
 
   const dateOnly = schema?.options?.format === 'dateOnly';
   const inputType = dateOnly ? 'date' : 'datetime-local';
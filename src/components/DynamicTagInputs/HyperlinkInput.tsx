@@ -1,9 +1,11 @@
 import { useState } from 'react';
 import type { HyperlinkValue } from '../../types/task';
+import { formatHyperlink, parseHyperlink } from '../../utils/typedValues';
 
 interface HyperlinkInputProps {
-  value: HyperlinkValue | undefined;
-  onChange: (value: HyperlinkValue) => void;
+  /** `[text](url)`またはURLのみの文字列（旧形式のオブジェクトも受け付ける） */
+  value: string | HyperlinkValue | undefined;
+  onChange: (value: string) => void;
   schema?: {
     type: 'Hyperlink';
     options: {};
@@ -11,19 +13,20 @@ interface HyperlinkInputProps {
 }
 
 export function HyperlinkInput({ value, onChange, schema: _schema }: HyperlinkInputProps) {
-  const [url, setUrl] = useState(value?.url || '');
-  const [text, setText] = useState(value?.text || '');
+  const initial = parseHyperlink(value);
+  const [url, setUrl] = useState(initial.url);
+  const [text, setText] = useState(initial.text);
 
   const handleUrlChange = (e: React.ChangeEvent<HTMLInputElement>) => {
     const newUrl = e.target.value;
     setUrl(newUrl);
-    onChange({ url: newUrl, text });
+    onChange(formatHyperlink({ url: newUrl, text }));
   };
 
   const handleTextChange = (e: React.ChangeEvent<HTMLInputElement>) => {
     const newText = e.target.value;
     setText(newText);
-    onChange({ url, text: newText });
+    onChange(formatHyperlink({ url, text: newText }));
   };
 
   return (
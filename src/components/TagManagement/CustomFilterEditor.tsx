@@ -38,6 +38,7 @@ export function CustomFilterEditor({
     { value: 'starts_with', label: 'Starts With' },
     { value: 'ends_with', label: 'Ends With' },
     { value: 'regex', label: 'Regex' },
+    { value: 'under', label: 'Under (hierarchical tag)' },
   ];
 
   const handleAddCondition = () => {
@@ -77,6 +77,7 @@ export function MetricEditor({ metric, tagSchema, onSave, onCancel, t }: MetricE
     { value: 'starts_with', label: 'で始まる (starts_with)' },
     { value: 'ends_with', label: 'で終わる (ends_with)' },
     { value: 'regex', label: '正規表現 (regex)' },
+    { value: 'under', label: '階層の配下 (under)' },
   ];
 
   useEffect(() => {
@@ -140,6 +140,26 @@ function AnalysisSettingsSection({ workspacePath, tagIndex, t }: AnalysisSetting
     await handleSave(newSettings);
   };
 
+  // 円グラフ/棒グラフで階層タグを集計する深さを更新
+  const handleChartDepthChange = async (chartType: 'pie' | 'bar', value: string) => {
+    if (!analysisSettings) return;
+
+    const depth = parseInt(value, 10);
+    const currentMapping = (analysisSettings.chartMappings[chartType]?.mapping || {}) as PieChartMapping | BarChartMapping;
+    const newMapping = {
+      ...currentMapping,
+      depth: depth > 0 ? depth : undefined,
+    };
+
+    const chartMappings = { ...analysisSettings.chartMappings };
+    if (chartType === 'pie') {
+      chartMappings.pie = { type: 'pie', mapping: newMapping };
+    } else {
+      chartMappings.bar = { type: 'bar', mapping: newMapping };
+    }
+    await handleSave({ ...analysisSettings, chartMappings });
+  };
+
   // 円グラフのメトリックプリセットを更新
   const handlePieChartMetricChange = async (metricId: string) => {
     if (!analysisSettings) return;
@@ -481,6 +501,18 @@ function AnalysisSettingsSection({ workspacePath, tagIndex, t }: AnalysisSetting
             ))}
           </select>
         </div>
+        <div className="setting-item">
+          <label htmlFor="pieChartDepth">{t.settings.hierarchyDepth || '階層の深さ'}:</label>
+          <input
+            id="pieChartDepth"
+            type="number"
+            min={1}
+            value={pieMapping.depth ?? ''}
+            onChange={(e) => handleChartDepthChange('pie', e.target.value)}
+            disabled={saving}
+            title={t.settings.hierarchyDepthHint}
+          />
+        </div>
         <div className="setting-item">
           <label htmlFor="pieChartMetric">{t.settings.metricManagement.metricLabel}</label>
           <select
@@ -521,6 +553,18 @@ function AnalysisSettingsSection({ workspacePath, tagIndex, t }: AnalysisSetting
             ))}
           </select>
         </div>
+        <div className="setting-item">
+          <label htmlFor="barChartDepth">{t.settings.hierarchyDepth || '階層の深さ'}:</label>
+          <input
+            id="barChartDepth"
+            type="number"
+            min={1}
+            value={barMapping.depth ?? ''}
+            onChange={(e) => handleChartDepthChange('bar', e.target.value)}
+            disabled={saving}
+            title={t.settings.hierarchyDepthHint}
+          />
+        </div>
         <div className="setting-item">
           <label htmlFor="barChartMetric">{t.settings.metricManagement.metricLabel}</label>
           <select
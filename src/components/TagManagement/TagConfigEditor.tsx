@@ -4,8 +4,9 @@
  */
 
 import { useState, useEffect } from 'react';
-import type { TagConfig, TagType, AllowedValueType, TagIndex, WorkspaceConfig } from '../../types/task';
+import type { TagConfig, TagType, AllowedValueType, TagIndex, TagSchema, WorkspaceConfig } from '../../types/task';
 import { WorkspaceConfigService } from '../../services/workspaceConfigService';
+import { loadTagSchema, saveTagSchema, tagConfigsToSchema, tagSchemaToConfigs } from '../../services/tagSchemaService';
 import './TagConfigEditor.css';
 
 interface TagConfigEditorProps {
@@ -24,6 +25,7 @@ export function TagConfigEditor({ workspacePath, tagIndex, targetCategory, onClo
   const [newTagKey, setNewTagKey] = useState('');
   const [editingKey, setEditingKey] = useState<string | null>(null);
   const [workspaceConfig, setWorkspaceConfig] = useState<WorkspaceConfig | null>(null);
+  const [schema, setSchema] = useState<TagSchema>({});
 
   const loadConfigs = async () => {
     if (!workspacePath) return;
@@ -32,14 +34,19 @@ export function TagConfigEditor({ workspacePath, tagIndex, targetCategory, onClo
     try {
       const config = await WorkspaceConfigService.getConfig(workspacePath);
       setWorkspaceConfig(config);
-      setSavedConfigs(config.tagConfigs.configs);
+
+      // タグ定義はタグスキーマ（tag_schema.json）から読み込む
+      const loadedSchema = await loadTagSchema(workspacePath);
+      const definedConfigs = tagSchemaToConfigs(loadedSchema);
+      setSchema(loadedSchema);
+      setSavedConfigs(definedConfigs);
       
       // タグインデックスから未設定のタグを取得して暗黙の設定を作成
-      const allConfigs: Record<string, TagConfig> = { ...config.tagConfigs.configs };
+      const allConfigs: Record<string, TagConfig> = { ...definedConfigs };
       
       if (tagIndex) {
         const allCategories = Object.keys(tagIndex.categories);
-        const missingCategories = allCategories.filter(cat => !config.tagConfigs.configs[cat]);
+        const missingCategories = allCategories.filter(cat => !definedConfigs[cat]);
         
         // 暗黙の設定を追加（表示用）
         if (missingCategories.length > 0) {
@@ -84,8 +91,6 @@ export function TagConfigEditor({ workspacePath, tagIndex, targetCategory, onClo
 
     setSaving(true);
     try {
-      const config = await WorkspaceConfigService.getConfig(workspacePath);
-      
       // 明示的に設定されたもののみ保存
       const actualConfigs: Record<string, TagConfig> = {};
       
@@ -99,8 +104,7 @@ export function TagConfigEditor({ workspacePath, tagIndex, targetCategory, onClo
         }
       });
       
-      config.tagConfigs.configs = actualConfigs;
-      await WorkspaceConfigService.updateConfig(workspacePath, config);
+      await saveTagSchema(workspacePath, tagConfigsToSchema(actualConfigs, schema));
       alert('タグ設定を保存しました');
       setEditingKey(null); // 編集状態をリセット
       await loadConfigs(); // 再読み込み
@@ -310,7 +314,7 @@ function TagConfigItem({ tagKey, config, onUpdate, onRemove, isEditing, onEditTo
     onEditToggle();
   };
 
-  const tagTypes: TagType[] = ['String', 'Select', 'MultiSelect', 'Number', 'Boolean', 'Date', 'Array'];
+  const tagTypes: TagType[] = ['String', 'Select', 'MultiSelect', 'Number', 'Boolean', 'Date', 'Datetime', 'Array', 'Currency', 'Hyperlink', 'Image'];
 
   const renderSortSettingsEditor = () => {
     return (
@@ -129,6 +129,7 @@ export function TagSettingsView({ workspacePath, workspace, onClose, onOpenTask
       Currency: { min: undefined, max: undefined, decimalPlaces: 2, defaultValue: undefined, currencyFormat: 'JPY' },
       Image: {},
       Hyperlink: {},
+      Computed: { expression: '' },
     };
 
     const newTag: TagAttributeOptions = {
@@ -450,6 +451,22 @@ export function TagSettingsView({ workspacePath, workspace, onClose, onOpenTask
           </div>
         );
 
+      case 'Computed':
+        return (
+          <div className="attribute-form">
+            <div className="form-group">
+              <label>{t.tagSchemaManager.expression}</label>
+              <input
+                type="text"
+                value={options.options.expression}
+                onChange={(e) => handleUpdateOptions(tagKey, 'expression', e.target.value)}
+                placeholder="end_date - start_date"
+              />
+            </div>
+            <p className="form-hint">{t.tagSchemaManager.computedExpressionHint}</p>
+          </div>
+        );
+
       default:
         return null;
     }
@@ -712,6 +729,7 @@ export function TagSettingsView({ workspacePath, workspace, onClose, onOpenTask
                   <option value="Currency">Currency</option>
                   <option value="Image">Image</option>
                   <option value="Hyperlink">Hyperlink</option>
+                  <option value="Computed">{t.tagSchemaManager.computedType}</option>
                 </select>
               </div>
               <div style={{ display: 'flex', gap: '0.5rem', marginTop: '1.5rem' }}>
@@ -926,6 +944,7 @@ export function TagSettingsView({ workspacePath, workspace, onClose, onOpenTask
                       Currency: { min: undefined, max: undefined, decimalPlaces: 2, defaultValue: undefined, currencyFormat: 'JPY' },
                       Image: {},
                       Hyperlink: {},
+                      Computed: { expression: '' },
                     };
                     const updated = {
                       ...localSchema,
@@ -5,7 +5,7 @@
 import { useState, useEffect } from 'react';
 import type { Task, TagConfig, CustomFilter, CustomSort } from '../../types/task';
 import { TaskFilterPanel, type TaskFilter, type SortCriteria } from './TaskFilter';
-import { sortTasks, filterTasks } from './taskUtils';
+import { sortTasks, filterTasks, getTagValue } from './taskUtils';
 import { evaluateFilterExpression } from './filterEvaluator';
 import { FilterSortService } from '../../services/filterSortService';
 import { WorkspaceConfigService } from '../../services/workspaceConfigService';
@@ -54,6 +54,7 @@ export function TaskBrowser({
   const [activeCustomSort, setActiveCustomSort] = useState<string | null>(null);
   const [customFilterDef, setCustomFilterDef] = useState<CustomFilter | null>(null);
   const [customSortDef, setCustomSortDef] = useState<CustomSort | null>(null);
+  const [tagSeparator, setTagSeparator] = useState('/');
 
   // デフォルトソートを読み込む
   useEffect(() => {
@@ -62,6 +63,7 @@ export function TaskBrowser({
       
       try {
         const config = await WorkspaceConfigService.getConfig(workspacePath);
+        setTagSeparator(config.tagSeparator ?? '/');
         if (config.defaultSortOrder) {
           // カスタムソートかどうかをチェック
           if (config.defaultSortOrder.startsWith('custom:')) {
@@ -117,7 +119,7 @@ export function TaskBrowser({
     }
 
     try {
-      return evaluateFilterExpression(tasks, filterDef.expression, tagConfigs);
+      return evaluateFilterExpression(tasks, filterDef.expression, tagConfigs, tagSeparator);
     } catch (error) {
       console.error('Error applying custom filter:', error);
       return tasks; // エラー時は全タスクを返す
@@ -146,8 +148,8 @@ export function TaskBrowser({
       sorted.sort((a, b) => {
         for (const sortKey of sortDef.sortKeys) {
           const { tagKey, order } = sortKey;
-          const aValue = a.frontMatter[tagKey];
-          const bValue = b.frontMatter[tagKey];
+          const aValue = getTagValue(a, tagKey);
+          const bValue = getTagValue(b, tagKey);
 
           // 値の比較
           let comparison = 0;
@@ -4,6 +4,7 @@
  */
 
 import type { Task, FilterExpression, FilterCondition, ComparisonOperator, TagConfig } from '../../types/task';
+import { getTagValue } from './taskUtils';
 
 /**
  * フィルター式を評価してタスクをフィルタリング
@@ -11,7 +12,8 @@ import type { Task, FilterExpression, FilterCondition, ComparisonOperator, TagCo
 export function evaluateFilterExpression(
   tasks: Task[],
   expression: FilterExpression,
-  tagConfigs: Record<string, TagConfig>
+  tagConfigs: Record<string, TagConfig>,
+  separator = '/'
 ): Task[] {
   // 式が未定義の場合は全タスクを返す
   if (!expression) {
@@ -20,7 +22,7 @@ export function evaluateFilterExpression(
   }
 
   try {
-    return tasks.filter((task) => evaluateExpression(task, expression, tagConfigs));
+    return tasks.filter((task) => evaluateExpression(task, expression, tagConfigs, separator));
   } catch (error) {
     console.error('Error evaluating filter expression:', error);
     return tasks; // エラー時は全タスクを返す
@@ -33,7 +35,8 @@ export function evaluateFilterExpression(
 function evaluateExpression(
   task: Task,
   expression: FilterExpression,
-  tagConfigs: Record<string, TagConfig>
+  tagConfigs: Record<string, TagConfig>,
+  separator: string
 ): boolean {
   // 式が未定義の場合はtrueを返す（フィルタリングしない）
   if (!expression) {
@@ -42,12 +45,12 @@ function evaluateExpression(
 
   // シンプルな条件
   if (expression.condition) {
-    return evaluateCondition(task, expression.condition);
+    return evaluateCondition(task, expression.condition, separator);
   }
 
   // 複合式
   if (expression.expressions && expression.expressions.length > 0) {
-    const results = expression.expressions.map((expr) => evaluateExpression(task, expr, tagConfigs));
+    const results = expression.expressions.map((expr) => evaluateExpression(task, expr, tagConfigs, separator));
 
     if (!expression.logicalOperator) {
       return results[0]; // デフォルトはAND
@@ -71,17 +74,33 @@ function evaluateExpression(
 /**
  * 条件を評価
  */
-function evaluateCondition(task: Task, condition: FilterCondition): boolean {
-  const value = task.frontMatter[condition.tagKey];
+function evaluateCondition(task: Task, condition: FilterCondition, separator: string): boolean {
+  const value = getTagValue(task, condition.tagKey);
   const conditionValue = condition.value;
 
   if (value === undefined) {
     return false;
   }
 
+  if (condition.operator === 'under') {
+    // 配列タグはいずれかの要素が配下にあれば一致
+    const values = Array.isArray(value) ? value : [value];
+    return values.some((v) => isUnderTagPath(String(v), String(conditionValue), separator));
+  }
+
   return compareValues(value, condition.operator, conditionValue);
 }
 
+/**
+ * 値が階層タグ prefix 自身、またはその配下にあるか（area/back は area/backend を含まない）
+ */
+function isUnderTagPath(value: string, prefix: string, separator: string): boolean {
+  if (value === prefix) {
+    return true;
+  }
+  return separator !== '' && value.startsWith(prefix + separator);
+}
+
 /**
  * 値を比較
  */
@@ -2,9 +2,17 @@
  * タスクのソート・フィルタユーティリティ
  */
 
-import type { Task, TagConfig } from '../../types/task';
+import type { Task, TagConfig, TagValue } from '../../types/task';
 import type { TaskFilter, SortCriteria } from './TaskFilter';
 
+/**
+ * タグの値を取得（フロントマターにない場合は計算フィールドの値）
+ */
+export function getTagValue(task: Task, tagKey: string): TagValue | undefined {
+  const value = task.frontMatter[tagKey];
+  return value !== undefined ? value : task.computedTags?.[tagKey];
+}
+
 /**
  * タスクをソート
  */
@@ -41,7 +49,7 @@ export function filterTasks(tasks: Task[], filter: TaskFilter, _tagConfigs: Reco
     // タグ条件によるフィルタ
     if (filter.tagConditions.length > 0) {
       for (const condition of filter.tagConditions) {
-        const tagValue = task.frontMatter[condition.category];
+        const tagValue = getTagValue(task, condition.category);
         if (tagValue === undefined) return false;
         
         const tagStr = String(tagValue);
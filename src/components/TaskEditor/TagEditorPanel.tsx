@@ -3,9 +3,9 @@
  * TaskEditor内でタグを視覚的に編集するパネル
  */
 
-import { useState, useRef, useEffect } from 'react';
+import { useState, useRef, useEffect, useMemo } from 'react';
 import React from 'react';
-import type { FrontMatter, TagValue, TagConfigCollection, TagAttributeType, TagAttributeOptions } from '../../types/task';
+import type { FrontMatter, TagValue, TagAttributeType, TagAttributeOptions } from '../../types/task';
 import { useTagSchema } from '../../hooks/useTagSchema';
 import * as tagSchemaService from '../../services/tagSchemaService';
 // すべての動的入力コンポーネントをインポート（将来的な拡張に備えて）
@@ -15,7 +15,6 @@ import { useLanguage } from '../../contexts/LanguageContext';
 
 interface TagEditorPanelProps {
   frontMatter: FrontMatter;
-  tagConfigs: TagConfigCollection | null;
   onTagChange: (key: string, value: TagValue) => void;
   onTagRemove: (key: string) => void;
   onTagAdd: (key: string, value: TagValue) => void;
@@ -27,12 +26,12 @@ interface TagEditorPanelProps {
   onDragStateChange?: (isDragging: boolean) => void; // ドラッグ状態変更通知
   onDirtyChange?: (isDirty: boolean) => void; // 編集状態変更通知
   workspacePath?: string | null; // ワークスペースパス（タグスキーマロード用）
+  computedTags?: FrontMatter; // 計算フィールドの値（読み取り専用）
 }
 
 
 export function TagEditorPanel({
   frontMatter,
-  tagConfigs,
   onTagChange,
   onTagRemove,
   onTagAdd,
@@ -42,7 +41,8 @@ export function TagEditorPanel({
   tagOrder = [],
   onDragStateChange,
   onDirtyChange,
-  workspacePath
+  workspacePath,
+  computedTags = {}
 }: TagEditorPanelProps) {
   const { t } = useLanguage();
   const [newTagKey, setNewTagKey] = useState('');
@@ -61,12 +61,16 @@ export function TagEditorPanel({
 
   // タグスキーマを読み込む
   const { schema } = useTagSchema(workspacePath || null);
+  // スキーマで定義されたタグは設定付きのフィールドとして表示する
+  const tagConfigs = useMemo(() => (schema ? tagSchemaService.tagSchemaToConfigs(schema) : null), [schema]);
 
   // 既存のタグを取得
   const existingTags = Object.keys(frontMatter);
   
-  // スキーマで定義されているがまだ追加されていないタグのリスト
-  const availableTagsFromSchema = schema ? Object.keys(schema).filter(key => !existingTags.includes(key)) : [];
+  // スキーマで定義されているがまだ追加されていないタグのリスト（計算フィールドは手動で追加できない）
+  const availableTagsFromSchema = schema
+    ? Object.keys(schema).filter(key => !existingTags.includes(key) && schema[key].type !== 'Computed')
+    : [];
 
   // スキーマに基づいて動的タグ入力をレンダリングする関数
   const renderTagInput = (tagKey: string, _config: any, currentValue: any) => {
@@ -127,7 +131,7 @@ export function TagEditorPanel({
         case 'Currency':
           return (
             <CurrencyInput
-              value={currentValue as number | undefined}
+              value={currentValue as number | string | undefined}
               onChange={(value) => onTagChange(tagKey, value)}
               schema={schemaDef}
             />
@@ -1149,6 +1153,7 @@ export function TagEditorPanel({
           Currency: { min: undefined, max: undefined, decimalPlaces: 2, defaultValue: undefined, currencyFormat: 'JPY' },
           Image: {},
           Hyperlink: {},
+          Computed: { expression: '' },
         };
         
         // 新しい属性タイプに応じた属性オプションを作成
@@ -1234,10 +1239,10 @@ export function TagEditorPanel({
 
         {/* タグ設定に基づくタグフィールド（順序に従って表示） */}
         {(tagOrder.length > 0 ? tagOrder : existingTags)
-          .filter(tagKey => tagConfigs?.configs[tagKey])
+          .filter(tagKey => tagConfigs?.[tagKey])
           .map((tagKey, index) => (
             <React.Fragment key={tagKey}>
-              {renderTagField(tagKey, tagConfigs!.configs[tagKey])}
+              {renderTagField(tagKey, tagConfigs![tagKey])}
               {renderDropZone(index + 1)}
             </React.Fragment>
           ))
@@ -1245,15 +1250,33 @@ export function TagEditorPanel({
 
         {/* カスタムタグ（設定にないタグ、順序に従って表示） */}
         {(tagOrder.length > 0 ? tagOrder : existingTags)
-          .filter(tagKey => !tagConfigs?.configs[tagKey])
+          .filter(tagKey => !tagConfigs?.[tagKey])
           .map((tagKey, index) => (
             <React.Fragment key={tagKey}>
               {renderCustomTag(tagKey)}
-              {renderDropZone((tagOrder.length > 0 ? tagOrder : existingTags).filter(key => tagConfigs?.configs[key]).length + index + 1)}
+              {renderDropZone((tagOrder.length > 0 ? tagOrder : existingTags).filter(key => tagConfigs?.[key]).length + index + 1)}
             </React.Fragment>
           ))
         }
 
+        {/* 計算フィールド（読み取り専用） */}
+        {Object.keys(computedTags).length > 0 && (
+          <div className="computed-tags">
+            <h4>{t.taskEditor.computedTags}</h4>
+            {Object.keys(computedTags).sort().map(key => {
+              const value = computedTags[key];
+              return (
+                <div key={key} className="tag-field computed-tag">
+                  <label className="tag-label">{schema?.[key]?.alias || key}</label>
+                  <div className="tag-value" title={(schema?.[key]?.options as any)?.expression}>
+                    <span className="tag-single">{Array.isArray(value) ? value.join(', ') : String(value)}</span>
+                  </div>
+                </div>
+              );
+            })}
+          </div>
+        )}
+
         {/* 新しいタグ追加フォーム */}
         {showAddForm && (
           <div className="tag-add-form">
@@ -1186,7 +1186,6 @@ export function TaskEditor({
             <div style={{ width: `${tagPanelWidth}px`, flexShrink: 0 }}>
               <TagEditorPanel
                 frontMatter={currentTask.frontMatter}
-                tagConfigs={workspace?.config.tagConfigs || null}
                 onTagChange={handleTagChange}
                 onTagRemove={handleTagRemove}
                 onTagAdd={handleTagAdd}
@@ -1194,6 +1193,7 @@ export function TaskEditor({
                 tagOrder={currentTask.tagOrder || Object.keys(currentTask.frontMatter)}
                 onDragStateChange={setIsDraggingTag}
                 workspacePath={workspacePath}
+                computedTags={currentTask.computedTags}
               />
             </div>
             <Resizer onResize={handleTagPanelResize} direction="horizontal" />
@@ -27,6 +27,7 @@ export function TemplateEditor({ workspacePath, template, tagConfigs, tagIndex,
   const [name, setName] = useState(template?.name ?? '');
   const [description, setDescription] = useState(template?.description ?? '');
   const [tags, setTags] = useState<Record<string, TagValue>>(template?.tags ?? {});
+  const [body, setBody] = useState(template?.body ?? '');
   const [newTagKey, setNewTagKey] = useState('');
   const [newTagValue, setNewTagValue] = useState('');
   const [newTagType, setNewTagType] = useState<'string' | 'number' | 'boolean' | 'date' | 'select' | 'multiselect' | 'currency' | 'image' | 'hyperlink'>('string');
@@ -230,7 +231,7 @@ export function TemplateEditor({ workspacePath, template, tagConfigs, tagIndex,
       return;
     }
 
-    if (Object.keys(tags).length === 0) {
+    if (Object.keys(tags).length === 0 && !body.trim()) {
       alert(t.templates.noTemplates);
       return;
     }
@@ -239,10 +240,10 @@ export function TemplateEditor({ workspacePath, template, tagConfigs, tagIndex,
 
     try {
       if (isEditing) {
-        await updateTemplate(name, description || undefined, tags);
+        await updateTemplate(name, description || undefined, tags, body || undefined);
         alert(t.templateEditor.savedSuccess);
       } else {
-        await createTemplate(name, description || undefined, tags);
+        await createTemplate(name, description || undefined, tags, body || undefined);
         alert(t.templateEditor.savedSuccess);
       }
       onClose();
@@ -358,7 +359,7 @@ export function TemplateEditor({ workspacePath, template, tagConfigs, tagIndex,
         case 'Currency':
           return (
             <CurrencyInput
-              value={value as number | undefined}
+              value={value as number | string | undefined}
               onChange={(val) => setTags({ ...tags, [key]: val ?? 0 })}
               schema={schemaDef}
             />
@@ -376,7 +377,7 @@ export function TemplateEditor({ workspacePath, template, tagConfigs, tagIndex,
         case 'Hyperlink':
           return (
             <HyperlinkInput
-              value={value as HyperlinkValue | undefined}
+              value={value as string | HyperlinkValue | undefined}
               onChange={(val) => setTags({ ...tags, [key]: val })}
               schema={schemaDef}
             />
@@ -427,6 +428,18 @@ export function TemplateEditor({ workspacePath, template, tagConfigs, tagIndex,
           />
         </div>
 
+        <div className="form-group">
+          <label htmlFor="template-body">{t.templateEditor.bodySection}</label>
+          <textarea
+            id="template-body"
+            value={body}
+            onChange={(e) => setBody(e.target.value)}
+            placeholder={t.templateEditor.bodyPlaceholder}
+            rows={8}
+          />
+          <small className="form-hint">{t.templateEditor.bodyHint}</small>
+        </div>
+
         <div className="tags-section">
           <h3>{t.templateEditor.tagsSection}</h3>
 
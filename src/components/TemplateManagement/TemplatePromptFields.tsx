@@ -0,0 +1,58 @@
+/**
+ * テンプレート本文の入力項目
+ * {{title}}や{{prompt:...}}に埋め込む値を、テンプレート選択モーダルの中で入力する
+ */
+
+import './TemplatePromptFields.css';
+
+/** テンプレート適用時に本文へ埋め込む値 */
+export interface TemplateInputValues {
+  title: string;
+  prompts: Record<string, string>;
+}
+
+interface TemplatePromptFieldsProps {
+  heading: string;
+  /** {{prompt:...}}のラベル（出現順） */
+  labels: string[];
+  values: Record<string, string>;
+  onChange: (values: Record<string, string>) => void;
+  /** {{title}}の入力欄（省略時は表示しない） */
+  title?: {
+    label: string;
+    value: string;
+    onChange: (title: string) => void;
+  };
+}
+
+export function TemplatePromptFields({ heading, labels, values, onChange, title }: TemplatePromptFieldsProps) {
+  if (!title && labels.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="template-prompt-fields">
+      <h3>{heading}</h3>
+      {title && (
+        <label className="template-prompt-field">
+          <span>{title.label}</span>
+          <input
+            type="text"
+            value={title.value}
+            onChange={(e) => title.onChange(e.target.value)}
+          />
+        </label>
+      )}
+      {labels.map((label) => (
+        <label key={label} className="template-prompt-field">
+          <span>{label}</span>
+          <input
+            type="text"
+            value={values[label] ?? ''}
+            onChange={(e) => onChange({ ...values, [label]: e.target.value })}
+          />
+        </label>
+      ))}
+    </div>
+  );
+}
@@ -3,8 +3,9 @@
  * タスク作成/編集時にテンプレートを選択して適用
  */
 
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { useTemplates } from '../../hooks/useTemplates';
+import { TemplatePromptFields } from './TemplatePromptFields';
 import './TemplateSelector.css';
 
 interface TemplateSelectorProps {
@@ -22,10 +23,29 @@ export function TemplateSelector({
   onClose,
   mode = 'new',
 }: TemplateSelectorProps) {
-  const { templates, loading, error } = useTemplates(workspacePath);
+  const { templates, loading, error, getTemplatePrompts } = useTemplates(workspacePath);
   const [selectedTemplate, setSelectedTemplate] = useState<string | null>(null);
   const [overwrite, setOverwrite] = useState(false);
   const [isApplying, setIsApplying] = useState(false);
+  const [promptLabels, setPromptLabels] = useState<string[]>([]);
+  const [promptValues, setPromptValues] = useState<Record<string, string>>({});
+
+  // 新規タスクでは、本文で入力を求める項目を選択中のテンプレートから読み込む
+  useEffect(() => {
+    let cancelled = false;
+    setPromptLabels([]);
+    setPromptValues({});
+    if (!selectedTemplate || mode !== 'new') return;
+
+    getTemplatePrompts(selectedTemplate)
+      .then((labels) => {
+        if (!cancelled) setPromptLabels(labels);
+      })
+      .catch((err) => console.warn('Failed to load template prompts:', err));
+    return () => {
+      cancelled = true;
+    };
+  }, [selectedTemplate, mode, getTemplatePrompts]);
 
   const handleApply = async () => {
     if (!selectedTemplate || !workspacePath) return;
@@ -37,10 +57,17 @@ export function TemplateSelector({
 
       let result: string;
       if (mode === 'new') {
+        // 表示した項目はすべて値を渡す（未入力は空文字として明示する）
+        const prompts: Record<string, string> = {};
+        for (const label of promptLabels) {
+          prompts[label] = promptValues[label] ?? '';
+        }
         result = await TemplateService.applyTemplateToNewTask(
           workspacePath,
           selectedTemplate,
-          currentContent
+          currentContent,
+          undefined,
+          prompts
         );
       } else {
         result = await TemplateService.applyTemplateToExistingTask(
@@ -128,6 +155,15 @@ export function TemplateSelector({
                   </div>
                 )}
 
+                {mode === 'new' && (
+                  <TemplatePromptFields
+                    heading="入力項目"
+                    labels={promptLabels}
+                    values={promptValues}
+                    onChange={setPromptValues}
+                  />
+                )}
+
                 {mode === 'existing' && (
                   <div className="apply-options">
                     <label className="checkbox-label">
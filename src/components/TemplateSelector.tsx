@@ -3,24 +3,63 @@
  * 新規タスク作成時にテンプレートを選択するためのモーダル
  */
 
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import type { TagTemplate } from '../types/task';
 import { useLanguage } from '../contexts/LanguageContext';
+import { TemplatePromptFields, type TemplateInputValues } from './TemplateManagement/TemplatePromptFields';
 import './TemplateSelector.css';
 
 interface TemplateSelectorProps {
   templates: TagTemplate[];
   defaultTemplate: TagTemplate | null;
-  onSelect: (templateName: string | null) => void;
+  /** {{title}}の初期値 */
+  defaultTitle: string;
+  /** テンプレート本文の{{prompt:...}}のラベルを取得 */
+  loadPrompts: (templateName: string) => Promise<string[]>;
+  onSelect: (templateName: string | null, values: TemplateInputValues) => void;
   onCancel: () => void;
 }
 
-export function TemplateSelector({ templates, defaultTemplate, onSelect, onCancel }: TemplateSelectorProps) {
+export function TemplateSelector({
+  templates,
+  defaultTemplate,
+  defaultTitle,
+  loadPrompts,
+  onSelect,
+  onCancel,
+}: TemplateSelectorProps) {
   const { t } = useLanguage();
   const [selectedTemplate, setSelectedTemplate] = useState<string | null>(defaultTemplate?.name || null);
+  const [title, setTitle] = useState(defaultTitle);
+  const [promptLabels, setPromptLabels] = useState<string[]>([]);
+  const [promptValues, setPromptValues] = useState<Record<string, string>>({});
+
+  // 選択したテンプレートの本文で入力を求める項目を読み込む
+  useEffect(() => {
+    let cancelled = false;
+    setPromptLabels([]);
+    setPromptValues({});
+    if (!selectedTemplate) return;
+
+    loadPrompts(selectedTemplate)
+      .then((labels) => {
+        if (!cancelled) setPromptLabels(labels);
+      })
+      .catch((err) => console.warn('Failed to load template prompts:', err));
+    return () => {
+      cancelled = true;
+    };
+  }, [selectedTemplate, loadPrompts]);
+
+  const hasBody = !!templates.find((template) => template.name === selectedTemplate)?.body;
 
   const handleConfirm = () => {
-    onSelect(selectedTemplate);
+    // 表示した項目はすべて値を渡す（未入力は空文字として明示する）
+    const prompts: Record<string, string> = {};
+    for (const label of promptLabels) {
+      prompts[label] = promptValues[label] ?? '';
+    }
+    onSelect(selectedTemplate, { title: title.trim() || defaultTitle, prompts });
   };
 
   const handleKeyDown = (e: React.KeyboardEvent) => {
@@ -87,6 +126,16 @@ export function TemplateSelector({ templates, defaultTemplate, onSelect, onCance
               </label>
             ))}
           </div>
+
+          {hasBody && (
+            <TemplatePromptFields
+              heading={t.templateSelector.inputs}
+              labels={promptLabels}
+              values={promptValues}
+              onChange={setPromptValues}
+              title={{ label: t.templateSelector.taskTitle, value: title, onChange: setTitle }}
+            />
+          )}
         </div>
 
         <div className="template-selector-footer">
@@ -4,7 +4,7 @@
 
 import { useState, useEffect, useCallback } from 'react';
 import { TagService } from '../services/tagService';
-import { WorkspaceConfigService } from '../services/workspaceConfigService';
+import { loadTagSchema, tagSchemaToConfigs } from '../services/tagSchemaService';
 import type { TagIndex, TagConfig } from '../types/task';
 
 export function useTags(workspacePath: string | null) {
@@ -25,10 +25,10 @@ export function useTags(workspacePath: string | null) {
       const index = await TagService.getTagIndex(workspacePath);
       setTagIndex(index);
 
-      // タグ設定も読み込む
+      // タグ設定もタグスキーマから読み込む
       try {
-        const config = await WorkspaceConfigService.getConfig(workspacePath);
-        setTagConfigs(config.tagConfigs.configs);
+        const schema = await loadTagSchema(workspacePath);
+        setTagConfigs(tagSchemaToConfigs(schema));
       } catch (err) {
         console.warn('Failed to load tag configs:', err);
         setTagConfigs({});
@@ -54,7 +54,8 @@ export function useTemplates(workspacePath: string | null) {
     async (
       name: string,
       description: string | undefined,
-      tags: Record<string, TagValue>
+      tags: Record<string, TagValue>,
+      body?: string
     ) => {
       if (!workspacePath) throw new Error('No workspace selected');
 
@@ -62,7 +63,8 @@ export function useTemplates(workspacePath: string | null) {
         workspacePath,
         name,
         description,
-        tags
+        tags,
+        body
       );
       await reload();
       return template;
@@ -77,7 +79,8 @@ export function useTemplates(workspacePath: string | null) {
     async (
       name: string,
       description: string | undefined,
-      tags: Record<string, TagValue>
+      tags: Record<string, TagValue>,
+      body?: string
     ) => {
       if (!workspacePath) throw new Error('No workspace selected');
 
@@ -85,7 +88,8 @@ export function useTemplates(workspacePath: string | null) {
         workspacePath,
         name,
         description,
-        tags
+        tags,
+        body
       );
       await reload();
       return template;
@@ -136,18 +140,37 @@ export function useTemplates(workspacePath: string | null) {
    * テンプレートを新規タスクに適用
    */
   const applyToNewTask = useCallback(
-    async (templateName: string, content: string = '') => {
+    async (
+      templateName: string,
+      content: string = '',
+      title?: string,
+      prompts?: Record<string, string>
+    ) => {
       if (!workspacePath) throw new Error('No workspace selected');
 
       return await TemplateService.applyTemplateToNewTask(
         workspacePath,
         templateName,
-        content
+        content,
+        title,
+        prompts
       );
     },
     [workspacePath]
   );
 
+  /**
+   * テンプレート本文で入力を求める項目を取得
+   */
+  const getTemplatePrompts = useCallback(
+    async (templateName: string) => {
+      if (!workspacePath) throw new Error('No workspace selected');
+
+      return await TemplateService.getTemplatePrompts(workspacePath, templateName);
+    },
+    [workspacePath]
+  );
+
   /**
    * テンプレートを既存タスクに適用
    */
@@ -196,10 +219,10 @@ export function useTemplates(workspacePath: string | null) {
    * テンプレートのプレビューを取得
    */
   const previewTemplate = useCallback(
-    async (templateName: string) => {
+    async (templateName: string, title?: string, prompts?: Record<string, string>) => {
       if (!workspacePath) throw new Error('No workspace selected');
 
-      return await TemplateService.previewTemplate(workspacePath, templateName);
+      return await TemplateService.previewTemplate(workspacePath, templateName, title, prompts);
     },
     [workspacePath]
   );
@@ -216,6 +239,7 @@ export function useTemplates(workspacePath: string | null) {
     renameTemplate,
     setAsDefault,
     applyToNewTask,
+    getTemplatePrompts,
     applyToExistingTask,
     createFromTask,
     previewTemplate,
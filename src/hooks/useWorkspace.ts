@@ -45,13 +45,28 @@ export function useWorkspace(): UseWorkspaceReturn {
         await fileWatcherService.startFileWatcher(workspace.rootPath);
         console.log('File watcher started for:', workspace.rootPath);
 
-        // ファイル変更イベントをリッスン
-        const unlisten = await fileWatcherService.listenToFileChanges((event) => {
-          console.log('File change detected:', event);
-          handleFileChange(event);
+        // 外部でのタスク変更をリッスン（バックエンドで読み込み済みのタスクを受け取る）
+        const unlisten = await fileWatcherService.listenToTaskUpdates((event) => {
+          console.log('Task updated externally:', event.taskId);
+          handleTaskUpdated(event);
         });
 
-        unlistenRef.current = unlisten;
+        // ワークスペース設定の外部変更を反映
+        const unlistenConfig = await fileWatcherService.listenToConfigChanges((event) => {
+          if (event.error) {
+            console.warn('Invalid config change ignored:', event.path, event.error);
+            return;
+          }
+          if (event.kind === 'workspaceConfig' && event.content) {
+            const config = event.content as Workspace['config'];
+            setWorkspace((prev) => (prev ? { ...prev, config } : prev));
+          }
+        });
+
+        unlistenRef.current = () => {
+          unlisten();
+          unlistenConfig();
+        };
       } catch (err) {
         console.error('Failed to setup file watcher:', err);
       }
@@ -72,50 +87,23 @@ export function useWorkspace(): UseWorkspaceReturn {
   }, [workspace?.rootPath]);
 
   /**
-   * ファイル変更を処理
+   * 外部でのタスク変更を反映
    */
-  const handleFileChange = useCallback(
-    async (event: fileWatcherService.FileChangeEvent) => {
-      if (!workspace) return;
-
-      // ファイル名からタスクIDを抽出
-      const pathParts = event.path.split(/[\\/]/);
-      const fileName = pathParts[pathParts.length - 1];
-      const taskId = fileName.replace('.md', '');
-
-      console.log(`File ${event.eventType}:`, taskId);
-
-      try {
-        if (event.eventType === 'removed') {
-          // タスクを削除
-          setWorkspace((prev) => {
-            if (!prev) return prev;
-            const { [taskId]: removed, ...remainingTasks } = prev.tasks;
-            return {
-              ...prev,
-              tasks: remainingTasks,
-            };
-          });
-        } else {
-          // タスクを再読み込み
-          const updatedTask = await workspaceService.getTask(workspace.rootPath, taskId);
-          setWorkspace((prev) => {
-            if (!prev) return prev;
-            return {
-              ...prev,
-              tasks: {
-                ...prev.tasks,
-                [taskId]: updatedTask,
-              },
-            };
-          });
-        }
-      } catch (err) {
-        console.error('Failed to handle file change:', err);
+  const handleTaskUpdated = useCallback((event: fileWatcherService.TaskUpdatedEvent) => {
+    setWorkspace((prev) => {
+      if (!prev) return prev;
+      const tasks = { ...prev.tasks };
+      if (event.previousTaskId) {
+        delete tasks[event.previousTaskId];
       }
-    },
-    [workspace]
-  );
+      if (event.deleted || !event.task) {
+        delete tasks[event.taskId];
+      } else {
+        tasks[event.taskId] = event.task;
+      }
+      return { ...prev, tasks };
+    });
+  }, []);
 
   /**
    * パスからワークスペースを読み込む
@@ -248,7 +236,10 @@ export function useWorkspace(): UseWorkspaceReturn {
       }
 
       try {
-        await workspaceService.saveTask(task);
+        const warnings = await workspaceService.saveTask(workspace.rootPath, task);
+        if (warnings.length > 0) {
+          console.warn('Task saved with tag violations:', warnings);
+        }
 
         // ワークスペースの状態を更新
         setWorkspace((prev) => {
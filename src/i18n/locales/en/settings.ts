@@ -78,6 +78,8 @@ export const settings = {
   barChartPreset: 'Bar Chart Tag Preset',
   barChartPresetDescription: 'Select the default tag category for bar charts.',
   tagCategory: 'Tag Category',
+  hierarchyDepth: 'Hierarchy Depth',
+  hierarchyDepthHint: 'Aggregate hierarchical tags such as area/backend/api at this level. Leave empty to use full values.',
   unselected: 'Unselected',
   metricManagement: {
     title: 'Metric Management',
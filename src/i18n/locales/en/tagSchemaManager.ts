@@ -56,5 +56,8 @@ export const tagSchemaManager = {
   hyperlinkType: 'Hyperlink',
   addToSchema: 'Add to Schema',
   dynamicValueExample: 'Dynamic value example: =[TODAY]+30',
+  computedType: 'Computed',
+  expression: 'Expression',
+  computedExpressionHint: 'Evaluated from other tags and never written to files. Example: due_date < TODAY && status != close',
 };
 
@@ -13,4 +13,5 @@ export const taskEditor = {
   enterValue: 'Enter value',
   invalidValue: 'Invalid value',
   dropHere: 'Drop here',
+  computedTags: 'Computed (read-only)',
 };
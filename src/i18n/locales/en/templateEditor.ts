@@ -5,6 +5,9 @@ export const templateEditor = {
   templateNameRequired: 'Please enter a template name',
   templateDescription: 'Description (Optional)',
   templateDescriptionPlaceholder: 'Enter the purpose or description of the template',
+  bodySection: 'Body (Optional)',
+  bodyPlaceholder: '# {{title}}\n\n## Steps to reproduce\n\n## Component\n{{prompt:Component}}',
+  bodyHint: 'Placeholders: {{title}}, {{date}}, {{user}}, {{prompt:Label}} (asked when the template is applied), {{=[TODAY]+7}} (dynamic default formula)',
   tagsSection: 'Tag Settings',
   noTagsMessage: 'No tags set',
   key: 'Key',
@@ -6,5 +6,7 @@ export const templateSelector = {
   default: 'Default',
   tags: 'tags',
   select: 'Select',
+  inputs: 'Template inputs',
+  taskTitle: 'Title',
 };
 
@@ -78,6 +78,8 @@ export const settings = {
   barChartPreset: '棒グラフのタグプリセット',
   barChartPresetDescription: '棒グラフで使用するデフォルトのタグカテゴリを選択します。',
   tagCategory: 'タグカテゴリ',
+  hierarchyDepth: '階層の深さ',
+  hierarchyDepthHint: 'area/backend/api のような階層タグをこの階層で集計します。空欄の場合は値をそのまま使います。',
   unselected: '未選択',
   metricManagement: {
     title: 'メトリック管理',
@@ -56,5 +56,8 @@ export const tagSchemaManager = {
   hyperlinkType: 'ハイパーリンク (Hyperlink)',
   addToSchema: 'スキーマに追加',
   dynamicValueExample: '動的値の例: =[TODAY]+30',
+  computedType: '計算フィールド (Computed)',
+  expression: '計算式',
+  computedExpressionHint: '他のタグから計算され、ファイルには書き込まれません。例: due_date < TODAY && status != close',
 };
 
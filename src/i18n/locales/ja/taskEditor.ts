@@ -13,4 +13,5 @@ export const taskEditor = {
   enterValue: '値を入力',
   invalidValue: '無効な値です',
   dropHere: 'ここにドロップ',
+  computedTags: '計算フィールド（読み取り専用）',
 };
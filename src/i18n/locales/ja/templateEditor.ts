@@ -5,6 +5,9 @@ export const templateEditor = {
   templateNameRequired: 'テンプレート名を入力してください',
   templateDescription: '説明（任意）',
   templateDescriptionPlaceholder: 'テンプレートの用途や説明を入力',
+  bodySection: '本文（オプション）',
+  bodyPlaceholder: '# {{title}}\n\n## 再現手順\n\n## コンポーネント\n{{prompt:Component}}',
+  bodyHint: 'プレースホルダー: {{title}}、{{date}}、{{user}}、{{prompt:ラベル}}（適用時に入力）、{{=[TODAY]+7}}（動的デフォルト値の計算式）',
   tagsSection: 'タグ設定',
   noTagsMessage: 'タグが設定されていません',
   key: 'キー',
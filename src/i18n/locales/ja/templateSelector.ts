@@ -6,5 +6,7 @@ export const templateSelector = {
   default: 'デフォルト',
   tags: 'タグ',
   select: '選択',
+  inputs: 'テンプレートの入力項目',
+  taskTitle: 'タイトル',
 };
 
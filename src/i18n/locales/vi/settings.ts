@@ -78,6 +78,8 @@ export const settings = {
   barChartPreset: 'Bộ chọn thẻ cho biểu đồ cột',
   barChartPresetDescription: 'Chọn danh mục thẻ mặc định cho biểu đồ cột.',
   tagCategory: 'Danh mục thẻ',
+  hierarchyDepth: 'Độ sâu phân cấp',
+  hierarchyDepthHint: 'Tổng hợp các thẻ phân cấp như area/backend/api ở cấp này. Để trống để dùng nguyên giá trị.',
   unselected: 'Chưa chọn',
   metricManagement: {
     title: 'Quản lý Metric',
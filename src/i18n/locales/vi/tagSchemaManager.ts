@@ -56,5 +56,8 @@ export const tagSchemaManager = {
   hyperlinkType: 'Siêu liên kết (Hyperlink)',
   addToSchema: 'Thêm vào Lược đồ',
   dynamicValueExample: 'Ví dụ giá trị động: =[TODAY]+30',
+  computedType: 'Trường tính toán (Computed)',
+  expression: 'Biểu thức',
+  computedExpressionHint: 'Được tính từ các thẻ khác và không ghi vào tệp. Ví dụ: due_date < TODAY && status != close',
 };
 
@@ -13,4 +13,5 @@ export const taskEditor = {
   enterValue: 'Nhập giá trị',
   invalidValue: 'Giá trị không hợp lệ',
   dropHere: 'Thả vào đây',
+  computedTags: 'Trường tính toán (chỉ đọc)',
 };
@@ -5,6 +5,9 @@ export const templateEditor = {
   templateNameRequired: 'Vui lòng nhập tên mẫu',
   templateDescription: 'Mô tả (Tùy chọn)',
   templateDescriptionPlaceholder: 'Nhập mục đích hoặc mô tả của mẫu',
+  bodySection: 'Nội dung (Tùy chọn)',
+  bodyPlaceholder: '# {{title}}\n\n## Các bước tái hiện\n\n## Thành phần\n{{prompt:Component}}',
+  bodyHint: 'Phần giữ chỗ: {{title}}, {{date}}, {{user}}, {{prompt:Nhãn}} (hỏi khi áp dụng mẫu), {{=[TODAY]+7}} (công thức giá trị mặc định động)',
   tagsSection: 'Cài đặt Thẻ',
   noTagsMessage: 'Chưa có thẻ',
   key: 'Khóa',
@@ -6,5 +6,7 @@ export const templateSelector = {
   default: 'Mặc định',
   tags: 'thẻ',
   select: 'Chọn',
+  inputs: 'Thông tin cho mẫu',
+  taskTitle: 'Tiêu đề',
 };
 
@@ -246,6 +246,8 @@ export interface Translations {
     barChartPreset?: string;
     barChartPresetDescription?: string;
     tagCategory?: string;
+    hierarchyDepth?: string;
+    hierarchyDepthHint?: string;
     unselected?: string;
     metricManagement: {
       title: string;
@@ -394,6 +396,8 @@ export interface Translations {
     default: string;
     tags: string;
     select: string;
+    inputs: string;
+    taskTitle: string;
   };
 
   // Template Editor
@@ -404,6 +408,9 @@ export interface Translations {
     templateNameRequired: string;
     templateDescription: string;
     templateDescriptionPlaceholder: string;
+    bodySection: string;
+    bodyPlaceholder: string;
+    bodyHint: string;
     tagsSection: string;
     noTagsMessage: string;
     key: string;
@@ -201,12 +201,14 @@ export class AnalysisService {
    */
   static async generatePieChartWithDsl(
     workspacePath: string,
-    category: string
+    category: string,
+    depth?: number
   ): Promise<ChartOutput> {
     try {
       const output = await invoke<ChartOutput>('generate_pie_chart_with_dsl', {
         workspacePath,
         category,
+        depth: depth ?? null,
       });
       return output;
     } catch (error) {
@@ -224,6 +226,7 @@ export class AnalysisService {
     filterDateField?: string,
     startDate?: string,
     endDate?: string,
+    depth?: number,
   ): Promise<ChartOutput> {
     return await invoke<ChartOutput>('generate_pie_chart_with_dsl_filtered', {
       workspacePath,
@@ -231,6 +234,7 @@ export class AnalysisService {
       filterDateField: filterDateField ?? null,
       startDate: startDate ?? null,
       endDate: endDate ?? null,
+      depth: depth ?? null,
     });
   }
 
@@ -239,12 +243,14 @@ export class AnalysisService {
    */
   static async generateBarChartWithDsl(
     workspacePath: string,
-    category: string
+    category: string,
+    depth?: number
   ): Promise<ChartOutput> {
     try {
       const output = await invoke<ChartOutput>('generate_bar_chart_with_dsl', {
         workspacePath,
         category,
+        depth: depth ?? null,
       });
       return output;
     } catch (error) {
@@ -262,6 +268,7 @@ export class AnalysisService {
     filterDateField?: string,
     startDate?: string,
     endDate?: string,
+    depth?: number,
   ): Promise<ChartOutput> {
     return await invoke<ChartOutput>('generate_bar_chart_with_dsl_filtered', {
       workspacePath,
@@ -269,6 +276,7 @@ export class AnalysisService {
       filterDateField: filterDateField ?? null,
       startDate: startDate ?? null,
       endDate: endDate ?? null,
+      depth: depth ?? null,
     });
   }
 
@@ -149,6 +149,16 @@ function transformLineChart(dsl: LineChart): EChartsOption {
 
   // メトリック名を取得（タイトルから推測、またはY軸ラベルを使用）
   const metricName = dsl.y_axis_label || 'Count';
+
+  // 通貨別の系列がある場合は通貨ごとに線を引く（異なる通貨は合算しない）
+  const byDate = (points: typeof series) =>
+    [...points].sort((a, b) => new Date(a.date).getTime() - new Date(b.date).getTime());
+  const lines = Object.keys(dsl.currency_series ?? {}).length > 0
+    ? Object.entries(dsl.currency_series ?? {}).map(([currency, points]) => ({
+        name: currency,
+        points: byDate(points),
+      }))
+    : [{ name: metricName, points: sortedSeries }];
   
   // ツールチップのフォーマッター
   const formatValue = (value: number) => {
@@ -171,14 +181,12 @@ function transformLineChart(dsl: LineChart): EChartsOption {
         type: 'cross',
       },
       formatter: (params: any) => {
-        if (!Array.isArray(params)) {
+        if (!Array.isArray(params) || params.length === 0) {
           return '';
         }
-        const param = params[0];
-        const date = param.name;
-        const value = param.value;
-        const formattedValue = formatValue(value);
-        return `${date}<br/>${metricName}: ${formattedValue}`;
+        const date = params[0].name;
+        const rows = params.map((param: any) => `${param.seriesName}: ${formatValue(param.value)}`);
+        return `${date}<br/>${rows.join('<br/>')}`;
       },
     },
     grid: {
@@ -202,17 +210,15 @@ function transformLineChart(dsl: LineChart): EChartsOption {
       type: 'value',
       name: metricName,
     },
-    series: [
-      {
-        name: metricName,
-        type: 'line',
-        smooth: true,
-        data: sortedSeries.map((point) => point.value),
-        areaStyle: {
-          opacity: 0.3,
-        },
+    series: lines.map((line) => ({
+      name: line.name,
+      type: 'line',
+      smooth: true,
+      data: line.points.map((point) => point.value),
+      areaStyle: {
+        opacity: 0.3,
       },
-    ],
+    })),
   };
 }
 
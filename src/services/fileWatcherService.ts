@@ -5,13 +5,47 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
+import type { Task, WorkspaceConfig } from '../types/task';
 
 /**
  * ファイル変更イベント
  */
 export interface FileChangeEvent {
-  eventType: 'created' | 'modified' | 'removed';
+  eventType: 'created' | 'modified' | 'removed' | 'renamed';
+  /** 変更されたファイルのパス（renamedの場合は変更後のパス） */
   path: string;
+  /** 変更前のパス（renamedの場合のみ） */
+  from?: string;
+  taskId: string;
+  /** 変更前のタスクID（renamedの場合のみ） */
+  previousTaskId?: string;
+  frontMatterChanged: boolean;
+}
+
+/**
+ * 外部で変更されたタスク（ウォッチャーが読み込み直した内容）
+ */
+export interface TaskUpdatedEvent {
+  taskId: string;
+  /** リネーム前のタスクID（リネームの場合のみ） */
+  previousTaskId?: string;
+  deleted: boolean;
+  /** 変更後のタスク（削除の場合はnull） */
+  task: Task | null;
+}
+
+/**
+ * 外部で変更された設定ファイル
+ */
+export interface ConfigChangedEvent {
+  kind: 'workspaceConfig' | 'tagSchema' | 'filtersAndSorts' | 'analysisSettings';
+  path: string;
+  /** ファイルが削除された場合はデフォルトの設定がcontentに入る */
+  removed: boolean;
+  /** 検証済みの内容（kindがworkspaceConfigの場合はWorkspaceConfig） */
+  content: WorkspaceConfig | Record<string, unknown> | null;
+  /** 検証エラー（不正な内容は反映されない） */
+  error: string | null;
 }
 
 /**
@@ -49,3 +83,31 @@ export async function listenToFileChanges(
     callback(event.payload);
   });
 }
+
+/**
+ * 外部でのタスク変更をリッスン
+ *
+ * @param callback - 読み込み直したタスクを受け取るコールバック関数
+ * @returns アンリッスン関数
+ */
+export async function listenToTaskUpdates(
+  callback: (event: TaskUpdatedEvent) => void
+): Promise<UnlistenFn> {
+  return await listen<TaskUpdatedEvent>('task-updated', (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * 設定ファイルの外部変更をリッスン
+ *
+ * @param callback - 検証済みの設定を受け取るコールバック関数
+ * @returns アンリッスン関数
+ */
+export async function listenToConfigChanges(
+  callback: (event: ConfigChangedEvent) => void
+): Promise<UnlistenFn> {
+  return await listen<ConfigChangedEvent>('config-changed', (event) => {
+    callback(event.payload);
+  });
+}
@@ -4,7 +4,21 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { TagSchema } from '../types/task';
+import { TAG_SCHEMA_VERSION } from '../types/task';
+import type {
+  AllowedValueType,
+  ImportAction,
+  ImportPreview,
+  ImportReport,
+  ImportResolution,
+  SchemaInference,
+  TagConfig,
+  TagDefinition,
+  TagSchema,
+  TagSchemaFile,
+  TagValue,
+  ValidationRule,
+} from '../types/task';
 
 /**
  * タグスキーマを読み込む
@@ -14,13 +28,8 @@ import type { TagSchema } from '../types/task';
  */
 export async function loadTagSchema(workspacePath: string): Promise<TagSchema> {
   try {
-    const schemaJson = await invoke<string>('load_tag_schema', { workspacePath });
-    
-    if (!schemaJson || schemaJson === '{}') {
-      return {};
-    }
-    
-    return JSON.parse(schemaJson) as TagSchema;
+    const schemaFile = await invoke<TagSchemaFile>('load_tag_schema', { workspacePath });
+    return schemaFile.tags ?? {};
   } catch (error) {
     console.error('Failed to load tag schema:', error);
     return {};
@@ -32,18 +41,327 @@ export async function loadTagSchema(workspacePath: string): Promise<TagSchema> {
  *
  * @param workspacePath - ワークスペースのルートパス
  * @param schema - 保存するタグスキーマ
+ * @param rules - 検証規則（省略時は保存済みの規則を残す）
+ */
+export async function saveTagSchema(
+  workspacePath: string,
+  schema: TagSchema,
+  rules?: ValidationRule[]
+): Promise<void> {
+  const currentRules = rules ?? (await loadValidationRules(workspacePath));
+  const schemaFile: TagSchemaFile = { version: TAG_SCHEMA_VERSION, tags: schema, rules: currentRules };
+  await invoke('save_tag_schema', { workspacePath, schema: schemaFile });
+}
+
+/**
+ * タグ定義をタグ設定の形に変換（バックエンドの`TagDefinition::to_tag_config`と同じ対応）
+ *
+ * 計算フィールドは文字列として扱う。
+ *
+ * @param definition - タグ定義
+ * @returns タグ設定
+ */
+export function tagDefinitionToConfig(definition: TagDefinition): TagConfig {
+  const range = (min?: number, max?: number): AllowedValueType | undefined =>
+    min === undefined && max === undefined
+      ? undefined
+      : { type: 'Range', min: min ?? -Infinity, max: max ?? Infinity };
+  const list = (values: string[], allowManualEntry?: boolean): AllowedValueType =>
+    allowManualEntry ? { type: 'DirectInput' } : { type: 'List', values };
+
+  const base = {
+    alias: definition.alias,
+    description: definition.description,
+    required: definition.required ?? false,
+    normalization: definition.normalization,
+  };
+
+  switch (definition.type) {
+    case 'String':
+      return {
+        ...base,
+        tagType: 'String',
+        allowedValueType: definition.options.pattern !== undefined
+          ? { type: 'Pattern', pattern: definition.options.pattern }
+          : undefined,
+        defaultValue: definition.options.defaultValue,
+      };
+    case 'Number':
+      return {
+        ...base,
+        tagType: 'Number',
+        allowedValueType: range(definition.options.min, definition.options.max),
+        defaultValue: definition.options.defaultValue,
+      };
+    case 'Currency':
+      return {
+        ...base,
+        tagType: 'Currency',
+        allowedValueType: range(definition.options.min, definition.options.max),
+        defaultValue: definition.options.defaultValue,
+        currency: definition.options.currencyFormat,
+      };
+    case 'Boolean':
+      return { ...base, tagType: 'Boolean', defaultValue: definition.options.defaultValue };
+    case 'Datetime': {
+      const defaultValue = definition.options.defaultValue;
+      return {
+        ...base,
+        tagType: definition.options.format === 'dateTime' ? 'Datetime' : 'Date',
+        // 計算式は値の作成時に評価する
+        defaultValue: typeof defaultValue === 'string'
+          ? defaultValue
+          : defaultValue?.type === 'static' ? defaultValue.value : undefined,
+      };
+    }
+    case 'Select':
+      return {
+        ...base,
+        tagType: 'Select',
+        allowedValueType: list(definition.options.optionsList, definition.options.allowManualEntry),
+        defaultValue: definition.options.defaultValue,
+      };
+    case 'MultiSelect':
+      return {
+        ...base,
+        tagType: 'MultiSelect',
+        allowedValueType: list(definition.options.optionsList, definition.options.allowManualEntry),
+        defaultValue: definition.options.defaultValue,
+      };
+    case 'Image':
+      return { ...base, tagType: 'Image' };
+    case 'Hyperlink':
+      return { ...base, tagType: 'Hyperlink' };
+    case 'Computed':
+      return { ...base, tagType: 'String' };
+  }
+}
+
+/**
+ * タグ設定をタグ定義に変換（バックエンドの旧形式からの移行と同じ対応）
+ *
+ * @param config - タグ設定
+ * @returns タグ定義
+ */
+export function tagConfigToDefinition(config: TagConfig): TagDefinition {
+  const allowed = config.allowedValueType;
+  const list = allowed?.type === 'List' ? allowed.values : undefined;
+  // 片側だけの範囲は無限大で表されている
+  const finite = (value: number | null | undefined) =>
+    typeof value === 'number' && Number.isFinite(value) ? value : undefined;
+  const min = allowed?.type === 'Range' ? finite(allowed.min) : undefined;
+  const max = allowed?.type === 'Range' ? finite(allowed.max) : undefined;
+  const defaultValue = config.defaultValue;
+  const numberDefault = typeof defaultValue === 'number' ? defaultValue : undefined;
+  const defaultText = typeof defaultValue === 'string' || typeof defaultValue === 'number' || typeof defaultValue === 'boolean'
+    ? String(defaultValue)
+    : Array.isArray(defaultValue) ? defaultValue.join(', ') : undefined;
+
+  const base = {
+    alias: config.alias,
+    description: config.description,
+    required: config.required,
+    normalization: config.normalization,
+  };
+
+  switch (config.tagType) {
+    case 'String':
+      return {
+        ...base,
+        type: 'String',
+        options: {
+          pattern: allowed?.type === 'Pattern' ? allowed.pattern : undefined,
+          defaultValue: defaultText,
+        },
+      };
+    case 'Select':
+      return {
+        ...base,
+        type: 'Select',
+        options: { optionsList: list ?? [], allowManualEntry: list === undefined, defaultValue: defaultText },
+      };
+    case 'MultiSelect':
+    case 'Array':
+      return {
+        ...base,
+        type: 'MultiSelect',
+        options: {
+          optionsList: list ?? [],
+          allowManualEntry: list === undefined,
+          defaultValue: Array.isArray(defaultValue)
+            ? defaultValue
+            : defaultText !== undefined ? [defaultText] : undefined,
+        },
+      };
+    case 'Number':
+      return { ...base, type: 'Number', options: { min, max, defaultValue: numberDefault } };
+    case 'Boolean':
+      return {
+        ...base,
+        type: 'Boolean',
+        options: { defaultValue: typeof defaultValue === 'boolean' ? defaultValue : undefined },
+      };
+    case 'Date':
+    case 'Datetime':
+      return {
+        ...base,
+        type: 'Datetime',
+        options: {
+          format: config.tagType === 'Datetime' ? 'dateTime' : 'dateOnly',
+          defaultValue: defaultText,
+        },
+      };
+    case 'Currency':
+      return {
+        ...base,
+        type: 'Currency',
+        options: { min, max, defaultValue: numberDefault, currencyFormat: config.currency },
+      };
+    case 'Hyperlink':
+      return { ...base, type: 'Hyperlink', options: {} };
+    case 'Image':
+      return { ...base, type: 'Image', options: {} };
+  }
+}
+
+/**
+ * タグスキーマをタグ名 → タグ設定の形に変換
+ *
+ * @param schema - タグスキーマ
+ * @returns タグ名 → タグ設定
+ */
+export function tagSchemaToConfigs(schema: TagSchema): Record<string, TagConfig> {
+  return Object.fromEntries(
+    Object.entries(schema).map(([key, definition]) => [key, tagDefinitionToConfig(definition)])
+  );
+}
+
+/**
+ * 編集したタグ設定をタグスキーマに戻す
+ *
+ * 変更していないタグは元の定義をそのまま残す（タグ設定で表せない属性や計算フィールドを失わないため）。
+ *
+ * @param configs - タグ名 → タグ設定
+ * @param previous - 編集前のタグスキーマ
+ * @returns 保存するタグスキーマ
+ */
+export function tagConfigsToSchema(configs: Record<string, TagConfig>, previous: TagSchema): TagSchema {
+  return Object.fromEntries(
+    Object.entries(configs).map(([key, config]) => {
+      const original = previous[key];
+      const unchanged = original !== undefined
+        && JSON.stringify(tagDefinitionToConfig(original)) === JSON.stringify(config);
+      return [key, unchanged ? original : tagConfigToDefinition(config)];
+    })
+  );
+}
+
+/**
+ * タグスキーマの検証規則を読み込む
+ *
+ * @param workspacePath - ワークスペースのルートパス
+ * @returns 検証規則（定義順）
  */
-export async function saveTagSchema(workspacePath: string, schema: TagSchema): Promise<void> {
-  const schemaJson = JSON.stringify(schema);
-  await invoke('save_tag_schema', { workspacePath, schemaJson });
+export async function loadValidationRules(workspacePath: string): Promise<ValidationRule[]> {
+  const schemaFile = await invoke<TagSchemaFile>('load_tag_schema', { workspacePath });
+  return schemaFile.rules ?? [];
 }
 
 /**
  * 動的デフォルト値を計算
  *
- * @param formula - 計算式（例: `=[TODAY]+30`）
+ * @param formula - 計算式（例: `=[TODAY]+30`、`=[NOW]+2w`、`=[start_date]+5bd`、`=next monday`）
+ * @param tags - 計算式から参照できるタスクのタグ
  * @returns 計算結果のISO 8601形式の文字列
  */
-export async function getDynamicDefaultValue(formula: string): Promise<string> {
-  return await invoke<string>('get_dynamic_default_value', { formula });
+export async function getDynamicDefaultValue(
+  formula: string,
+  tags?: Record<string, TagValue>
+): Promise<string> {
+  return await invoke<string>('get_dynamic_default_value', { formula, tags });
+}
+
+/**
+ * タグ定義とテンプレートをバンドルファイルにエクスポート
+ *
+ * @param workspacePath - ワークスペースのルートパス
+ * @param outputPath - 出力先（`.yaml`/`.yml`ならYAML、それ以外はJSON）
+ * @param tagNames - エクスポートするタグ（省略時はすべて）
+ * @param templateNames - エクスポートするテンプレート（省略時はすべて）
+ */
+export async function exportTagBundle(
+  workspacePath: string,
+  outputPath: string,
+  tagNames?: string[],
+  templateNames?: string[]
+): Promise<void> {
+  await invoke('export_tag_bundle', { workspacePath, outputPath, tagNames, templateNames });
+}
+
+/**
+ * バンドルファイルをインポートした場合の競合を確認
+ *
+ * @param workspacePath - インポート先のワークスペースのルートパス
+ * @param bundlePath - バンドルファイルのパス
+ * @returns 項目ごとの比較結果
+ */
+export async function previewTagBundleImport(
+  workspacePath: string,
+  bundlePath: string
+): Promise<ImportPreview> {
+  return await invoke<ImportPreview>('preview_tag_bundle_import', { workspacePath, bundlePath });
+}
+
+/**
+ * バンドルファイルをインポート
+ *
+ * @param workspacePath - インポート先のワークスペースのルートパス
+ * @param bundlePath - バンドルファイルのパス
+ * @param resolutions - 項目ごとのインポート方法
+ * @param defaultAction - 指定のない競合項目の扱い（省略時はスキップ）
+ * @returns インポート結果
+ */
+export async function importTagBundle(
+  workspacePath: string,
+  bundlePath: string,
+  resolutions: ImportResolution[],
+  defaultAction?: ImportAction
+): Promise<ImportReport> {
+  return await invoke<ImportReport>('import_tag_bundle', {
+    workspacePath,
+    bundlePath,
+    resolutions,
+    defaultAction,
+  });
+}
+
+/**
+ * 既存タスクのタグからタグスキーマを推定（ファイルは変更しない）
+ *
+ * @param workspacePath - ワークスペースのルートパス
+ * @returns タグごとの提案（型・選択肢・必須・外れ値）
+ */
+export async function inferTagSchema(workspacePath: string): Promise<SchemaInference> {
+  return await invoke<SchemaInference>('infer_tag_schema', { workspacePath });
+}
+
+/**
+ * 確認した推定結果をタグスキーマに保存
+ *
+ * @param workspacePath - ワークスペースのルートパス
+ * @param definitions - タグ名 → 保存するタグ定義
+ * @param overwrite - 既存の定義を置き換えるか（falseの場合は未定義のタグだけ追加）
+ * @returns 保存後のタグスキーマ
+ */
+export async function saveInferredSchema(
+  workspacePath: string,
+  definitions: TagSchema,
+  overwrite = false
+): Promise<TagSchema> {
+  const schemaFile = await invoke<TagSchemaFile>('save_inferred_schema', {
+    workspacePath,
+    definitions,
+    overwrite,
+  });
+  return schemaFile.tags ?? {};
 }
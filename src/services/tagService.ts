@@ -4,7 +4,13 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { TagIndex } from '../types/task';
+import type {
+  CategoryConflictPolicy,
+  CategoryRenameReport,
+  NormalizationReport,
+  TagIndex,
+  TagNode,
+} from '../types/task';
 
 export class TagService {
   /**
@@ -16,6 +22,16 @@ export class TagService {
     return await invoke<TagIndex>('get_tag_index', { workspacePath });
   }
 
+  /**
+   * 階層タグのツリーを取得（区切り文字はワークスペース設定に従う）
+   * @param workspacePath - ワークスペースのルートパス
+   * @param category - タグ名
+   * @returns 最上位のノード（各ノードは配下を含めた件数を持つ）
+   */
+  static async getTagHierarchy(workspacePath: string, category: string): Promise<TagNode[]> {
+    return await invoke<TagNode[]>('get_tag_hierarchy', { workspacePath, category });
+  }
+
   /**
    * タグをリネーム
    * @param workspacePath - ワークスペースのルートパス
@@ -38,11 +54,52 @@ export class TagService {
     });
   }
 
+  /**
+   * 複数のタグ値を1つに統合（配列は要素単位で置き換え、重複をまとめる）
+   * @param workspacePath - ワークスペースのルートパス
+   * @param category - タグ名
+   * @param oldValues - 統合元の値
+   * @param newValue - 統合先の値
+   * @returns 更新されたタスク数
+   */
+  static async mergeTagValues(
+    workspacePath: string,
+    category: string,
+    oldValues: string[],
+    newValue: string
+  ): Promise<number> {
+    return await invoke<number>('merge_tag_values', {
+      workspacePath,
+      category,
+      oldValues,
+      newValue,
+    });
+  }
+
+  /**
+   * タグ値を正規化ルールの正規の値に書き換える
+   * @param workspacePath - ワークスペースのルートパス
+   * @param dryRun - trueの場合はファイルを書き換えずに差分だけを返す
+   * @param categories - 対象のタグ名（省略時は正規化ルールを持つすべてのタグ）
+   * @returns 書き換える値の一覧と更新されたタスク数
+   */
+  static async normalizeTags(
+    workspacePath: string,
+    dryRun: boolean,
+    categories?: string[]
+  ): Promise<NormalizationReport> {
+    return await invoke<NormalizationReport>('normalize_tags', {
+      workspacePath,
+      categories: categories ?? null,
+      dryRun,
+    });
+  }
+
   /**
    * タグを削除
    * @param workspacePath - ワークスペースのルートパス
    * @param category - タグ名
-   * @param value - 削除する値（undefinedの場合はタグごと削除）
+   * @param value - 削除する値（配列の場合は該当要素のみ、undefinedの場合はタグごと削除）
    * @returns 更新されたタスク数
    */
   static async deleteTag(
@@ -56,4 +113,49 @@ export class TagService {
       value: value || null,
     });
   }
+
+  /**
+   * タグ名（カテゴリ）を変更
+   * タスク・テンプレート・スキーマ・保存済みフィルタ・分析設定の参照もまとめて更新する
+   * @param workspacePath - ワークスペースのルートパス
+   * @param oldCategory - 変更前のタグ名
+   * @param newCategory - 変更後のタグ名
+   * @param conflictPolicy - 変更後のタグ名が既にある場合の扱い（未指定なら既存の名前への変更はエラー）
+   * @returns 更新内容
+   */
+  static async renameCategory(
+    workspacePath: string,
+    oldCategory: string,
+    newCategory: string,
+    conflictPolicy?: CategoryConflictPolicy
+  ): Promise<CategoryRenameReport> {
+    return await invoke<CategoryRenameReport>('rename_tag_category', {
+      workspacePath,
+      oldCategory,
+      newCategory,
+      conflictPolicy: conflictPolicy ?? null,
+    });
+  }
+
+  /**
+   * 複数のタグ名（カテゴリ）を1つに統合
+   * @param workspacePath - ワークスペースのルートパス
+   * @param sourceCategories - 統合元のタグ名
+   * @param targetCategory - 統合先のタグ名
+   * @param conflictPolicy - 統合先のタグを既に持つタスクの扱い
+   * @returns 更新内容
+   */
+  static async mergeCategories(
+    workspacePath: string,
+    sourceCategories: string[],
+    targetCategory: string,
+    conflictPolicy: CategoryConflictPolicy
+  ): Promise<CategoryRenameReport> {
+    return await invoke<CategoryRenameReport>('merge_tag_categories', {
+      workspacePath,
+      sourceCategories,
+      targetCategory,
+      conflictPolicy,
+    });
+  }
 }
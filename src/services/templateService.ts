@@ -43,13 +43,15 @@ export class TemplateService {
     workspacePath: string,
     name: string,
     description: string | undefined,
-    tags: Record<string, TagValue>
+    tags: Record<string, TagValue>,
+    body?: string
   ): Promise<TagTemplate> {
     return await invoke<TagTemplate>('create_template', {
       workspacePath,
       name,
       description,
       tags,
+      body: body ?? null,
     });
   }
 
@@ -60,13 +62,15 @@ export class TemplateService {
     workspacePath: string,
     name: string,
     description: string | undefined,
-    tags: Record<string, TagValue>
+    tags: Record<string, TagValue>,
+    body?: string
   ): Promise<TagTemplate> {
     return await invoke<TagTemplate>('update_template', {
       workspacePath,
       name,
       description,
       tags,
+      body: body ?? null,
     });
   }
 
@@ -103,16 +107,35 @@ export class TemplateService {
 
   /**
    * テンプレートを新規タスクに適用
+   * @param title - 本文の{{title}}に埋め込むタイトル
+   * @param prompts - 本文の{{prompt:...}}のラベル → 入力値（未入力の項目は空になる）
    */
   static async applyTemplateToNewTask(
     workspacePath: string,
     templateName: string,
-    content: string
+    content: string,
+    title?: string,
+    prompts?: Record<string, string>
   ): Promise<string> {
     return await invoke<string>('apply_template_to_new_task', {
       workspacePath,
       templateName,
       content,
+      title: title ?? null,
+      prompts: prompts ?? null,
+    });
+  }
+
+  /**
+   * テンプレート本文で入力を求める項目（{{prompt:...}}のラベル）を取得
+   */
+  static async getTemplatePrompts(
+    workspacePath: string,
+    templateName: string
+  ): Promise<string[]> {
+    return await invoke<string[]>('get_template_prompts', {
+      workspacePath,
+      templateName,
     });
   }
 
@@ -151,15 +174,20 @@ export class TemplateService {
   }
 
   /**
-   * テンプレートのプレビューを取得（YAML形式）
+   * テンプレートのプレビューを取得（フロントマターと展開済みの本文）
+   * 未入力の{{prompt:...}}はそのまま残る
    */
   static async previewTemplate(
     workspacePath: string,
-    templateName: string
+    templateName: string,
+    title?: string,
+    prompts?: Record<string, string>
   ): Promise<string> {
     return await invoke<string>('preview_template', {
       workspacePath,
       templateName,
+      title: title ?? null,
+      prompts: prompts ?? null,
     });
   }
 }
@@ -25,6 +25,8 @@ export interface LineChart {
   title: string;
   y_axis_label: string;
   series: TimeSeriesPoint[];
+  /** 通貨タグのメトリックの通貨コード別の系列（seriesと同じ日付、表示はこちらを優先） */
+  currency_series?: Record<string, TimeSeriesPoint[]>;
 }
 
 export interface GanttChart {
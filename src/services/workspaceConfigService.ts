@@ -22,12 +22,11 @@ export class WorkspaceConfigService {
       allowedCategories: raw?.allowedCategories ?? [],
       watchEnabled: raw?.watchEnabled ?? true,
       templates: raw?.templates ?? { templates: {} },
-      tagConfigs: raw?.tagConfigs ?? { configs: {} },
       theme: raw?.theme ?? 'HienMark Dark',
     } as WorkspaceConfig;
 
     // Persist defaults back if important sections were missing
-    const needsPersist = !raw?.tagConfigs || !raw?.templates || raw?.theme === undefined;
+    const needsPersist = !raw?.templates || raw?.theme === undefined;
     if (needsPersist) {
       try {
         await this.updateConfig(workspacePath, filled);
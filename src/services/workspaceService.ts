@@ -53,13 +53,60 @@ export async function createTask(
   return await invoke<Task>('create_task', { workspacePath, taskId, content });
 }
 
+/**
+ * タグ設定に対する違反
+ */
+export interface TagViolation {
+  taskId: string;
+  category: string;
+  kind:
+    | 'missingRequired'
+    | 'typeMismatch'
+    | 'notInList'
+    | 'patternMismatch'
+    | 'outOfRange'
+    | 'unknownCategory'
+    | 'readOnly'
+    | 'missingAsset'
+    | 'ruleViolation';
+  /** タグ固定モードではerror（保存は拒否される） */
+  severity: 'error' | 'warning';
+  value?: unknown;
+  message: string;
+  /** 違反した検証規則のID（ruleViolationの場合） */
+  rule?: string;
+}
+
+/**
+ * ワークスペース全体の検証結果
+ */
+export interface ValidationReport {
+  tasksChecked: number;
+  strictMode: boolean;
+  violations: TagViolation[];
+  schemaErrors: string[];
+}
+
 /**
  * タスクを保存
  *
+ * タグ固定モードでタグ設定に違反している場合は保存されずにエラーになる。
+ *
+ * @param workspacePath - タスクが属するワークスペースのルートパス
  * @param task - 保存するタスク
+ * @returns 保存時の警告（タグ設定に対する違反）
+ */
+export async function saveTask(workspacePath: string, task: Task): Promise<TagViolation[]> {
+  return await invoke<TagViolation[]>('save_task', { workspacePath, task });
+}
+
+/**
+ * ワークスペース内の全タスクをタグ設定に照らして検証
+ *
+ * @param workspacePath - ワークスペースのルートパス
  */
-export async function saveTask(task: Task): Promise<void> {
-  await invoke('save_task', { task });
+export async function validateWorkspace(workspacePath: string): Promise<ValidationReport> {
+  return await invoke<ValidationReport>('validate_workspace', { workspacePath });
 }
 
 /**
@@ -7,6 +7,7 @@ export interface Task {
   content: string;
   modifiedAt: string; // ISO 8601形式
   tagOrder?: string[]; // Front Matterのタグ順序を保持
+  computedTags?: FrontMatter; // 計算フィールドの値（読み取り専用、ファイルには保存されない）
 }
 
 export interface FrontMatter {
@@ -32,14 +33,75 @@ export interface TagCategory {
   taskIds: string[];
 }
 
+/** 階層タグのツリーのノード */
+export interface TagNode {
+  name: string;
+  /** 最上位からのパス（例: area/backend） */
+  path: string;
+  /** このパスちょうどの値の出現回数 */
+  count: number;
+  /** 配下のノードを含めた出現回数 */
+  total: number;
+  children: TagNode[];
+}
+
+/** タグ名の変更・統合で、両方のタグを持つタスクの扱い */
+export type CategoryConflictPolicy = 'keepTarget' | 'overwrite' | 'combine' | 'skip';
+
+export interface CategoryRenameReport {
+  tasksUpdated: number;
+  /** 両方のタグを持っていたタスクID */
+  conflicts: string[];
+  /** 競合のため変更しなかったタスクID（skipの場合） */
+  skippedTasks: string[];
+  templatesUpdated: number;
+  schemaUpdated: boolean;
+  filtersUpdated: number;
+  analysisSettingsUpdated: boolean;
+}
+
+/** タグ値の正規化での大文字・小文字の扱い（lower: 一致しない値も小文字にそろえる） */
+export type CaseFolding = 'sensitive' | 'insensitive' | 'lower';
+
+/** タグ値の正規化ルール */
+export interface ValueNormalization {
+  /** 正規の値 → 同義語のリスト */
+  synonyms: Record<string, string[]>;
+  caseFolding?: CaseFolding;
+}
+
+/** タグ値の正規化で書き換わる値 */
+export interface TagValueChange {
+  taskId: string;
+  category: string;
+  before: TagValue;
+  after: TagValue;
+}
+
+export interface NormalizationReport {
+  changes: TagValueChange[];
+  /** 更新されたタスク数（ドライランでは0） */
+  tasksUpdated: number;
+  dryRun: boolean;
+}
+
 export type ThemeType = 'HienMark Dark' | 'HienMark White';
 
 export interface WorkspaceConfig {
   strictTagMode: boolean;
   allowedCategories: string[];
   watchEnabled: boolean;
+  watchDebounceMs?: number;
+  /**
+   * ファイル監視の方式（auto: ネイティブ監視が使えない場合にポーリング）
+   * SMB・NFSなどのネットワークドライブでは他のマシンからの変更が通知されないため、
+   * Linux以外ではこうしたフォルダに'polling'を指定する
+   */
+  watchBackend?: 'auto' | 'native' | 'polling';
+  watchPollIntervalMs?: number;
+  /** 階層タグの区切り文字（例: area/backend/api の '/'）。空文字の場合は階層化しない */
+  tagSeparator?: string;
   templates: TemplateCollection;
-  tagConfigs: TagConfigCollection;
   theme?: ThemeType;
   autoSaveEnabled?: boolean;
   autoSaveInterval?: number;
@@ -63,6 +125,8 @@ export interface TagTemplate {
   name: string;
   description?: string;
   tags: Record<string, TagValue>;
+  /** タスク本文のひな形（{{title}}、{{date}}、{{user}}、{{prompt:...}}、{{=計算式}}を含められる） */
+  body?: string;
   createdAt: string;
   updatedAt: string;
 }
@@ -80,7 +144,11 @@ export type TagType =
   | 'Number'
   | 'Boolean'
   | 'Date'
-  | 'Array';
+  | 'Array'
+  | 'Currency'
+  | 'Hyperlink'
+  | 'Image'
+  | 'Datetime';
 
 export type AllowedValueType = 
   | { type: 'DirectInput' }
@@ -115,19 +183,19 @@ export interface TagConfig {
   defaultValue?: TagValue;
   required: boolean;
   description?: string;
+  /** 通貨型で通貨コードのない数値に適用する通貨（ISO 4217） */
+  currency?: string;
+  /** 値の正規化ルール（同義語・大文字小文字の扱い） */
+  normalization?: ValueNormalization;
   /** ソート設定 */
   sortSettings?: SortSettings;
   /** フィルタ設定 */
   filterSettings?: FilterSettings;
 }
 
-export interface TagConfigCollection {
-  configs: Record<string, TagConfig>;
-}
-
 // カスタムフィルター/ソート設定
 
-export type ComparisonOperator = '==' | '!=' | '>' | '<' | '>=' | '<=' | 'contains' | 'starts_with' | 'ends_with' | 'regex';
+export type ComparisonOperator = '==' | '!=' | '>' | '<' | '>=' | '<=' | 'contains' | 'starts_with' | 'ends_with' | 'regex' | 'under';
 export type LogicalOperator = 'AND' | 'OR' | 'NOT';
 export type SortOrder = 'asc' | 'desc';
 
@@ -182,10 +250,13 @@ export type TagAttributeType =
   | 'MultiSelect'
   | 'Currency'
   | 'Image'
-  | 'Hyperlink';
+  | 'Hyperlink'
+  | 'Computed';
 
 export interface StringAttributeOptions {
   maxLength?: number;
+  /** 値全体が一致すべき正規表現 */
+  pattern?: string;
   defaultValue?: string;
 }
 
@@ -235,6 +306,11 @@ export interface HyperlinkAttributeOptions {
   // No options for now
 }
 
+export interface ComputedAttributeOptions {
+  /** 計算式（例: `end_date - start_date`） */
+  expression: string;
+}
+
 export type TagAttributeOptions = 
   | { type: 'String'; options: StringAttributeOptions }
   | { type: 'Number'; options: NumberAttributeOptions }
@@ -244,10 +320,115 @@ export type TagAttributeOptions =
   | { type: 'MultiSelect'; options: MultiSelectAttributeOptions }
   | { type: 'Currency'; options: CurrencyAttributeOptions }
   | { type: 'Image'; options: ImageAttributeOptions }
-  | { type: 'Hyperlink'; options: HyperlinkAttributeOptions };
+  | { type: 'Hyperlink'; options: HyperlinkAttributeOptions }
+  | { type: 'Computed'; options: ComputedAttributeOptions };
+
+export type TagDefinition = TagAttributeOptions & {
+  alias?: string;
+  description?: string;
+  required?: boolean;
+  /** 値の正規化ルール（同義語・大文字小文字の扱い） */
+  normalization?: ValueNormalization;
+};
 
 export interface TagSchema {
-  [tagKey: string]: TagAttributeOptions;
+  [tagKey: string]: TagDefinition;
+}
+
+/** tag_schema.json のフォーマットバージョン */
+export const TAG_SCHEMA_VERSION = 1;
+
+/** tag_schema.json（バージョン付き） */
+export interface TagSchemaFile {
+  version: number;
+  tags: TagSchema;
+  /** 複数のタグにまたがる検証規則 */
+  rules?: ValidationRule[];
+}
+
+/**
+ * タスクごとに評価する検証規則（式は計算フィールドと同じ構文）
+ * 例: `{ id: 'close-needs-date', when: 'status == close', require: ['completed_at'] }`
+ */
+export interface ValidationRule {
+  id: string;
+  /** 規則を適用する条件の式（省略時は全タスク） */
+  when?: string;
+  /** 値が必要なタグ */
+  require?: string[];
+  /** 満たすべき条件の式（例: `end_date >= start_date`） */
+  assert?: string;
+  message?: string;
+  /** 指定した場合はタグ固定モードでなくても優先する（省略時はタグ固定モードならerror、それ以外はwarning） */
+  severity?: 'error' | 'warning';
+}
+
+// タグ定義バンドル（インポート/エクスポート）
+
+export type BundleItemKind = 'tag' | 'template';
+
+/** インポート先との比較結果 */
+export type BundleItemStatus = 'new' | 'identical' | 'conflict';
+
+/** 同名の項目がある場合の扱い */
+export type ImportAction = 'skip' | 'overwrite' | 'rename';
+
+export interface ImportResolution {
+  kind: BundleItemKind;
+  name: string;
+  action: ImportAction;
+  /** rename の場合のインポート後の名前 */
+  newName?: string;
+}
+
+export interface BundleItemPreview {
+  kind: BundleItemKind;
+  name: string;
+  status: BundleItemStatus;
+}
+
+export interface ImportPreview {
+  version: number;
+  exportedAt: string;
+  items: BundleItemPreview[];
+}
+
+export interface ImportReport {
+  tagsImported: string[];
+  templatesImported: string[];
+  /** スキップした項目（内容が同じだったものを含む） */
+  skipped: BundleItemPreview[];
+}
+
+/** 既存タスクから推定したタグスキーマの提案 */
+export interface SchemaInference {
+  tasksScanned: number;
+  /** タグごとの提案（タグ名順） */
+  tags: InferredTag[];
+}
+
+export interface InferredTag {
+  name: string;
+  /** 提案するタグ定義（確認・編集してから保存する） */
+  definition: TagDefinition;
+  /** タグを持つタスクの割合（0〜1） */
+  coverage: number;
+  taskCount: number;
+  /** 出現した値と出現回数（多い順） */
+  values: { value: string; count: number }[];
+  outliers: TagOutlier[];
+  /** タグスキーマに既に定義があるか */
+  existing: boolean;
+}
+
+export type OutlierKind = 'typeMismatch' | 'caseVariant' | 'rareValue' | 'outOfRange' | 'missing';
+
+export interface TagOutlier {
+  taskId: string;
+  kind: OutlierKind;
+  /** 対象の値（欠落の場合はなし） */
+  value?: TagValue;
+  message: string;
 }
 
 export interface HyperlinkValue {
@@ -278,11 +459,13 @@ export interface GanttChartMapping {
 export interface PieChartMapping {
   category?: string; // Select型タグ
   value?: string; // メトリックID（デフォルト: '__default_count__'）
+  depth?: number; // 階層タグを集計する深さ（未指定なら値をそのまま使う）
 }
 
 export interface BarChartMapping {
   category?: string; // Select型タグ
   value?: string; // メトリックID（デフォルト: '__default_count__'）
+  depth?: number; // 階層タグを集計する深さ（未指定なら値をそのまま使う）
 }
 
 export interface LineChartMapping {
@@ -4,6 +4,7 @@
  */
 
 import type { FrontMatter } from '../types/task';
+import { formatHyperlink } from './typedValues';
 
 export interface ParsedContent {
   frontMatter: FrontMatter;
@@ -75,9 +76,12 @@ export function parseMarkdownContent(content: string): ParsedContent {
         }
       }
 
-      // インライン配列の場合 [item1, item2]
+      // インライン配列の場合 [item1, item2]（引用符で囲まれた要素はカンマを含められる）
       if (value.startsWith('[') && value.endsWith(']')) {
-        frontMatter[key] = value.slice(1, -1).split(',').map((v) => v.trim());
+        const items = value.slice(1, -1).match(/"(?:[^"\\]|\\.)*"|[^,]+/g) ?? [];
+        frontMatter[key] = items.map((v) => unquote(v.trim())).filter((v) => v !== '');
+      } else if (value.startsWith('"') && value.endsWith('"') && value.length >= 2) {
+        frontMatter[key] = unquote(value);
       } else if (value === 'null') {
         frontMatter[key] = null;
       } else if (value === 'true' || value === 'false') {
@@ -116,17 +120,56 @@ export function serializeFrontMatter(frontMatter: FrontMatter, tagOrder?: string
     .map(key => {
       const value = frontMatter[key];
       if (Array.isArray(value)) {
-        return `${key}: [${value.join(', ')}]`;
+        return `${key}: [${value.map((v) => yamlScalar(v, true)).join(', ')}]`;
       }
       if (value === null) {
         return `${key}: null`;
       }
+      if (typeof value === 'object') {
+        // 旧形式のハイパーリンクは`[text](url)`の文字列で保存する
+        return `${key}: ${yamlScalar(formatHyperlink(value), false)}`;
+      }
+      if (typeof value === 'string') {
+        return `${key}: ${yamlScalar(value, false)}`;
+      }
       return `${key}: ${value}`;
     });
 
   return lines.join('\n');
 }
 
+/**
+ * 文字列をYAMLのスカラーとして書き出す
+ * そのままでは別の値（数値・真偽値・リストなど）として読まれる文字列は二重引用符で囲む
+ */
+function yamlScalar(value: string, inFlow: boolean): string {
+  const needsQuote =
+    value !== value.trim() ||
+    /^(true|false|null|~)$/.test(value) ||
+    (value !== '' && !isNaN(Number(value))) ||
+    /^[[\]{}"'&*!|>%@#`,]/.test(value) ||
+    /^- /.test(value) ||
+    value.includes(': ') ||
+    value.includes(' #') ||
+    value.includes('\n') ||
+    (inFlow && /[,[\]{}]/.test(value));
+  return needsQuote ? JSON.stringify(value) : value;
+}
+
+/**
+ * 二重引用符で囲まれたスカラーを元の文字列に戻す
+ */
+function unquote(value: string): string {
+  if (value.length >= 2 && value.startsWith('"') && value.endsWith('"')) {
+    try {
+      return JSON.parse(value);
+    } catch {
+      return value;
+    }
+  }
+  return value;
+}
+
 /**
  * Front MatterとBodyを結合してMarkdownコンテンツを生成
  */
@@ -0,0 +1,71 @@
+/**
+ * 通貨・ハイパーリンクタグの値の変換ユーティリティ
+ * Front Matterでの保存形式はバックエンド（models/typed_value.rs）と同じ
+ */
+
+import type { HyperlinkValue, TagValue } from '../types/task';
+
+export interface CurrencyAmount {
+  amount: number;
+  currency: string;
+}
+
+const CURRENCY_CODE = /^[A-Z]{3}$/;
+const DECIMAL = /^-?\d+(\.\d+)?$/;
+const MARKDOWN_LINK = /^\[(.*)\]\((\S*)\)$/;
+
+/**
+ * 通貨タグの値を解析
+ *
+ * @param value - `1200.50 USD`形式の文字列、または既定の通貨の金額
+ * @param defaultCurrency - 通貨コードのない数値に適用する通貨
+ * @returns 金額と通貨コード（解析できない場合はnull）
+ */
+export function parseCurrency(
+  value: TagValue | undefined,
+  defaultCurrency?: string
+): CurrencyAmount | null {
+  if (typeof value === 'number') {
+    return defaultCurrency ? { amount: value, currency: defaultCurrency } : null;
+  }
+  if (typeof value !== 'string') {
+    return null;
+  }
+  const parts = value.trim().split(/\s+/);
+  if (parts.length !== 2) {
+    return null;
+  }
+  const [amount, currency] = CURRENCY_CODE.test(parts[1]) ? parts : [parts[1], parts[0]];
+  if (!CURRENCY_CODE.test(currency) || !DECIMAL.test(amount)) {
+    return null;
+  }
+  return { amount: Number(amount), currency };
+}
+
+/**
+ * 通貨タグの値を保存形式に変換（既定の通貨なら数値のまま）
+ */
+export function toCurrencyTagValue(amount: CurrencyAmount, defaultCurrency?: string): TagValue {
+  return amount.currency === defaultCurrency
+    ? amount.amount
+    : `${amount.amount} ${amount.currency}`;
+}
+
+/**
+ * ハイパーリンクタグの値を解析（`[text](url)`、URLのみ、または旧形式のオブジェクト）
+ */
+export function parseHyperlink(value: TagValue | undefined): HyperlinkValue {
+  if (value && typeof value === 'object' && !Array.isArray(value)) {
+    return value;
+  }
+  const text = typeof value === 'string' ? value.trim() : '';
+  const match = text.match(MARKDOWN_LINK);
+  return match ? { text: match[1], url: match[2] } : { url: text, text: '' };
+}
+
+/**
+ * ハイパーリンクを保存形式の文字列に変換
+ */
+export function formatHyperlink(link: HyperlinkValue): string {
+  return link.text ? `[${link.text}](${link.url})` : link.url;
+}